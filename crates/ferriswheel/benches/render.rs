@@ -0,0 +1,74 @@
+//! Render-path benchmarks at the largest supported ring size.
+//!
+//! Run with `cargo bench -p ferriswheel`. Each benchmark renders one frame
+//! of 256 LEDs with `update()`, which is what a render loop pays per frame.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use ferriswheel::{
+    gamma_correct, ChaseEffect, ColorPalette, Effect, GradientPalette, ProgressEffect, PulseEffect,
+    RainbowEffect, SectionEffect, SpinnerEffect, TwinkleEffect, MAX_LEDS,
+};
+use rgb::RGB8;
+
+fn bench_update(c: &mut Criterion, name: &str, effect: &mut dyn Effect) {
+    let mut buffer = [RGB8::default(); MAX_LEDS];
+    c.bench_function(name, |b| {
+        b.iter(|| {
+            effect.update(black_box(&mut buffer)).unwrap();
+        })
+    });
+}
+
+fn effects(c: &mut Criterion) {
+    bench_update(c, "rainbow_hsv", &mut RainbowEffect::new(MAX_LEDS).unwrap());
+    bench_update(
+        c,
+        "rainbow_palette",
+        &mut RainbowEffect::new(MAX_LEDS)
+            .unwrap()
+            .with_palette(GradientPalette::OCEAN),
+    );
+    bench_update(
+        c,
+        "spinner",
+        &mut SpinnerEffect::new(MAX_LEDS).unwrap().with_tail_length(16),
+    );
+    bench_update(
+        c,
+        "chase",
+        &mut ChaseEffect::new(MAX_LEDS)
+            .unwrap()
+            .with_segment_length(32)
+            .with_soft_edges(true),
+    );
+    bench_update(c, "pulse", &mut PulseEffect::new(MAX_LEDS).unwrap());
+    bench_update(
+        c,
+        "twinkle",
+        &mut TwinkleEffect::new(MAX_LEDS).unwrap().with_density(255),
+    );
+
+    let mut progress = ProgressEffect::new(MAX_LEDS)
+        .unwrap()
+        .with_gradient(RGB8::new(255, 0, 0), RGB8::new(0, 255, 0));
+    progress.set_progress(170);
+    bench_update(c, "progress_gradient", &mut progress);
+
+    let mut sections = SectionEffect::new(MAX_LEDS).unwrap();
+    let red = ColorPalette::mono(RGB8::new(255, 0, 0));
+    let blue = ColorPalette::mono(RGB8::new(0, 0, 255));
+    sections
+        .set_sections(&[(red, 2), (blue, 1), (red, 1)])
+        .unwrap();
+    bench_update(c, "sections", &mut sections);
+}
+
+fn post_processing(c: &mut Criterion) {
+    let mut buffer = [RGB8::new(200, 120, 40); MAX_LEDS];
+    c.bench_function("gamma_correct", |b| {
+        b.iter(|| gamma_correct(black_box(&mut buffer)))
+    });
+}
+
+criterion_group!(benches, effects, post_processing);
+criterion_main!(benches);
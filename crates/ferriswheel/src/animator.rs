@@ -0,0 +1,343 @@
+//! Frame pacing for blocking main loops.
+//!
+//! A superloop usually polls several things and should only render when a new
+//! frame is due. [`Animator`] keeps the schedule: call
+//! [`tick`](Animator::tick) with the current time as often as you like and it
+//! renders and writes a frame only when the frame interval has passed.
+
+use crate::effect::{Effect, EffectError};
+use crate::writer::{FrameWriter, RunError};
+use rgb::RGB8;
+
+/// Renders an effect into a buffer and writes it at a fixed frame interval.
+///
+/// Timestamps are milliseconds from any monotonic clock and may wrap around
+/// `u32::MAX`. Frames are scheduled on a fixed grid, so a late tick does not
+/// shift later frames. If the loop falls more than a whole frame behind, the
+/// missed frames are skipped and the grid restarts at the current time.
+///
+/// The effect advances once per rendered frame.
+///
+/// # Example
+///
+/// ```
+/// use ferriswheel::{Animator, RainbowEffect};
+/// use rgb::RGB8;
+///
+/// let rainbow = RainbowEffect::new(12).unwrap();
+/// let mut buffer = [RGB8::default(); 12];
+/// let write = |frame: &[RGB8]| -> Result<(), ()> {
+///     // driver.write(frame)
+///     Ok(())
+/// };
+///
+/// // 50 frames per second
+/// let mut animator = Animator::new(rainbow, &mut buffer, write, 20).unwrap();
+///
+/// assert!(animator.tick(0).unwrap());
+/// assert!(!animator.tick(5).unwrap());
+/// assert!(animator.tick(20).unwrap());
+/// ```
+pub struct Animator<'a, E, W> {
+    effect: E,
+    buffer: &'a mut [RGB8],
+    writer: W,
+    frame_ms: u32,
+    next_due_ms: Option<u32>,
+}
+
+impl<'a, E: Effect, W: FrameWriter> Animator<'a, E, W> {
+    /// Creates an animator that renders `effect` into `buffer` every `frame_ms`
+    /// milliseconds and passes each frame to `writer`.
+    ///
+    /// The first call to [`tick`](Animator::tick) always renders a frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::ZeroLeds` if `buffer` is empty.
+    /// Returns `EffectError::ZeroStep` if `frame_ms` is 0.
+    pub fn new(
+        effect: E,
+        buffer: &'a mut [RGB8],
+        writer: W,
+        frame_ms: u32,
+    ) -> Result<Self, EffectError> {
+        if buffer.is_empty() {
+            return Err(EffectError::ZeroLeds);
+        }
+        if frame_ms == 0 {
+            return Err(EffectError::ZeroStep);
+        }
+
+        Ok(Self {
+            effect,
+            buffer,
+            writer,
+            frame_ms,
+            next_due_ms: None,
+        })
+    }
+
+    /// Changes the frame interval.
+    ///
+    /// The next frame stays scheduled; the new interval applies after it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::ZeroStep` if `frame_ms` is 0.
+    pub fn set_frame_ms(&mut self, frame_ms: u32) -> Result<(), EffectError> {
+        if frame_ms == 0 {
+            return Err(EffectError::ZeroStep);
+        }
+        self.frame_ms = frame_ms;
+        Ok(())
+    }
+
+    /// Returns the frame interval in milliseconds.
+    pub fn frame_ms(&self) -> u32 {
+        self.frame_ms
+    }
+
+    /// Returns `true` if a frame is due at `now_ms`.
+    pub fn is_due(&self, now_ms: u32) -> bool {
+        self.ms_until_next(now_ms) == 0
+    }
+
+    /// Returns the milliseconds from `now_ms` until the next frame is due.
+    ///
+    /// Useful to sleep until the next call to [`tick`](Animator::tick).
+    pub fn ms_until_next(&self, now_ms: u32) -> u32 {
+        match self.next_due_ms {
+            Some(due) if (due.wrapping_sub(now_ms) as i32) > 0 => due.wrapping_sub(now_ms),
+            _ => 0,
+        }
+    }
+
+    /// Renders and writes a frame if one is due at `now_ms`.
+    ///
+    /// Returns `true` if a frame was written.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RunError::Effect` if the effect fails to render, or
+    /// `RunError::Write` if the writer fails. The frame still counts as
+    /// rendered, so a failing driver is not retried before the next frame.
+    pub fn tick(&mut self, now_ms: u32) -> Result<bool, RunError<W::Error>> {
+        if !self.is_due(now_ms) {
+            return Ok(false);
+        }
+
+        let next = match self.next_due_ms {
+            Some(due) => due.wrapping_add(self.frame_ms),
+            None => now_ms.wrapping_add(self.frame_ms),
+        };
+        // More than a frame behind: skip the missed frames
+        self.next_due_ms = if (next.wrapping_sub(now_ms) as i32) > 0 {
+            Some(next)
+        } else {
+            Some(now_ms.wrapping_add(self.frame_ms))
+        };
+
+        self.effect.update(self.buffer)?;
+        self.writer
+            .write_frame(self.buffer)
+            .map_err(RunError::Write)?;
+        Ok(true)
+    }
+
+    /// Resets the effect and makes the next [`tick`](Animator::tick) render immediately.
+    pub fn restart(&mut self) {
+        self.effect.reset();
+        self.next_due_ms = None;
+    }
+
+    /// Returns a reference to the effect.
+    pub fn effect(&self) -> &E {
+        &self.effect
+    }
+
+    /// Returns a mutable reference to the effect, e.g. to change its settings.
+    pub fn effect_mut(&mut self) -> &mut E {
+        &mut self.effect
+    }
+
+    /// Returns the most recently rendered frame.
+    pub fn frame(&self) -> &[RGB8] {
+        self.buffer
+    }
+}
+
+impl<E: core::fmt::Debug, W> core::fmt::Debug for Animator<'_, E, W> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Animator")
+            .field("effect", &self.effect)
+            .field("num_leds", &self.buffer.len())
+            .field("frame_ms", &self.frame_ms)
+            .field("next_due_ms", &self.next_due_ms)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SpinnerEffect;
+
+    fn dot(n: usize) -> SpinnerEffect {
+        SpinnerEffect::new(n).unwrap().with_tail_length(0)
+    }
+
+    fn lit(frame: &[RGB8]) -> usize {
+        frame
+            .iter()
+            .position(|&led| led != RGB8::default())
+            .unwrap()
+    }
+
+    /// Ticks at each timestamp and returns the timestamps that rendered a frame.
+    fn rendered_at(frame_ms: u32, ticks: &[u32]) -> Vec<u32> {
+        let mut buffer = [RGB8::default(); 4];
+        let write = |_: &[RGB8]| Ok::<(), ()>(());
+        let mut animator = Animator::new(dot(4), &mut buffer, write, frame_ms).unwrap();
+        ticks
+            .iter()
+            .copied()
+            .filter(|&now| animator.tick(now).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_new_with_empty_buffer_returns_error() {
+        let mut buffer: [RGB8; 0] = [];
+        let write = |_: &[RGB8]| Ok::<(), ()>(());
+        assert_eq!(
+            Animator::new(dot(4), &mut buffer, write, 20).unwrap_err(),
+            EffectError::ZeroLeds
+        );
+    }
+
+    #[test]
+    fn test_new_with_zero_interval_returns_error() {
+        let mut buffer = [RGB8::default(); 4];
+        let write = |_: &[RGB8]| Ok::<(), ()>(());
+        assert_eq!(
+            Animator::new(dot(4), &mut buffer, write, 0).unwrap_err(),
+            EffectError::ZeroStep
+        );
+    }
+
+    #[test]
+    fn test_renders_only_when_due() {
+        assert_eq!(
+            rendered_at(20, &[100, 110, 119, 120, 125, 140]),
+            [100, 120, 140]
+        );
+    }
+
+    #[test]
+    fn test_late_tick_keeps_grid() {
+        // Frame due at 20 rendered at 25; the next is still due at 40
+        assert_eq!(rendered_at(20, &[0, 25, 39, 40]), [0, 25, 40]);
+    }
+
+    #[test]
+    fn test_stall_skips_missed_frames() {
+        assert_eq!(rendered_at(20, &[0, 100, 110, 120]), [0, 100, 120]);
+    }
+
+    #[test]
+    fn test_timestamp_wraparound() {
+        let start = u32::MAX - 10;
+        assert_eq!(
+            rendered_at(20, &[start, start.wrapping_add(15), 9, 29]),
+            [start, 9, 29]
+        );
+    }
+
+    #[test]
+    fn test_ms_until_next() {
+        let mut buffer = [RGB8::default(); 4];
+        let write = |_: &[RGB8]| Ok::<(), ()>(());
+        let mut animator = Animator::new(dot(4), &mut buffer, write, 20).unwrap();
+        assert_eq!(animator.ms_until_next(50), 0);
+        animator.tick(50).unwrap();
+        assert_eq!(animator.ms_until_next(55), 15);
+        assert_eq!(animator.ms_until_next(80), 0);
+    }
+
+    #[test]
+    fn test_writes_each_rendered_frame() {
+        let mut frames: Vec<Vec<RGB8>> = Vec::new();
+        let mut buffer = [RGB8::default(); 4];
+        let write = |frame: &[RGB8]| {
+            frames.push(frame.to_vec());
+            Ok::<(), ()>(())
+        };
+        {
+            let mut animator = Animator::new(dot(4), &mut buffer, write, 10).unwrap();
+            for now in 0..30 {
+                animator.tick(now).unwrap();
+            }
+            assert_eq!(lit(animator.frame()), 2);
+        }
+
+        let positions: Vec<usize> = frames.iter().map(|frame| lit(frame)).collect();
+        assert_eq!(positions, [0, 1, 2]);
+    }
+
+    #[test]
+    fn test_write_error_is_returned() {
+        let mut buffer = [RGB8::default(); 4];
+        let write = |_: &[RGB8]| Err::<(), _>("bus");
+        let mut animator = Animator::new(dot(4), &mut buffer, write, 10).unwrap();
+        assert_eq!(animator.tick(0).unwrap_err(), RunError::Write("bus"));
+        // Not retried before the next frame
+        assert!(!animator.tick(1).unwrap());
+    }
+
+    #[test]
+    fn test_effect_error_is_returned() {
+        let mut buffer = [RGB8::default(); 2];
+        let write = |_: &[RGB8]| Ok::<(), ()>(());
+        let mut animator = Animator::new(dot(4), &mut buffer, write, 10).unwrap();
+        assert_eq!(
+            animator.tick(0).unwrap_err(),
+            RunError::Effect(EffectError::BufferTooSmall {
+                required: 4,
+                actual: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_restart_renders_immediately_from_first_frame() {
+        let mut buffer = [RGB8::default(); 4];
+        let write = |_: &[RGB8]| Ok::<(), ()>(());
+        let mut animator = Animator::new(dot(4), &mut buffer, write, 10).unwrap();
+        animator.tick(0).unwrap();
+        animator.tick(10).unwrap();
+        animator.restart();
+        assert!(animator.tick(12).unwrap());
+        assert_eq!(lit(animator.frame()), 0);
+    }
+
+    #[test]
+    fn test_driver_type_as_writer() {
+        struct Counter(usize);
+
+        impl FrameWriter for Counter {
+            type Error = ();
+
+            fn write_frame(&mut self, _frame: &[RGB8]) -> Result<(), ()> {
+                self.0 += 1;
+                Ok(())
+            }
+        }
+
+        let mut buffer = [RGB8::default(); 4];
+        let mut animator = Animator::new(dot(4), &mut buffer, Counter(0), 10).unwrap();
+        animator.tick(0).unwrap();
+        animator.tick(10).unwrap();
+        assert_eq!(animator.writer.0, 2);
+    }
+}
@@ -0,0 +1,374 @@
+//! Music-reactive modulation for any effect.
+//!
+//! A microphone or line-in gives a stream of loudness samples that jumps
+//! around far too much to drive LEDs directly. [`AudioReactive`] smooths the
+//! samples with separate attack and decay rates, optionally normalizes them
+//! with an automatic gain control, and uses the result to modulate the
+//! brightness or speed of the wrapped effect.
+
+use crate::effect::{Effect, EffectError};
+use crate::tempo::tempo_steps;
+use crate::util::scale_brightness;
+use rgb::RGB8;
+
+/// Lowest peak the automatic gain control normalizes to, so silence and
+/// background noise are not amplified to full scale.
+const AGC_MIN_PEAK: u16 = 32 << 8;
+
+/// What the audio level of an [`AudioReactive`] controls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Modulation {
+    /// Scale every frame between `min` (silence) and `max` (full level) brightness.
+    Brightness {
+        /// Brightness at level 0.
+        min: u8,
+        /// Brightness at level 255.
+        max: u8,
+    },
+    /// Run the effect between `min` (silence) and `max` (full level) percent of
+    /// its original speed.
+    Speed {
+        /// Tempo at level 0, in percent.
+        min: u16,
+        /// Tempo at level 255, in percent.
+        max: u16,
+    },
+}
+
+impl Default for Modulation {
+    fn default() -> Self {
+        Modulation::Brightness { min: 0, max: 255 }
+    }
+}
+
+/// Wraps an effect and modulates it with a smoothed audio level.
+///
+/// Feed loudness samples (0–255) with [`push_sample`](AudioReactive::push_sample),
+/// as often as the audio input provides them. Each sample moves the smoothed
+/// level towards it: by `attack / 255` of the distance when louder, by
+/// `decay / 255` when quieter. A fast attack and a slow decay make the LEDs
+/// jump on a beat and fade out afterwards.
+///
+/// With [`with_auto_gain`](AudioReactive::with_auto_gain), the level is
+/// divided by a slowly falling peak, so quiet and loud music both use the
+/// full range.
+///
+/// # Example
+///
+/// ```
+/// use ferriswheel::{AudioReactive, Effect, RainbowEffect};
+/// use rgb::RGB8;
+///
+/// let rainbow = RainbowEffect::new(12).unwrap();
+/// let mut reactive = AudioReactive::new(rainbow).with_auto_gain(true);
+/// let mut buffer = [RGB8::default(); 12];
+///
+/// // From the microphone ADC
+/// for sample in [20, 180, 90] {
+///     reactive.push_sample(sample);
+/// }
+/// reactive.update(&mut buffer).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct AudioReactive<E> {
+    effect: E,
+    modulation: Modulation,
+    attack: u8,
+    decay: u8,
+    auto_gain: bool,
+    level: u16,
+    peak: u16,
+    carry: u32,
+}
+
+impl<E: Effect> AudioReactive<E> {
+    /// Wraps `effect`.
+    ///
+    /// # Default Configuration
+    ///
+    /// - Modulation: brightness from 0 to 255
+    /// - Attack: 192
+    /// - Decay: 24
+    /// - Automatic gain control: off
+    pub fn new(effect: E) -> Self {
+        Self {
+            effect,
+            modulation: Modulation::default(),
+            attack: 192,
+            decay: 24,
+            auto_gain: false,
+            level: 0,
+            peak: AGC_MIN_PEAK,
+            carry: 0,
+        }
+    }
+
+    /// Sets what the audio level controls.
+    pub fn with_modulation(mut self, modulation: Modulation) -> Self {
+        self.modulation = modulation;
+        self
+    }
+
+    /// Sets how quickly the level follows louder and quieter samples (1–255).
+    ///
+    /// 255 follows a sample immediately. Values below 1 are clamped to 1.
+    pub fn with_smoothing(mut self, attack: u8, decay: u8) -> Self {
+        self.attack = attack.max(1);
+        self.decay = decay.max(1);
+        self
+    }
+
+    /// Enables automatic gain control.
+    pub fn with_auto_gain(mut self, auto_gain: bool) -> Self {
+        self.auto_gain = auto_gain;
+        self
+    }
+
+    /// Changes what the audio level controls.
+    pub fn set_modulation(&mut self, modulation: Modulation) {
+        self.modulation = modulation;
+    }
+
+    /// Feeds one loudness sample (0–255).
+    pub fn push_sample(&mut self, sample: u8) {
+        let target = (sample as u16) << 8;
+        if target > self.level {
+            let rise = (target - self.level) as u32 * self.attack as u32 / 255;
+            self.level += rise.max(1) as u16;
+        } else {
+            let fall = (self.level - target) as u32 * self.decay as u32 / 255;
+            self.level -= fall.max(1).min((self.level - target) as u32) as u16;
+        }
+
+        if target > self.peak {
+            self.peak = target;
+        } else {
+            // Release the peak over a few hundred samples
+            self.peak = (self.peak - (self.peak >> 8).max(1)).max(AGC_MIN_PEAK);
+        }
+    }
+
+    /// Returns the smoothed level (0–255), after automatic gain control if enabled.
+    pub fn level(&self) -> u8 {
+        if self.auto_gain {
+            (self.level as u32 * 255 / self.peak as u32).min(255) as u8
+        } else {
+            (self.level >> 8) as u8
+        }
+    }
+
+    /// Returns a reference to the wrapped effect.
+    pub fn inner(&self) -> &E {
+        &self.effect
+    }
+
+    /// Returns a mutable reference to the wrapped effect.
+    pub fn inner_mut(&mut self) -> &mut E {
+        &mut self.effect
+    }
+
+    /// Consumes the wrapper and returns the wrapped effect.
+    pub fn into_inner(self) -> E {
+        self.effect
+    }
+
+    /// Maps the level onto the range from `min` to `max`.
+    fn scaled(&self, min: u16, max: u16) -> u16 {
+        let delta = max as i32 - min as i32;
+        (min as i32 + delta * self.level() as i32 / 255) as u16
+    }
+
+    fn apply(&self, buffer: &mut [RGB8]) {
+        if let Modulation::Brightness { min, max } = self.modulation {
+            let brightness = self.scaled(min as u16, max as u16) as u8;
+            if brightness == u8::MAX {
+                return;
+            }
+            for led in buffer.iter_mut() {
+                *led = scale_brightness(*led, brightness);
+            }
+        }
+    }
+
+    /// Fills the buffer with the modulated frame without advancing.
+    pub fn current(&self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        self.effect.current(buffer)?;
+        self.apply(buffer);
+        Ok(())
+    }
+
+    /// Fills the buffer with the modulated frame and advances the wrapped effect.
+    ///
+    /// In [`Modulation::Speed`] the effect advances by the steps due at the
+    /// current level, which may be none.
+    pub fn update(&mut self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        match self.modulation {
+            Modulation::Brightness { .. } => self.effect.update(buffer)?,
+            Modulation::Speed { min, max } => {
+                let tempo = self.scaled(min, max);
+                match tempo_steps(&mut self.carry, tempo) {
+                    0 => self.effect.current(buffer)?,
+                    steps => {
+                        self.effect.update(buffer)?;
+                        self.effect.advance(steps - 1)?;
+                    }
+                }
+            }
+        }
+        self.apply(buffer);
+        Ok(())
+    }
+
+    /// Resets the wrapped effect and forgets the audio level.
+    pub fn reset(&mut self) {
+        self.effect.reset();
+        self.level = 0;
+        self.peak = AGC_MIN_PEAK;
+        self.carry = 0;
+    }
+}
+
+impl<E: Effect> Effect for AudioReactive<E> {
+    fn update(&mut self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        self.update(buffer)
+    }
+
+    fn current(&self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        self.current(buffer)
+    }
+
+    fn reset(&mut self) {
+        self.reset();
+    }
+
+    fn advance(&mut self, steps: u32) -> Result<(), EffectError> {
+        self.effect.advance(steps)
+    }
+
+    fn is_finished(&self) -> bool {
+        self.effect.is_finished()
+    }
+
+    fn set_phase(&mut self, phase: u8) {
+        self.effect.set_phase(phase);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FlashEffect, SpinnerEffect};
+
+    fn solid() -> FlashEffect {
+        FlashEffect::new(2).unwrap().with_duty(255, 1).unwrap()
+    }
+
+    #[test]
+    fn test_instant_attack_follows_sample() {
+        let mut reactive = AudioReactive::new(solid()).with_smoothing(255, 255);
+        reactive.push_sample(200);
+        assert_eq!(reactive.level(), 200);
+        reactive.push_sample(10);
+        assert_eq!(reactive.level(), 10);
+    }
+
+    #[test]
+    fn test_slow_decay_fades_out() {
+        let mut reactive = AudioReactive::new(solid()).with_smoothing(255, 32);
+        reactive.push_sample(255);
+        let mut levels = Vec::new();
+        for _ in 0..4 {
+            reactive.push_sample(0);
+            levels.push(reactive.level());
+        }
+        assert!(levels.windows(2).all(|pair| pair[0] > pair[1]));
+        assert!(levels[3] > 100);
+
+        for _ in 0..200 {
+            reactive.push_sample(0);
+        }
+        assert_eq!(reactive.level(), 0);
+    }
+
+    #[test]
+    fn test_brightness_follows_level() {
+        let mut reactive = AudioReactive::new(solid())
+            .with_smoothing(255, 255)
+            .with_modulation(Modulation::Brightness { min: 0, max: 255 });
+        let mut buffer = [RGB8::default(); 2];
+
+        reactive.update(&mut buffer).unwrap();
+        assert_eq!(buffer[0], RGB8::default());
+
+        reactive.push_sample(128);
+        reactive.current(&mut buffer).unwrap();
+        assert_eq!(buffer[0], RGB8::new(128, 128, 128));
+    }
+
+    #[test]
+    fn test_brightness_floor_keeps_leds_lit() {
+        let reactive = AudioReactive::new(solid())
+            .with_modulation(Modulation::Brightness { min: 64, max: 255 });
+        let mut buffer = [RGB8::default(); 2];
+        reactive.current(&mut buffer).unwrap();
+        assert_eq!(buffer[0], RGB8::new(64, 64, 64));
+    }
+
+    #[test]
+    fn test_speed_follows_level() {
+        let spinner = SpinnerEffect::new(64).unwrap().with_tail_length(0);
+        let mut reactive = AudioReactive::new(spinner)
+            .with_smoothing(255, 255)
+            .with_modulation(Modulation::Speed { min: 0, max: 400 });
+        let mut buffer = [RGB8::default(); 64];
+        let head = |buffer: &[RGB8]| buffer.iter().position(|&led| led != RGB8::default());
+
+        // Silence stops the spinner
+        reactive.update(&mut buffer).unwrap();
+        reactive.update(&mut buffer).unwrap();
+        assert_eq!(head(&buffer), Some(0));
+
+        // Full level runs it at four times its speed
+        reactive.push_sample(255);
+        reactive.update(&mut buffer).unwrap();
+        reactive.update(&mut buffer).unwrap();
+        assert_eq!(head(&buffer), Some(4));
+    }
+
+    #[test]
+    fn test_auto_gain_normalizes_quiet_signal() {
+        let mut reactive = AudioReactive::new(solid())
+            .with_smoothing(255, 255)
+            .with_auto_gain(true);
+        for _ in 0..10 {
+            reactive.push_sample(80);
+        }
+        assert_eq!(reactive.level(), 255);
+
+        // Background noise below the minimum peak is not amplified to full scale
+        let mut quiet = AudioReactive::new(solid())
+            .with_smoothing(255, 255)
+            .with_auto_gain(true);
+        quiet.push_sample(8);
+        assert_eq!(quiet.level(), 63);
+    }
+
+    #[test]
+    fn test_reset_forgets_level() {
+        let mut reactive = AudioReactive::new(solid()).with_smoothing(255, 255);
+        reactive.push_sample(255);
+        reactive.reset();
+        assert_eq!(reactive.level(), 0);
+    }
+
+    #[test]
+    fn test_trait_object_update() {
+        let mut reactive = AudioReactive::new(solid()).with_smoothing(255, 255);
+        reactive.push_sample(255);
+        let effect_ref: &mut dyn Effect = &mut reactive;
+        let mut buffer = [RGB8::default(); 2];
+        effect_ref.update(&mut buffer).unwrap();
+        assert_eq!(buffer[0], RGB8::new(255, 255, 255));
+    }
+}
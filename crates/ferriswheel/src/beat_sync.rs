@@ -0,0 +1,229 @@
+//! Beat-aligned animations.
+//!
+//! Free-running effects drift away from music within a few bars. [`BeatSync`]
+//! snaps the wrapped effect to a fixed phase every time a beat is reported,
+//! e.g. by a beat detector or a MIDI clock, so flashes land on the beat.
+
+use crate::effect::{Effect, EffectError};
+use rgb::RGB8;
+
+/// Wraps an effect and realigns its phase on every beat.
+///
+/// Between beats the wrapped effect runs at its own speed. On
+/// [`beat`](BeatSync::beat) it jumps to the configured beat phase with
+/// [`Effect::set_phase`]. Set the effect's speed close to the tempo so the
+/// snaps stay small; only the drift is corrected.
+///
+/// # Example
+///
+/// ```
+/// use ferriswheel::{BeatSync, Effect, FlashEffect};
+/// use rgb::RGB8;
+///
+/// let flash = FlashEffect::new(12).unwrap().with_duty(3, 20).unwrap();
+/// let mut synced = BeatSync::new(flash);
+/// let mut buffer = [RGB8::default(); 12];
+///
+/// // In the render loop
+/// let beat_detected = true;
+/// if beat_detected {
+///     synced.beat();
+/// }
+/// synced.update(&mut buffer).unwrap();
+/// assert_eq!(buffer[0], RGB8::new(255, 255, 255));
+/// ```
+#[derive(Debug, Clone)]
+pub struct BeatSync<E> {
+    effect: E,
+    beat_phase: u8,
+    beats: u32,
+}
+
+impl<E: Effect> BeatSync<E> {
+    /// Wraps `effect`.
+    ///
+    /// # Default Configuration
+    ///
+    /// - Beat phase: 0 (start of the effect's cycle)
+    pub fn new(effect: E) -> Self {
+        Self {
+            effect,
+            beat_phase: 0,
+            beats: 0,
+        }
+    }
+
+    /// Sets the phase the effect jumps to on each beat.
+    ///
+    /// For example, 128 lands a [`PulseEffect`](crate::PulseEffect) with the
+    /// [`Sine`](crate::Waveform::Sine) waveform at its brightest point on the beat.
+    pub fn with_beat_phase(mut self, phase: u8) -> Self {
+        self.beat_phase = phase;
+        self
+    }
+
+    /// Changes the phase the effect jumps to on each beat.
+    pub fn set_beat_phase(&mut self, phase: u8) {
+        self.beat_phase = phase;
+    }
+
+    /// Returns the phase the effect jumps to on each beat.
+    pub fn beat_phase(&self) -> u8 {
+        self.beat_phase
+    }
+
+    /// Reports a beat and snaps the wrapped effect to the beat phase.
+    pub fn beat(&mut self) {
+        self.beats = self.beats.wrapping_add(1);
+        self.effect.set_phase(self.beat_phase);
+    }
+
+    /// Returns the number of beats reported since creation or the last reset.
+    pub fn beats(&self) -> u32 {
+        self.beats
+    }
+
+    /// Returns a reference to the wrapped effect.
+    pub fn inner(&self) -> &E {
+        &self.effect
+    }
+
+    /// Returns a mutable reference to the wrapped effect.
+    pub fn inner_mut(&mut self) -> &mut E {
+        &mut self.effect
+    }
+
+    /// Consumes the wrapper and returns the wrapped effect.
+    pub fn into_inner(self) -> E {
+        self.effect
+    }
+
+    /// Fills the buffer without advancing.
+    pub fn current(&self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        self.effect.current(buffer)
+    }
+
+    /// Fills the buffer and advances the wrapped effect.
+    pub fn update(&mut self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        self.effect.update(buffer)
+    }
+
+    /// Resets the wrapped effect and the beat counter.
+    pub fn reset(&mut self) {
+        self.beats = 0;
+        self.effect.reset();
+    }
+}
+
+impl<E: Effect> Effect for BeatSync<E> {
+    fn update(&mut self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        self.update(buffer)
+    }
+
+    fn current(&self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        self.current(buffer)
+    }
+
+    fn reset(&mut self) {
+        self.reset();
+    }
+
+    fn advance(&mut self, steps: u32) -> Result<(), EffectError> {
+        self.effect.advance(steps)
+    }
+
+    fn is_finished(&self) -> bool {
+        self.effect.is_finished()
+    }
+
+    fn set_phase(&mut self, phase: u8) {
+        self.effect.set_phase(phase);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Brightness, FlashEffect, PulseEffect, SpinnerEffect, Waveform};
+
+    const WHITE: RGB8 = RGB8::new(255, 255, 255);
+
+    fn flash() -> FlashEffect {
+        FlashEffect::new(2).unwrap().with_duty(2, 6).unwrap()
+    }
+
+    #[test]
+    fn test_beat_restarts_flash() {
+        let mut synced = BeatSync::new(flash());
+        let mut buffer = [RGB8::default(); 2];
+        for _ in 0..5 {
+            synced.update(&mut buffer).unwrap();
+        }
+        assert_eq!(buffer[0], RGB8::default());
+
+        synced.beat();
+        synced.update(&mut buffer).unwrap();
+        assert_eq!(buffer[0], WHITE);
+        assert_eq!(synced.beats(), 1);
+    }
+
+    #[test]
+    fn test_beat_phase_offsets_snap() {
+        let pulse = PulseEffect::new(1).unwrap().with_waveform(Waveform::Sine);
+        let mut synced = BeatSync::new(pulse).with_beat_phase(128);
+        let mut buffer = [RGB8::default(); 1];
+        synced.beat();
+        synced.current(&mut buffer).unwrap();
+        assert_eq!(buffer[0], WHITE);
+    }
+
+    #[test]
+    fn test_beat_reaches_through_wrappers() {
+        let mut synced = BeatSync::new(Brightness::new(flash(), 255));
+        let mut buffer = [RGB8::default(); 2];
+        synced.inner_mut().advance(4).unwrap();
+        synced.beat();
+        synced.current(&mut buffer).unwrap();
+        assert_eq!(buffer[0], WHITE);
+    }
+
+    #[test]
+    fn test_default_set_phase_restarts() {
+        struct Counter(u32);
+        impl Effect for Counter {
+            fn update(&mut self, _buffer: &mut [RGB8]) -> Result<(), EffectError> {
+                self.0 += 1;
+                Ok(())
+            }
+            fn current(&self, _buffer: &mut [RGB8]) -> Result<(), EffectError> {
+                Ok(())
+            }
+            fn reset(&mut self) {
+                self.0 = 0;
+            }
+        }
+
+        let mut synced = BeatSync::new(Counter(7)).with_beat_phase(200);
+        synced.beat();
+        assert_eq!(synced.inner().0, 0);
+    }
+
+    #[test]
+    fn test_reset_clears_beats() {
+        let mut synced = BeatSync::new(SpinnerEffect::new(4).unwrap());
+        synced.beat();
+        synced.beat();
+        synced.reset();
+        assert_eq!(synced.beats(), 0);
+    }
+
+    #[test]
+    fn test_trait_object_update() {
+        let mut synced = BeatSync::new(flash());
+        synced.beat();
+        let effect_ref: &mut dyn Effect = &mut synced;
+        let mut buffer = [RGB8::default(); 2];
+        effect_ref.update(&mut buffer).unwrap();
+        assert_eq!(buffer[0], WHITE);
+    }
+}
@@ -0,0 +1,196 @@
+//! Master brightness control for any effect.
+
+use crate::effect::{Effect, EffectError};
+use crate::util::scale_brightness;
+use rgb::RGB8;
+
+/// Wraps an effect and scales every rendered frame by a master brightness.
+///
+/// The brightness can be changed at any time without disturbing the wrapped
+/// effect's animation, e.g. to switch a device into a dimmed night mode.
+///
+/// Scaling applies to the whole buffer passed to `update()`/`current()`,
+/// so pass a slice of exactly the LEDs the effect drives.
+///
+/// # Example
+///
+/// ```
+/// use ferriswheel::{Brightness, Effect, RainbowEffect};
+/// use rgb::RGB8;
+///
+/// let rainbow = RainbowEffect::new(12).unwrap();
+/// let mut dimmed = Brightness::new(rainbow, 255);
+/// let mut buffer = [RGB8::default(); 12];
+///
+/// dimmed.update(&mut buffer).unwrap();
+///
+/// // Night mode
+/// dimmed.set_brightness(32);
+/// dimmed.update(&mut buffer).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Brightness<E> {
+    effect: E,
+    brightness: u8,
+}
+
+impl<E: Effect> Brightness<E> {
+    /// Wraps `effect` with the given master brightness (0–255).
+    pub fn new(effect: E, brightness: u8) -> Self {
+        Self { effect, brightness }
+    }
+
+    /// Changes the master brightness (0–255).
+    pub fn set_brightness(&mut self, brightness: u8) {
+        self.brightness = brightness;
+    }
+
+    /// Returns the master brightness.
+    pub fn brightness(&self) -> u8 {
+        self.brightness
+    }
+
+    /// Returns a reference to the wrapped effect.
+    pub fn inner(&self) -> &E {
+        &self.effect
+    }
+
+    /// Returns a mutable reference to the wrapped effect.
+    pub fn inner_mut(&mut self) -> &mut E {
+        &mut self.effect
+    }
+
+    /// Consumes the wrapper and returns the wrapped effect.
+    pub fn into_inner(self) -> E {
+        self.effect
+    }
+
+    fn apply(&self, buffer: &mut [RGB8]) {
+        if self.brightness == u8::MAX {
+            return;
+        }
+        for led in buffer.iter_mut() {
+            *led = scale_brightness(*led, self.brightness);
+        }
+    }
+
+    /// Fills the buffer with the dimmed frame without advancing.
+    pub fn current(&self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        self.effect.current(buffer)?;
+        self.apply(buffer);
+        Ok(())
+    }
+
+    /// Fills the buffer with the dimmed frame and advances the wrapped effect.
+    pub fn update(&mut self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        self.effect.update(buffer)?;
+        self.apply(buffer);
+        Ok(())
+    }
+
+    /// Resets the wrapped effect; the master brightness is kept.
+    pub fn reset(&mut self) {
+        self.effect.reset();
+    }
+}
+
+impl<E: Effect> Effect for Brightness<E> {
+    fn update(&mut self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        self.update(buffer)
+    }
+
+    fn current(&self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        self.current(buffer)
+    }
+
+    fn reset(&mut self) {
+        self.reset();
+    }
+
+    fn advance(&mut self, steps: u32) -> Result<(), EffectError> {
+        self.effect.advance(steps)
+    }
+
+    fn is_finished(&self) -> bool {
+        self.effect.is_finished()
+    }
+
+    fn set_phase(&mut self, phase: u8) {
+        self.effect.set_phase(phase);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FlashEffect, SpinnerEffect};
+
+    fn solid() -> FlashEffect {
+        FlashEffect::new(4)
+            .unwrap()
+            .with_color(RGB8::new(200, 100, 50))
+            .with_duty(255, 1)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_full_brightness_is_unchanged() {
+        let mut effect = Brightness::new(solid(), 255);
+        let mut buffer = [RGB8::default(); 4];
+        effect.update(&mut buffer).unwrap();
+        assert_eq!(buffer, [RGB8::new(200, 100, 50); 4]);
+    }
+
+    #[test]
+    fn test_frame_is_scaled() {
+        let mut effect = Brightness::new(solid(), 128);
+        let mut buffer = [RGB8::default(); 4];
+        effect.update(&mut buffer).unwrap();
+        assert_eq!(buffer, [scale_brightness(RGB8::new(200, 100, 50), 128); 4]);
+    }
+
+    #[test]
+    fn test_zero_brightness_is_dark() {
+        let effect = Brightness::new(solid(), 0);
+        let mut buffer = [RGB8::new(1, 1, 1); 4];
+        effect.current(&mut buffer).unwrap();
+        assert_eq!(buffer, [RGB8::default(); 4]);
+    }
+
+    #[test]
+    fn test_set_brightness_keeps_animation_state() {
+        let spinner = SpinnerEffect::new(8).unwrap().with_tail_length(0);
+        let mut effect = Brightness::new(spinner, 255);
+        let mut buffer = [RGB8::default(); 8];
+        for _ in 0..3 {
+            effect.update(&mut buffer).unwrap();
+        }
+
+        effect.set_brightness(64);
+        effect.current(&mut buffer).unwrap();
+        assert_eq!(buffer[3], scale_brightness(RGB8::new(255, 255, 255), 64));
+        assert_eq!(effect.brightness(), 64);
+    }
+
+    #[test]
+    fn test_errors_are_passed_through() {
+        let effect = Brightness::new(solid(), 128);
+        let mut buffer = [RGB8::default(); 2];
+        assert_eq!(
+            effect.current(&mut buffer).unwrap_err(),
+            EffectError::BufferTooSmall {
+                required: 4,
+                actual: 2
+            }
+        );
+    }
+
+    #[test]
+    fn test_trait_object_update() {
+        let mut effect = Brightness::new(solid(), 0);
+        let effect_ref: &mut dyn Effect = &mut effect;
+        let mut buffer = [RGB8::new(9, 9, 9); 4];
+        effect_ref.update(&mut buffer).unwrap();
+        assert_eq!(buffer, [RGB8::default(); 4]);
+    }
+}
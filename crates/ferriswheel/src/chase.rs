@@ -2,11 +2,16 @@
 //!
 //! A solid block of lit LEDs moves around the ring, distinct from
 //! [`SpinnerEffect`](crate::SpinnerEffect) which uses a single dot with a fading tail.
+//!
+//! The segment position is tracked in 1/256 LED steps, so slow chases can move
+//! by less than one LED per update. With soft edges enabled the first and last
+//! LED of the segment are blended according to that fractional position.
 
 use crate::effect::{
-    advance_position, validate_buffer, validate_num_leds, validate_speed, Direction, Effect,
-    EffectError,
+    advance_position_fine, validate_buffer, validate_fine_speed, validate_num_leds, validate_speed,
+    Direction, Effect, EffectError,
 };
+use crate::util::scale_brightness;
 use rgb::RGB8;
 
 /// A chase effect where a solid segment moves around the ring.
@@ -31,10 +36,13 @@ use rgb::RGB8;
 pub struct ChaseEffect {
     num_leds: usize,
     color: RGB8,
-    position: u8,
-    speed: u8,
+    /// Segment start in 1/256 LED units (Q8.8).
+    position: u32,
+    /// Position increment per update in 1/256 LED units (Q8.8).
+    speed: u16,
     segment_length: u8,
     direction: Direction,
+    soft_edges: bool,
 }
 
 impl ChaseEffect {
@@ -43,7 +51,8 @@ impl ChaseEffect {
     /// # Errors
     ///
     /// Returns `EffectError::ZeroLeds` if `num_leds` is 0.
-    /// Returns `EffectError::TooManyLeds` if `num_leds` exceeds `MAX_LEDS`.
+    /// Returns `EffectError::TooManyLeds` if `num_leds` exceeds `MAX_LEDS`
+    /// (`MAX_LEDS_ALLOC` with the `alloc` feature).
     ///
     /// # Default Configuration
     ///
@@ -51,6 +60,7 @@ impl ChaseEffect {
     /// - Speed: 1
     /// - Segment length: 3
     /// - Direction: Clockwise
+    /// - Soft edges: disabled
     pub fn new(num_leds: usize) -> Result<Self, EffectError> {
         validate_num_leds(num_leds)?;
 
@@ -58,9 +68,10 @@ impl ChaseEffect {
             num_leds,
             color: RGB8::new(255, 255, 255),
             position: 0,
-            speed: 1,
+            speed: 256,
             segment_length: 3,
             direction: Direction::Clockwise,
+            soft_edges: false,
         })
     }
 
@@ -76,8 +87,21 @@ impl ChaseEffect {
     ///
     /// Returns `EffectError::ZeroStep` if `speed` is 0.
     pub fn with_speed(mut self, speed: u8) -> Result<Self, EffectError> {
-        validate_speed(speed)?;
-        self.speed = speed;
+        self.set_speed(speed)?;
+        Ok(self)
+    }
+
+    /// Sets the animation speed in 1/256 LED steps per update.
+    ///
+    /// A value of 256 equals `with_speed(1)`; smaller values move the segment
+    /// by a fraction of an LED per update. Combine with
+    /// [`with_soft_edges`](Self::with_soft_edges) for smooth slow chases.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::ZeroStep` if `speed` is 0.
+    pub fn with_fine_speed(mut self, speed: u16) -> Result<Self, EffectError> {
+        self.set_fine_speed(speed)?;
         Ok(self)
     }
 
@@ -93,6 +117,90 @@ impl ChaseEffect {
         self
     }
 
+    /// Enables anti-aliased segment edges.
+    ///
+    /// When enabled, the LEDs at both ends of the segment are rendered at
+    /// partial brightness according to the fractional segment position.
+    /// When disabled, the segment snaps to whole LEDs.
+    pub fn with_soft_edges(mut self, soft_edges: bool) -> Self {
+        self.soft_edges = soft_edges;
+        self
+    }
+
+    /// Changes the segment color.
+    pub fn set_color(&mut self, color: RGB8) {
+        self.color = color;
+    }
+
+    /// Changes the animation speed without restarting the animation.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::ZeroStep` if `speed` is 0.
+    pub fn set_speed(&mut self, speed: u8) -> Result<(), EffectError> {
+        validate_speed(speed)?;
+        self.speed = (speed as u16) << 8;
+        Ok(())
+    }
+
+    /// Changes the animation speed in 1/256 LED steps without restarting the animation.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::ZeroStep` if `speed` is 0.
+    pub fn set_fine_speed(&mut self, speed: u16) -> Result<(), EffectError> {
+        validate_fine_speed(speed)?;
+        self.speed = speed;
+        Ok(())
+    }
+
+    /// Changes the number of LEDs in the moving segment.
+    pub fn set_segment_length(&mut self, segment_length: u8) {
+        self.segment_length = segment_length;
+    }
+
+    /// Changes the movement direction; the segment continues from its current position.
+    pub fn set_direction(&mut self, direction: Direction) {
+        self.direction = direction;
+    }
+
+    /// Enables or disables anti-aliased segment edges.
+    pub fn set_soft_edges(&mut self, soft_edges: bool) {
+        self.soft_edges = soft_edges;
+    }
+
+    /// Returns the segment color.
+    pub fn color(&self) -> RGB8 {
+        self.color
+    }
+
+    /// Returns the animation speed in whole LED steps per update.
+    ///
+    /// Fractional speeds are truncated; see [`fine_speed`](Self::fine_speed).
+    pub fn speed(&self) -> u8 {
+        (self.speed >> 8) as u8
+    }
+
+    /// Returns the animation speed in 1/256 LED steps per update.
+    pub fn fine_speed(&self) -> u16 {
+        self.speed
+    }
+
+    /// Returns the number of LEDs in the moving segment.
+    pub fn segment_length(&self) -> u8 {
+        self.segment_length
+    }
+
+    /// Returns the movement direction.
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    /// Returns `true` if anti-aliased segment edges are enabled.
+    pub fn soft_edges(&self) -> bool {
+        self.soft_edges
+    }
+
     /// Returns the number of LEDs this effect is configured for.
     pub fn num_leds(&self) -> usize {
         self.num_leds
@@ -109,10 +217,27 @@ impl ChaseEffect {
             *led = RGB8::new(0, 0, 0);
         }
 
-        // Fill the segment at the current position (wrapping around)
-        for i in 0..self.segment_length as usize {
-            let idx = (self.position as usize + i) % n;
-            buffer[idx] = self.color;
+        let start = (self.position >> 8) as usize;
+        let fraction = (self.position & 0xFF) as u8;
+
+        if self.soft_edges && fraction > 0 && self.segment_length > 0 {
+            // The segment covers [position, position + length), so the first LED
+            // is only partially covered and the LED past the end picks up the rest.
+            let len = self.segment_length as usize;
+            buffer[start % n] = scale_brightness(self.color, 255 - fraction);
+            for i in 1..len {
+                buffer[(start + i) % n] = self.color;
+            }
+            let tail = (start + len) % n;
+            if len < n {
+                buffer[tail] = scale_brightness(self.color, fraction);
+            }
+        } else {
+            // Fill the segment at the current position (wrapping around)
+            for i in 0..self.segment_length as usize {
+                let idx = (start + i) % n;
+                buffer[idx] = self.color;
+            }
         }
 
         Ok(())
@@ -121,7 +246,14 @@ impl ChaseEffect {
     /// Fills the buffer with chase state and advances the animation.
     pub fn update(&mut self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
         self.current(buffer)?;
-        self.position = advance_position(self.position, self.speed, self.num_leds, self.direction);
+        self.advance(1)
+    }
+
+    /// Advances the animation by `steps` updates without rendering.
+    pub fn advance(&mut self, steps: u32) -> Result<(), EffectError> {
+        let distance = self.speed as u64 * steps as u64;
+        self.position =
+            advance_position_fine(self.position, distance, self.num_leds, self.direction);
         Ok(())
     }
 
@@ -129,6 +261,14 @@ impl ChaseEffect {
     pub fn reset(&mut self) {
         self.position = 0;
     }
+
+    /// Jumps to `phase` (0–255) of a full revolution in the configured direction.
+    ///
+    /// Phase 64 on a 12-LED ring puts the segment at LED 3 clockwise.
+    pub fn set_phase(&mut self, phase: u8) {
+        let distance = phase as u64 * self.num_leds as u64;
+        self.position = advance_position_fine(0, distance, self.num_leds, self.direction);
+    }
 }
 
 impl Effect for ChaseEffect {
@@ -143,6 +283,14 @@ impl Effect for ChaseEffect {
     fn reset(&mut self) {
         self.reset();
     }
+
+    fn advance(&mut self, steps: u32) -> Result<(), EffectError> {
+        self.advance(steps)
+    }
+
+    fn set_phase(&mut self, phase: u8) {
+        self.set_phase(phase);
+    }
 }
 
 #[cfg(test)]
@@ -196,6 +344,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::needless_range_loop)]
     fn test_non_segment_pixels_are_off() {
         let effect = ChaseEffect::new(8)
             .unwrap()
@@ -288,6 +437,85 @@ mod tests {
         assert_eq!(initial, after_reset);
     }
 
+    #[test]
+    fn test_with_fine_speed_zero_returns_error() {
+        let result = ChaseEffect::new(12).unwrap().with_fine_speed(0);
+        assert_eq!(result.unwrap_err(), EffectError::ZeroStep);
+    }
+
+    #[test]
+    fn test_fine_speed_without_soft_edges_snaps_to_whole_leds() {
+        let mut effect = ChaseEffect::new(8)
+            .unwrap()
+            .with_color(RGB8::new(255, 0, 0))
+            .with_segment_length(2)
+            .with_fine_speed(128)
+            .unwrap();
+
+        let mut buffer = [RGB8::default(); 8];
+        effect.update(&mut buffer).unwrap();
+
+        // Half an LED in, the hard-edged segment still covers 0 and 1
+        effect.current(&mut buffer).unwrap();
+        assert_eq!(buffer[0], RGB8::new(255, 0, 0));
+        assert_eq!(buffer[1], RGB8::new(255, 0, 0));
+        assert_eq!(buffer[2], RGB8::new(0, 0, 0));
+    }
+
+    #[test]
+    fn test_soft_edges_blend_boundary_leds() {
+        let mut effect = ChaseEffect::new(8)
+            .unwrap()
+            .with_color(RGB8::new(255, 0, 0))
+            .with_segment_length(2)
+            .with_soft_edges(true)
+            .with_fine_speed(64)
+            .unwrap();
+
+        let mut buffer = [RGB8::default(); 8];
+        effect.update(&mut buffer).unwrap();
+
+        // Segment now spans 0.25..2.25
+        effect.current(&mut buffer).unwrap();
+        assert_eq!(buffer[0], scale_brightness(RGB8::new(255, 0, 0), 191));
+        assert_eq!(buffer[1], RGB8::new(255, 0, 0));
+        assert_eq!(buffer[2], scale_brightness(RGB8::new(255, 0, 0), 64));
+        assert_eq!(buffer[3], RGB8::new(0, 0, 0));
+    }
+
+    #[test]
+    fn test_soft_edges_whole_position_matches_hard_edges() {
+        let soft = ChaseEffect::new(8).unwrap().with_soft_edges(true);
+        let hard = ChaseEffect::new(8).unwrap();
+
+        let mut soft_buf = [RGB8::default(); 8];
+        let mut hard_buf = [RGB8::default(); 8];
+        soft.current(&mut soft_buf).unwrap();
+        hard.current(&mut hard_buf).unwrap();
+
+        assert_eq!(soft_buf, hard_buf);
+    }
+
+    #[test]
+    fn test_soft_edges_wrap_around_ring() {
+        let mut effect = ChaseEffect::new(4)
+            .unwrap()
+            .with_color(RGB8::new(0, 0, 255))
+            .with_segment_length(1)
+            .with_soft_edges(true)
+            .with_fine_speed(128)
+            .unwrap()
+            .with_direction(Direction::CounterClockwise);
+
+        let mut buffer = [RGB8::default(); 4];
+        effect.update(&mut buffer).unwrap();
+
+        // Position is 3.5: LED 3 and LED 0 share the segment
+        effect.current(&mut buffer).unwrap();
+        assert_eq!(buffer[3], scale_brightness(RGB8::new(0, 0, 255), 127));
+        assert_eq!(buffer[0], scale_brightness(RGB8::new(0, 0, 255), 128));
+    }
+
     #[test]
     fn test_trait_object_update() {
         let mut effect = ChaseEffect::new(8)
@@ -306,4 +534,45 @@ mod tests {
 
         assert_ne!(buf1, buf2, "chase should advance between updates");
     }
+
+    #[test]
+    fn test_set_direction_preserves_position() {
+        let mut effect = ChaseEffect::new(8)
+            .unwrap()
+            .with_segment_length(1)
+            .with_speed(1)
+            .unwrap();
+        let mut buffer = [RGB8::default(); 8];
+        for _ in 0..3 {
+            effect.update(&mut buffer).unwrap();
+        }
+
+        effect.set_direction(Direction::CounterClockwise);
+        effect.update(&mut buffer).unwrap();
+        assert_ne!(buffer[3], RGB8::default(), "segment should resume at LED 3");
+        effect.current(&mut buffer).unwrap();
+        assert_ne!(buffer[2], RGB8::default(), "then move backwards to LED 2");
+        assert_eq!(effect.direction(), Direction::CounterClockwise);
+    }
+
+    #[test]
+    fn test_advance_matches_repeated_update() {
+        let mut updated = ChaseEffect::new(8)
+            .unwrap()
+            .with_direction(Direction::CounterClockwise)
+            .with_fine_speed(300)
+            .unwrap();
+        let mut advanced = updated.clone();
+        let mut buf_updated = [RGB8::default(); 8];
+        let mut buf_advanced = [RGB8::default(); 8];
+
+        for _ in 0..123 {
+            updated.update(&mut buf_updated).unwrap();
+        }
+        advanced.advance(123).unwrap();
+
+        updated.current(&mut buf_updated).unwrap();
+        advanced.current(&mut buf_advanced).unwrap();
+        assert_eq!(buf_updated, buf_advanced);
+    }
 }
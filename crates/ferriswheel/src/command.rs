@@ -0,0 +1,241 @@
+//! Runtime control from interrupts and other contexts.
+//!
+//! Button handlers and interrupts should not hold `&mut` references to effects.
+//! They describe what should change as an [`EffectCommand`] and push it to a
+//! shared [`CommandQueue`]; the render loop drains the queue before each frame
+//! and applies the commands to its own effects.
+
+use core::cell::RefCell;
+use critical_section::Mutex;
+
+/// A control request for the render loop.
+///
+/// The payloads are plain numbers so commands stay `Copy` and can be created in
+/// interrupt handlers. Their meaning is up to the render loop, e.g. a scene key
+/// for a [`SceneManager`](crate::SceneManager) or the brightness of a
+/// [`Brightness`](crate::Brightness) wrapper.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum EffectCommand {
+    /// Switch to the scene with this key.
+    SetScene(u8),
+    /// Set the master brightness (0–255).
+    SetBrightness(u8),
+    /// Set the animation speed.
+    SetSpeed(u8),
+    /// Fire an application-defined event, e.g. a one-shot flash.
+    Trigger(u8),
+}
+
+struct Ring<const N: usize> {
+    commands: [EffectCommand; N],
+    head: usize,
+    len: usize,
+}
+
+/// A fixed-capacity FIFO of [`EffectCommand`]s shared between contexts.
+///
+/// Every operation runs inside a `critical_section::with` block, so the queue
+/// can live in a `static` and be pushed to from interrupt handlers while the
+/// render loop pops from it. The firmware must provide a critical-section
+/// implementation, as HAL crates usually do.
+///
+/// # Example
+///
+/// ```
+/// use ferriswheel::{CommandQueue, EffectCommand};
+///
+/// static COMMANDS: CommandQueue<8> = CommandQueue::new();
+///
+/// // In a button interrupt
+/// COMMANDS.push(EffectCommand::SetScene(2)).ok();
+///
+/// // In the render loop
+/// while let Some(command) = COMMANDS.pop() {
+///     match command {
+///         EffectCommand::SetScene(key) => { /* scenes.set_scene(key) */ }
+///         EffectCommand::SetBrightness(level) => { /* brightness.set_brightness(level) */ }
+///         EffectCommand::SetSpeed(_) | EffectCommand::Trigger(_) => {}
+///     }
+/// }
+/// ```
+pub struct CommandQueue<const N: usize> {
+    ring: Mutex<RefCell<Ring<N>>>,
+}
+
+impl<const N: usize> CommandQueue<N> {
+    /// Creates an empty queue.
+    pub const fn new() -> Self {
+        Self {
+            ring: Mutex::new(RefCell::new(Ring {
+                commands: [EffectCommand::Trigger(0); N],
+                head: 0,
+                len: 0,
+            })),
+        }
+    }
+
+    /// Appends a command.
+    ///
+    /// # Errors
+    ///
+    /// Returns the command back if the queue already holds `N` commands.
+    pub fn push(&self, command: EffectCommand) -> Result<(), EffectCommand> {
+        critical_section::with(|cs| {
+            let mut ring = self.ring.borrow_ref_mut(cs);
+            if ring.len == N {
+                return Err(command);
+            }
+            let tail = (ring.head + ring.len) % N;
+            ring.commands[tail] = command;
+            ring.len += 1;
+            Ok(())
+        })
+    }
+
+    /// Removes and returns the oldest command.
+    pub fn pop(&self) -> Option<EffectCommand> {
+        critical_section::with(|cs| {
+            let mut ring = self.ring.borrow_ref_mut(cs);
+            if ring.len == 0 {
+                return None;
+            }
+            let command = ring.commands[ring.head];
+            ring.head = (ring.head + 1) % N;
+            ring.len -= 1;
+            Some(command)
+        })
+    }
+
+    /// Returns an iterator that pops commands until the queue is empty.
+    ///
+    /// Commands pushed while draining are returned as well.
+    pub fn drain(&self) -> impl Iterator<Item = EffectCommand> + '_ {
+        core::iter::from_fn(|| self.pop())
+    }
+
+    /// Discards all queued commands.
+    pub fn clear(&self) {
+        critical_section::with(|cs| {
+            let mut ring = self.ring.borrow_ref_mut(cs);
+            ring.head = 0;
+            ring.len = 0;
+        });
+    }
+
+    /// Returns the number of queued commands.
+    pub fn len(&self) -> usize {
+        critical_section::with(|cs| self.ring.borrow_ref(cs).len)
+    }
+
+    /// Returns `true` if no commands are queued.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the maximum number of queued commands.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+}
+
+impl<const N: usize> Default for CommandQueue<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> core::fmt::Debug for CommandQueue<N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("CommandQueue")
+            .field("len", &self.len())
+            .field("capacity", &N)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commands_come_out_in_order() {
+        let queue: CommandQueue<4> = CommandQueue::new();
+        queue.push(EffectCommand::SetScene(1)).unwrap();
+        queue.push(EffectCommand::SetBrightness(64)).unwrap();
+
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue.pop(), Some(EffectCommand::SetScene(1)));
+        assert_eq!(queue.pop(), Some(EffectCommand::SetBrightness(64)));
+        assert_eq!(queue.pop(), None);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_full_queue_returns_command() {
+        let queue: CommandQueue<2> = CommandQueue::new();
+        queue.push(EffectCommand::Trigger(1)).unwrap();
+        queue.push(EffectCommand::Trigger(2)).unwrap();
+        assert_eq!(
+            queue.push(EffectCommand::Trigger(3)),
+            Err(EffectCommand::Trigger(3))
+        );
+        assert_eq!(queue.pop(), Some(EffectCommand::Trigger(1)));
+    }
+
+    #[test]
+    fn test_wraps_around() {
+        let queue: CommandQueue<3> = CommandQueue::new();
+        for round in 0..5 {
+            queue.push(EffectCommand::SetSpeed(round)).unwrap();
+            queue.push(EffectCommand::Trigger(round)).unwrap();
+            assert_eq!(queue.pop(), Some(EffectCommand::SetSpeed(round)));
+            assert_eq!(queue.pop(), Some(EffectCommand::Trigger(round)));
+        }
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_drain_empties_queue() {
+        let queue: CommandQueue<4> = CommandQueue::new();
+        queue.push(EffectCommand::SetScene(0)).unwrap();
+        queue.push(EffectCommand::SetScene(1)).unwrap();
+        queue.push(EffectCommand::SetScene(2)).unwrap();
+
+        let drained: Vec<EffectCommand> = queue.drain().collect();
+        assert_eq!(
+            drained,
+            [
+                EffectCommand::SetScene(0),
+                EffectCommand::SetScene(1),
+                EffectCommand::SetScene(2)
+            ]
+        );
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_clear() {
+        let queue: CommandQueue<4> = CommandQueue::new();
+        queue.push(EffectCommand::Trigger(0)).unwrap();
+        queue.clear();
+        assert!(queue.is_empty());
+        assert_eq!(queue.capacity(), 4);
+    }
+
+    #[test]
+    fn test_push_from_other_thread() {
+        static QUEUE: CommandQueue<16> = CommandQueue::new();
+        std::thread::spawn(|| {
+            for level in 0..8 {
+                QUEUE.push(EffectCommand::SetBrightness(level)).unwrap();
+            }
+        })
+        .join()
+        .unwrap();
+
+        let levels: Vec<EffectCommand> = QUEUE.drain().collect();
+        assert_eq!(levels.len(), 8);
+        assert_eq!(levels[7], EffectCommand::SetBrightness(7));
+    }
+}
@@ -0,0 +1,107 @@
+//! Per-channel color correction for LED strips.
+//!
+//! WS2812 dies are not color balanced: driving all three channels at full
+//! power gives a greenish or bluish white depending on the package.
+//! A color correction scales each channel so whites look white.
+
+use rgb::RGB8;
+
+/// Per-channel color correction applied to rendered frames.
+///
+/// Each variant describes the channel scale factors (255 = unchanged).
+/// The presets follow the values commonly used for the listed LED packages.
+///
+/// # Example
+///
+/// ```
+/// use ferriswheel::ColorCorrection;
+/// use rgb::RGB8;
+///
+/// let white = ColorCorrection::TypicalSMD5050.apply(RGB8::new(255, 255, 255));
+/// assert_eq!(white, RGB8::new(255, 176, 240));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ColorCorrection {
+    /// No correction.
+    #[default]
+    Uncorrected,
+    /// Typical 5050 SMD LEDs such as the WS2812B (255, 176, 240).
+    TypicalSMD5050,
+    /// Typical 8 mm through-hole "pixel" LEDs (255, 224, 140).
+    Typical8mmPixel,
+    /// Custom per-channel scale factors.
+    Custom(RGB8),
+}
+
+impl ColorCorrection {
+    /// Returns the per-channel scale factors of this correction.
+    pub fn scale(self) -> RGB8 {
+        match self {
+            ColorCorrection::Uncorrected => RGB8::new(255, 255, 255),
+            ColorCorrection::TypicalSMD5050 => RGB8::new(255, 176, 240),
+            ColorCorrection::Typical8mmPixel => RGB8::new(255, 224, 140),
+            ColorCorrection::Custom(scale) => scale,
+        }
+    }
+
+    /// Applies the correction to a single color.
+    pub fn apply(self, color: RGB8) -> RGB8 {
+        let scale = self.scale();
+        RGB8::new(
+            ((color.r as u16 * scale.r as u16) / 255) as u8,
+            ((color.g as u16 * scale.g as u16) / 255) as u8,
+            ((color.b as u16 * scale.b as u16) / 255) as u8,
+        )
+    }
+
+    /// Applies the correction to every color in `buffer` in place.
+    pub fn apply_frame(self, buffer: &mut [RGB8]) {
+        if self == ColorCorrection::Uncorrected {
+            return;
+        }
+        for pixel in buffer.iter_mut() {
+            *pixel = self.apply(*pixel);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uncorrected_is_identity() {
+        let color = RGB8::new(12, 34, 56);
+        assert_eq!(ColorCorrection::Uncorrected.apply(color), color);
+    }
+
+    #[test]
+    fn test_presets_scale_white() {
+        let white = RGB8::new(255, 255, 255);
+        assert_eq!(
+            ColorCorrection::TypicalSMD5050.apply(white),
+            RGB8::new(255, 176, 240)
+        );
+        assert_eq!(
+            ColorCorrection::Typical8mmPixel.apply(white),
+            RGB8::new(255, 224, 140)
+        );
+    }
+
+    #[test]
+    fn test_custom_scale() {
+        let correction = ColorCorrection::Custom(RGB8::new(128, 255, 0));
+        assert_eq!(
+            correction.apply(RGB8::new(200, 100, 50)),
+            RGB8::new(100, 100, 0)
+        );
+    }
+
+    #[test]
+    fn test_apply_frame() {
+        let mut buffer = [RGB8::new(255, 255, 255); 3];
+        ColorCorrection::TypicalSMD5050.apply_frame(&mut buffer);
+        assert_eq!(buffer, [RGB8::new(255, 176, 240); 3]);
+    }
+}
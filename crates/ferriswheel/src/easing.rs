@@ -0,0 +1,180 @@
+//! Easing curves for smoother animations.
+//!
+//! All curves use integer math and map an input of 0–255 to an output of 0–255,
+//! with `0 → 0` and `255 → 255`. They can be used to shape brightness ramps,
+//! transition progress, or any other 8-bit animation parameter.
+
+use ws2812_pure::QUARTER_SINE;
+
+/// An easing curve mapping 0–255 to 0–255.
+///
+/// "In" curves start slowly and accelerate, "Out" curves start quickly and
+/// decelerate, and "InOut" curves do both.
+///
+/// # Example
+///
+/// ```
+/// use ferriswheel::Easing;
+///
+/// assert_eq!(Easing::QuadIn.apply(0), 0);
+/// assert_eq!(Easing::QuadIn.apply(255), 255);
+/// assert!(Easing::QuadIn.apply(128) < 128);
+/// assert!(Easing::QuadOut.apply(128) > 128);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Easing {
+    /// No easing; the output equals the input.
+    #[default]
+    Linear,
+    /// Quadratic acceleration.
+    QuadIn,
+    /// Quadratic deceleration.
+    QuadOut,
+    /// Quadratic acceleration, then deceleration.
+    QuadInOut,
+    /// Cubic acceleration.
+    CubicIn,
+    /// Cubic deceleration.
+    CubicOut,
+    /// Cubic acceleration, then deceleration.
+    CubicInOut,
+    /// Sinusoidal acceleration.
+    SineIn,
+    /// Sinusoidal deceleration.
+    SineOut,
+    /// Sinusoidal acceleration, then deceleration.
+    SineInOut,
+}
+
+impl Easing {
+    /// Applies the curve to `t` (0–255).
+    pub fn apply(self, t: u8) -> u8 {
+        match self {
+            Easing::Linear => t,
+            Easing::QuadIn => quad_in(t),
+            Easing::QuadOut => ease_out(quad_in, t),
+            Easing::QuadInOut => ease_in_out(quad_in, t),
+            Easing::CubicIn => cubic_in(t),
+            Easing::CubicOut => ease_out(cubic_in, t),
+            Easing::CubicInOut => ease_in_out(cubic_in, t),
+            Easing::SineIn => ease_out(quarter_sine, t),
+            Easing::SineOut => quarter_sine(t),
+            Easing::SineInOut => ease_in_out(|t| ease_out(quarter_sine, t), t),
+        }
+    }
+}
+
+fn quad_in(t: u8) -> u8 {
+    let t = t as u32;
+    (t * t / 255) as u8
+}
+
+fn cubic_in(t: u8) -> u8 {
+    let t = t as u32;
+    (t * t * t / (255 * 255)) as u8
+}
+
+/// Quarter sine from 0 to 255, linearly interpolated between table entries.
+fn quarter_sine(t: u8) -> u8 {
+    // Position in the 64-step table, in 1/256 steps
+    let pos = t as u32 * 64 * 256 / 255;
+    let idx = (pos >> 8) as usize;
+    if idx >= 64 {
+        return QUARTER_SINE[64];
+    }
+    let frac = pos & 0xFF;
+    let a = QUARTER_SINE[idx] as u32;
+    let b = QUARTER_SINE[idx + 1] as u32;
+    ((a * (256 - frac) + b * frac) / 256) as u8
+}
+
+/// Mirrors an "in" curve into the matching "out" curve (and vice versa).
+fn ease_out(f: fn(u8) -> u8, t: u8) -> u8 {
+    255 - f(255 - t)
+}
+
+/// Runs an "in" curve over the first half and its mirror over the second half.
+fn ease_in_out(f: impl Fn(u8) -> u8, t: u8) -> u8 {
+    if t < 128 {
+        f(t * 2) / 2
+    } else {
+        255 - f((255 - t) * 2) / 2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [Easing; 10] = [
+        Easing::Linear,
+        Easing::QuadIn,
+        Easing::QuadOut,
+        Easing::QuadInOut,
+        Easing::CubicIn,
+        Easing::CubicOut,
+        Easing::CubicInOut,
+        Easing::SineIn,
+        Easing::SineOut,
+        Easing::SineInOut,
+    ];
+
+    #[test]
+    fn test_endpoints_are_fixed() {
+        for easing in ALL {
+            assert_eq!(easing.apply(0), 0, "{:?} at 0", easing);
+            assert_eq!(easing.apply(255), 255, "{:?} at 255", easing);
+        }
+    }
+
+    #[test]
+    fn test_curves_are_monotonic() {
+        for easing in ALL {
+            for t in 0..255u8 {
+                assert!(
+                    easing.apply(t) <= easing.apply(t + 1),
+                    "{:?} decreases at {}",
+                    easing,
+                    t
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_in_curves_below_linear_out_curves_above() {
+        for easing in [Easing::QuadIn, Easing::CubicIn, Easing::SineIn] {
+            assert!(easing.apply(128) < 128, "{:?}", easing);
+        }
+        for easing in [Easing::QuadOut, Easing::CubicOut, Easing::SineOut] {
+            assert!(easing.apply(128) > 128, "{:?}", easing);
+        }
+    }
+
+    #[test]
+    fn test_cubic_is_steeper_than_quad() {
+        assert!(Easing::CubicIn.apply(64) < Easing::QuadIn.apply(64));
+        assert!(Easing::CubicOut.apply(192) > Easing::QuadOut.apply(192));
+    }
+
+    #[test]
+    fn test_in_out_is_symmetric() {
+        for easing in [Easing::QuadInOut, Easing::CubicInOut, Easing::SineInOut] {
+            assert!(easing.apply(64) < 64, "{:?} should start slowly", easing);
+            assert!(easing.apply(191) > 191, "{:?} should end slowly", easing);
+            for t in 0..=127u8 {
+                let low = easing.apply(t) as i16;
+                let high = easing.apply(255 - t) as i16;
+                assert!((low + high - 255).abs() <= 1, "{:?} at {}", easing, t);
+            }
+        }
+    }
+
+    #[test]
+    fn test_sine_out_matches_quarter_wave() {
+        assert_eq!(Easing::SineOut.apply(0), 0);
+        // sin(π/4) · 255 ≈ 180
+        assert!((Easing::SineOut.apply(128) as i16 - 180).abs() <= 1);
+    }
+}
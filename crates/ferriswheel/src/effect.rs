@@ -3,6 +3,8 @@
 //! All effects share the [`Effect`] trait, which provides a uniform interface
 //! for rendering animations into an `RGB8` buffer.
 
+use crate::gradient::MAX_GRADIENT_STOPS;
+use crate::tempo::MAX_TEMPO_KEYFRAMES;
 use rgb::RGB8;
 
 /// Maximum supported number of LEDs in a ring.
@@ -10,10 +12,27 @@ use rgb::RGB8;
 /// This limit ensures correct hue distribution across LEDs using simple integer math.
 /// LED rings larger than this are rare in practice.
 /// See ADR-002 for the rationale.
+///
+/// With the `alloc` feature, most effects accept up to `MAX_LEDS_ALLOC` LEDs instead.
 pub const MAX_LEDS: usize = 256;
 
+/// Maximum number of LEDs with the `alloc` feature enabled.
+///
+/// Runtime-sized effects that keep per-LED state move it to the heap when
+/// the strip is longer than [`MAX_LEDS`]; `new_fixed()` state stays inline. [`SectionEffect`](crate::SectionEffect)
+/// and [`PixelMap`](crate::PixelMap) stay limited to [`MAX_LEDS`].
+#[cfg(feature = "alloc")]
+pub const MAX_LEDS_ALLOC: usize = 4096;
+
+/// The LED limit enforced by [`validate_num_leds`].
+#[cfg(not(feature = "alloc"))]
+pub(crate) const LED_LIMIT: usize = MAX_LEDS;
+#[cfg(feature = "alloc")]
+pub(crate) const LED_LIMIT: usize = MAX_LEDS_ALLOC;
+
 /// Error type for effect configuration and operations.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum EffectError {
     /// The number of LEDs must be greater than 0.
     ZeroLeds,
@@ -42,6 +61,55 @@ pub enum EffectError {
         /// Maximum supported.
         max: usize,
     },
+    /// Repeat count must be greater than 0.
+    ZeroRepeat,
+    /// Too many steps for the sequencer.
+    TooManySteps {
+        /// Maximum supported.
+        max: usize,
+    },
+    /// Gradient stops must be non-empty, within the maximum count, and in ascending order.
+    InvalidGradient,
+    /// A pixel index lies outside the configured number of LEDs.
+    InvalidPixelIndex {
+        /// Index found.
+        index: usize,
+        /// Number of LEDs configured.
+        num_leds: usize,
+    },
+    /// A section index lies outside the section capacity.
+    InvalidSectionIndex {
+        /// Index requested.
+        index: usize,
+        /// Section capacity of the effect.
+        capacity: usize,
+    },
+    /// Too many scenes for the scene manager.
+    TooManyScenes {
+        /// Maximum supported.
+        max: usize,
+    },
+    /// No scene was added for the requested key.
+    UnknownScene,
+    /// Tempo keyframes must be non-empty, within the maximum count, and in ascending order.
+    InvalidKeyframes,
+    /// No built-in effect has the requested name.
+    UnknownEffect,
+    /// Too many effects for the effect runner.
+    TooManyEffects {
+        /// Maximum supported.
+        max: usize,
+    },
+    /// Too many segments for the segment controller.
+    TooManySegments {
+        /// Maximum supported.
+        max: usize,
+    },
+    /// Two logical LEDs map to the same physical LED.
+    DuplicatePixelIndex {
+        /// Index found twice.
+        index: usize,
+    },
 }
 
 impl core::fmt::Display for EffectError {
@@ -73,12 +141,55 @@ impl core::fmt::Display for EffectError {
                     requested, max
                 )
             }
+            EffectError::ZeroRepeat => write!(f, "repeat count must be greater than 0"),
+            EffectError::TooManySteps { max } => {
+                write!(f, "too many steps: maximum is {}", max)
+            }
+            EffectError::InvalidGradient => {
+                write!(
+                    f,
+                    "gradient stops must be non-empty, at most {}, and ascending",
+                    MAX_GRADIENT_STOPS
+                )
+            }
+            EffectError::InvalidPixelIndex { index, num_leds } => {
+                write!(f, "invalid pixel index {} for {} LEDs", index, num_leds)
+            }
+            EffectError::InvalidSectionIndex { index, capacity } => {
+                write!(
+                    f,
+                    "invalid section index {} for {} sections",
+                    index, capacity
+                )
+            }
+            EffectError::TooManyScenes { max } => {
+                write!(f, "too many scenes: maximum is {}", max)
+            }
+            EffectError::UnknownScene => write!(f, "no scene for the requested key"),
+            EffectError::InvalidKeyframes => {
+                write!(
+                    f,
+                    "tempo keyframes must be non-empty, at most {}, and ascending",
+                    MAX_TEMPO_KEYFRAMES
+                )
+            }
+            EffectError::UnknownEffect => write!(f, "no effect with the requested name"),
+            EffectError::TooManyEffects { max } => {
+                write!(f, "too many effects: maximum is {}", max)
+            }
+            EffectError::TooManySegments { max } => {
+                write!(f, "too many segments: maximum is {}", max)
+            }
+            EffectError::DuplicatePixelIndex { index } => {
+                write!(f, "pixel index {} is mapped more than once", index)
+            }
         }
     }
 }
 
 /// Direction of animation rotation.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum Direction {
     /// Animation rotates clockwise.
     #[default]
@@ -91,6 +202,11 @@ pub enum Direction {
 ///
 /// All effects implement this trait, allowing polymorphic usage via `&dyn Effect`.
 ///
+/// Most effects loop forever. One-shot effects such as
+/// [`WipeTransitionEffect`](crate::WipeTransitionEffect) override
+/// [`is_finished`](Effect::is_finished) so sequencers and controllers can tell
+/// when they are done.
+///
 /// # Example
 ///
 /// ```
@@ -116,6 +232,65 @@ pub trait Effect {
 
     /// Resets the animation to its initial state.
     fn reset(&mut self);
+
+    /// Returns `true` once a one-shot effect has completed.
+    ///
+    /// Looping effects never finish, which is the default.
+    /// After finishing, `update()` keeps rendering the final frame.
+    fn is_finished(&self) -> bool {
+        false
+    }
+
+    /// Advances the animation by `steps` updates without rendering.
+    ///
+    /// Use this to catch up after the display was paused, e.g. after waking
+    /// from sleep. The result matches calling `update()` `steps` times.
+    ///
+    /// The default implementation runs `update()` against a scratch buffer of
+    /// [`MAX_LEDS`] LEDs; the built-in effects jump there directly. With the
+    /// `alloc` feature, an effect that rejects that buffer with
+    /// `EffectError::BufferTooSmall` is run against a heap buffer of the
+    /// required size instead, so longer effects need not override this.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error reported by `update()` in the default implementation.
+    /// Without `alloc`, that is `EffectError::BufferTooSmall` for effects on more
+    /// than [`MAX_LEDS`] LEDs; override `advance` for those.
+    fn advance(&mut self, steps: u32) -> Result<(), EffectError> {
+        if steps == 0 {
+            return Ok(());
+        }
+        let mut scratch = [RGB8::default(); MAX_LEDS];
+        match self.update(&mut scratch) {
+            #[cfg(feature = "alloc")]
+            Err(EffectError::BufferTooSmall { required, .. }) => {
+                let mut scratch = alloc::vec![RGB8::default(); required];
+                for _ in 0..steps {
+                    self.update(&mut scratch)?;
+                }
+                return Ok(());
+            }
+            result => result?,
+        }
+        for _ in 1..steps {
+            self.update(&mut scratch)?;
+        }
+        Ok(())
+    }
+
+    /// Jumps to `phase` (0–255) of the effect's repeating cycle.
+    ///
+    /// Phase 0 is the state after `reset()`, 128 is half a cycle later. Use it
+    /// to align the animation with an external clock, such as the beats
+    /// reported to a [`BeatSync`](crate::BeatSync).
+    ///
+    /// Effects without a repeating cycle restart, whatever the phase, which is
+    /// the default. Wrappers forward the phase to the wrapped effect.
+    fn set_phase(&mut self, phase: u8) {
+        let _ = phase;
+        self.reset();
+    }
 }
 
 /// Validates that the speed is greater than 0.
@@ -126,17 +301,33 @@ pub(crate) fn validate_speed(speed: u8) -> Result<(), EffectError> {
     Ok(())
 }
 
+/// Validates that a Q8.8 fine speed is greater than 0.
+pub(crate) fn validate_fine_speed(speed: u16) -> Result<(), EffectError> {
+    if speed == 0 {
+        return Err(EffectError::ZeroStep);
+    }
+    Ok(())
+}
+
 /// Validates the number of LEDs for an effect.
 ///
-/// Returns `Ok(())` if `num_leds` is within the valid range (1..=MAX_LEDS).
+/// Returns `Ok(())` if `num_leds` is within the valid range (1..=MAX_LEDS, or
+/// 1..=MAX_LEDS_ALLOC with the `alloc` feature).
 pub(crate) fn validate_num_leds(num_leds: usize) -> Result<(), EffectError> {
+    validate_num_leds_up_to(num_leds, LED_LIMIT)
+}
+
+/// Validates the number of LEDs against an explicit maximum.
+///
+/// Used by effects whose state cannot grow past [`MAX_LEDS`] regardless of features.
+pub(crate) fn validate_num_leds_up_to(num_leds: usize, max: usize) -> Result<(), EffectError> {
     if num_leds == 0 {
         return Err(EffectError::ZeroLeds);
     }
-    if num_leds > MAX_LEDS {
+    if num_leds > max {
         return Err(EffectError::TooManyLeds {
             requested: num_leds,
-            max: MAX_LEDS,
+            max,
         });
     }
     Ok(())
@@ -171,6 +362,26 @@ pub(crate) fn advance_position(
     }
 }
 
+/// Advances a fixed-point position around a ring of `num_leds` LEDs.
+///
+/// Both `position` and `distance` are in 1/256 LED units, so `position >> 8`
+/// is the LED index and the lower byte the fraction towards the next LED.
+/// The result wraps around the ring the same way as [`advance_position`].
+pub(crate) fn advance_position_fine(
+    position: u32,
+    distance: u64,
+    num_leds: usize,
+    direction: Direction,
+) -> u32 {
+    let ring = (num_leds * 256) as i64;
+    let delta = (distance % ring as u64) as i64;
+    let delta = match direction {
+        Direction::Clockwise => delta,
+        Direction::CounterClockwise => -delta,
+    };
+    (position as i64 + delta).rem_euclid(ring) as u32
+}
+
 /// Validates that both on and off tick durations are greater than 0.
 pub(crate) fn validate_duty(on_ticks: u8, off_ticks: u8) -> Result<(), EffectError> {
     if on_ticks == 0 || off_ticks == 0 {
@@ -204,17 +415,40 @@ mod tests {
         assert!(validate_num_leds(1).is_ok());
         assert!(validate_num_leds(12).is_ok());
         assert!(validate_num_leds(MAX_LEDS).is_ok());
+        assert!(validate_num_leds(LED_LIMIT).is_ok());
     }
 
     #[test]
     fn test_validate_num_leds_too_many() {
         assert_eq!(
-            validate_num_leds(MAX_LEDS + 1).unwrap_err(),
+            validate_num_leds(LED_LIMIT + 1).unwrap_err(),
+            EffectError::TooManyLeds {
+                requested: LED_LIMIT + 1,
+                max: LED_LIMIT
+            }
+        );
+    }
+
+    #[test]
+    fn test_validate_num_leds_up_to() {
+        assert!(validate_num_leds_up_to(MAX_LEDS, MAX_LEDS).is_ok());
+        assert_eq!(
+            validate_num_leds_up_to(MAX_LEDS + 1, MAX_LEDS).unwrap_err(),
             EffectError::TooManyLeds {
                 requested: MAX_LEDS + 1,
                 max: MAX_LEDS
             }
         );
+        assert_eq!(
+            validate_num_leds_up_to(0, MAX_LEDS).unwrap_err(),
+            EffectError::ZeroLeds
+        );
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    #[test]
+    fn test_led_limit_without_alloc_is_max_leds() {
+        assert_eq!(LED_LIMIT, MAX_LEDS);
     }
 
     #[test]
@@ -281,6 +515,36 @@ mod tests {
         assert_eq!(advance_position(0, 10, 8, Direction::CounterClockwise), 6);
     }
 
+    #[test]
+    fn test_advance_position_fine_clockwise() {
+        assert_eq!(advance_position_fine(0, 128, 8, Direction::Clockwise), 128);
+        assert_eq!(
+            advance_position_fine(7 * 256 + 200, 100, 8, Direction::Clockwise),
+            44
+        );
+    }
+
+    #[test]
+    fn test_advance_position_fine_counter_clockwise() {
+        assert_eq!(
+            advance_position_fine(0, 64, 8, Direction::CounterClockwise),
+            8 * 256 - 64
+        );
+    }
+
+    #[test]
+    fn test_advance_position_fine_max_leds_does_not_overflow() {
+        assert_eq!(
+            advance_position_fine(
+                (MAX_LEDS * 256 - 1) as u32,
+                1,
+                MAX_LEDS,
+                Direction::Clockwise
+            ),
+            0
+        );
+    }
+
     #[test]
     fn test_error_display() {
         assert_eq!(
@@ -325,5 +589,151 @@ mod tests {
             ),
             "too many sections: requested 10, maximum is 8"
         );
+        assert_eq!(
+            format!("{}", EffectError::ZeroRepeat),
+            "repeat count must be greater than 0"
+        );
+        assert_eq!(
+            format!("{}", EffectError::TooManySteps { max: 4 }),
+            "too many steps: maximum is 4"
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                EffectError::InvalidPixelIndex {
+                    index: 12,
+                    num_leds: 12
+                }
+            ),
+            "invalid pixel index 12 for 12 LEDs"
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                EffectError::InvalidSectionIndex {
+                    index: 8,
+                    capacity: 8
+                }
+            ),
+            "invalid section index 8 for 8 sections"
+        );
+        assert_eq!(
+            format!("{}", EffectError::InvalidGradient),
+            "gradient stops must be non-empty, at most 16, and ascending"
+        );
+        assert_eq!(
+            format!("{}", EffectError::TooManyScenes { max: 4 }),
+            "too many scenes: maximum is 4"
+        );
+        assert_eq!(
+            format!("{}", EffectError::UnknownScene),
+            "no scene for the requested key"
+        );
+        assert_eq!(
+            format!("{}", EffectError::InvalidKeyframes),
+            "tempo keyframes must be non-empty, at most 8, and ascending"
+        );
+        assert_eq!(
+            format!("{}", EffectError::UnknownEffect),
+            "no effect with the requested name"
+        );
+        assert_eq!(
+            format!("{}", EffectError::TooManyEffects { max: 4 }),
+            "too many effects: maximum is 4"
+        );
+        assert_eq!(
+            format!("{}", EffectError::TooManySegments { max: 4 }),
+            "too many segments: maximum is 4"
+        );
+        assert_eq!(
+            format!("{}", EffectError::DuplicatePixelIndex { index: 3 }),
+            "pixel index 3 is mapped more than once"
+        );
+    }
+
+    #[test]
+    fn test_advance_position_fine_beyond_max_leds() {
+        let ring = 1000 * 256;
+        assert_eq!(
+            advance_position_fine(ring - 1, 2, 1000, Direction::Clockwise),
+            1
+        );
+        assert_eq!(
+            advance_position_fine(0, 256, 1000, Direction::CounterClockwise),
+            ring - 256
+        );
+    }
+
+    #[test]
+    fn test_advance_position_fine_long_distance_wraps() {
+        // 8 LEDs: 10 full turns plus 3 LEDs
+        assert_eq!(
+            advance_position_fine(0, (80 + 3) * 256, 8, Direction::Clockwise),
+            3 * 256
+        );
+        assert_eq!(
+            advance_position_fine(0, (80 + 3) * 256, 8, Direction::CounterClockwise),
+            5 * 256
+        );
+    }
+
+    #[test]
+    fn test_default_advance_matches_update() {
+        struct Counter(u32);
+        impl Effect for Counter {
+            fn update(&mut self, _buffer: &mut [RGB8]) -> Result<(), EffectError> {
+                self.0 += 1;
+                Ok(())
+            }
+            fn current(&self, _buffer: &mut [RGB8]) -> Result<(), EffectError> {
+                Ok(())
+            }
+            fn reset(&mut self) {
+                self.0 = 0;
+            }
+        }
+
+        let mut counter = Counter(0);
+        counter.advance(7).unwrap();
+        assert_eq!(counter.0, 7);
+    }
+
+    /// An effect on more than `MAX_LEDS` LEDs that keeps the default `advance`.
+    struct LongStrip(u32);
+
+    impl Effect for LongStrip {
+        fn update(&mut self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+            validate_buffer(buffer, MAX_LEDS + 44)?;
+            self.0 += 1;
+            Ok(())
+        }
+        fn current(&self, _buffer: &mut [RGB8]) -> Result<(), EffectError> {
+            Ok(())
+        }
+        fn reset(&mut self) {
+            self.0 = 0;
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_default_advance_beyond_max_leds() {
+        let mut strip = LongStrip(0);
+        strip.advance(5).unwrap();
+        assert_eq!(strip.0, 5);
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    #[test]
+    fn test_default_advance_beyond_max_leds_needs_alloc() {
+        let mut strip = LongStrip(0);
+        assert_eq!(
+            strip.advance(5).unwrap_err(),
+            EffectError::BufferTooSmall {
+                required: MAX_LEDS + 44,
+                actual: MAX_LEDS
+            }
+        );
+        assert_eq!(strip.0, 0);
     }
 }
@@ -3,8 +3,12 @@
 //! All LEDs toggle between a color and an off color on a configurable duty cycle.
 //! Each [`update`](FlashEffect::update) call advances a tick counter; the phase
 //! (on/off) is determined by where the counter sits in the cycle.
+//!
+//! With [`with_burst`](FlashEffect::with_burst) the effect flashes several times
+//! and then stays off for a longer pause, the classic blink-code pattern.
 
 use crate::effect::{validate_buffer, validate_duty, validate_num_leds, Effect, EffectError};
+use crate::frame_iter::PixelSource;
 use crate::util::fill_solid;
 use rgb::RGB8;
 
@@ -33,7 +37,9 @@ pub struct FlashEffect {
     off_color: RGB8,
     on_ticks: u8,
     off_ticks: u8,
-    counter: u8,
+    burst_count: u8,
+    pause_ticks: u8,
+    counter: u32,
 }
 
 impl FlashEffect {
@@ -42,7 +48,8 @@ impl FlashEffect {
     /// # Errors
     ///
     /// Returns `EffectError::ZeroLeds` if `num_leds` is 0.
-    /// Returns `EffectError::TooManyLeds` if `num_leds` exceeds `MAX_LEDS`.
+    /// Returns `EffectError::TooManyLeds` if `num_leds` exceeds `MAX_LEDS`
+    /// (`MAX_LEDS_ALLOC` with the `alloc` feature).
     ///
     /// # Default Configuration
     ///
@@ -50,6 +57,7 @@ impl FlashEffect {
     /// - Off color: black (0, 0, 0)
     /// - On ticks: 4
     /// - Off ticks: 4
+    /// - Burst: 1 flash, no extra pause
     pub fn new(num_leds: usize) -> Result<Self, EffectError> {
         validate_num_leds(num_leds)?;
 
@@ -59,6 +67,8 @@ impl FlashEffect {
             off_color: RGB8::new(0, 0, 0),
             on_ticks: 4,
             off_ticks: 4,
+            burst_count: 1,
+            pause_ticks: 0,
             counter: 0,
         })
     }
@@ -81,10 +91,79 @@ impl FlashEffect {
     ///
     /// Returns `EffectError::ZeroDuty` if either `on_ticks` or `off_ticks` is 0.
     pub fn with_duty(mut self, on_ticks: u8, off_ticks: u8) -> Result<Self, EffectError> {
+        self.set_duty(on_ticks, off_ticks)?;
+        Ok(self)
+    }
+
+    /// Groups flashes into bursts of `count` flashes followed by a pause.
+    ///
+    /// Each flash uses the configured duty cycle; after the last flash of a
+    /// burst the off color is held for an additional `pause_ticks`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::ZeroRepeat` if `count` is 0.
+    pub fn with_burst(mut self, count: u8, pause_ticks: u8) -> Result<Self, EffectError> {
+        self.set_burst(count, pause_ticks)?;
+        Ok(self)
+    }
+
+    /// Changes the on-phase color.
+    pub fn set_color(&mut self, color: RGB8) {
+        self.color = color;
+    }
+
+    /// Changes the off-phase color.
+    pub fn set_off_color(&mut self, off_color: RGB8) {
+        self.off_color = off_color;
+    }
+
+    /// Changes the duty cycle, keeping the tick counter within the new cycle.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::ZeroDuty` if either `on_ticks` or `off_ticks` is 0.
+    pub fn set_duty(&mut self, on_ticks: u8, off_ticks: u8) -> Result<(), EffectError> {
         validate_duty(on_ticks, off_ticks)?;
         self.on_ticks = on_ticks;
         self.off_ticks = off_ticks;
-        Ok(self)
+        self.counter %= self.cycle_ticks();
+        Ok(())
+    }
+
+    /// Changes the burst pattern, keeping the tick counter within the new cycle.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::ZeroRepeat` if `count` is 0.
+    pub fn set_burst(&mut self, count: u8, pause_ticks: u8) -> Result<(), EffectError> {
+        if count == 0 {
+            return Err(EffectError::ZeroRepeat);
+        }
+        self.burst_count = count;
+        self.pause_ticks = pause_ticks;
+        self.counter %= self.cycle_ticks();
+        Ok(())
+    }
+
+    /// Returns the on-phase color.
+    pub fn color(&self) -> RGB8 {
+        self.color
+    }
+
+    /// Returns the off-phase color.
+    pub fn off_color(&self) -> RGB8 {
+        self.off_color
+    }
+
+    /// Returns the duty cycle as `(on_ticks, off_ticks)`.
+    pub fn duty(&self) -> (u8, u8) {
+        (self.on_ticks, self.off_ticks)
+    }
+
+    /// Returns the burst pattern as `(count, pause_ticks)`.
+    pub fn burst(&self) -> (u8, u8) {
+        (self.burst_count, self.pause_ticks)
     }
 
     /// Returns the number of LEDs this effect is configured for.
@@ -94,7 +173,18 @@ impl FlashEffect {
 
     /// Returns `true` if the effect is currently in the on phase.
     pub fn is_on(&self) -> bool {
-        self.counter < self.on_ticks
+        let flash = self.on_ticks as u32 + self.off_ticks as u32;
+        if self.counter >= flash * self.burst_count as u32 {
+            // Pause after the burst
+            return false;
+        }
+        self.counter % flash < self.on_ticks as u32
+    }
+
+    /// Returns the length of a full cycle (all flashes plus the pause) in ticks.
+    fn cycle_ticks(&self) -> u32 {
+        let flash = self.on_ticks as u32 + self.off_ticks as u32;
+        flash * self.burst_count as u32 + self.pause_ticks as u32
     }
 
     /// Fills the buffer with the current flash state without advancing.
@@ -115,9 +205,14 @@ impl FlashEffect {
     pub fn update(&mut self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
         self.current(buffer)?;
 
-        let cycle = self.on_ticks as u16 + self.off_ticks as u16;
-        self.counter = ((self.counter as u16 + 1) % cycle) as u8;
+        self.counter = (self.counter + 1) % self.cycle_ticks();
+
+        Ok(())
+    }
 
+    /// Advances the animation by `steps` updates without rendering.
+    pub fn advance(&mut self, steps: u32) -> Result<(), EffectError> {
+        self.counter = (self.counter + steps % self.cycle_ticks()) % self.cycle_ticks();
         Ok(())
     }
 
@@ -125,6 +220,13 @@ impl FlashEffect {
     pub fn reset(&mut self) {
         self.counter = 0;
     }
+
+    /// Jumps to `phase` (0–255) of the full cycle, bursts and pause included.
+    ///
+    /// Phase 0 is the start of the first flash.
+    pub fn set_phase(&mut self, phase: u8) {
+        self.counter = phase as u32 * self.cycle_ticks() / 256;
+    }
 }
 
 impl Effect for FlashEffect {
@@ -139,6 +241,28 @@ impl Effect for FlashEffect {
     fn reset(&mut self) {
         self.reset();
     }
+
+    fn advance(&mut self, steps: u32) -> Result<(), EffectError> {
+        self.advance(steps)
+    }
+
+    fn set_phase(&mut self, phase: u8) {
+        self.set_phase(phase);
+    }
+}
+
+impl PixelSource for FlashEffect {
+    fn num_leds(&self) -> usize {
+        self.num_leds
+    }
+
+    fn pixel(&self, _index: usize) -> RGB8 {
+        if self.is_on() {
+            self.color
+        } else {
+            self.off_color
+        }
+    }
 }
 
 #[cfg(test)]
@@ -277,6 +401,56 @@ mod tests {
         assert!(effect.is_on());
     }
 
+    #[test]
+    fn test_with_burst_zero_count_returns_error() {
+        let result = FlashEffect::new(4).unwrap().with_burst(0, 10);
+        assert_eq!(result.unwrap_err(), EffectError::ZeroRepeat);
+    }
+
+    #[test]
+    fn test_burst_flashes_then_pauses() {
+        let mut effect = FlashEffect::new(1)
+            .unwrap()
+            .with_duty(1, 1)
+            .unwrap()
+            .with_burst(3, 4)
+            .unwrap();
+
+        let mut buffer = [RGB8::default(); 1];
+        let mut pattern = Vec::new();
+        for _ in 0..20 {
+            pattern.push(effect.is_on());
+            effect.update(&mut buffer).unwrap();
+        }
+
+        // 3 × (on, off) + 4 pause ticks = 10-tick cycle
+        let cycle = [
+            true, false, true, false, true, false, false, false, false, false,
+        ];
+        assert_eq!(&pattern[..10], &cycle);
+        assert_eq!(&pattern[10..], &cycle);
+    }
+
+    #[test]
+    fn test_burst_cycle_longer_than_255_ticks() {
+        let mut effect = FlashEffect::new(1)
+            .unwrap()
+            .with_duty(100, 100)
+            .unwrap()
+            .with_burst(2, 255)
+            .unwrap();
+
+        let mut buffer = [RGB8::default(); 1];
+        for _ in 0..400 {
+            effect.update(&mut buffer).unwrap();
+        }
+        assert!(!effect.is_on(), "tick 400 is inside the pause");
+        for _ in 0..255 {
+            effect.update(&mut buffer).unwrap();
+        }
+        assert!(effect.is_on(), "tick 655 starts the next burst");
+    }
+
     #[test]
     fn test_trait_object_update() {
         let mut effect = FlashEffect::new(4)
@@ -295,4 +469,52 @@ mod tests {
 
         assert_ne!(buf1, buf2, "flash should toggle between updates");
     }
+
+    #[test]
+    fn test_set_duty_keeps_counter_in_cycle() {
+        let mut effect = FlashEffect::new(4).unwrap().with_duty(10, 10).unwrap();
+        let mut buffer = [RGB8::default(); 4];
+        for _ in 0..15 {
+            effect.update(&mut buffer).unwrap();
+        }
+        assert!(!effect.is_on());
+
+        // Counter 15 wraps to 15 % 4 = 3, the off phase of the new 2/2 cycle
+        effect.set_duty(2, 2).unwrap();
+        assert!(!effect.is_on());
+        assert_eq!(effect.duty(), (2, 2));
+        effect.update(&mut buffer).unwrap();
+        assert!(effect.is_on());
+
+        assert_eq!(effect.set_duty(0, 2).unwrap_err(), EffectError::ZeroDuty);
+        assert_eq!(effect.duty(), (2, 2));
+    }
+
+    #[test]
+    fn test_advance_matches_repeated_update() {
+        let mut updated = FlashEffect::new(2).unwrap().with_duty(3, 4).unwrap();
+        let mut advanced = updated.clone();
+        let mut buf_updated = [RGB8::default(); 2];
+        let mut buf_advanced = [RGB8::default(); 2];
+
+        for _ in 0..45 {
+            updated.update(&mut buf_updated).unwrap();
+        }
+        advanced.advance(45).unwrap();
+
+        updated.current(&mut buf_updated).unwrap();
+        advanced.current(&mut buf_advanced).unwrap();
+        assert_eq!(buf_updated, buf_advanced);
+    }
+
+    #[test]
+    fn test_set_phase_maps_onto_cycle() {
+        let mut effect = FlashEffect::new(1).unwrap().with_duty(2, 2).unwrap();
+        effect.set_phase(64);
+        assert!(effect.is_on());
+        effect.set_phase(128);
+        assert!(!effect.is_on());
+        effect.set_phase(0);
+        assert!(effect.is_on());
+    }
 }
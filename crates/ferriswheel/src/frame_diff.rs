@@ -0,0 +1,261 @@
+//! Changed-pixel detection for partial strip updates.
+//!
+//! Mostly static status displays change a handful of pixels per frame.
+//! [`changed_ranges`] compares two frames and yields the index ranges that
+//! differ, so drivers that support partial updates can skip the rest and debug
+//! output can show only what moved. [`FrameDiff`] keeps the previous frame
+//! for you.
+
+use crate::effect::{EffectError, MAX_LEDS};
+use core::ops::Range;
+use rgb::RGB8;
+
+/// Returns an iterator over the index ranges where `current` differs from `previous`.
+///
+/// Pixels past the end of `previous` count as changed; pixels past the end of
+/// `current` are not reported.
+///
+/// # Example
+///
+/// ```
+/// use ferriswheel::changed_ranges;
+/// use rgb::RGB8;
+///
+/// let red = RGB8::new(255, 0, 0);
+/// let previous = [RGB8::default(); 8];
+/// let mut current = previous;
+/// current[1] = red;
+/// current[2] = red;
+/// current[6] = red;
+///
+/// let ranges: Vec<_> = changed_ranges(&previous, &current).collect();
+/// assert_eq!(ranges, [1..3, 6..7]);
+///
+/// // Fewer, larger writes: bridge up to 4 unchanged pixels
+/// let ranges: Vec<_> = changed_ranges(&previous, &current).with_max_gap(4).collect();
+/// assert_eq!(ranges, [1..7]);
+/// ```
+pub fn changed_ranges<'a>(previous: &'a [RGB8], current: &'a [RGB8]) -> ChangedRanges<'a> {
+    ChangedRanges {
+        previous,
+        current,
+        index: 0,
+        max_gap: 0,
+    }
+}
+
+/// Iterator over the changed index ranges between two frames.
+///
+/// Created by [`changed_ranges`] and [`FrameDiff::diff`].
+#[derive(Debug, Clone)]
+pub struct ChangedRanges<'a> {
+    previous: &'a [RGB8],
+    current: &'a [RGB8],
+    index: usize,
+    max_gap: usize,
+}
+
+impl ChangedRanges<'_> {
+    /// Merges ranges separated by at most `max_gap` unchanged pixels (default: 0).
+    ///
+    /// Each partial update has a fixed cost, so rewriting a few unchanged
+    /// pixels is often cheaper than starting another transfer.
+    pub fn with_max_gap(mut self, max_gap: usize) -> Self {
+        self.max_gap = max_gap;
+        self
+    }
+
+    fn is_changed(&self, index: usize) -> bool {
+        self.previous.get(index) != Some(&self.current[index])
+    }
+}
+
+impl Iterator for ChangedRanges<'_> {
+    type Item = Range<usize>;
+
+    fn next(&mut self) -> Option<Range<usize>> {
+        let len = self.current.len();
+        while self.index < len && !self.is_changed(self.index) {
+            self.index += 1;
+        }
+        if self.index >= len {
+            return None;
+        }
+
+        let start = self.index;
+        let mut last_changed = start;
+        let mut index = start + 1;
+        while index < len && index - last_changed - 1 <= self.max_gap {
+            if self.is_changed(index) {
+                last_changed = index;
+            }
+            index += 1;
+        }
+        self.index = last_changed + 1;
+        Some(start..self.index)
+    }
+}
+
+/// Remembers the last written frame and reports what changed since.
+///
+/// Holds up to `N` pixels; the default capacity is [`MAX_LEDS`]. Before the
+/// first [`store`](FrameDiff::store), every pixel counts as changed.
+///
+/// # Example
+///
+/// ```
+/// use ferriswheel::{Effect, FrameDiff, ProgressEffect};
+/// use rgb::RGB8;
+///
+/// let mut progress = ProgressEffect::new(12).unwrap();
+/// let mut diff: FrameDiff = FrameDiff::new();
+/// let mut buffer = [RGB8::default(); 12];
+///
+/// progress.update(&mut buffer).unwrap();
+/// for range in diff.diff(&buffer) {
+///     // driver.write_range(range.start, &buffer[range])
+///     let _ = range;
+/// }
+/// diff.store(&buffer).unwrap();
+///
+/// // Nothing moved, nothing to send
+/// progress.update(&mut buffer).unwrap();
+/// assert_eq!(diff.diff(&buffer).count(), 0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FrameDiff<const N: usize = MAX_LEDS> {
+    previous: [RGB8; N],
+    len: usize,
+}
+
+impl<const N: usize> FrameDiff<N> {
+    /// Creates a differ without a previous frame.
+    pub const fn new() -> Self {
+        Self {
+            previous: [RGB8 { r: 0, g: 0, b: 0 }; N],
+            len: 0,
+        }
+    }
+
+    /// Returns the changed index ranges of `frame` compared to the stored frame.
+    pub fn diff<'a>(&'a self, frame: &'a [RGB8]) -> ChangedRanges<'a> {
+        changed_ranges(&self.previous[..self.len], frame)
+    }
+
+    /// Returns `true` if any pixel of `frame` differs from the stored frame.
+    pub fn has_changes(&self, frame: &[RGB8]) -> bool {
+        self.diff(frame).next().is_some()
+    }
+
+    /// Stores `frame` as the reference for the next comparison.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::TooManyLeds` if `frame` has more than `N` pixels.
+    pub fn store(&mut self, frame: &[RGB8]) -> Result<(), EffectError> {
+        if frame.len() > N {
+            return Err(EffectError::TooManyLeds {
+                requested: frame.len(),
+                max: N,
+            });
+        }
+        self.previous[..frame.len()].copy_from_slice(frame);
+        self.len = frame.len();
+        Ok(())
+    }
+
+    /// Forgets the stored frame, so the next comparison reports every pixel.
+    pub fn invalidate(&mut self) {
+        self.len = 0;
+    }
+}
+
+impl<const N: usize> Default for FrameDiff<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: RGB8 = RGB8::new(255, 0, 0);
+
+    fn spans(ranges: ChangedRanges<'_>) -> Vec<(usize, usize)> {
+        ranges.map(|range| (range.start, range.end)).collect()
+    }
+
+    fn ranges(previous: &[RGB8], current: &[RGB8], max_gap: usize) -> Vec<(usize, usize)> {
+        spans(changed_ranges(previous, current).with_max_gap(max_gap))
+    }
+
+    #[test]
+    fn test_identical_frames_have_no_ranges() {
+        let frame = [RED; 6];
+        assert!(ranges(&frame, &frame, 0).is_empty());
+    }
+
+    #[test]
+    fn test_ranges_at_both_ends() {
+        let previous = [RGB8::default(); 6];
+        let mut current = previous;
+        current[0] = RED;
+        current[5] = RED;
+        assert_eq!(ranges(&previous, &current, 0), [(0, 1), (5, 6)]);
+        assert_eq!(ranges(&previous, &current, 3), [(0, 1), (5, 6)]);
+        assert_eq!(ranges(&previous, &current, 4), [(0, 6)]);
+    }
+
+    #[test]
+    fn test_gap_does_not_extend_past_last_change() {
+        let previous = [RGB8::default(); 8];
+        let mut current = previous;
+        current[2] = RED;
+        assert_eq!(ranges(&previous, &current, 10), [(2, 3)]);
+    }
+
+    #[test]
+    fn test_longer_current_frame_counts_as_changed() {
+        let previous = [RED; 2];
+        let current = [RED; 4];
+        assert_eq!(ranges(&previous, &current, 0), [(2, 4)]);
+        // Pixels only in the previous frame are not reported
+        assert!(ranges(&current, &previous, 0).is_empty());
+    }
+
+    #[test]
+    fn test_first_diff_reports_whole_frame() {
+        let diff: FrameDiff<8> = FrameDiff::new();
+        let frame = [RGB8::default(); 5];
+        assert_eq!(spans(diff.diff(&frame)), [(0, 5)]);
+        assert!(diff.has_changes(&frame));
+    }
+
+    #[test]
+    fn test_store_and_diff() {
+        let mut diff: FrameDiff<8> = FrameDiff::new();
+        let mut frame = [RGB8::default(); 8];
+        diff.store(&frame).unwrap();
+        assert!(!diff.has_changes(&frame));
+
+        frame[3] = RED;
+        frame[4] = RED;
+        assert_eq!(spans(diff.diff(&frame)), [(3, 5)]);
+
+        diff.invalidate();
+        assert_eq!(spans(diff.diff(&frame)), [(0, 8)]);
+    }
+
+    #[test]
+    fn test_store_too_large_returns_error() {
+        let mut diff: FrameDiff<4> = FrameDiff::new();
+        assert_eq!(
+            diff.store(&[RGB8::default(); 5]).unwrap_err(),
+            EffectError::TooManyLeds {
+                requested: 5,
+                max: 4
+            }
+        );
+    }
+}
@@ -0,0 +1,131 @@
+//! Buffer-less rendering for pixel-streaming drivers.
+//!
+//! [`Effect`](crate::Effect) renders a whole frame into a caller-provided
+//! buffer. Drivers that encode pixels one at a time (SPI or RMT encoders) can
+//! instead pull colors from a [`PixelSource`] through a [`FrameIter`] and skip
+//! the intermediate buffer entirely.
+
+use rgb::RGB8;
+
+/// An effect whose LED colors can be computed one at a time.
+///
+/// Only effects that don't need to look at neighboring LEDs implement this.
+/// Between frames, move the animation on with
+/// [`Effect::advance`](crate::Effect::advance).
+///
+/// # Example
+///
+/// ```
+/// use ferriswheel::{Effect, PixelSource, RainbowEffect};
+///
+/// let mut rainbow = RainbowEffect::new(12).unwrap();
+///
+/// for color in rainbow.pixels() {
+///     // encode `color` straight into the driver's output
+///     let _ = color;
+/// }
+/// rainbow.advance(1).unwrap();
+/// ```
+pub trait PixelSource {
+    /// Returns the number of LEDs in a frame.
+    fn num_leds(&self) -> usize;
+
+    /// Returns the color of LED `index` in the current frame.
+    ///
+    /// `index` must be below [`num_leds`](Self::num_leds).
+    fn pixel(&self, index: usize) -> RGB8;
+
+    /// Returns an iterator over the colors of the current frame.
+    fn pixels(&self) -> FrameIter<'_, Self>
+    where
+        Self: Sized,
+    {
+        FrameIter::new(self)
+    }
+}
+
+/// Iterator over the colors of one frame of a [`PixelSource`].
+///
+/// Created by [`PixelSource::pixels`].
+#[derive(Debug, Clone)]
+pub struct FrameIter<'a, S: ?Sized> {
+    source: &'a S,
+    index: usize,
+    len: usize,
+}
+
+impl<'a, S: PixelSource + ?Sized> FrameIter<'a, S> {
+    /// Creates an iterator over the current frame of `source`.
+    pub fn new(source: &'a S) -> Self {
+        Self {
+            source,
+            index: 0,
+            len: source.num_leds(),
+        }
+    }
+}
+
+impl<S: PixelSource + ?Sized> Iterator for FrameIter<'_, S> {
+    type Item = RGB8;
+
+    fn next(&mut self) -> Option<RGB8> {
+        if self.index >= self.len {
+            return None;
+        }
+        let color = self.source.pixel(self.index);
+        self.index += 1;
+        Some(color)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.len - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<S: PixelSource + ?Sized> ExactSizeIterator for FrameIter<'_, S> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Effect, FlashEffect, PulseEffect, RainbowEffect};
+
+    fn assert_matches_buffer<E: Effect + PixelSource>(effect: &mut E, n: usize) {
+        let mut buffer = [RGB8::default(); 16];
+        for _ in 0..5 {
+            effect.current(&mut buffer[..n]).unwrap();
+            assert_eq!(effect.pixels().len(), n);
+            assert!(effect.pixels().eq(buffer[..n].iter().copied()));
+            effect.advance(1).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_rainbow_pixels_match_buffer() {
+        let mut rainbow = RainbowEffect::new(12).unwrap().with_speed(7).unwrap();
+        assert_matches_buffer(&mut rainbow, 12);
+    }
+
+    #[test]
+    fn test_pulse_pixels_match_buffer() {
+        let mut pulse = PulseEffect::new(4).unwrap().with_speed(20).unwrap();
+        assert_matches_buffer(&mut pulse, 4);
+    }
+
+    #[test]
+    fn test_flash_pixels_match_buffer() {
+        let mut flash = FlashEffect::new(3).unwrap().with_duty(2, 2).unwrap();
+        assert_matches_buffer(&mut flash, 3);
+    }
+
+    #[test]
+    fn test_advance_matches_update() {
+        let mut advanced = RainbowEffect::new(8).unwrap();
+        let mut updated = advanced.clone();
+        let mut buffer = [RGB8::default(); 8];
+
+        advanced.advance(1).unwrap();
+        updated.update(&mut buffer).unwrap();
+        assert!(advanced.pixels().eq(updated.pixels()));
+    }
+}
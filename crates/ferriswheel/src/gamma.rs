@@ -0,0 +1,153 @@
+//! Gamma correction for any effect.
+
+use crate::effect::{Effect, EffectError};
+use crate::util::gamma_correct;
+use rgb::RGB8;
+
+/// Wraps an effect and gamma-corrects every rendered frame.
+///
+/// Effects compute brightness linearly, but WS2812 LEDs look much brighter at
+/// low PWM values than expected, so ramps appear to jump at the dark end.
+/// Wrapping the effect in `GammaCorrected` applies [`gamma8`](crate::gamma8)
+/// to every channel so fades look evenly paced.
+///
+/// Correction applies to the whole buffer passed to `update()`/`current()`.
+///
+/// # Example
+///
+/// ```
+/// use ferriswheel::{Effect, GammaCorrected, PulseEffect};
+/// use rgb::RGB8;
+///
+/// let pulse = PulseEffect::new(12).unwrap();
+/// let mut effect = GammaCorrected::new(pulse);
+/// let mut buffer = [RGB8::default(); 12];
+///
+/// effect.update(&mut buffer).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct GammaCorrected<E> {
+    effect: E,
+}
+
+impl<E: Effect> GammaCorrected<E> {
+    /// Wraps `effect` with gamma correction.
+    pub fn new(effect: E) -> Self {
+        Self { effect }
+    }
+
+    /// Returns a reference to the wrapped effect.
+    pub fn inner(&self) -> &E {
+        &self.effect
+    }
+
+    /// Returns a mutable reference to the wrapped effect.
+    pub fn inner_mut(&mut self) -> &mut E {
+        &mut self.effect
+    }
+
+    /// Consumes the wrapper and returns the wrapped effect.
+    pub fn into_inner(self) -> E {
+        self.effect
+    }
+
+    /// Fills the buffer with the corrected frame without advancing.
+    pub fn current(&self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        self.effect.current(buffer)?;
+        gamma_correct(buffer);
+        Ok(())
+    }
+
+    /// Fills the buffer with the corrected frame and advances the wrapped effect.
+    pub fn update(&mut self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        self.effect.update(buffer)?;
+        gamma_correct(buffer);
+        Ok(())
+    }
+
+    /// Resets the wrapped effect.
+    pub fn reset(&mut self) {
+        self.effect.reset();
+    }
+}
+
+impl<E: Effect> Effect for GammaCorrected<E> {
+    fn update(&mut self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        self.update(buffer)
+    }
+
+    fn current(&self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        self.current(buffer)
+    }
+
+    fn reset(&mut self) {
+        self.reset();
+    }
+
+    fn advance(&mut self, steps: u32) -> Result<(), EffectError> {
+        self.effect.advance(steps)
+    }
+
+    fn is_finished(&self) -> bool {
+        self.effect.is_finished()
+    }
+
+    fn set_phase(&mut self, phase: u8) {
+        self.effect.set_phase(phase);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::gamma8;
+    use crate::FlashEffect;
+
+    fn solid(color: RGB8) -> FlashEffect {
+        FlashEffect::new(3)
+            .unwrap()
+            .with_color(color)
+            .with_duty(255, 1)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_frame_is_corrected() {
+        let mut effect = GammaCorrected::new(solid(RGB8::new(255, 128, 64)));
+        let mut buffer = [RGB8::default(); 3];
+        effect.update(&mut buffer).unwrap();
+        assert_eq!(buffer, [RGB8::new(255, gamma8(128), gamma8(64)); 3]);
+    }
+
+    #[test]
+    fn test_current_matches_update() {
+        let mut effect = GammaCorrected::new(solid(RGB8::new(10, 100, 200)));
+        let mut from_current = [RGB8::default(); 3];
+        let mut from_update = [RGB8::default(); 3];
+        effect.current(&mut from_current).unwrap();
+        effect.update(&mut from_update).unwrap();
+        assert_eq!(from_current, from_update);
+    }
+
+    #[test]
+    fn test_errors_are_passed_through() {
+        let effect = GammaCorrected::new(solid(RGB8::new(1, 2, 3)));
+        let mut buffer = [RGB8::default(); 1];
+        assert_eq!(
+            effect.current(&mut buffer).unwrap_err(),
+            EffectError::BufferTooSmall {
+                required: 3,
+                actual: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_trait_object_update() {
+        let mut effect = GammaCorrected::new(solid(RGB8::new(128, 128, 128)));
+        let effect_ref: &mut dyn Effect = &mut effect;
+        let mut buffer = [RGB8::default(); 3];
+        effect_ref.update(&mut buffer).unwrap();
+        assert_eq!(buffer[0], RGB8::new(56, 56, 56));
+    }
+}
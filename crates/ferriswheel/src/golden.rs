@@ -0,0 +1,407 @@
+//! Golden-frame tests for effects.
+//!
+//! Per-pixel assertions are tedious to write and easy to get wrong. A golden
+//! test renders an effect for a number of frames and compares the output with
+//! frames recorded earlier, so any change to the rendering shows up as a diff.
+//!
+//! Golden data is plain text: one frame per line, one `rrggbb` hex color per LED,
+//! separated by spaces. Empty lines and lines starting with `#` are ignored.
+//!
+//! ```text
+//! # spinner, 4 LEDs, tail length 1
+//! ffffff 000000 000000 7f7f7f
+//! 7f7f7f ffffff 000000 000000
+//! ```
+//!
+//! This module needs `std` and is meant for tests only.
+
+use crate::effect::{Effect, EffectError};
+use rgb::RGB8;
+use std::fmt::Write as _;
+use std::path::Path;
+use std::string::String;
+use std::vec::Vec;
+
+/// Environment variable that makes [`assert_golden_file`] write the golden file
+/// instead of comparing against it.
+pub const BLESS_ENV: &str = "FERRISWHEEL_BLESS";
+
+/// A pixel that differs from the golden data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PixelMismatch {
+    /// Zero-based frame index.
+    pub frame: usize,
+    /// Zero-based LED index.
+    pub led: usize,
+    /// Color in the golden data.
+    pub expected: RGB8,
+    /// Color the effect rendered.
+    pub actual: RGB8,
+}
+
+/// Error returned by [`check_golden`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GoldenError {
+    /// The golden data is not valid.
+    Parse {
+        /// One-based line number.
+        line: usize,
+    },
+    /// The golden frames don't all have the same number of LEDs.
+    UnevenFrames {
+        /// Zero-based frame index.
+        frame: usize,
+    },
+    /// The effect failed to render.
+    Effect(EffectError),
+    /// Rendered frames differ from the golden data.
+    Mismatch {
+        /// Every differing pixel.
+        pixels: Vec<PixelMismatch>,
+        /// The golden frames.
+        expected: Vec<Vec<RGB8>>,
+        /// The rendered frames.
+        actual: Vec<Vec<RGB8>>,
+    },
+}
+
+impl From<EffectError> for GoldenError {
+    fn from(error: EffectError) -> Self {
+        GoldenError::Effect(error)
+    }
+}
+
+impl core::fmt::Display for GoldenError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            GoldenError::Parse { line } => write!(f, "invalid golden data on line {}", line),
+            GoldenError::UnevenFrames { frame } => {
+                write!(f, "golden frame {} has a different number of LEDs", frame)
+            }
+            GoldenError::Effect(error) => write!(f, "effect failed: {}", error),
+            GoldenError::Mismatch {
+                pixels,
+                expected,
+                actual,
+            } => {
+                writeln!(f, "{} pixel(s) differ from the golden frames", pixels.len())?;
+                let mut frames: Vec<usize> = pixels.iter().map(|pixel| pixel.frame).collect();
+                frames.dedup();
+                for frame in frames {
+                    let mut markers = String::new();
+                    for led in 0..expected[frame].len() {
+                        let differs = pixels
+                            .iter()
+                            .any(|pixel| pixel.frame == frame && pixel.led == led);
+                        markers.push_str(if differs { "^^^^^^ " } else { "       " });
+                    }
+                    writeln!(f, "frame {}:", frame)?;
+                    writeln!(f, "  expected: {}", format_frame(&expected[frame]))?;
+                    writeln!(f, "  actual:   {}", format_frame(&actual[frame]))?;
+                    writeln!(f, "            {}", markers.trim_end())?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Runs `effect` for `frames` updates on `num_leds` LEDs and returns every frame.
+///
+/// # Errors
+///
+/// Returns the first error reported by the effect.
+pub fn render_frames(
+    effect: &mut dyn Effect,
+    num_leds: usize,
+    frames: usize,
+) -> Result<Vec<Vec<RGB8>>, EffectError> {
+    let mut rendered = Vec::with_capacity(frames);
+    for _ in 0..frames {
+        let mut buffer = std::vec![RGB8::default(); num_leds];
+        effect.update(&mut buffer)?;
+        rendered.push(buffer);
+    }
+    Ok(rendered)
+}
+
+fn format_frame(frame: &[RGB8]) -> String {
+    let mut line = String::with_capacity(frame.len() * 7);
+    for (i, pixel) in frame.iter().enumerate() {
+        if i > 0 {
+            line.push(' ');
+        }
+        let _ = write!(line, "{:02x}{:02x}{:02x}", pixel.r, pixel.g, pixel.b);
+    }
+    line
+}
+
+/// Formats frames as golden data.
+pub fn format_frames(frames: &[Vec<RGB8>]) -> String {
+    let mut text = String::new();
+    for frame in frames {
+        text.push_str(&format_frame(frame));
+        text.push('\n');
+    }
+    text
+}
+
+fn parse_pixel(word: &str) -> Option<RGB8> {
+    if word.len() != 6 {
+        return None;
+    }
+    let value = u32::from_str_radix(word, 16).ok()?;
+    Some(RGB8::new(
+        (value >> 16) as u8,
+        (value >> 8) as u8,
+        value as u8,
+    ))
+}
+
+/// Parses golden data into frames.
+///
+/// # Errors
+///
+/// Returns `GoldenError::Parse` for a word that is not a `rrggbb` color, and
+/// `GoldenError::UnevenFrames` if the frames differ in length.
+pub fn parse_frames(text: &str) -> Result<Vec<Vec<RGB8>>, GoldenError> {
+    let mut frames: Vec<Vec<RGB8>> = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let frame = line
+            .split_whitespace()
+            .map(parse_pixel)
+            .collect::<Option<Vec<RGB8>>>()
+            .ok_or(GoldenError::Parse { line: index + 1 })?;
+        if frames
+            .first()
+            .is_some_and(|first| first.len() != frame.len())
+        {
+            return Err(GoldenError::UnevenFrames {
+                frame: frames.len(),
+            });
+        }
+        frames.push(frame);
+    }
+    Ok(frames)
+}
+
+/// Renders `effect` and compares it with the golden data in `expected`.
+///
+/// The number of frames and LEDs is taken from the golden data.
+///
+/// # Errors
+///
+/// Returns `GoldenError::Mismatch` listing every differing pixel, or an error
+/// if the golden data is invalid or the effect fails.
+pub fn check_golden(effect: &mut dyn Effect, expected: &str) -> Result<(), GoldenError> {
+    let expected = parse_frames(expected)?;
+    let num_leds = expected.first().map_or(0, Vec::len);
+    let actual = render_frames(effect, num_leds, expected.len())?;
+
+    let pixels: Vec<PixelMismatch> = expected
+        .iter()
+        .zip(&actual)
+        .enumerate()
+        .flat_map(|(frame, (expected, actual))| {
+            expected
+                .iter()
+                .zip(actual)
+                .enumerate()
+                .filter(|(_, (expected, actual))| expected != actual)
+                .map(move |(led, (&expected, &actual))| PixelMismatch {
+                    frame,
+                    led,
+                    expected,
+                    actual,
+                })
+        })
+        .collect();
+
+    if pixels.is_empty() {
+        Ok(())
+    } else {
+        Err(GoldenError::Mismatch {
+            pixels,
+            expected,
+            actual,
+        })
+    }
+}
+
+/// Asserts that `effect` renders the golden data in `expected`.
+///
+/// # Panics
+///
+/// Panics with a diff of the differing pixels if the frames don't match.
+///
+/// # Example
+///
+/// ```
+/// use ferriswheel::golden::assert_golden;
+/// use ferriswheel::SpinnerEffect;
+///
+/// let mut spinner = SpinnerEffect::new(4).unwrap().with_tail_length(1);
+/// assert_golden(
+///     &mut spinner,
+///     "
+///     ffffff 000000 000000 7f7f7f
+///     7f7f7f ffffff 000000 000000
+///     ",
+/// );
+/// ```
+#[track_caller]
+pub fn assert_golden(effect: &mut dyn Effect, expected: &str) {
+    if let Err(error) = check_golden(effect, expected) {
+        panic!("{}", error);
+    }
+}
+
+/// Asserts that `effect` renders the golden frames stored at `path`.
+///
+/// When the environment variable [`BLESS_ENV`] is set, the golden file is
+/// (re)written from `frames` updates on `num_leds` LEDs instead.
+///
+/// # Panics
+///
+/// Panics if the file can't be read or written, or with a diff if the frames
+/// don't match.
+#[track_caller]
+pub fn assert_golden_file(
+    effect: &mut dyn Effect,
+    num_leds: usize,
+    frames: usize,
+    path: impl AsRef<Path>,
+) {
+    let path = path.as_ref();
+    if std::env::var_os(BLESS_ENV).is_some() {
+        let rendered = render_frames(effect, num_leds, frames)
+            .unwrap_or_else(|error| panic!("effect failed: {}", error));
+        std::fs::write(path, format_frames(&rendered))
+            .unwrap_or_else(|error| panic!("cannot write {}: {}", path.display(), error));
+        return;
+    }
+
+    let expected = std::fs::read_to_string(path).unwrap_or_else(|error| {
+        panic!(
+            "cannot read {}: {} (set {} to create it)",
+            path.display(),
+            error,
+            BLESS_ENV
+        )
+    });
+    assert_golden(effect, &expected);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SpinnerEffect;
+
+    const RED: RGB8 = RGB8::new(255, 0, 0);
+
+    fn spinner() -> SpinnerEffect {
+        SpinnerEffect::new(4).unwrap().with_tail_length(1)
+    }
+
+    #[test]
+    fn test_format_and_parse_round_trip() {
+        let frames = std::vec![
+            std::vec![RED, RGB8::new(0, 16, 255)],
+            std::vec![RGB8::default(), RGB8::new(1, 2, 3)],
+        ];
+        let text = format_frames(&frames);
+        assert_eq!(text, "ff0000 0010ff\n000000 010203\n");
+        assert_eq!(parse_frames(&text).unwrap(), frames);
+    }
+
+    #[test]
+    fn test_parse_skips_comments_and_blank_lines() {
+        let frames = parse_frames("# header\n\n  ff0000  \n").unwrap();
+        assert_eq!(frames, [[RED]]);
+    }
+
+    #[test]
+    fn test_parse_invalid_word_returns_error() {
+        assert_eq!(
+            parse_frames("ff0000\nff00zz\n").unwrap_err(),
+            GoldenError::Parse { line: 2 }
+        );
+        assert_eq!(
+            parse_frames("ff00\n").unwrap_err(),
+            GoldenError::Parse { line: 1 }
+        );
+    }
+
+    #[test]
+    fn test_parse_uneven_frames_returns_error() {
+        assert_eq!(
+            parse_frames("ff0000 ff0000\nff0000\n").unwrap_err(),
+            GoldenError::UnevenFrames { frame: 1 }
+        );
+    }
+
+    #[test]
+    fn test_matching_frames_pass() {
+        let golden = format_frames(&render_frames(&mut spinner(), 4, 6).unwrap());
+        assert_eq!(check_golden(&mut spinner(), &golden), Ok(()));
+    }
+
+    #[test]
+    fn test_mismatch_lists_differing_pixels() {
+        let golden = "ffffff 000000 000000 7f7f7f\n7f7f7f ffffff 000000 ff0000\n";
+        let error = check_golden(&mut spinner(), golden).unwrap_err();
+        let GoldenError::Mismatch { pixels, .. } = &error else {
+            panic!("unexpected error: {:?}", error);
+        };
+        assert_eq!(
+            pixels.as_slice(),
+            [PixelMismatch {
+                frame: 1,
+                led: 3,
+                expected: RED,
+                actual: RGB8::default(),
+            }]
+        );
+
+        let diff = error.to_string();
+        assert!(diff.contains("frame 1:"));
+        assert!(diff.contains("  expected: 7f7f7f ffffff 000000 ff0000"));
+        assert!(diff.contains("  actual:   7f7f7f ffffff 000000 000000"));
+        assert!(diff.ends_with("                                 ^^^^^^\n"));
+    }
+
+    #[test]
+    fn test_effect_error_is_reported() {
+        let mut effect = SpinnerEffect::new(8).unwrap();
+        assert_eq!(
+            check_golden(&mut effect, "000000 000000\n").unwrap_err(),
+            GoldenError::Effect(EffectError::BufferTooSmall {
+                required: 8,
+                actual: 2
+            })
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "1 pixel(s) differ")]
+    fn test_assert_golden_panics_on_mismatch() {
+        assert_golden(&mut spinner(), "ffffff 000000 000000 000000\n");
+    }
+
+    #[test]
+    fn test_golden_file_round_trip() {
+        let path = std::env::temp_dir().join(std::format!(
+            "ferriswheel-golden-{}.txt",
+            std::process::id()
+        ));
+        let frames = render_frames(&mut spinner(), 4, 3).unwrap();
+        std::fs::write(&path, format_frames(&frames)).unwrap();
+
+        assert_golden_file(&mut spinner(), 4, 3, &path);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,277 @@
+//! Multi-stop color gradients.
+//!
+//! A [`GradientPalette`] maps a position (0–255) to a color by interpolating
+//! between color stops. It can express heat maps, sunsets, or any other
+//! gradient that the three-color [`ColorPalette`](crate::ColorPalette) cannot.
+
+use crate::effect::EffectError;
+use crate::util::lerp_color;
+use rgb::RGB8;
+
+/// Maximum number of color stops in a [`GradientPalette`].
+pub const MAX_GRADIENT_STOPS: usize = 16;
+
+/// A gradient defined by up to [`MAX_GRADIENT_STOPS`] color stops.
+///
+/// Presets such as [`GradientPalette::HEAT`] or [`GradientPalette::OCEAN`]
+/// are available as associated constants.
+///
+/// Each stop is a `(position, color)` pair. Positions must be in ascending
+/// order. Positions before the first stop take the first stop's color,
+/// positions after the last stop take the last stop's color.
+///
+/// # Example
+///
+/// ```
+/// use ferriswheel::GradientPalette;
+/// use rgb::RGB8;
+///
+/// let sunset = GradientPalette::new(&[
+///     (0, RGB8::new(120, 0, 60)),
+///     (128, RGB8::new(255, 60, 0)),
+///     (255, RGB8::new(255, 200, 0)),
+/// ]).unwrap();
+///
+/// assert_eq!(sunset.color_at(128), RGB8::new(255, 60, 0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct GradientPalette {
+    stops: [(u8, RGB8); MAX_GRADIENT_STOPS],
+    len: usize,
+}
+
+impl GradientPalette {
+    /// Black → red → yellow → white, like glowing embers.
+    pub const HEAT: GradientPalette = Self::preset(&[
+        (0, RGB8::new(0, 0, 0)),
+        (128, RGB8::new(255, 0, 0)),
+        (224, RGB8::new(255, 255, 0)),
+        (255, RGB8::new(255, 255, 255)),
+    ]);
+
+    /// Mostly dark reds with bright orange and yellow highlights.
+    pub const LAVA: GradientPalette = Self::preset(&[
+        (0, RGB8::new(0, 0, 0)),
+        (96, RGB8::new(113, 0, 0)),
+        (146, RGB8::new(213, 44, 2)),
+        (202, RGB8::new(255, 156, 4)),
+        (234, RGB8::new(255, 255, 4)),
+        (255, RGB8::new(255, 255, 255)),
+    ]);
+
+    /// Deep blue through turquoise to pale aqua.
+    pub const OCEAN: GradientPalette = Self::preset(&[
+        (0, RGB8::new(0, 0, 64)),
+        (64, RGB8::new(0, 0, 160)),
+        (128, RGB8::new(0, 128, 255)),
+        (192, RGB8::new(0, 200, 200)),
+        (255, RGB8::new(160, 255, 255)),
+    ]);
+
+    /// Dark and light greens with olive tones.
+    pub const FOREST: GradientPalette = Self::preset(&[
+        (0, RGB8::new(0, 64, 0)),
+        (64, RGB8::new(34, 139, 34)),
+        (128, RGB8::new(85, 107, 47)),
+        (192, RGB8::new(107, 142, 35)),
+        (255, RGB8::new(144, 238, 144)),
+    ]);
+
+    /// Eight solid rainbow bands with hard edges.
+    pub const RAINBOW_STRIPE: GradientPalette = Self::preset(&[
+        (0, RGB8::new(255, 0, 0)),
+        (31, RGB8::new(255, 0, 0)),
+        (32, RGB8::new(255, 128, 0)),
+        (63, RGB8::new(255, 128, 0)),
+        (64, RGB8::new(255, 255, 0)),
+        (95, RGB8::new(255, 255, 0)),
+        (96, RGB8::new(0, 255, 0)),
+        (127, RGB8::new(0, 255, 0)),
+        (128, RGB8::new(0, 255, 255)),
+        (159, RGB8::new(0, 255, 255)),
+        (160, RGB8::new(0, 0, 255)),
+        (191, RGB8::new(0, 0, 255)),
+        (192, RGB8::new(128, 0, 255)),
+        (223, RGB8::new(128, 0, 255)),
+        (224, RGB8::new(255, 0, 128)),
+        (255, RGB8::new(255, 0, 128)),
+    ]);
+
+    /// Saturated purples, reds, oranges and yellows; wraps back to purple.
+    pub const PARTY: GradientPalette = Self::preset(&[
+        (0, RGB8::new(85, 0, 171)),
+        (42, RGB8::new(132, 0, 124)),
+        (84, RGB8::new(181, 0, 75)),
+        (112, RGB8::new(229, 0, 27)),
+        (140, RGB8::new(232, 23, 0)),
+        (168, RGB8::new(184, 71, 0)),
+        (196, RGB8::new(171, 119, 0)),
+        (224, RGB8::new(171, 171, 0)),
+        (255, RGB8::new(85, 0, 171)),
+    ]);
+
+    /// Builds a preset at compile time; `stops` must be valid.
+    const fn preset(stops: &[(u8, RGB8)]) -> Self {
+        let mut palette = [(0, RGB8::new(0, 0, 0)); MAX_GRADIENT_STOPS];
+        let mut i = 0;
+        while i < stops.len() {
+            palette[i] = stops[i];
+            i += 1;
+        }
+        Self {
+            stops: palette,
+            len: stops.len(),
+        }
+    }
+
+    /// Creates a gradient from `(position, color)` stops.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::InvalidGradient` if `stops` is empty, has more than
+    /// [`MAX_GRADIENT_STOPS`] entries, or its positions are not in ascending order.
+    pub fn new(stops: &[(u8, RGB8)]) -> Result<Self, EffectError> {
+        if stops.is_empty()
+            || stops.len() > MAX_GRADIENT_STOPS
+            || stops.windows(2).any(|pair| pair[0].0 > pair[1].0)
+        {
+            return Err(EffectError::InvalidGradient);
+        }
+
+        let mut palette = Self {
+            stops: [(0, RGB8::default()); MAX_GRADIENT_STOPS],
+            len: stops.len(),
+        };
+        palette.stops[..stops.len()].copy_from_slice(stops);
+        Ok(palette)
+    }
+
+    /// Creates a two-stop gradient from `start` (position 0) to `end` (position 255).
+    pub const fn linear(start: RGB8, end: RGB8) -> Self {
+        Self::preset(&[(0, start), (255, end)])
+    }
+
+    /// Returns the color stops.
+    pub fn stops(&self) -> &[(u8, RGB8)] {
+        &self.stops[..self.len]
+    }
+
+    /// Returns the interpolated color at `position` (0–255).
+    pub fn color_at(&self, position: u8) -> RGB8 {
+        let stops = self.stops();
+        let first = stops[0];
+        if position <= first.0 {
+            return first.1;
+        }
+
+        for pair in stops.windows(2) {
+            let (start, end) = (pair[0], pair[1]);
+            if position <= end.0 {
+                let span = (end.0 - start.0) as u16;
+                let t = ((position - start.0) as u16 * 255 / span) as u8;
+                return lerp_color(start.1, end.1, t);
+            }
+        }
+
+        stops[stops.len() - 1].1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: RGB8 = RGB8::new(255, 0, 0);
+    const GREEN: RGB8 = RGB8::new(0, 255, 0);
+    const BLUE: RGB8 = RGB8::new(0, 0, 255);
+
+    #[test]
+    fn test_empty_stops_return_error() {
+        assert_eq!(
+            GradientPalette::new(&[]).unwrap_err(),
+            EffectError::InvalidGradient
+        );
+    }
+
+    #[test]
+    fn test_too_many_stops_return_error() {
+        let stops = [(0, RED); MAX_GRADIENT_STOPS + 1];
+        assert_eq!(
+            GradientPalette::new(&stops).unwrap_err(),
+            EffectError::InvalidGradient
+        );
+    }
+
+    #[test]
+    fn test_unordered_stops_return_error() {
+        assert_eq!(
+            GradientPalette::new(&[(100, RED), (50, GREEN)]).unwrap_err(),
+            EffectError::InvalidGradient
+        );
+    }
+
+    #[test]
+    fn test_single_stop_is_solid() {
+        let palette = GradientPalette::new(&[(128, GREEN)]).unwrap();
+        for position in [0, 128, 255] {
+            assert_eq!(palette.color_at(position), GREEN);
+        }
+    }
+
+    #[test]
+    fn test_interpolates_between_stops() {
+        let palette = GradientPalette::new(&[(0, RED), (128, GREEN), (255, BLUE)]).unwrap();
+        assert_eq!(palette.color_at(0), RED);
+        assert_eq!(palette.color_at(128), GREEN);
+        assert_eq!(palette.color_at(255), BLUE);
+        assert_eq!(palette.color_at(64), lerp_color(RED, GREEN, 127));
+    }
+
+    #[test]
+    fn test_clamps_outside_stops() {
+        let palette = GradientPalette::new(&[(64, RED), (192, BLUE)]).unwrap();
+        assert_eq!(palette.color_at(0), RED);
+        assert_eq!(palette.color_at(255), BLUE);
+    }
+
+    #[test]
+    fn test_duplicate_positions_make_hard_edge() {
+        let palette =
+            GradientPalette::new(&[(0, RED), (128, RED), (128, BLUE), (255, BLUE)]).unwrap();
+        assert_eq!(palette.color_at(127), RED);
+        assert_eq!(palette.color_at(129), BLUE);
+    }
+
+    #[test]
+    fn test_linear() {
+        let palette = GradientPalette::linear(RED, BLUE);
+        assert_eq!(palette.stops(), &[(0, RED), (255, BLUE)]);
+        assert_eq!(palette.color_at(255), BLUE);
+    }
+
+    #[test]
+    fn test_presets_are_valid() {
+        let presets = [
+            GradientPalette::HEAT,
+            GradientPalette::LAVA,
+            GradientPalette::OCEAN,
+            GradientPalette::FOREST,
+            GradientPalette::RAINBOW_STRIPE,
+            GradientPalette::PARTY,
+        ];
+        for preset in presets {
+            assert_eq!(GradientPalette::new(preset.stops()).unwrap(), preset);
+        }
+    }
+
+    #[test]
+    fn test_heat_runs_from_black_to_white() {
+        assert_eq!(GradientPalette::HEAT.color_at(0), RGB8::new(0, 0, 0));
+        assert_eq!(GradientPalette::HEAT.color_at(128), RED);
+        assert_eq!(
+            GradientPalette::HEAT.color_at(255),
+            RGB8::new(255, 255, 255)
+        );
+    }
+}
@@ -2,6 +2,11 @@
 //!
 //! This module provides a `no_std` compatible, float-free implementation
 //! of HSV to RGB color conversion suitable for embedded systems.
+//!
+//! Two hue mappings are available: [`hsv_to_rgb`] spreads the hues evenly
+//! over the color spectrum, while [`hsv_to_rgb_rainbow`] uses a
+//! perceptually balanced rainbow with a wider yellow band.
+//! [`rgb_to_hsv`] converts back using the spectrum mapping.
 
 use rgb::RGB8;
 
@@ -62,6 +67,121 @@ pub fn hsv_to_rgb(hue: u8, saturation: u8, value: u8) -> RGB8 {
     }
 }
 
+/// Converts HSV color values to RGB using a perceptually balanced rainbow.
+///
+/// Matches the "rainbow" hue mapping popularized by FastLED: the hue wheel
+/// is split into eight equal sections (red, orange, yellow, green, aqua, blue,
+/// purple, pink), which gives yellow and orange more room and keeps green
+/// from dominating. Colors around yellow look noticeably richer than with
+/// [`hsv_to_rgb`].
+///
+/// # Arguments
+///
+/// * `hue` - Color hue (0-255, wraps around: 0=red, 64=yellow, 96=green, 160=blue)
+/// * `saturation` - Color saturation (0=grayscale, 255=full color)
+/// * `value` - Brightness value (0=black, 255=full brightness)
+///
+/// # Example
+///
+/// ```
+/// use ferriswheel::hsv_to_rgb_rainbow;
+/// use rgb::RGB8;
+///
+/// assert_eq!(hsv_to_rgb_rainbow(0, 255, 255), RGB8::new(255, 0, 0));
+/// assert_eq!(hsv_to_rgb_rainbow(96, 255, 255), RGB8::new(0, 255, 0));
+/// ```
+pub fn hsv_to_rgb_rainbow(hue: u8, saturation: u8, value: u8) -> RGB8 {
+    if saturation == 0 {
+        return RGB8::new(value, value, value);
+    }
+
+    // Position within one of the eight 32-step sections, scaled to 0-248
+    let offset8 = ((hue & 0x1F) as u16) << 3;
+    let third = ((offset8 * 85) >> 8) as u8;
+    let two_thirds = ((offset8 * 170) >> 8) as u8;
+
+    let (r, g, b) = match hue >> 5 {
+        0 => (255 - third, third, 0),                // Red to Orange
+        1 => (171, 85 + third, 0),                   // Orange to Yellow
+        2 => (171 - two_thirds, 170 + third, 0),     // Yellow to Green
+        3 => (0, 255 - third, third),                // Green to Aqua
+        4 => (0, 171 - two_thirds, 85 + two_thirds), // Aqua to Blue
+        5 => (third, 0, 255 - third),                // Blue to Purple
+        6 => (85 + third, 0, 171 - third),           // Purple to Pink
+        _ => (170 + third, 0, 85 - third),           // Pink to Red (section 7)
+    };
+
+    // Desaturate towards white; the squared curve keeps pastels vivid
+    let desat = 255 - saturation as u16;
+    let floor = desat * desat / 255;
+    let scale = 255 - floor;
+    let channel = |c: u8| {
+        let saturated = c as u16 * scale / 255 + floor;
+        (saturated * value as u16 / 255) as u8
+    };
+
+    RGB8::new(channel(r), channel(g), channel(b))
+}
+
+/// Converts an RGB color to HSV values.
+///
+/// The inverse of [`hsv_to_rgb`], returning `(hue, saturation, value)` on the
+/// same 0–255 scales. Grays (including black and white) have no hue and
+/// return a hue of 0 with a saturation of 0.
+///
+/// Integer rounding makes the round trip approximate: converting a color there
+/// and back may change each channel by a few steps.
+///
+/// # Example
+///
+/// ```
+/// use ferriswheel::{hsv_to_rgb, rgb_to_hsv};
+/// use rgb::RGB8;
+///
+/// assert_eq!(rgb_to_hsv(RGB8::new(255, 0, 0)), (0, 255, 255));
+/// assert_eq!(rgb_to_hsv(RGB8::new(128, 128, 128)), (0, 0, 128));
+///
+/// let (hue, saturation, value) = rgb_to_hsv(hsv_to_rgb(170, 255, 255));
+/// assert_eq!((hue, saturation, value), (170, 255, 255));
+/// ```
+pub fn rgb_to_hsv(color: RGB8) -> (u8, u8, u8) {
+    let (r, g, b) = (color.r as u16, color.g as u16, color.b as u16);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+    if delta == 0 {
+        return (0, 0, max as u8);
+    }
+
+    let saturation = (delta * 255 / max) as u8;
+
+    // Position within the sector, matching the rising and falling edges of hsv_to_rgb
+    let rising = |c: u16, low: u16| (c - low) * 255 / delta;
+    let falling = |c: u16, low: u16| 255 - (c - low) * 255 / delta;
+    let (sector, fraction) = if max == r {
+        if g >= b {
+            (0, rising(g, b)) // Red to Yellow
+        } else {
+            (5, falling(b, g)) // Magenta to Red
+        }
+    } else if max == g {
+        if r > b {
+            (1, falling(r, b)) // Yellow to Green
+        } else {
+            (2, rising(b, r)) // Green to Cyan
+        }
+    } else if g > r {
+        (3, falling(g, r)) // Cyan to Blue
+    } else {
+        (4, rising(r, g)) // Blue to Magenta
+    };
+
+    // Undo the hue * 6 scaling, rounding to the nearest hue; 256 wraps to red
+    let h = sector * 256 + fraction;
+    let hue = ((h + 3) / 6 % 256) as u8;
+    (hue, saturation, max as u8)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -147,4 +267,89 @@ mod tests {
         assert!(color_255.r > 200);
         assert_eq!(color_0.r, 255);
     }
+
+    #[test]
+    fn test_rainbow_section_anchors() {
+        assert_eq!(hsv_to_rgb_rainbow(0, 255, 255), RGB8::new(255, 0, 0));
+        assert_eq!(hsv_to_rgb_rainbow(64, 255, 255), RGB8::new(171, 170, 0));
+        assert_eq!(hsv_to_rgb_rainbow(96, 255, 255), RGB8::new(0, 255, 0));
+        assert_eq!(hsv_to_rgb_rainbow(160, 255, 255), RGB8::new(0, 0, 255));
+    }
+
+    #[test]
+    fn test_rainbow_yellow_band_is_wider() {
+        // Spectrum conversion is already green-dominant at hue 64, rainbow is still yellow
+        let spectrum = hsv_to_rgb(64, 255, 255);
+        let rainbow = hsv_to_rgb_rainbow(64, 255, 255);
+        assert!(spectrum.r < spectrum.g);
+        assert!(rainbow.r >= rainbow.g);
+    }
+
+    #[test]
+    fn test_rainbow_saturation_and_value() {
+        assert_eq!(hsv_to_rgb_rainbow(42, 0, 200), RGB8::new(200, 200, 200));
+        assert_eq!(hsv_to_rgb_rainbow(42, 255, 0), RGB8::new(0, 0, 0));
+        assert_eq!(hsv_to_rgb_rainbow(0, 255, 128), RGB8::new(128, 0, 0));
+
+        let pastel = hsv_to_rgb_rainbow(0, 128, 255);
+        assert_eq!(pastel.r, 255);
+        assert!(pastel.g > 0 && pastel.g < 128);
+    }
+
+    #[test]
+    fn test_rainbow_is_continuous() {
+        for hue in 0..=255u8 {
+            let a = hsv_to_rgb_rainbow(hue, 255, 255);
+            let b = hsv_to_rgb_rainbow(hue.wrapping_add(1), 255, 255);
+            for (x, y) in [(a.r, b.r), (a.g, b.g), (a.b, b.b)] {
+                assert!((x as i16 - y as i16).abs() <= 8, "jump at hue {}", hue);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rgb_to_hsv_primaries() {
+        assert_eq!(rgb_to_hsv(RGB8::new(255, 0, 0)), (0, 255, 255));
+        assert_eq!(rgb_to_hsv(RGB8::new(0, 255, 0)).0, 85);
+        assert_eq!(rgb_to_hsv(RGB8::new(0, 0, 255)).0, 171);
+        assert_eq!(rgb_to_hsv(RGB8::new(0, 0, 128)).2, 128);
+    }
+
+    #[test]
+    fn test_rgb_to_hsv_grays_have_no_saturation() {
+        for level in [0, 1, 128, 255] {
+            assert_eq!(rgb_to_hsv(RGB8::new(level, level, level)), (0, 0, level));
+        }
+    }
+
+    #[test]
+    fn test_rgb_to_hsv_round_trips_hue() {
+        for hue in 0..=255u8 {
+            let (back, saturation, value) = rgb_to_hsv(hsv_to_rgb(hue, 255, 255));
+            let distance = back.wrapping_sub(hue).min(hue.wrapping_sub(back));
+            assert!(distance <= 1, "hue {} came back as {}", hue, back);
+            assert_eq!((saturation, value), (255, 255));
+        }
+    }
+
+    #[test]
+    fn test_rgb_to_hsv_round_trips_color() {
+        for color in [
+            RGB8::new(200, 100, 50),
+            RGB8::new(10, 200, 180),
+            RGB8::new(90, 30, 240),
+            RGB8::new(255, 128, 128),
+        ] {
+            let (h, s, v) = rgb_to_hsv(color);
+            let back = hsv_to_rgb(h, s, v);
+            for (x, y) in [(color.r, back.r), (color.g, back.g), (color.b, back.b)] {
+                assert!(
+                    (x as i16 - y as i16).abs() <= 6,
+                    "{:?} became {:?}",
+                    color,
+                    back
+                );
+            }
+        }
+    }
 }
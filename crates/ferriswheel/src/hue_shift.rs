@@ -0,0 +1,207 @@
+//! Runtime hue rotation for any effect.
+
+use crate::effect::{Effect, EffectError};
+use crate::hsv::{hsv_to_rgb, rgb_to_hsv};
+use rgb::RGB8;
+
+/// Wraps an effect and rotates the hue of every rendered pixel.
+///
+/// Each pixel goes through [`rgb_to_hsv`](crate::rgb_to_hsv), gets `shift`
+/// added to its hue, and is converted back with [`hsv_to_rgb`](crate::hsv_to_rgb).
+/// Saturation and brightness are kept, so one effect can be themed in any
+/// color, e.g. from a user preference, without rebuilding it.
+///
+/// Grays, including white, have no hue and are left unchanged. Design the
+/// wrapped effect in a saturated color instead: a red effect with a shift of
+/// 85 turns green, 170 turns blue.
+///
+/// A shift of 0 passes frames through untouched. Other shifts are subject to
+/// the integer rounding of the HSV round trip.
+///
+/// # Example
+///
+/// ```
+/// use ferriswheel::{Effect, HueShift, SpinnerEffect};
+/// use rgb::RGB8;
+///
+/// let spinner = SpinnerEffect::new(12).unwrap().with_color(RGB8::new(255, 0, 0));
+/// let mut themed = HueShift::new(spinner, 0);
+/// let mut buffer = [RGB8::default(); 12];
+///
+/// // User picked blue
+/// themed.set_shift(170);
+/// themed.update(&mut buffer).unwrap();
+/// assert!(buffer[0].b > buffer[0].r);
+/// ```
+#[derive(Debug, Clone)]
+pub struct HueShift<E> {
+    effect: E,
+    shift: u8,
+}
+
+impl<E: Effect> HueShift<E> {
+    /// Wraps `effect`, rotating every hue by `shift` (0–255 is one full turn).
+    pub fn new(effect: E, shift: u8) -> Self {
+        Self { effect, shift }
+    }
+
+    /// Changes the hue rotation.
+    pub fn set_shift(&mut self, shift: u8) {
+        self.shift = shift;
+    }
+
+    /// Returns the hue rotation.
+    pub fn shift(&self) -> u8 {
+        self.shift
+    }
+
+    /// Returns a reference to the wrapped effect.
+    pub fn inner(&self) -> &E {
+        &self.effect
+    }
+
+    /// Returns a mutable reference to the wrapped effect.
+    pub fn inner_mut(&mut self) -> &mut E {
+        &mut self.effect
+    }
+
+    /// Consumes the wrapper and returns the wrapped effect.
+    pub fn into_inner(self) -> E {
+        self.effect
+    }
+
+    fn apply(&self, buffer: &mut [RGB8]) {
+        if self.shift == 0 {
+            return;
+        }
+        for led in buffer.iter_mut() {
+            let (hue, saturation, value) = rgb_to_hsv(*led);
+            if saturation > 0 {
+                *led = hsv_to_rgb(hue.wrapping_add(self.shift), saturation, value);
+            }
+        }
+    }
+
+    /// Fills the buffer with the recolored frame without advancing.
+    pub fn current(&self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        self.effect.current(buffer)?;
+        self.apply(buffer);
+        Ok(())
+    }
+
+    /// Fills the buffer with the recolored frame and advances the wrapped effect.
+    pub fn update(&mut self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        self.effect.update(buffer)?;
+        self.apply(buffer);
+        Ok(())
+    }
+
+    /// Resets the wrapped effect; the hue rotation is kept.
+    pub fn reset(&mut self) {
+        self.effect.reset();
+    }
+}
+
+impl<E: Effect> Effect for HueShift<E> {
+    fn update(&mut self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        self.update(buffer)
+    }
+
+    fn current(&self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        self.current(buffer)
+    }
+
+    fn reset(&mut self) {
+        self.reset();
+    }
+
+    fn advance(&mut self, steps: u32) -> Result<(), EffectError> {
+        self.effect.advance(steps)
+    }
+
+    fn is_finished(&self) -> bool {
+        self.effect.is_finished()
+    }
+
+    fn set_phase(&mut self, phase: u8) {
+        self.effect.set_phase(phase);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FlashEffect, SpinnerEffect};
+
+    fn solid(color: RGB8) -> FlashEffect {
+        FlashEffect::new(4)
+            .unwrap()
+            .with_color(color)
+            .with_duty(255, 1)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_zero_shift_is_unchanged() {
+        let mut effect = HueShift::new(solid(RGB8::new(200, 100, 50)), 0);
+        let mut buffer = [RGB8::default(); 4];
+        effect.update(&mut buffer).unwrap();
+        assert_eq!(buffer, [RGB8::new(200, 100, 50); 4]);
+    }
+
+    #[test]
+    fn test_red_shifts_to_green_and_blue() {
+        let mut effect = HueShift::new(solid(RGB8::new(255, 0, 0)), 85);
+        let mut buffer = [RGB8::default(); 4];
+        effect.current(&mut buffer).unwrap();
+        assert_eq!(buffer[0], hsv_to_rgb(85, 255, 255));
+
+        effect.set_shift(170);
+        effect.current(&mut buffer).unwrap();
+        assert_eq!(buffer[0], hsv_to_rgb(170, 255, 255));
+        assert_eq!(effect.shift(), 170);
+    }
+
+    #[test]
+    fn test_brightness_is_kept() {
+        let effect = HueShift::new(solid(RGB8::new(100, 0, 0)), 85);
+        let mut buffer = [RGB8::default(); 4];
+        effect.current(&mut buffer).unwrap();
+        assert_eq!(buffer[0], RGB8::new(0, 100, 0));
+    }
+
+    #[test]
+    fn test_grays_are_unchanged() {
+        let effect = HueShift::new(solid(RGB8::new(255, 255, 255)), 100);
+        let mut buffer = [RGB8::default(); 4];
+        effect.current(&mut buffer).unwrap();
+        assert_eq!(buffer, [RGB8::new(255, 255, 255); 4]);
+    }
+
+    #[test]
+    fn test_animation_state_is_kept() {
+        let spinner = SpinnerEffect::new(8)
+            .unwrap()
+            .with_color(RGB8::new(255, 0, 0))
+            .with_tail_length(0);
+        let mut effect = HueShift::new(spinner, 0);
+        let mut buffer = [RGB8::default(); 8];
+        for _ in 0..3 {
+            effect.update(&mut buffer).unwrap();
+        }
+
+        effect.set_shift(85);
+        effect.current(&mut buffer).unwrap();
+        assert_eq!(buffer[3], hsv_to_rgb(85, 255, 255));
+        assert_eq!(buffer[0], RGB8::default());
+    }
+
+    #[test]
+    fn test_trait_object_update() {
+        let mut effect = HueShift::new(solid(RGB8::new(255, 0, 0)), 170);
+        let effect_ref: &mut dyn Effect = &mut effect;
+        let mut buffer = [RGB8::default(); 4];
+        effect_ref.update(&mut buffer).unwrap();
+        assert_eq!(buffer[0], hsv_to_rgb(170, 255, 255));
+    }
+}
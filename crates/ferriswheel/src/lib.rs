@@ -16,14 +16,66 @@
 //! - [`FlashEffect`] — rapid on/off toggle with configurable duty cycle
 //! - [`ProgressEffect`] — proportional ring fill
 //! - [`SectionEffect`] — weighted color sections on a ring
+//! - [`TwinkleEffect`] — random LEDs fading in and out in palette colors
+//! - [`WipeTransitionEffect`] — one-shot wipe or crossfade between two frames
+//!
+//! # Composition
+//!
+//! - [`EffectSequencer`] — play several effects one after another
+//! - [`Segments`] — independent effects on separate ranges of one strip
+//! - [`SceneManager`] — effects keyed by application state, with optional transitions
+//! - [`AudioReactive`] — modulate brightness or speed of any effect with a smoothed audio level
+//! - [`BeatSync`] — snap an effect's phase to externally reported beats
+//! - [`TempoEnvelope`] — speed an effect up or down along a keyframe list
+//! - [`TimedEffect`] — drive an effect by elapsed milliseconds instead of frames
+//! - [`Brightness`] — master brightness applied to any effect
+//! - [`GammaCorrected`] — gamma correction applied to any effect
+//! - [`HueShift`] — recolor any effect by rotating its hues at runtime
+//! - [`Pipeline`] — brightness, [`ColorCorrection`] and gamma as one frame post-process
+//! - [`PowerLimiter`] — keep frames within a current budget ([`estimate_current_ma`])
+//! - [`PowerSaver`] — blank or slow down an effect after a period without activity
+//! - [`Mirror`] — reflect or repeat an effect around the ring for symmetric patterns
+//! - [`PixelMap`] — map logical LED order to the physical wiring
+//! - [`PixelSource`] — stream pixels through a [`FrameIter`] without a frame buffer
+//! - [`Animator`] — render and write frames at a fixed interval from a blocking main loop
 //!
 //! # Utilities
 //!
 //! - [`ColorPalette`] — three-color theme for effects
+//! - [`PaletteFader`] — smooth crossfade between palettes
+//! - [`GradientPalette`] — multi-stop color gradient with built-in presets
+//! - [`Easing`] — integer easing curves for ramps and transitions
+//! - [`FrameDiff`] / [`changed_ranges`] — changed index ranges between frames for partial updates
 //! - [`fill_solid`] — fill a buffer with a single color
 //! - [`sine_wave`] — sine lookup for smooth animations
+//! - [`full_sine_wave`], [`triangle_wave`] and [`Waveform`] — full-cycle waveforms for pulsing
 //! - [`scale_brightness`] — scale an RGB color's brightness
+//! - [`hsv_to_rgb`] / [`rgb_to_hsv`] — convert between RGB and integer HSV
 //! - [`lerp_color`] — linearly interpolate between two colors
+//! - [`add_color`], [`nblend`], [`fade_to_black_by`], [`blur1d`] — blending and trail building blocks
+//! - [`gamma8`] / [`gamma_correct`] — perceptual gamma correction, using the gamma 2.2
+//!   table shared with `ws2812-pure`
+//! - [`RGBW8`] and [`rgb_to_rgbw`] — white-channel frames for SK6812 RGBW rings, with
+//!   [`scale_brightness_rgbw`] and [`gamma_correct_rgbw`]
+//! - [`Rng`] / [`XorShift32`] — random number source for randomized effects
+//!
+//! Most firmware only needs `use ferriswheel::prelude::*;`, which imports the
+//! [`Effect`] trait, the built-in effects, palettes and color helpers; see
+//! [`prelude`].
+//!
+//! # Features
+//!
+//! - `alloc` — most effects accept up to `MAX_LEDS_ALLOC` LEDs instead of
+//!   [`MAX_LEDS`]; per-LED state for the extra LEDs lives on the heap
+//! - `async` — `EffectRunner`, an async render loop built on `embedded-hal-async`
+//!   delays, with an `EffectSwitch` for changing effects from other tasks
+//! - `critical-section` — `CommandQueue`, a fixed-size queue of `EffectCommand`s
+//!   that interrupt handlers push to and the render loop drains
+//! - `named` — `EffectKind` and `NamedEffect` for picking built-in effects by
+//!   name, e.g. from serial or MQTT commands
+//! - `std` — the `golden` module for golden-frame effect tests
+//! - `defmt` — derives `defmt::Format` for [`EffectError`], [`Direction`], palettes
+//!   and configuration enums, for logging over RTT
 //!
 //! # Example
 //!
@@ -42,26 +94,111 @@
 //! effect.current(&mut buffer).unwrap();
 //! ```
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(all(feature = "std", not(test)))]
+extern crate std;
+
+mod animator;
+mod audio;
+mod beat_sync;
+mod brightness;
 mod chase;
+#[cfg(feature = "critical-section")]
+mod command;
+mod correction;
+mod easing;
 mod effect;
 mod flash;
+mod frame_diff;
+mod frame_iter;
+mod gamma;
+#[cfg(any(test, feature = "std"))]
+pub mod golden;
+mod gradient;
 mod hsv;
+mod hue_shift;
+mod mirror;
+#[cfg(feature = "named")]
+mod named;
 mod palette;
+mod pipeline;
+mod pixel_map;
+mod power;
+mod power_saver;
+pub mod prelude;
 mod progress;
 mod pulse;
 mod rainbow;
+mod rgbw;
+mod rng;
+#[cfg(feature = "async")]
+mod runner;
+mod scene;
 mod section;
+mod segments;
+mod sequencer;
 mod spinner;
+mod state;
+mod tempo;
+mod timed;
+mod transition;
+mod twinkle;
 mod util;
+mod waveform;
+mod writer;
 
+pub use animator::Animator;
+pub use audio::{AudioReactive, Modulation};
+pub use beat_sync::BeatSync;
+pub use brightness::Brightness;
 pub use chase::ChaseEffect;
+#[cfg(feature = "critical-section")]
+pub use command::{CommandQueue, EffectCommand};
+pub use correction::ColorCorrection;
+pub use easing::Easing;
+#[cfg(feature = "alloc")]
+pub use effect::MAX_LEDS_ALLOC;
 pub use effect::{Direction, Effect, EffectError, MAX_LEDS};
 pub use flash::FlashEffect;
-pub use hsv::hsv_to_rgb;
-pub use palette::ColorPalette;
-pub use progress::ProgressEffect;
+pub use frame_diff::{changed_ranges, ChangedRanges, FrameDiff};
+pub use frame_iter::{FrameIter, PixelSource};
+pub use gamma::GammaCorrected;
+pub use gradient::{GradientPalette, MAX_GRADIENT_STOPS};
+pub use hsv::{hsv_to_rgb, hsv_to_rgb_rainbow, rgb_to_hsv};
+pub use hue_shift::HueShift;
+pub use mirror::{Mirror, MirrorMode};
+#[cfg(feature = "named")]
+pub use named::{EffectKind, NamedEffect};
+pub use palette::{ColorPalette, PaletteFader};
+pub use pipeline::Pipeline;
+pub use pixel_map::PixelMap;
+pub use power::{estimate_current_ma, PowerLimiter, PowerModel};
+pub use power_saver::{IdleMode, PowerSaver};
+pub use progress::{FillMode, ProgressEffect};
 pub use pulse::PulseEffect;
 pub use rainbow::RainbowEffect;
-pub use section::{SectionEffect, MAX_SECTIONS};
+pub use rgbw::{
+    fill_solid_rgbw, gamma_correct_rgbw, rgb_frame_to_rgbw, rgb_to_rgbw, rgbw_to_rgb,
+    scale_brightness_rgbw, RGBW, RGBW8,
+};
+pub use rng::{Rng, XorShift32};
+#[cfg(feature = "async")]
+pub use runner::{EffectRunner, EffectSwitch};
+pub use scene::SceneManager;
+pub use section::{SectionEffect, SectionRenderMode, MAX_SECTIONS};
+pub use segments::Segments;
+pub use sequencer::{EffectSequencer, StepDuration};
 pub use spinner::SpinnerEffect;
-pub use util::{fill_solid, lerp_color, scale_brightness, sine_wave};
+pub use tempo::{TempoEnvelope, MAX_TEMPO_KEYFRAMES};
+pub use timed::TimedEffect;
+pub use transition::{TransitionMode, WipeTransitionEffect};
+pub use twinkle::TwinkleEffect;
+pub use util::{
+    add_color, blur1d, fade_to_black_by, fill_solid, full_sine_wave, gamma8, gamma_correct,
+    lerp_color, nblend, scale_brightness, sine_wave, triangle_wave,
+};
+pub use waveform::Waveform;
+#[cfg(feature = "async")]
+pub use writer::AsyncFrameWriter;
+pub use writer::{FrameWriter, RunError};
@@ -0,0 +1,289 @@
+//! Symmetric patterns from a single segment.
+//!
+//! [`Mirror`] renders the wrapped effect on the first part of the ring and
+//! copies it around the rest, reflected or repeated. Any effect becomes a
+//! symmetric or kaleidoscope-like pattern, and the effect only has to render a
+//! fraction of the LEDs.
+
+use crate::effect::{validate_buffer, validate_num_leds, Effect, EffectError};
+use rgb::RGB8;
+
+/// How [`Mirror`] fills the ring from the first segment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum MirrorMode {
+    /// Every other segment runs backwards, so neighboring segments mirror each other.
+    #[default]
+    Reflect,
+    /// Every segment is an identical copy of the first.
+    Repeat,
+}
+
+/// Wraps an effect and mirrors or repeats it around the ring.
+///
+/// The ring of `num_leds` LEDs is split into `copies` segments of
+/// [`segment_len`](Mirror::segment_len) LEDs; create the wrapped effect for
+/// that many LEDs. If `num_leds` is not a multiple of `copies`, the last
+/// segment is cut short.
+///
+/// With [`MirrorMode::Reflect`], use an even number of copies on a closed ring;
+/// with an odd number the last segment meets the first without being mirrored.
+///
+/// # Example
+///
+/// ```
+/// use ferriswheel::{Effect, Mirror, SpinnerEffect};
+/// use rgb::RGB8;
+///
+/// // Two spinners chasing each other around a 12-LED ring in opposite directions
+/// let spinner = SpinnerEffect::new(6).unwrap();
+/// let mut mirrored = Mirror::new(spinner, 12).unwrap();
+/// assert_eq!(mirrored.segment_len(), 6);
+///
+/// let mut buffer = [RGB8::default(); 12];
+/// mirrored.update(&mut buffer).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Mirror<E> {
+    effect: E,
+    num_leds: usize,
+    copies: usize,
+    mode: MirrorMode,
+}
+
+impl<E: Effect> Mirror<E> {
+    /// Wraps `effect` to fill a ring of `num_leds` LEDs.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::ZeroLeds` if `num_leds` is 0.
+    /// Returns `EffectError::TooManyLeds` if `num_leds` exceeds `MAX_LEDS`
+    /// (`MAX_LEDS_ALLOC` with the `alloc` feature).
+    ///
+    /// # Default Configuration
+    ///
+    /// - Copies: 2 (one half of the ring mirrored onto the other)
+    /// - Mode: [`MirrorMode::Reflect`]
+    pub fn new(effect: E, num_leds: usize) -> Result<Self, EffectError> {
+        validate_num_leds(num_leds)?;
+
+        Ok(Self {
+            effect,
+            num_leds,
+            copies: 2.min(num_leds),
+            mode: MirrorMode::Reflect,
+        })
+    }
+
+    /// Sets the number of segments the ring is split into.
+    ///
+    /// Values above `num_leds` are clamped, giving one LED per segment.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::ZeroRepeat` if `copies` is 0.
+    pub fn with_copies(mut self, copies: u8) -> Result<Self, EffectError> {
+        if copies == 0 {
+            return Err(EffectError::ZeroRepeat);
+        }
+        self.copies = (copies as usize).min(self.num_leds);
+        Ok(self)
+    }
+
+    /// Sets how the segments are filled.
+    pub fn with_mode(mut self, mode: MirrorMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Returns the number of LEDs the wrapped effect renders.
+    pub fn segment_len(&self) -> usize {
+        self.num_leds.div_ceil(self.copies)
+    }
+
+    /// Returns the number of LEDs this mirror fills.
+    pub fn num_leds(&self) -> usize {
+        self.num_leds
+    }
+
+    /// Returns a reference to the wrapped effect.
+    pub fn inner(&self) -> &E {
+        &self.effect
+    }
+
+    /// Returns a mutable reference to the wrapped effect.
+    pub fn inner_mut(&mut self) -> &mut E {
+        &mut self.effect
+    }
+
+    /// Consumes the wrapper and returns the wrapped effect.
+    pub fn into_inner(self) -> E {
+        self.effect
+    }
+
+    /// Copies the first segment of `buffer` over the remaining segments.
+    fn fill_copies(&self, buffer: &mut [RGB8]) {
+        let len = self.segment_len();
+        for i in len..self.num_leds {
+            let segment = i / len;
+            let offset = i % len;
+            let source = match self.mode {
+                MirrorMode::Reflect if segment % 2 == 1 => len - 1 - offset,
+                _ => offset,
+            };
+            buffer[i] = buffer[source];
+        }
+    }
+
+    /// Fills the buffer with the mirrored frame without advancing.
+    pub fn current(&self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        validate_buffer(buffer, self.num_leds)?;
+        self.effect.current(&mut buffer[..self.segment_len()])?;
+        self.fill_copies(buffer);
+        Ok(())
+    }
+
+    /// Fills the buffer with the mirrored frame and advances the wrapped effect.
+    pub fn update(&mut self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        validate_buffer(buffer, self.num_leds)?;
+        let len = self.segment_len();
+        self.effect.update(&mut buffer[..len])?;
+        self.fill_copies(buffer);
+        Ok(())
+    }
+
+    /// Resets the wrapped effect.
+    pub fn reset(&mut self) {
+        self.effect.reset();
+    }
+}
+
+impl<E: Effect> Effect for Mirror<E> {
+    fn update(&mut self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        self.update(buffer)
+    }
+
+    fn current(&self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        self.current(buffer)
+    }
+
+    fn reset(&mut self) {
+        self.reset();
+    }
+
+    fn advance(&mut self, steps: u32) -> Result<(), EffectError> {
+        self.effect.advance(steps)
+    }
+
+    fn is_finished(&self) -> bool {
+        self.effect.is_finished()
+    }
+
+    fn set_phase(&mut self, phase: u8) {
+        self.effect.set_phase(phase);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SpinnerEffect;
+
+    const WHITE: RGB8 = RGB8::new(255, 255, 255);
+
+    fn dot(num_leds: usize) -> SpinnerEffect {
+        SpinnerEffect::new(num_leds).unwrap().with_tail_length(0)
+    }
+
+    fn lit(buffer: &[RGB8]) -> Vec<usize> {
+        buffer
+            .iter()
+            .enumerate()
+            .filter(|(_, &led)| led == WHITE)
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    #[test]
+    fn test_reflect_halves() {
+        let mut mirror = Mirror::new(dot(4), 8).unwrap();
+        let mut buffer = [RGB8::default(); 8];
+        mirror.update(&mut buffer).unwrap();
+        assert_eq!(lit(&buffer), [0, 7]);
+        mirror.update(&mut buffer).unwrap();
+        assert_eq!(lit(&buffer), [1, 6]);
+    }
+
+    #[test]
+    fn test_repeat_copies() {
+        let mut mirror = Mirror::new(dot(3), 9)
+            .unwrap()
+            .with_copies(3)
+            .unwrap()
+            .with_mode(MirrorMode::Repeat);
+        let mut buffer = [RGB8::default(); 9];
+        mirror.update(&mut buffer).unwrap();
+        mirror.update(&mut buffer).unwrap();
+        assert_eq!(lit(&buffer), [1, 4, 7]);
+    }
+
+    #[test]
+    fn test_reflect_four_copies() {
+        let mirror = Mirror::new(dot(3), 12).unwrap().with_copies(4).unwrap();
+        let mut buffer = [RGB8::default(); 12];
+        mirror.current(&mut buffer).unwrap();
+        assert_eq!(lit(&buffer), [0, 5, 6, 11]);
+    }
+
+    #[test]
+    fn test_uneven_ring_cuts_last_segment() {
+        let mut mirror = Mirror::new(dot(4), 7).unwrap();
+        assert_eq!(mirror.segment_len(), 4);
+        let mut buffer = [RGB8::default(); 7];
+        mirror.advance(3).unwrap();
+        mirror.current(&mut buffer).unwrap();
+        assert_eq!(lit(&buffer), [3, 4]);
+
+        mirror.reset();
+        mirror.current(&mut buffer).unwrap();
+        // The reflected LED 0 falls past the end of the ring
+        assert_eq!(lit(&buffer), [0]);
+    }
+
+    #[test]
+    fn test_copies_clamped_to_ring() {
+        let mirror = Mirror::new(dot(1), 3).unwrap().with_copies(10).unwrap();
+        assert_eq!(mirror.segment_len(), 1);
+        let mut buffer = [RGB8::default(); 3];
+        mirror.current(&mut buffer).unwrap();
+        assert_eq!(buffer, [WHITE; 3]);
+    }
+
+    #[test]
+    fn test_zero_copies_returns_error() {
+        let result = Mirror::new(dot(4), 8).unwrap().with_copies(0);
+        assert_eq!(result.unwrap_err(), EffectError::ZeroRepeat);
+    }
+
+    #[test]
+    fn test_buffer_too_small_returns_error() {
+        let mirror = Mirror::new(dot(4), 8).unwrap();
+        let mut buffer = [RGB8::default(); 6];
+        assert_eq!(
+            mirror.current(&mut buffer).unwrap_err(),
+            EffectError::BufferTooSmall {
+                required: 8,
+                actual: 6
+            }
+        );
+    }
+
+    #[test]
+    fn test_trait_object_update() {
+        let mut mirror = Mirror::new(dot(2), 4).unwrap();
+        let effect_ref: &mut dyn Effect = &mut mirror;
+        let mut buffer = [RGB8::default(); 4];
+        effect_ref.update(&mut buffer).unwrap();
+        assert_eq!(lit(&buffer), [0, 3]);
+    }
+}
@@ -0,0 +1,314 @@
+//! Effect selection by name.
+//!
+//! Serial consoles, MQTT topics and web UIs talk in strings. [`EffectKind`]
+//! parses an effect name with [`FromStr`](core::str::FromStr) and lists all
+//! names for menus; [`NamedEffect`] holds any of the built-in effects behind
+//! one type, so picking an effect at runtime needs neither a heap nor a
+//! `match` in application code.
+
+use crate::effect::{Effect, EffectError};
+use crate::{
+    ChaseEffect, FlashEffect, ProgressEffect, PulseEffect, RainbowEffect, SpinnerEffect,
+    TwinkleEffect,
+};
+use core::fmt;
+use core::str::FromStr;
+use rgb::RGB8;
+
+/// A built-in effect that can be selected by name.
+///
+/// Names are lowercase and parsed case-insensitively.
+///
+/// # Example
+///
+/// ```
+/// use ferriswheel::EffectKind;
+///
+/// let kind: EffectKind = "Rainbow".parse().unwrap();
+/// assert_eq!(kind, EffectKind::Rainbow);
+/// assert_eq!(kind.name(), "rainbow");
+///
+/// // Build a menu
+/// for kind in EffectKind::ALL {
+///     let _label = kind.name();
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum EffectKind {
+    /// [`RainbowEffect`]
+    Rainbow,
+    /// [`PulseEffect`]
+    Pulse,
+    /// [`SpinnerEffect`]
+    Spinner,
+    /// [`ChaseEffect`]
+    Chase,
+    /// [`FlashEffect`]
+    Flash,
+    /// [`TwinkleEffect`]
+    Twinkle,
+    /// [`ProgressEffect`]
+    Progress,
+}
+
+impl EffectKind {
+    /// All selectable effects, in menu order.
+    pub const ALL: [EffectKind; 7] = [
+        EffectKind::Rainbow,
+        EffectKind::Pulse,
+        EffectKind::Spinner,
+        EffectKind::Chase,
+        EffectKind::Flash,
+        EffectKind::Twinkle,
+        EffectKind::Progress,
+    ];
+
+    /// Returns the name this effect is selected by.
+    pub const fn name(self) -> &'static str {
+        match self {
+            EffectKind::Rainbow => "rainbow",
+            EffectKind::Pulse => "pulse",
+            EffectKind::Spinner => "spinner",
+            EffectKind::Chase => "chase",
+            EffectKind::Flash => "flash",
+            EffectKind::Twinkle => "twinkle",
+            EffectKind::Progress => "progress",
+        }
+    }
+
+    /// Creates the effect with its default configuration for `num_leds` LEDs.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the effect's `new()`, e.g. `EffectError::ZeroLeds`.
+    pub fn create(self, num_leds: usize) -> Result<NamedEffect, EffectError> {
+        Ok(match self {
+            EffectKind::Rainbow => NamedEffect::Rainbow(RainbowEffect::new(num_leds)?),
+            EffectKind::Pulse => NamedEffect::Pulse(PulseEffect::new(num_leds)?),
+            EffectKind::Spinner => NamedEffect::Spinner(SpinnerEffect::new(num_leds)?),
+            EffectKind::Chase => NamedEffect::Chase(ChaseEffect::new(num_leds)?),
+            EffectKind::Flash => NamedEffect::Flash(FlashEffect::new(num_leds)?),
+            EffectKind::Twinkle => NamedEffect::Twinkle(TwinkleEffect::new(num_leds)?),
+            EffectKind::Progress => NamedEffect::Progress(ProgressEffect::new(num_leds)?),
+        })
+    }
+}
+
+impl FromStr for EffectKind {
+    type Err = EffectError;
+
+    /// Parses an effect name, ignoring ASCII case and surrounding whitespace.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::UnknownEffect` if no effect has that name.
+    fn from_str(name: &str) -> Result<Self, EffectError> {
+        let name = name.trim();
+        EffectKind::ALL
+            .into_iter()
+            .find(|kind| kind.name().eq_ignore_ascii_case(name))
+            .ok_or(EffectError::UnknownEffect)
+    }
+}
+
+impl fmt::Display for EffectKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// One of the built-in effects, selected at runtime.
+///
+/// Dispatches [`Effect`] to the contained effect. Match on the variant to
+/// change its configuration after creation.
+///
+/// The enum is as large as its largest variant, the [`TwinkleEffect`] with
+/// its per-LED state.
+///
+/// # Example
+///
+/// ```
+/// use ferriswheel::{Effect, NamedEffect};
+/// use rgb::RGB8;
+///
+/// // e.g. from a serial command "effect spinner"
+/// let mut effect = NamedEffect::by_name("spinner", 12).unwrap();
+/// let mut buffer = [RGB8::default(); 12];
+/// effect.update(&mut buffer).unwrap();
+///
+/// assert!(NamedEffect::by_name("disco", 12).is_err());
+/// ```
+// Boxing the twinkle state would need a heap
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone)]
+pub enum NamedEffect {
+    /// A [`RainbowEffect`].
+    Rainbow(RainbowEffect),
+    /// A [`PulseEffect`].
+    Pulse(PulseEffect),
+    /// A [`SpinnerEffect`].
+    Spinner(SpinnerEffect),
+    /// A [`ChaseEffect`].
+    Chase(ChaseEffect),
+    /// A [`FlashEffect`].
+    Flash(FlashEffect),
+    /// A [`TwinkleEffect`].
+    Twinkle(TwinkleEffect),
+    /// A [`ProgressEffect`].
+    Progress(ProgressEffect),
+}
+
+impl NamedEffect {
+    /// Creates the effect called `name` with its default configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::UnknownEffect` if no effect has that name, or the
+    /// error of the effect's `new()`.
+    pub fn by_name(name: &str, num_leds: usize) -> Result<Self, EffectError> {
+        name.parse::<EffectKind>()?.create(num_leds)
+    }
+
+    /// Returns which effect this is.
+    pub fn kind(&self) -> EffectKind {
+        match self {
+            NamedEffect::Rainbow(_) => EffectKind::Rainbow,
+            NamedEffect::Pulse(_) => EffectKind::Pulse,
+            NamedEffect::Spinner(_) => EffectKind::Spinner,
+            NamedEffect::Chase(_) => EffectKind::Chase,
+            NamedEffect::Flash(_) => EffectKind::Flash,
+            NamedEffect::Twinkle(_) => EffectKind::Twinkle,
+            NamedEffect::Progress(_) => EffectKind::Progress,
+        }
+    }
+
+    /// Returns the name of the contained effect.
+    pub fn name(&self) -> &'static str {
+        self.kind().name()
+    }
+
+    fn as_effect(&self) -> &dyn Effect {
+        match self {
+            NamedEffect::Rainbow(effect) => effect,
+            NamedEffect::Pulse(effect) => effect,
+            NamedEffect::Spinner(effect) => effect,
+            NamedEffect::Chase(effect) => effect,
+            NamedEffect::Flash(effect) => effect,
+            NamedEffect::Twinkle(effect) => effect,
+            NamedEffect::Progress(effect) => effect,
+        }
+    }
+
+    fn as_effect_mut(&mut self) -> &mut dyn Effect {
+        match self {
+            NamedEffect::Rainbow(effect) => effect,
+            NamedEffect::Pulse(effect) => effect,
+            NamedEffect::Spinner(effect) => effect,
+            NamedEffect::Chase(effect) => effect,
+            NamedEffect::Flash(effect) => effect,
+            NamedEffect::Twinkle(effect) => effect,
+            NamedEffect::Progress(effect) => effect,
+        }
+    }
+}
+
+impl Effect for NamedEffect {
+    fn update(&mut self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        self.as_effect_mut().update(buffer)
+    }
+
+    fn current(&self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        self.as_effect().current(buffer)
+    }
+
+    fn reset(&mut self) {
+        self.as_effect_mut().reset();
+    }
+
+    fn advance(&mut self, steps: u32) -> Result<(), EffectError> {
+        self.as_effect_mut().advance(steps)
+    }
+
+    fn is_finished(&self) -> bool {
+        self.as_effect().is_finished()
+    }
+
+    fn set_phase(&mut self, phase: u8) {
+        self.as_effect_mut().set_phase(phase);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_name_parses_back() {
+        for kind in EffectKind::ALL {
+            assert_eq!(kind.name().parse::<EffectKind>(), Ok(kind));
+            assert_eq!(format!("{}", kind), kind.name());
+        }
+    }
+
+    #[test]
+    fn test_parse_ignores_case_and_whitespace() {
+        assert_eq!(" PULSE\r\n".parse::<EffectKind>(), Ok(EffectKind::Pulse));
+    }
+
+    #[test]
+    fn test_unknown_name_returns_error() {
+        assert_eq!(
+            "strobe".parse::<EffectKind>(),
+            Err(EffectError::UnknownEffect)
+        );
+        assert_eq!("".parse::<EffectKind>(), Err(EffectError::UnknownEffect));
+    }
+
+    #[test]
+    fn test_created_effect_matches_kind() {
+        for kind in EffectKind::ALL {
+            let effect = kind.create(8).unwrap();
+            assert_eq!(effect.kind(), kind);
+            assert_eq!(effect.name(), kind.name());
+        }
+    }
+
+    #[test]
+    fn test_create_passes_constructor_errors() {
+        assert_eq!(
+            NamedEffect::by_name("rainbow", 0).unwrap_err(),
+            EffectError::ZeroLeds
+        );
+    }
+
+    #[test]
+    fn test_dispatches_to_contained_effect() {
+        let mut named = NamedEffect::by_name("rainbow", 12).unwrap();
+        let mut direct = RainbowEffect::new(12).unwrap();
+        let mut buffer = [RGB8::default(); 12];
+        let mut expected = [RGB8::default(); 12];
+        for _ in 0..3 {
+            named.update(&mut buffer).unwrap();
+            direct.update(&mut expected).unwrap();
+        }
+        assert_eq!(buffer, expected);
+
+        named.advance(5).unwrap();
+        direct.advance(5).unwrap();
+        named.current(&mut buffer).unwrap();
+        direct.current(&mut expected).unwrap();
+        assert_eq!(buffer, expected);
+    }
+
+    #[test]
+    fn test_variant_can_be_configured() {
+        let mut named = NamedEffect::by_name("progress", 4).unwrap();
+        if let NamedEffect::Progress(progress) = &mut named {
+            progress.set_progress(255);
+        }
+        let mut buffer = [RGB8::default(); 4];
+        named.current(&mut buffer).unwrap();
+        assert_eq!(buffer, [RGB8::new(0, 255, 0); 4]);
+    }
+}
@@ -1,5 +1,9 @@
 //! A three-color palette for themed LED effects.
+//!
+//! [`PaletteFader`] morphs one palette into another over several updates,
+//! so themed effects can change colors smoothly.
 
+use crate::util::lerp_color;
 use rgb::RGB8;
 
 /// A three-color theme palette.
@@ -7,9 +11,10 @@ use rgb::RGB8;
 /// Provides primary, secondary, and accent colors for effects that
 /// need a coordinated color scheme (e.g., [`SectionEffect`](crate::SectionEffect)).
 ///
-/// Currently [`SectionEffect`](crate::SectionEffect) uses only `primary` for rendering.
-/// The `secondary` and `accent` fields are available for future rendering
-/// modes such as gradients or alternating patterns within a section.
+/// [`SectionEffect`](crate::SectionEffect) renders `primary` by default and
+/// uses `secondary` for its gradient and alternating render modes
+/// (see [`SectionRenderMode`](crate::SectionRenderMode)).
+/// The `accent` field is available for custom effects.
 ///
 /// # Example
 ///
@@ -25,6 +30,7 @@ use rgb::RGB8;
 /// assert_eq!(palette.primary, RGB8::new(255, 0, 0));
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct ColorPalette {
     /// The dominant color.
     pub primary: RGB8,
@@ -52,6 +58,100 @@ impl ColorPalette {
             accent: color,
         }
     }
+
+    /// Linearly interpolates each color between two palettes.
+    ///
+    /// `t` ranges from 0 (returns `a`) to 255 (returns `b`).
+    pub fn lerp(a: &ColorPalette, b: &ColorPalette, t: u8) -> Self {
+        Self {
+            primary: lerp_color(a.primary, b.primary, t),
+            secondary: lerp_color(a.secondary, b.secondary, t),
+            accent: lerp_color(a.accent, b.accent, t),
+        }
+    }
+}
+
+/// Gradually morphs the active palette toward a target palette.
+///
+/// Call [`fade_to`](PaletteFader::fade_to) when the target changes and
+/// [`update`](PaletteFader::update) once per frame; pass the returned palette
+/// to the effect (e.g. via [`SectionEffect::set_sections`](crate::SectionEffect::set_sections)).
+/// Starting a new fade mid-way continues from the currently shown colors.
+///
+/// # Example
+///
+/// ```
+/// use ferriswheel::{ColorPalette, PaletteFader};
+/// use rgb::RGB8;
+///
+/// let ok = ColorPalette::mono(RGB8::new(0, 255, 0));
+/// let error = ColorPalette::mono(RGB8::new(255, 0, 0));
+///
+/// let mut fader = PaletteFader::new(ok);
+/// fader.fade_to(error, 30);
+///
+/// while !fader.is_finished() {
+///     let palette = fader.update();
+///     // render with `palette`
+/// #   let _ = palette;
+/// }
+/// assert_eq!(fader.current(), error);
+/// ```
+#[derive(Debug, Clone)]
+pub struct PaletteFader {
+    from: ColorPalette,
+    to: ColorPalette,
+    duration: u16,
+    step: u16,
+}
+
+impl PaletteFader {
+    /// Creates a fader showing `palette` with no fade in progress.
+    pub fn new(palette: ColorPalette) -> Self {
+        Self {
+            from: palette,
+            to: palette,
+            duration: 0,
+            step: 0,
+        }
+    }
+
+    /// Starts fading from the current colors to `target` over `updates` updates.
+    ///
+    /// With `updates` of 0 the target is shown immediately.
+    pub fn fade_to(&mut self, target: ColorPalette, updates: u16) {
+        self.from = self.current();
+        self.to = target;
+        self.duration = updates;
+        self.step = 0;
+    }
+
+    /// Returns the palette currently shown.
+    pub fn current(&self) -> ColorPalette {
+        if self.step >= self.duration {
+            return self.to;
+        }
+        let t = (self.step as u32 * 255 / self.duration as u32) as u8;
+        ColorPalette::lerp(&self.from, &self.to, t)
+    }
+
+    /// Advances the fade by one update and returns the new palette.
+    pub fn update(&mut self) -> ColorPalette {
+        if self.step < self.duration {
+            self.step += 1;
+        }
+        self.current()
+    }
+
+    /// Returns the palette being faded to.
+    pub fn target(&self) -> ColorPalette {
+        self.to
+    }
+
+    /// Returns `true` once the target palette is fully shown.
+    pub fn is_finished(&self) -> bool {
+        self.step >= self.duration
+    }
 }
 
 #[cfg(test)]
@@ -79,6 +179,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::clone_on_copy)]
     fn test_copy_clone() {
         let palette = ColorPalette::mono(RGB8::new(10, 20, 30));
         let copied = palette;
@@ -95,4 +196,58 @@ mod tests {
         assert_eq!(a, b);
         assert_ne!(a, c);
     }
+
+    #[test]
+    fn test_lerp_endpoints_and_midpoint() {
+        let a = ColorPalette::mono(RGB8::new(0, 0, 0));
+        let b = ColorPalette::new(
+            RGB8::new(200, 0, 0),
+            RGB8::new(0, 200, 0),
+            RGB8::new(0, 0, 200),
+        );
+        assert_eq!(ColorPalette::lerp(&a, &b, 0), a);
+        assert_eq!(ColorPalette::lerp(&a, &b, 255), b);
+        let mid = ColorPalette::lerp(&a, &b, 128);
+        assert_eq!(mid.primary, lerp_color(a.primary, b.primary, 128));
+        assert_eq!(mid.accent, lerp_color(a.accent, b.accent, 128));
+    }
+
+    #[test]
+    fn test_fader_reaches_target_after_updates() {
+        let a = ColorPalette::mono(RGB8::new(0, 0, 0));
+        let b = ColorPalette::mono(RGB8::new(255, 255, 255));
+        let mut fader = PaletteFader::new(a);
+        assert!(fader.is_finished());
+
+        fader.fade_to(b, 4);
+        let mut levels = Vec::new();
+        for _ in 0..4 {
+            levels.push(fader.update().primary.r);
+        }
+        assert_eq!(levels, [63, 127, 191, 255]);
+        assert!(fader.is_finished());
+        assert_eq!(fader.update(), b);
+    }
+
+    #[test]
+    fn test_fader_zero_updates_snaps() {
+        let a = ColorPalette::mono(RGB8::new(0, 0, 0));
+        let b = ColorPalette::mono(RGB8::new(9, 9, 9));
+        let mut fader = PaletteFader::new(a);
+        fader.fade_to(b, 0);
+        assert_eq!(fader.current(), b);
+    }
+
+    #[test]
+    fn test_fader_retarget_continues_from_current_colors() {
+        let black = ColorPalette::mono(RGB8::new(0, 0, 0));
+        let white = ColorPalette::mono(RGB8::new(255, 255, 255));
+        let mut fader = PaletteFader::new(black);
+        fader.fade_to(white, 2);
+        let halfway = fader.update();
+
+        fader.fade_to(black, 10);
+        assert_eq!(fader.current(), halfway);
+        assert_eq!(fader.target(), black);
+    }
 }
@@ -0,0 +1,180 @@
+//! Frame post-processing: brightness, color correction and gamma in one pass.
+
+use crate::correction::ColorCorrection;
+use crate::util::gamma8;
+use rgb::RGB8;
+
+/// Post-processes finished frames before they are sent to the LEDs.
+///
+/// The steps run in a fixed order:
+///
+/// 1. master brightness and [`ColorCorrection`] (combined into one scale per channel)
+/// 2. optional gamma correction
+///
+/// Scaling happens before gamma so that brightness stays perceptually linear.
+///
+/// # Example
+///
+/// ```
+/// use ferriswheel::{ColorCorrection, Effect, Pipeline, RainbowEffect};
+/// use rgb::RGB8;
+///
+/// let pipeline = Pipeline::new()
+///     .with_brightness(64)
+///     .with_correction(ColorCorrection::TypicalSMD5050)
+///     .with_gamma(true);
+///
+/// let mut rainbow = RainbowEffect::new(12).unwrap();
+/// let mut buffer = [RGB8::default(); 12];
+/// rainbow.update(&mut buffer).unwrap();
+/// pipeline.apply(&mut buffer);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Pipeline {
+    brightness: u8,
+    correction: ColorCorrection,
+    gamma: bool,
+}
+
+impl Pipeline {
+    /// Creates a pipeline that leaves frames unchanged.
+    ///
+    /// # Default Configuration
+    ///
+    /// - Brightness: 255 (full)
+    /// - Correction: [`ColorCorrection::Uncorrected`]
+    /// - Gamma: off
+    pub fn new() -> Self {
+        Self {
+            brightness: 255,
+            correction: ColorCorrection::Uncorrected,
+            gamma: false,
+        }
+    }
+
+    /// Sets the master brightness (0–255).
+    pub fn with_brightness(mut self, brightness: u8) -> Self {
+        self.brightness = brightness;
+        self
+    }
+
+    /// Sets the color correction.
+    pub fn with_correction(mut self, correction: ColorCorrection) -> Self {
+        self.correction = correction;
+        self
+    }
+
+    /// Enables or disables gamma correction.
+    pub fn with_gamma(mut self, gamma: bool) -> Self {
+        self.gamma = gamma;
+        self
+    }
+
+    /// Changes the master brightness (0–255).
+    pub fn set_brightness(&mut self, brightness: u8) {
+        self.brightness = brightness;
+    }
+
+    /// Returns the master brightness.
+    pub fn brightness(&self) -> u8 {
+        self.brightness
+    }
+
+    /// Returns the color correction.
+    pub fn correction(&self) -> ColorCorrection {
+        self.correction
+    }
+
+    /// Returns `true` if gamma correction is enabled.
+    pub fn gamma(&self) -> bool {
+        self.gamma
+    }
+
+    /// Processes a single color.
+    pub fn apply_color(&self, color: RGB8) -> RGB8 {
+        let scale = self.channel_scale();
+        let channel = |value: u8, scale: u8| {
+            let scaled = ((value as u16 * scale as u16) / 255) as u8;
+            if self.gamma {
+                gamma8(scaled)
+            } else {
+                scaled
+            }
+        };
+        RGB8::new(
+            channel(color.r, scale.r),
+            channel(color.g, scale.g),
+            channel(color.b, scale.b),
+        )
+    }
+
+    /// Processes every color in `buffer` in place.
+    pub fn apply(&self, buffer: &mut [RGB8]) {
+        for pixel in buffer.iter_mut() {
+            *pixel = self.apply_color(*pixel);
+        }
+    }
+
+    /// Combines the master brightness with the color correction factors.
+    fn channel_scale(&self) -> RGB8 {
+        let correction = self.correction.scale();
+        let b = self.brightness as u16;
+        RGB8::new(
+            ((correction.r as u16 * b) / 255) as u8,
+            ((correction.g as u16 * b) / 255) as u8,
+            ((correction.b as u16 * b) / 255) as u8,
+        )
+    }
+}
+
+impl Default for Pipeline {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_is_identity() {
+        let mut buffer = [RGB8::new(1, 128, 255), RGB8::new(77, 0, 3)];
+        let expected = buffer;
+        Pipeline::new().apply(&mut buffer);
+        assert_eq!(buffer, expected);
+    }
+
+    #[test]
+    fn test_brightness_and_correction_combine() {
+        let pipeline = Pipeline::new()
+            .with_brightness(128)
+            .with_correction(ColorCorrection::Custom(RGB8::new(255, 128, 0)));
+        assert_eq!(
+            pipeline.apply_color(RGB8::new(255, 255, 255)),
+            RGB8::new(128, 64, 0)
+        );
+    }
+
+    #[test]
+    fn test_gamma_runs_after_scaling() {
+        let pipeline = Pipeline::new().with_brightness(128).with_gamma(true);
+        // 255 scaled to 128, then gamma-corrected
+        assert_eq!(
+            pipeline.apply_color(RGB8::new(255, 0, 0)),
+            RGB8::new(gamma8(128), 0, 0)
+        );
+    }
+
+    #[test]
+    fn test_set_brightness() {
+        let mut pipeline = Pipeline::new();
+        pipeline.set_brightness(0);
+        assert_eq!(pipeline.brightness(), 0);
+        assert_eq!(
+            pipeline.apply_color(RGB8::new(255, 255, 255)),
+            RGB8::default()
+        );
+    }
+}
@@ -0,0 +1,338 @@
+//! Logical-to-physical LED index mapping.
+//!
+//! Effects treat LED 0 as the start of the ring and count clockwise.
+//! Real rings are rarely wired that way: the data input may sit at any
+//! position and the LEDs may run counter-clockwise. [`PixelMap`] moves the
+//! rendered frame to the physical layout so effects don't need their own
+//! offset options.
+
+use crate::effect::{validate_buffer, validate_num_leds_up_to, Effect, EffectError, MAX_LEDS};
+use rgb::RGB8;
+
+/// Wraps an effect and remaps its logical LED indices to physical ones.
+///
+/// The mapping is either a rotation offset combined with an optional reversal,
+/// or a custom lookup table set with [`with_table`](PixelMap::with_table).
+/// A table replaces the offset and reversal.
+///
+/// # Example
+///
+/// ```
+/// use ferriswheel::{Effect, PixelMap, SpinnerEffect};
+/// use rgb::RGB8;
+///
+/// // 12 o'clock is physical LED 3, and the ring is wired counter-clockwise
+/// let spinner = SpinnerEffect::new(12).unwrap();
+/// let mut mapped = PixelMap::new(spinner, 12).unwrap()
+///     .with_offset(3)
+///     .with_reversed(true);
+/// let mut buffer = [RGB8::default(); 12];
+///
+/// mapped.update(&mut buffer).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct PixelMap<E> {
+    effect: E,
+    num_leds: usize,
+    offset: usize,
+    reversed: bool,
+    table: Option<[u8; MAX_LEDS]>,
+}
+
+impl<E: Effect> PixelMap<E> {
+    /// Wraps `effect`, which renders `num_leds` LEDs.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::ZeroLeds` if `num_leds` is 0.
+    /// Returns `EffectError::TooManyLeds` if `num_leds` exceeds `MAX_LEDS`, even with the `alloc` feature.
+    ///
+    /// # Default Configuration
+    ///
+    /// - Offset: 0
+    /// - Reversed: false
+    /// - No lookup table (identity mapping)
+    pub fn new(effect: E, num_leds: usize) -> Result<Self, EffectError> {
+        validate_num_leds_up_to(num_leds, MAX_LEDS)?;
+
+        Ok(Self {
+            effect,
+            num_leds,
+            offset: 0,
+            reversed: false,
+            table: None,
+        })
+    }
+
+    /// Sets the physical index of logical LED 0.
+    ///
+    /// The offset wraps around the ring.
+    pub fn with_offset(mut self, offset: usize) -> Self {
+        self.offset = offset % self.num_leds;
+        self
+    }
+
+    /// Sets whether physical indices run in the opposite direction.
+    pub fn with_reversed(mut self, reversed: bool) -> Self {
+        self.reversed = reversed;
+        self
+    }
+
+    /// Sets a custom lookup table: logical LED `i` is shown on physical LED `table[i]`.
+    ///
+    /// The first `num_leds` entries must be a permutation of `0..num_leds`, so
+    /// every physical LED is written exactly once per frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::BufferTooSmall` if `table` has fewer than `num_leds` entries.
+    /// Returns `EffectError::InvalidPixelIndex` if an entry is not below `num_leds`.
+    /// Returns `EffectError::DuplicatePixelIndex` if two entries map to the same LED.
+    pub fn with_table(mut self, table: &[u8]) -> Result<Self, EffectError> {
+        if table.len() < self.num_leds {
+            return Err(EffectError::BufferTooSmall {
+                required: self.num_leds,
+                actual: table.len(),
+            });
+        }
+        let mut lookup = [0; MAX_LEDS];
+        let mut used = [false; MAX_LEDS];
+        for (entry, &index) in lookup.iter_mut().zip(&table[..self.num_leds]) {
+            let index = index as usize;
+            if index >= self.num_leds {
+                return Err(EffectError::InvalidPixelIndex {
+                    index,
+                    num_leds: self.num_leds,
+                });
+            }
+            if used[index] {
+                return Err(EffectError::DuplicatePixelIndex { index });
+            }
+            used[index] = true;
+            *entry = index as u8;
+        }
+        self.table = Some(lookup);
+        Ok(self)
+    }
+
+    /// Returns the number of LEDs this mapping is configured for.
+    pub fn num_leds(&self) -> usize {
+        self.num_leds
+    }
+
+    /// Returns the physical index of logical LED `logical`.
+    pub fn physical_index(&self, logical: usize) -> usize {
+        let n = self.num_leds;
+        let logical = logical % n;
+        if let Some(table) = &self.table {
+            return table[logical] as usize;
+        }
+        if self.reversed {
+            (self.offset + n - logical) % n
+        } else {
+            (self.offset + logical) % n
+        }
+    }
+
+    /// Returns a reference to the wrapped effect.
+    pub fn inner(&self) -> &E {
+        &self.effect
+    }
+
+    /// Returns a mutable reference to the wrapped effect.
+    pub fn inner_mut(&mut self) -> &mut E {
+        &mut self.effect
+    }
+
+    /// Consumes the wrapper and returns the wrapped effect.
+    pub fn into_inner(self) -> E {
+        self.effect
+    }
+
+    /// Copies the logical frame into `buffer` at the physical positions.
+    fn remap(&self, logical: &[RGB8], buffer: &mut [RGB8]) {
+        for (i, &color) in logical.iter().enumerate() {
+            buffer[self.physical_index(i)] = color;
+        }
+    }
+
+    /// Fills the buffer with the remapped frame without advancing.
+    pub fn current(&self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        validate_buffer(buffer, self.num_leds)?;
+
+        let mut logical = [RGB8::default(); MAX_LEDS];
+        self.effect.current(&mut logical[..self.num_leds])?;
+        self.remap(&logical[..self.num_leds], buffer);
+        Ok(())
+    }
+
+    /// Fills the buffer with the remapped frame and advances the wrapped effect.
+    pub fn update(&mut self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        validate_buffer(buffer, self.num_leds)?;
+
+        let mut logical = [RGB8::default(); MAX_LEDS];
+        self.effect.update(&mut logical[..self.num_leds])?;
+        self.remap(&logical[..self.num_leds], buffer);
+        Ok(())
+    }
+
+    /// Resets the wrapped effect.
+    pub fn reset(&mut self) {
+        self.effect.reset();
+    }
+}
+
+impl<E: Effect> Effect for PixelMap<E> {
+    fn update(&mut self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        self.update(buffer)
+    }
+
+    fn current(&self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        self.current(buffer)
+    }
+
+    fn reset(&mut self) {
+        self.reset();
+    }
+
+    fn advance(&mut self, steps: u32) -> Result<(), EffectError> {
+        self.effect.advance(steps)
+    }
+
+    fn is_finished(&self) -> bool {
+        self.effect.is_finished()
+    }
+
+    fn set_phase(&mut self, phase: u8) {
+        self.effect.set_phase(phase);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SpinnerEffect;
+
+    const WHITE: RGB8 = RGB8::new(255, 255, 255);
+
+    fn dot(n: usize) -> SpinnerEffect {
+        SpinnerEffect::new(n).unwrap().with_tail_length(0)
+    }
+
+    fn lit(buffer: &[RGB8]) -> usize {
+        buffer.iter().position(|&led| led == WHITE).unwrap()
+    }
+
+    #[test]
+    fn test_new_with_zero_leds_returns_error() {
+        assert_eq!(PixelMap::new(dot(4), 0).unwrap_err(), EffectError::ZeroLeds);
+    }
+
+    #[test]
+    fn test_identity_mapping() {
+        let map = PixelMap::new(dot(8), 8).unwrap();
+        for i in 0..8 {
+            assert_eq!(map.physical_index(i), i);
+        }
+    }
+
+    #[test]
+    fn test_offset_rotates_frame() {
+        let mut map = PixelMap::new(dot(8), 8).unwrap().with_offset(3);
+        let mut buffer = [RGB8::default(); 8];
+        map.update(&mut buffer).unwrap();
+        assert_eq!(lit(&buffer), 3);
+        map.update(&mut buffer).unwrap();
+        assert_eq!(lit(&buffer), 4);
+    }
+
+    #[test]
+    fn test_offset_wraps() {
+        let map = PixelMap::new(dot(8), 8).unwrap().with_offset(10);
+        assert_eq!(map.physical_index(0), 2);
+    }
+
+    #[test]
+    fn test_reversed_runs_backwards_from_offset() {
+        let mut map = PixelMap::new(dot(8), 8)
+            .unwrap()
+            .with_offset(2)
+            .with_reversed(true);
+        let mut buffer = [RGB8::default(); 8];
+        let mut positions = Vec::new();
+        for _ in 0..4 {
+            map.update(&mut buffer).unwrap();
+            positions.push(lit(&buffer));
+        }
+        assert_eq!(positions, [2, 1, 0, 7]);
+    }
+
+    #[test]
+    fn test_custom_table() {
+        let table = [3, 2, 1, 0];
+        let mut map = PixelMap::new(dot(4), 4)
+            .unwrap()
+            .with_table(&table)
+            .unwrap();
+        let mut buffer = [RGB8::default(); 4];
+        map.update(&mut buffer).unwrap();
+        assert_eq!(lit(&buffer), 3);
+        map.update(&mut buffer).unwrap();
+        assert_eq!(lit(&buffer), 2);
+    }
+
+    #[test]
+    fn test_table_too_short_returns_error() {
+        let result = PixelMap::new(dot(4), 4).unwrap().with_table(&[0, 1]);
+        assert_eq!(
+            result.unwrap_err(),
+            EffectError::BufferTooSmall {
+                required: 4,
+                actual: 2
+            }
+        );
+    }
+
+    #[test]
+    fn test_table_with_invalid_index_returns_error() {
+        let result = PixelMap::new(dot(4), 4).unwrap().with_table(&[0, 1, 4, 2]);
+        assert_eq!(
+            result.unwrap_err(),
+            EffectError::InvalidPixelIndex {
+                index: 4,
+                num_leds: 4
+            }
+        );
+    }
+
+    #[test]
+    fn test_table_with_duplicate_index_returns_error() {
+        let result = PixelMap::new(dot(4), 4).unwrap().with_table(&[0, 1, 1, 2]);
+        assert_eq!(
+            result.unwrap_err(),
+            EffectError::DuplicatePixelIndex { index: 1 }
+        );
+    }
+
+    #[test]
+    fn test_buffer_too_small_returns_error() {
+        let map = PixelMap::new(dot(8), 8).unwrap();
+        let mut buffer = [RGB8::default(); 4];
+        assert_eq!(
+            map.current(&mut buffer).unwrap_err(),
+            EffectError::BufferTooSmall {
+                required: 8,
+                actual: 4
+            }
+        );
+    }
+
+    #[test]
+    fn test_trait_object_update() {
+        let mut map = PixelMap::new(dot(8), 8).unwrap().with_offset(5);
+        let effect_ref: &mut dyn Effect = &mut map;
+        let mut buffer = [RGB8::default(); 8];
+        effect_ref.update(&mut buffer).unwrap();
+        assert_eq!(lit(&buffer), 5);
+    }
+}
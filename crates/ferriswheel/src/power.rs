@@ -0,0 +1,229 @@
+//! Current draw estimation and limiting.
+//!
+//! A WS2812 ring at full white easily draws more than a USB port can supply.
+//! [`estimate_current_ma`] predicts the draw of a frame from a [`PowerModel`],
+//! and [`PowerLimiter`] scales frames down so they stay within a budget.
+
+use crate::util::scale_brightness;
+use rgb::RGB8;
+
+/// Current draw of a single LED.
+///
+/// Channel values are the draw at full brightness (255) in milliamps;
+/// lower values are assumed to scale linearly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PowerModel {
+    /// Red channel draw at full brightness (mA).
+    pub red_ma: u16,
+    /// Green channel draw at full brightness (mA).
+    pub green_ma: u16,
+    /// Blue channel draw at full brightness (mA).
+    pub blue_ma: u16,
+    /// Quiescent draw of the LED driver, even when dark (mA).
+    pub idle_ma: u16,
+}
+
+impl PowerModel {
+    /// Typical WS2812B: 20 mA per channel and 1 mA idle.
+    pub const WS2812B: PowerModel = PowerModel {
+        red_ma: 20,
+        green_ma: 20,
+        blue_ma: 20,
+        idle_ma: 1,
+    };
+}
+
+impl Default for PowerModel {
+    fn default() -> Self {
+        Self::WS2812B
+    }
+}
+
+/// Estimates the current draw of a frame in milliamps.
+///
+/// # Example
+///
+/// ```
+/// use ferriswheel::{estimate_current_ma, PowerModel};
+/// use rgb::RGB8;
+///
+/// let frame = [RGB8::new(255, 255, 255); 12];
+/// // 12 LEDs × (3 × 20 mA + 1 mA idle)
+/// assert_eq!(estimate_current_ma(&frame, &PowerModel::WS2812B), 732);
+/// ```
+pub fn estimate_current_ma(frame: &[RGB8], model: &PowerModel) -> u32 {
+    let idle = frame.len() as u32 * model.idle_ma as u32;
+    idle.saturating_add(active_current_ma(frame, model))
+}
+
+/// Current draw of the lit channels, without the idle draw.
+fn active_current_ma(frame: &[RGB8], model: &PowerModel) -> u32 {
+    let (mut r, mut g, mut b) = (0u64, 0u64, 0u64);
+    for pixel in frame {
+        r += pixel.r as u64;
+        g += pixel.g as u64;
+        b += pixel.b as u64;
+    }
+    let total = r * model.red_ma as u64 + g * model.green_ma as u64 + b * model.blue_ma as u64;
+    (total / 255).min(u32::MAX as u64) as u32
+}
+
+/// Scales frames down so their estimated current stays within a budget.
+///
+/// Frames below the budget are left untouched. Frames above it are dimmed
+/// uniformly, so colors keep their hue.
+///
+/// # Example
+///
+/// ```
+/// use ferriswheel::{estimate_current_ma, PowerLimiter, PowerModel};
+/// use rgb::RGB8;
+///
+/// // 500 mA USB port
+/// let limiter = PowerLimiter::new(500);
+/// let mut frame = [RGB8::new(255, 255, 255); 24];
+///
+/// limiter.limit(&mut frame);
+/// assert!(estimate_current_ma(&frame, &PowerModel::WS2812B) <= 500);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PowerLimiter {
+    budget_ma: u32,
+    model: PowerModel,
+}
+
+impl PowerLimiter {
+    /// Creates a limiter for the given budget in milliamps.
+    ///
+    /// # Default Configuration
+    ///
+    /// - Model: [`PowerModel::WS2812B`]
+    pub fn new(budget_ma: u32) -> Self {
+        Self {
+            budget_ma,
+            model: PowerModel::WS2812B,
+        }
+    }
+
+    /// Sets the LED power model.
+    pub fn with_model(mut self, model: PowerModel) -> Self {
+        self.model = model;
+        self
+    }
+
+    /// Changes the budget in milliamps.
+    pub fn set_budget_ma(&mut self, budget_ma: u32) {
+        self.budget_ma = budget_ma;
+    }
+
+    /// Returns the budget in milliamps.
+    pub fn budget_ma(&self) -> u32 {
+        self.budget_ma
+    }
+
+    /// Returns the brightness scale (0–255) that brings `frame` within budget.
+    ///
+    /// A dark frame returns 255: there is nothing to dim, even when the idle
+    /// draw alone exceeds the budget.
+    pub fn scale_for(&self, frame: &[RGB8]) -> u8 {
+        let idle = frame.len() as u64 * self.model.idle_ma as u64;
+        let active = active_current_ma(frame, &self.model) as u64;
+        if active == 0 || idle + active <= self.budget_ma as u64 {
+            return u8::MAX;
+        }
+        let available = (self.budget_ma as u64).saturating_sub(idle);
+        (available * 255 / active) as u8
+    }
+
+    /// Dims `frame` in place if needed and returns the applied scale (255 = unchanged).
+    pub fn limit(&self, frame: &mut [RGB8]) -> u8 {
+        let scale = self.scale_for(frame);
+        if scale < u8::MAX {
+            for pixel in frame.iter_mut() {
+                *pixel = scale_brightness(*pixel, scale);
+            }
+        }
+        scale
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WHITE: RGB8 = RGB8::new(255, 255, 255);
+
+    #[test]
+    fn test_estimate_dark_frame_is_idle_only() {
+        let frame = [RGB8::default(); 10];
+        assert_eq!(estimate_current_ma(&frame, &PowerModel::WS2812B), 10);
+    }
+
+    #[test]
+    fn test_estimate_uses_per_channel_draw() {
+        let model = PowerModel {
+            red_ma: 10,
+            green_ma: 20,
+            blue_ma: 30,
+            idle_ma: 0,
+        };
+        let frame = [
+            RGB8::new(255, 0, 0),
+            RGB8::new(0, 255, 0),
+            RGB8::new(0, 0, 128),
+        ];
+        // 10 + 20 + 30 × 128/255
+        assert_eq!(estimate_current_ma(&frame, &model), 45);
+    }
+
+    #[test]
+    fn test_frame_within_budget_is_unchanged() {
+        let limiter = PowerLimiter::new(1_000);
+        let mut frame = [WHITE; 8];
+        assert_eq!(limiter.limit(&mut frame), 255);
+        assert_eq!(frame, [WHITE; 8]);
+    }
+
+    #[test]
+    fn test_frame_over_budget_is_dimmed_within_budget() {
+        let limiter = PowerLimiter::new(500);
+        let mut frame = [WHITE; 24];
+        let scale = limiter.limit(&mut frame);
+
+        assert!(scale < 255);
+        assert!(estimate_current_ma(&frame, &PowerModel::WS2812B) <= 500);
+        // Hue is preserved
+        assert_eq!(frame[0].r, frame[0].g);
+        assert_eq!(frame[0].g, frame[0].b);
+    }
+
+    #[test]
+    fn test_budget_below_idle_turns_frame_off() {
+        let limiter = PowerLimiter::new(5);
+        let mut frame = [WHITE; 10];
+        assert_eq!(limiter.limit(&mut frame), 0);
+        assert_eq!(frame, [RGB8::default(); 10]);
+    }
+
+    #[test]
+    fn test_dark_frame_with_budget_below_idle_is_unchanged() {
+        let limiter = PowerLimiter::new(5);
+        let mut frame = [RGB8::default(); 10];
+        assert_eq!(limiter.limit(&mut frame), u8::MAX);
+        assert_eq!(frame, [RGB8::default(); 10]);
+    }
+
+    #[test]
+    fn test_large_strip_with_high_draw_does_not_overflow() {
+        let model = PowerModel {
+            red_ma: u16::MAX,
+            green_ma: u16::MAX,
+            blue_ma: u16::MAX,
+            idle_ma: 0,
+        };
+        let limiter = PowerLimiter::new(u32::MAX).with_model(model);
+        let frame = [WHITE; 4096];
+        assert_eq!(limiter.scale_for(&frame), u8::MAX);
+    }
+}
@@ -0,0 +1,332 @@
+//! Idling animations on battery-powered devices.
+//!
+//! A status ring that keeps animating long after anyone looked at it wastes
+//! power on rendering, on the data line and on the LEDs themselves.
+//! [`PowerSaver`] idles the wrapped effect after a number of updates without
+//! activity and resumes it on [`wake`](PowerSaver::wake).
+
+use crate::effect::{Effect, EffectError};
+use crate::util::fill_solid;
+use rgb::RGB8;
+
+/// What a [`PowerSaver`] does while idle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum IdleMode {
+    /// Turn the LEDs off and pause the effect.
+    #[default]
+    Blank,
+    /// Advance the effect only on every n-th update; values below 1 count as 1.
+    Throttle(u8),
+}
+
+/// Wraps an effect and idles it after a period without activity.
+///
+/// The idle timeout counts updates. Call [`wake`](PowerSaver::wake) whenever
+/// something happens that the user should see; the effect resumes where it
+/// was paused, without being reset.
+///
+/// While idle, most updates leave the frame unchanged. Check
+/// [`frame_changed`](PowerSaver::frame_changed) after each update to skip
+/// writing identical frames to the LEDs.
+///
+/// # Example
+///
+/// ```
+/// use ferriswheel::{IdleMode, PowerSaver, PulseEffect};
+/// use rgb::RGB8;
+///
+/// let pulse = PulseEffect::new(12).unwrap();
+/// // Blank after 30 s at 50 frames per second
+/// let mut saver = PowerSaver::new(pulse, 1_500).with_idle_mode(IdleMode::Blank);
+/// let mut buffer = [RGB8::default(); 12];
+///
+/// saver.update(&mut buffer).unwrap();
+/// if saver.frame_changed() {
+///     // driver.write(&buffer)
+/// }
+///
+/// // Button pressed
+/// saver.wake();
+/// ```
+#[derive(Debug, Clone)]
+pub struct PowerSaver<E> {
+    effect: E,
+    idle_after: u32,
+    mode: IdleMode,
+    since_wake: u32,
+    asleep: bool,
+    throttle_count: u8,
+    changed: bool,
+}
+
+impl<E: Effect> PowerSaver<E> {
+    /// Wraps `effect`, idling it after `idle_after` updates without [`wake`](Self::wake).
+    ///
+    /// An `idle_after` of 0 only idles on [`sleep`](Self::sleep).
+    ///
+    /// # Default Configuration
+    ///
+    /// - Idle mode: [`IdleMode::Blank`]
+    pub fn new(effect: E, idle_after: u32) -> Self {
+        Self {
+            effect,
+            idle_after,
+            mode: IdleMode::Blank,
+            since_wake: 0,
+            asleep: false,
+            throttle_count: 0,
+            changed: false,
+        }
+    }
+
+    /// Sets what happens while idle.
+    pub fn with_idle_mode(mut self, mode: IdleMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Changes the number of updates without activity before idling.
+    pub fn set_idle_after(&mut self, idle_after: u32) {
+        self.idle_after = idle_after;
+    }
+
+    /// Returns the number of updates without activity before idling.
+    pub fn idle_after(&self) -> u32 {
+        self.idle_after
+    }
+
+    /// Returns `true` while the effect is idle.
+    pub fn is_idle(&self) -> bool {
+        self.asleep || (self.idle_after > 0 && self.since_wake >= self.idle_after)
+    }
+
+    /// Leaves idle mode and restarts the idle timeout.
+    pub fn wake(&mut self) {
+        self.since_wake = 0;
+        self.asleep = false;
+        self.throttle_count = 0;
+    }
+
+    /// Enters idle mode immediately.
+    pub fn sleep(&mut self) {
+        self.asleep = true;
+    }
+
+    /// Returns `true` if the last `update()` produced a different frame than
+    /// the one before it, i.e. the frame needs to be written.
+    pub fn frame_changed(&self) -> bool {
+        self.changed
+    }
+
+    /// Returns a reference to the wrapped effect.
+    pub fn inner(&self) -> &E {
+        &self.effect
+    }
+
+    /// Returns a mutable reference to the wrapped effect.
+    pub fn inner_mut(&mut self) -> &mut E {
+        &mut self.effect
+    }
+
+    /// Consumes the wrapper and returns the wrapped effect.
+    pub fn into_inner(self) -> E {
+        self.effect
+    }
+
+    /// Fills the buffer with the current frame without advancing.
+    ///
+    /// While blanked, the whole buffer is filled with black.
+    pub fn current(&self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        if self.is_idle() && self.mode == IdleMode::Blank {
+            fill_solid(buffer, RGB8::default());
+            return Ok(());
+        }
+        self.effect.current(buffer)
+    }
+
+    /// Fills the buffer and advances the wrapped effect unless it is idle.
+    pub fn update(&mut self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        let was_idle = self.is_idle();
+        self.since_wake = self.since_wake.saturating_add(1);
+
+        if !was_idle {
+            self.changed = true;
+            return self.effect.update(buffer);
+        }
+
+        match self.mode {
+            IdleMode::Blank => {
+                // Only the first blank frame differs from the previous one
+                self.changed = self.throttle_count == 0;
+                self.throttle_count = 1;
+                fill_solid(buffer, RGB8::default());
+                Ok(())
+            }
+            IdleMode::Throttle(every) => {
+                // Hold each frame for `every` updates, then advance once
+                self.changed = self.throttle_count == 0;
+                self.effect.current(buffer)?;
+                self.throttle_count = (self.throttle_count + 1) % every.max(1);
+                if self.throttle_count == 0 {
+                    self.effect.advance(1)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Resets the wrapped effect and wakes up.
+    pub fn reset(&mut self) {
+        self.effect.reset();
+        self.wake();
+    }
+}
+
+impl<E: Effect> Effect for PowerSaver<E> {
+    fn update(&mut self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        self.update(buffer)
+    }
+
+    fn current(&self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        self.current(buffer)
+    }
+
+    fn reset(&mut self) {
+        self.reset();
+    }
+
+    fn advance(&mut self, steps: u32) -> Result<(), EffectError> {
+        self.effect.advance(steps)
+    }
+
+    fn is_finished(&self) -> bool {
+        self.effect.is_finished()
+    }
+
+    fn set_phase(&mut self, phase: u8) {
+        self.effect.set_phase(phase);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SpinnerEffect;
+
+    const WHITE: RGB8 = RGB8::new(255, 255, 255);
+
+    fn dot() -> SpinnerEffect {
+        SpinnerEffect::new(4).unwrap().with_tail_length(0)
+    }
+
+    fn lit(buffer: &[RGB8]) -> Option<usize> {
+        buffer.iter().position(|&led| led == WHITE)
+    }
+
+    #[test]
+    fn test_active_until_timeout() {
+        let mut saver = PowerSaver::new(dot(), 3);
+        let mut buffer = [RGB8::default(); 4];
+        for expected in 0..3 {
+            assert!(!saver.is_idle());
+            saver.update(&mut buffer).unwrap();
+            assert!(saver.frame_changed());
+            assert_eq!(lit(&buffer), Some(expected));
+        }
+        assert!(saver.is_idle());
+    }
+
+    #[test]
+    fn test_blank_turns_leds_off_once() {
+        let mut saver = PowerSaver::new(dot(), 1);
+        let mut buffer = [RGB8::default(); 4];
+        saver.update(&mut buffer).unwrap();
+
+        saver.update(&mut buffer).unwrap();
+        assert!(saver.frame_changed());
+        assert_eq!(buffer, [RGB8::default(); 4]);
+
+        saver.update(&mut buffer).unwrap();
+        assert!(!saver.frame_changed());
+        assert_eq!(buffer, [RGB8::default(); 4]);
+    }
+
+    #[test]
+    fn test_wake_resumes_without_reset() {
+        let mut saver = PowerSaver::new(dot(), 2);
+        let mut buffer = [RGB8::default(); 4];
+        saver.update(&mut buffer).unwrap();
+        saver.update(&mut buffer).unwrap();
+        saver.update(&mut buffer).unwrap();
+        saver.update(&mut buffer).unwrap();
+
+        saver.wake();
+        assert!(!saver.is_idle());
+        saver.update(&mut buffer).unwrap();
+        assert!(saver.frame_changed());
+        assert_eq!(lit(&buffer), Some(2));
+    }
+
+    #[test]
+    fn test_throttle_advances_every_nth_update() {
+        let mut saver = PowerSaver::new(dot(), 1).with_idle_mode(IdleMode::Throttle(3));
+        let mut buffer = [RGB8::default(); 4];
+        saver.update(&mut buffer).unwrap();
+
+        let mut positions = Vec::new();
+        let mut changed = Vec::new();
+        for _ in 0..6 {
+            saver.update(&mut buffer).unwrap();
+            positions.push(lit(&buffer).unwrap());
+            changed.push(saver.frame_changed());
+        }
+        assert_eq!(positions, [1, 1, 1, 2, 2, 2]);
+        assert_eq!(changed, [true, false, false, true, false, false]);
+    }
+
+    #[test]
+    fn test_zero_timeout_never_idles_on_its_own() {
+        let mut saver = PowerSaver::new(dot(), 0);
+        let mut buffer = [RGB8::default(); 4];
+        for _ in 0..100 {
+            saver.update(&mut buffer).unwrap();
+        }
+        assert!(!saver.is_idle());
+
+        saver.sleep();
+        assert!(saver.is_idle());
+        saver.update(&mut buffer).unwrap();
+        assert_eq!(buffer, [RGB8::default(); 4]);
+    }
+
+    #[test]
+    fn test_current_while_blank_is_black() {
+        let mut saver = PowerSaver::new(dot(), 5);
+        saver.sleep();
+        let mut buffer = [WHITE; 4];
+        saver.current(&mut buffer).unwrap();
+        assert_eq!(buffer, [RGB8::default(); 4]);
+    }
+
+    #[test]
+    fn test_reset_wakes_and_restarts_effect() {
+        let mut saver = PowerSaver::new(dot(), 1);
+        let mut buffer = [RGB8::default(); 4];
+        saver.update(&mut buffer).unwrap();
+        saver.update(&mut buffer).unwrap();
+        saver.reset();
+        assert!(!saver.is_idle());
+        saver.update(&mut buffer).unwrap();
+        assert_eq!(lit(&buffer), Some(0));
+    }
+
+    #[test]
+    fn test_trait_object_update() {
+        let mut saver = PowerSaver::new(dot(), 10);
+        let effect_ref: &mut dyn Effect = &mut saver;
+        let mut buffer = [RGB8::default(); 4];
+        effect_ref.update(&mut buffer).unwrap();
+        assert_eq!(lit(&buffer), Some(0));
+    }
+}
@@ -0,0 +1,40 @@
+//! Commonly used items in one import.
+//!
+//! Brings the [`Effect`] trait, the built-in effects, [`Direction`], the
+//! palettes, the color helpers and `RGB8` into scope. Wrappers, composition
+//! types and feature-gated items still need their own `use` line.
+//!
+//! # Example
+//!
+//! ```
+//! use ferriswheel::prelude::*;
+//!
+//! let mut spinner = SpinnerEffect::new(12)
+//!     .unwrap()
+//!     .with_direction(Direction::CounterClockwise);
+//! let mut buffer = [RGB8::default(); 12];
+//!
+//! spinner.update(&mut buffer).unwrap();
+//! gamma_correct(&mut buffer);
+//! ```
+
+pub use crate::chase::ChaseEffect;
+pub use crate::easing::Easing;
+pub use crate::effect::{Direction, Effect, EffectError, MAX_LEDS};
+pub use crate::flash::FlashEffect;
+pub use crate::gradient::GradientPalette;
+pub use crate::hsv::{hsv_to_rgb, rgb_to_hsv};
+pub use crate::palette::{ColorPalette, PaletteFader};
+pub use crate::progress::{FillMode, ProgressEffect};
+pub use crate::pulse::PulseEffect;
+pub use crate::rainbow::RainbowEffect;
+pub use crate::section::SectionEffect;
+pub use crate::spinner::SpinnerEffect;
+pub use crate::transition::{TransitionMode, WipeTransitionEffect};
+pub use crate::twinkle::TwinkleEffect;
+pub use crate::util::{
+    add_color, blur1d, fade_to_black_by, fill_solid, full_sine_wave, gamma8, gamma_correct,
+    lerp_color, nblend, scale_brightness, sine_wave, triangle_wave,
+};
+pub use crate::waveform::Waveform;
+pub use rgb::RGB8;
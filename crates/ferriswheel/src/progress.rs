@@ -2,15 +2,36 @@
 //!
 //! Fills the ring proportionally based on a progress value (0–255).
 //! Supports partial LED blending for smooth transitions.
-
-use crate::effect::{validate_buffer, validate_num_leds, Effect, EffectError};
+//!
+//! The fill can start at any LED and sweep in either direction, or grow
+//! symmetrically outward from the start LED (see [`FillMode`]).
+//! The filled arc is either a single color or a gradient along the arc.
+//! With smoothing enabled, new targets are approached over several updates
+//! instead of jumping.
+
+use crate::easing::Easing;
+use crate::effect::{validate_buffer, validate_num_leds, Direction, Effect, EffectError};
 use crate::util::lerp_color;
 use rgb::RGB8;
 
+/// How [`ProgressEffect`] grows its filled arc.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum FillMode {
+    /// Fill from the start LED in the configured direction.
+    #[default]
+    Sweep,
+    /// Fill symmetrically in both directions from the start LED.
+    CenterOut,
+}
+
 /// A progress indicator effect that fills the ring proportionally.
 ///
-/// Progress is set externally via [`set_progress`](ProgressEffect::set_progress).
-/// Calling `update()` renders the current progress without advancing any animation.
+/// Progress is set externally via [`set_progress`](ProgressEffect::set_progress),
+/// which jumps straight to the new value, or [`set_target`](ProgressEffect::set_target),
+/// which animates towards it when [`with_smoothing`](ProgressEffect::with_smoothing)
+/// is configured. Calling `update()` renders the current progress and advances
+/// a running smoothing animation; otherwise nothing moves.
 ///
 /// # Example
 ///
@@ -25,12 +46,38 @@ use rgb::RGB8;
 /// progress.set_progress(128);
 /// progress.update(&mut buffer).unwrap();
 /// ```
+///
+/// Smoothing a jumpy OTA progress report over 20 frames:
+///
+/// ```
+/// use ferriswheel::{Easing, ProgressEffect};
+/// use rgb::RGB8;
+///
+/// let mut progress = ProgressEffect::new(12).unwrap()
+///     .with_smoothing(20)
+///     .with_easing(Easing::QuadOut);
+/// let mut buffer = [RGB8::default(); 12];
+///
+/// progress.set_target(200);
+/// progress.update(&mut buffer).unwrap();
+/// assert!(progress.progress() < 200);
+/// assert_eq!(progress.target(), 200);
+/// ```
 #[derive(Debug, Clone)]
 pub struct ProgressEffect {
     num_leds: usize,
     fill_color: RGB8,
+    gradient_end: Option<RGB8>,
     empty_color: RGB8,
     progress: u8,
+    start_index: usize,
+    direction: Direction,
+    fill_mode: FillMode,
+    smoothing_ticks: u16,
+    easing: Easing,
+    from: u8,
+    target: u8,
+    tick: u16,
 }
 
 impl ProgressEffect {
@@ -39,27 +86,56 @@ impl ProgressEffect {
     /// # Errors
     ///
     /// Returns `EffectError::ZeroLeds` if `num_leds` is 0.
-    /// Returns `EffectError::TooManyLeds` if `num_leds` exceeds `MAX_LEDS`.
+    /// Returns `EffectError::TooManyLeds` if `num_leds` exceeds `MAX_LEDS`
+    /// (`MAX_LEDS_ALLOC` with the `alloc` feature).
     ///
     /// # Default Configuration
     ///
     /// - Fill color: green (0, 255, 0)
     /// - Empty color: off (0, 0, 0)
     /// - Progress: 0
+    /// - Start index: 0
+    /// - Direction: Clockwise
+    /// - Fill mode: [`FillMode::Sweep`]
+    /// - Smoothing: off
+    /// - Easing: linear
     pub fn new(num_leds: usize) -> Result<Self, EffectError> {
         validate_num_leds(num_leds)?;
 
         Ok(Self {
             num_leds,
             fill_color: RGB8::new(0, 255, 0),
+            gradient_end: None,
             empty_color: RGB8::new(0, 0, 0),
             progress: 0,
+            start_index: 0,
+            direction: Direction::Clockwise,
+            fill_mode: FillMode::Sweep,
+            smoothing_ticks: 0,
+            easing: Easing::Linear,
+            from: 0,
+            target: 0,
+            tick: 0,
         })
     }
 
     /// Sets the color of filled LEDs.
+    ///
+    /// Replaces any gradient set with [`with_gradient`](Self::with_gradient).
     pub fn with_fill_color(mut self, color: RGB8) -> Self {
         self.fill_color = color;
+        self.gradient_end = None;
+        self
+    }
+
+    /// Colors the filled arc as a gradient along the ring.
+    ///
+    /// The first LED of the arc uses `start`, the LED reached at 100% uses `end`,
+    /// and the LEDs in between are interpolated with [`lerp_color`].
+    /// For example, red to green makes a battery gauge that turns greener as it fills.
+    pub fn with_gradient(mut self, start: RGB8, end: RGB8) -> Self {
+        self.fill_color = start;
+        self.gradient_end = Some(end);
         self
     }
 
@@ -69,39 +145,142 @@ impl ProgressEffect {
         self
     }
 
+    /// Sets the LED index where the fill begins.
+    ///
+    /// Use this to align 0% with the physical top of the ring.
+    /// Indices past the end of the ring wrap around.
+    pub fn with_start_index(mut self, start_index: usize) -> Self {
+        self.start_index = start_index % self.num_leds;
+        self
+    }
+
+    /// Sets the direction the fill sweeps in.
+    ///
+    /// Ignored in [`FillMode::CenterOut`], which grows in both directions.
+    pub fn with_direction(mut self, direction: Direction) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Sets how the filled arc grows.
+    pub fn with_fill_mode(mut self, fill_mode: FillMode) -> Self {
+        self.fill_mode = fill_mode;
+        self
+    }
+
+    /// Makes [`set_target`](Self::set_target) animate over `ticks` updates.
+    ///
+    /// A value of 0 disables smoothing, so targets are shown immediately.
+    pub fn with_smoothing(mut self, ticks: u16) -> Self {
+        self.smoothing_ticks = ticks;
+        self
+    }
+
+    /// Sets the easing curve of the smoothing animation.
+    ///
+    /// [`Easing::QuadOut`] or [`Easing::CubicOut`] move quickly at first and
+    /// settle gently on the target.
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
     /// Sets the current progress (0–255, mapping to 0%–100%).
+    ///
+    /// The new value is shown immediately and stops any smoothing animation.
     pub fn set_progress(&mut self, progress: u8) {
         self.progress = progress;
+        self.from = progress;
+        self.target = progress;
+        self.tick = self.smoothing_ticks;
+    }
+
+    /// Animates the displayed progress towards `progress` (0–255).
+    ///
+    /// The animation starts at the currently displayed value, so a new target
+    /// can be set while the previous one is still being approached. Without
+    /// smoothing this behaves like [`set_progress`](Self::set_progress).
+    pub fn set_target(&mut self, progress: u8) {
+        if self.smoothing_ticks == 0 {
+            self.set_progress(progress);
+            return;
+        }
+        self.from = self.progress;
+        self.target = progress;
+        self.tick = 0;
     }
 
-    /// Returns the current progress value.
+    /// Returns the currently displayed progress value.
     pub fn progress(&self) -> u8 {
         self.progress
     }
 
+    /// Returns the progress value the effect is animating towards.
+    pub fn target(&self) -> u8 {
+        self.target
+    }
+
+    /// Returns `true` while the displayed progress is moving towards the target.
+    pub fn is_animating(&self) -> bool {
+        self.tick < self.smoothing_ticks
+    }
+
     /// Returns the number of LEDs this effect is configured for.
     pub fn num_leds(&self) -> usize {
         self.num_leds
     }
 
+    /// Returns the fill level of LED `i` and the total number of levels.
+    ///
+    /// Levels are filled in order: in sweep mode each LED is its own level,
+    /// in center-out mode both LEDs at the same distance share a level.
+    fn level(&self, i: usize) -> (usize, usize) {
+        let n = self.num_leds;
+        let forward = (i + n - self.start_index) % n;
+        match self.fill_mode {
+            FillMode::Sweep => {
+                let level = match self.direction {
+                    Direction::Clockwise => forward,
+                    Direction::CounterClockwise => (n - forward) % n,
+                };
+                (level, n)
+            }
+            FillMode::CenterOut => (forward.min(n - forward), n / 2 + 1),
+        }
+    }
+
+    /// Returns the fill color for an LED at `level` out of `levels`.
+    fn fill_color_at(&self, level: usize, levels: usize) -> RGB8 {
+        match self.gradient_end {
+            Some(end) if levels > 1 => {
+                let t = (level * 255 / (levels - 1)) as u8;
+                lerp_color(self.fill_color, end, t)
+            }
+            _ => self.fill_color,
+        }
+    }
+
     /// Fills the buffer with the current progress state without changing it.
     pub fn current(&self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
         validate_buffer(buffer, self.num_leds)?;
 
         let n = self.num_leds;
 
-        // Scale progress (0–255) to LED-space (0–num_leds*256)
-        // This gives sub-LED resolution for partial fill.
-        let fill_256 = self.progress as u32 * n as u32; // 0..(255 * n)
-        let full_leds = (fill_256 / 255) as usize;
-        let fractional = ((fill_256 % 255) * 255 / 255) as u8;
-
         for (i, led) in buffer.iter_mut().take(n).enumerate() {
-            if i < full_leds {
-                *led = self.fill_color;
-            } else if i == full_leds && full_leds < n {
+            let (level, levels) = self.level(i);
+
+            // Scale progress (0–255) to level-space (0–levels*255)
+            // This gives sub-LED resolution for partial fill.
+            let fill_255 = self.progress as u32 * levels as u32;
+            let full_levels = (fill_255 / 255) as usize;
+            let fractional = (fill_255 % 255) as u8;
+
+            let fill = self.fill_color_at(level, levels);
+            if level < full_levels {
+                *led = fill;
+            } else if level == full_levels {
                 // Partial LED: blend between empty and fill based on fraction
-                *led = lerp_color(self.empty_color, self.fill_color, fractional);
+                *led = lerp_color(self.empty_color, fill, fractional);
             } else {
                 *led = self.empty_color;
             }
@@ -110,14 +289,34 @@ impl ProgressEffect {
         Ok(())
     }
 
-    /// Renders the current progress (same as `current` — progress is externally driven).
+    /// Renders the current progress, then advances the smoothing animation, if any.
     pub fn update(&mut self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
-        self.current(buffer)
+        self.current(buffer)?;
+        self.advance(1)
+    }
+
+    /// Advances the smoothing animation by `steps` updates without rendering.
+    ///
+    /// Does nothing when no animation is running; progress only changes through
+    /// [`set_progress`](Self::set_progress) and [`set_target`](Self::set_target).
+    pub fn advance(&mut self, steps: u32) -> Result<(), EffectError> {
+        if !self.is_animating() {
+            return Ok(());
+        }
+        let ticks = self.smoothing_ticks as u32;
+        let tick = (self.tick as u32).saturating_add(steps).min(ticks);
+        self.tick = tick as u16;
+
+        let t = self.easing.apply((tick * 255 / ticks) as u8) as i32;
+        let from = self.from as i32;
+        let delta = self.target as i32 - from;
+        self.progress = (from + delta * t / 255) as u8;
+        Ok(())
     }
 
-    /// Resets progress to 0.
+    /// Resets progress to 0 and stops any smoothing animation.
     pub fn reset(&mut self) {
-        self.progress = 0;
+        self.set_progress(0);
     }
 }
 
@@ -133,6 +332,10 @@ impl Effect for ProgressEffect {
     fn reset(&mut self) {
         self.reset();
     }
+
+    fn advance(&mut self, steps: u32) -> Result<(), EffectError> {
+        self.advance(steps)
+    }
 }
 
 #[cfg(test)]
@@ -195,6 +398,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::manual_range_contains)]
     fn test_half_progress() {
         let mut effect = ProgressEffect::new(8)
             .unwrap()
@@ -244,6 +448,153 @@ mod tests {
         assert_eq!(buffer[3], RGB8::new(0, 0, 0));
     }
 
+    #[test]
+    fn test_start_index_offsets_fill() {
+        let mut effect = ProgressEffect::new(8)
+            .unwrap()
+            .with_fill_color(RGB8::new(255, 0, 0))
+            .with_start_index(6);
+        // Exactly 2 of 8 LEDs
+        effect.set_progress(64);
+
+        let mut buffer = [RGB8::default(); 8];
+        effect.current(&mut buffer).unwrap();
+
+        assert_eq!(buffer[6], RGB8::new(255, 0, 0));
+        assert_eq!(buffer[7], RGB8::new(255, 0, 0));
+        assert_eq!(buffer[0].r, 2, "wrapped partial LED is barely lit");
+        assert_eq!(buffer[5], RGB8::new(0, 0, 0));
+    }
+
+    #[test]
+    fn test_start_index_wraps() {
+        let effect = ProgressEffect::new(8).unwrap().with_start_index(10);
+        let same = ProgressEffect::new(8).unwrap().with_start_index(2);
+        assert_eq!(effect.start_index, same.start_index);
+    }
+
+    #[test]
+    fn test_counter_clockwise_fill() {
+        let mut effect = ProgressEffect::new(8)
+            .unwrap()
+            .with_fill_color(RGB8::new(255, 0, 0))
+            .with_direction(Direction::CounterClockwise);
+        effect.set_progress(96); // 3 LEDs
+
+        let mut buffer = [RGB8::default(); 8];
+        effect.current(&mut buffer).unwrap();
+
+        assert_eq!(buffer[0], RGB8::new(255, 0, 0));
+        assert_eq!(buffer[7], RGB8::new(255, 0, 0));
+        assert_eq!(buffer[6], RGB8::new(255, 0, 0));
+        assert_eq!(buffer[1], RGB8::new(0, 0, 0));
+    }
+
+    #[test]
+    fn test_center_out_fills_symmetrically() {
+        let mut effect = ProgressEffect::new(12)
+            .unwrap()
+            .with_fill_color(RGB8::new(0, 0, 255))
+            .with_fill_mode(FillMode::CenterOut);
+
+        let mut buffer = [RGB8::default(); 12];
+        for progress in [0, 40, 100, 180, 255] {
+            effect.set_progress(progress);
+            effect.current(&mut buffer).unwrap();
+            for d in 1..6 {
+                assert_eq!(
+                    buffer[d],
+                    buffer[12 - d],
+                    "progress {}: distance {} should mirror",
+                    progress,
+                    d
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_center_out_full_progress_fills_ring() {
+        for n in [1, 7, 8] {
+            let mut effect = ProgressEffect::new(n)
+                .unwrap()
+                .with_fill_color(RGB8::new(0, 255, 0))
+                .with_fill_mode(FillMode::CenterOut)
+                .with_start_index(3);
+            effect.set_progress(255);
+
+            let mut buffer = [RGB8::default(); 8];
+            effect.current(&mut buffer).unwrap();
+            for led in buffer.iter().take(n) {
+                assert_eq!(*led, RGB8::new(0, 255, 0), "ring of {} not full", n);
+            }
+        }
+    }
+
+    #[test]
+    fn test_gradient_spans_full_arc() {
+        let mut effect = ProgressEffect::new(5)
+            .unwrap()
+            .with_gradient(RGB8::new(255, 0, 0), RGB8::new(0, 255, 0));
+        effect.set_progress(255);
+
+        let mut buffer = [RGB8::default(); 5];
+        effect.current(&mut buffer).unwrap();
+
+        assert_eq!(buffer[0], RGB8::new(255, 0, 0));
+        assert_eq!(
+            buffer[2],
+            lerp_color(RGB8::new(255, 0, 0), RGB8::new(0, 255, 0), 127)
+        );
+        assert_eq!(buffer[4], RGB8::new(0, 255, 0));
+    }
+
+    #[test]
+    fn test_gradient_colors_stay_put_while_filling() {
+        let mut effect = ProgressEffect::new(5)
+            .unwrap()
+            .with_gradient(RGB8::new(255, 0, 0), RGB8::new(0, 255, 0));
+
+        let mut full = [RGB8::default(); 5];
+        effect.set_progress(255);
+        effect.current(&mut full).unwrap();
+
+        let mut partial = [RGB8::default(); 5];
+        effect.set_progress(102); // exactly 2 LEDs
+        effect.current(&mut partial).unwrap();
+
+        assert_eq!(partial[0], full[0]);
+        assert_eq!(partial[1], full[1]);
+        assert_eq!(partial[2], RGB8::new(0, 0, 0));
+    }
+
+    #[test]
+    fn test_fill_color_clears_gradient() {
+        let mut effect = ProgressEffect::new(4)
+            .unwrap()
+            .with_gradient(RGB8::new(255, 0, 0), RGB8::new(0, 255, 0))
+            .with_fill_color(RGB8::new(0, 0, 255));
+        effect.set_progress(255);
+
+        let mut buffer = [RGB8::default(); 4];
+        effect.current(&mut buffer).unwrap();
+        for led in &buffer {
+            assert_eq!(*led, RGB8::new(0, 0, 255));
+        }
+    }
+
+    #[test]
+    fn test_gradient_single_led_uses_start_color() {
+        let mut effect = ProgressEffect::new(1)
+            .unwrap()
+            .with_gradient(RGB8::new(255, 0, 0), RGB8::new(0, 255, 0));
+        effect.set_progress(255);
+
+        let mut buffer = [RGB8::default(); 1];
+        effect.current(&mut buffer).unwrap();
+        assert_eq!(buffer[0], RGB8::new(255, 0, 0));
+    }
+
     #[test]
     fn test_custom_empty_color() {
         let effect = ProgressEffect::new(4)
@@ -293,4 +644,94 @@ mod tests {
         let filled = buffer.iter().filter(|led| led.r > 128).count();
         assert!(filled >= 2, "most LEDs should be filled at progress 200");
     }
+
+    #[test]
+    fn test_set_target_without_smoothing_jumps() {
+        let mut effect = ProgressEffect::new(4).unwrap();
+        effect.set_target(180);
+        assert_eq!(effect.progress(), 180);
+        assert!(!effect.is_animating());
+    }
+
+    #[test]
+    fn test_set_target_animates_over_ticks() {
+        let mut effect = ProgressEffect::new(4).unwrap().with_smoothing(5);
+        effect.set_target(200);
+
+        let mut buffer = [RGB8::default(); 4];
+        let mut shown = Vec::new();
+        for _ in 0..7 {
+            effect.update(&mut buffer).unwrap();
+            shown.push(effect.progress());
+        }
+        assert_eq!(shown, [40, 80, 120, 160, 200, 200, 200]);
+        assert!(!effect.is_animating());
+    }
+
+    #[test]
+    fn test_first_update_renders_start_value() {
+        let mut effect = ProgressEffect::new(4)
+            .unwrap()
+            .with_fill_color(RGB8::new(255, 0, 0))
+            .with_smoothing(10);
+        effect.set_target(255);
+
+        let mut buffer = [RGB8::default(); 4];
+        effect.update(&mut buffer).unwrap();
+        assert_eq!(buffer, [RGB8::default(); 4]);
+    }
+
+    #[test]
+    fn test_smoothing_downwards() {
+        let mut effect = ProgressEffect::new(4).unwrap().with_smoothing(5);
+        effect.set_progress(200);
+        effect.set_target(100);
+        effect.advance(1).unwrap();
+        assert_eq!(effect.progress(), 180);
+        effect.advance(4).unwrap();
+        assert_eq!(effect.progress(), 100);
+    }
+
+    #[test]
+    fn test_retarget_starts_from_displayed_value() {
+        let mut effect = ProgressEffect::new(4).unwrap().with_smoothing(5);
+        effect.set_target(200);
+        effect.advance(2).unwrap();
+        assert_eq!(effect.progress(), 80);
+
+        effect.set_target(0);
+        effect.advance(1).unwrap();
+        assert_eq!(effect.progress(), 64);
+    }
+
+    #[test]
+    fn test_easing_shapes_smoothing() {
+        let mut linear = ProgressEffect::new(4).unwrap().with_smoothing(10);
+        let mut eased = ProgressEffect::new(4)
+            .unwrap()
+            .with_smoothing(10)
+            .with_easing(Easing::QuadOut);
+        linear.set_target(255);
+        eased.set_target(255);
+        linear.advance(3).unwrap();
+        eased.advance(3).unwrap();
+        assert!(eased.progress() > linear.progress());
+
+        linear.advance(100).unwrap();
+        eased.advance(100).unwrap();
+        assert_eq!(linear.progress(), 255);
+        assert_eq!(eased.progress(), 255);
+    }
+
+    #[test]
+    fn test_set_progress_stops_animation() {
+        let mut effect = ProgressEffect::new(4).unwrap().with_smoothing(8);
+        effect.set_target(255);
+        effect.advance(2).unwrap();
+        effect.set_progress(10);
+        assert!(!effect.is_animating());
+        assert_eq!(effect.target(), 10);
+        effect.advance(5).unwrap();
+        assert_eq!(effect.progress(), 10);
+    }
 }
@@ -1,9 +1,14 @@
 //! Breathing/pulsing animation effect for LED rings.
 //!
-//! All LEDs display the same color with brightness oscillating via a sine wave.
-
-use crate::effect::{validate_buffer, validate_num_leds, validate_speed, Effect, EffectError};
-use crate::util::{scale_brightness, sine_wave};
+//! All LEDs display the same color with brightness oscillating via a periodic [`Waveform`].
+
+use crate::easing::Easing;
+use crate::effect::{
+    validate_buffer, validate_fine_speed, validate_num_leds, validate_speed, Effect, EffectError,
+};
+use crate::frame_iter::PixelSource;
+use crate::util::scale_brightness;
+use crate::waveform::Waveform;
 use rgb::RGB8;
 
 /// A breathing/pulsing animation effect.
@@ -11,6 +16,9 @@ use rgb::RGB8;
 /// All LEDs share the same color, with brightness oscillating smoothly
 /// between configurable minimum and maximum values using a sine wave.
 ///
+/// The default [`Waveform::HalfSine`] keeps the LEDs at minimum brightness for
+/// half of each cycle; [`Waveform::Sine`] breathes continuously.
+///
 /// # Example
 ///
 /// ```
@@ -27,10 +35,14 @@ use rgb::RGB8;
 pub struct PulseEffect {
     num_leds: usize,
     color: RGB8,
-    phase: u8,
-    speed: u8,
+    /// Sine wave phase in 1/256 steps (Q8.8).
+    phase: u16,
+    /// Phase increment per update in 1/256 steps (Q8.8).
+    speed: u16,
     min_brightness: u8,
     max_brightness: u8,
+    easing: Easing,
+    waveform: Waveform,
 }
 
 impl PulseEffect {
@@ -39,7 +51,8 @@ impl PulseEffect {
     /// # Errors
     ///
     /// Returns `EffectError::ZeroLeds` if `num_leds` is 0.
-    /// Returns `EffectError::TooManyLeds` if `num_leds` exceeds `MAX_LEDS`.
+    /// Returns `EffectError::TooManyLeds` if `num_leds` exceeds `MAX_LEDS`
+    /// (`MAX_LEDS_ALLOC` with the `alloc` feature).
     ///
     /// # Default Configuration
     ///
@@ -47,6 +60,8 @@ impl PulseEffect {
     /// - Speed: 2
     /// - Min brightness: 0
     /// - Max brightness: 255
+    /// - Easing: linear
+    /// - Waveform: [`Waveform::HalfSine`]
     pub fn new(num_leds: usize) -> Result<Self, EffectError> {
         validate_num_leds(num_leds)?;
 
@@ -54,9 +69,11 @@ impl PulseEffect {
             num_leds,
             color: RGB8::new(255, 255, 255),
             phase: 0,
-            speed: 2,
+            speed: 2 << 8,
             min_brightness: 0,
             max_brightness: 255,
+            easing: Easing::Linear,
+            waveform: Waveform::HalfSine,
         })
     }
 
@@ -74,8 +91,19 @@ impl PulseEffect {
     ///
     /// Returns `EffectError::ZeroStep` if `speed` is 0.
     pub fn with_speed(mut self, speed: u8) -> Result<Self, EffectError> {
-        validate_speed(speed)?;
-        self.speed = speed;
+        self.set_speed(speed)?;
+        Ok(self)
+    }
+
+    /// Sets the animation speed in 1/256 phase steps per update.
+    ///
+    /// A value of 256 equals `with_speed(1)`; smaller values give very slow breathing.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::ZeroStep` if `speed` is 0.
+    pub fn with_fine_speed(mut self, speed: u16) -> Result<Self, EffectError> {
+        self.set_fine_speed(speed)?;
         Ok(self)
     }
 
@@ -91,16 +119,106 @@ impl PulseEffect {
         self
     }
 
+    /// Sets an easing curve applied to the sine wave before it is mapped to brightness.
+    ///
+    /// [`Easing::QuadIn`] or [`Easing::CubicIn`] keep the LEDs dim for longer and
+    /// make the peak short and bright, similar to a heartbeat.
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Sets the waveform that drives the brightness.
+    pub fn with_waveform(mut self, waveform: Waveform) -> Self {
+        self.waveform = waveform;
+        self
+    }
+
+    /// Changes the pulse color without restarting the animation.
+    pub fn set_color(&mut self, color: RGB8) {
+        self.color = color;
+    }
+
+    /// Changes the animation speed without restarting the animation.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::ZeroStep` if `speed` is 0.
+    pub fn set_speed(&mut self, speed: u8) -> Result<(), EffectError> {
+        validate_speed(speed)?;
+        self.speed = (speed as u16) << 8;
+        Ok(())
+    }
+
+    /// Changes the animation speed in 1/256 phase steps without restarting the animation.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::ZeroStep` if `speed` is 0.
+    pub fn set_fine_speed(&mut self, speed: u16) -> Result<(), EffectError> {
+        validate_fine_speed(speed)?;
+        self.speed = speed;
+        Ok(())
+    }
+
+    /// Changes the minimum brightness (0-255).
+    pub fn set_min_brightness(&mut self, min: u8) {
+        self.min_brightness = min;
+    }
+
+    /// Changes the maximum brightness (0-255).
+    pub fn set_max_brightness(&mut self, max: u8) {
+        self.max_brightness = max;
+    }
+
+    /// Changes the waveform without restarting the animation.
+    pub fn set_waveform(&mut self, waveform: Waveform) {
+        self.waveform = waveform;
+    }
+
+    /// Returns the pulse color.
+    pub fn color(&self) -> RGB8 {
+        self.color
+    }
+
+    /// Returns the animation speed in whole phase steps per update.
+    ///
+    /// Fractional speeds are truncated; see [`fine_speed`](Self::fine_speed).
+    pub fn speed(&self) -> u8 {
+        (self.speed >> 8) as u8
+    }
+
+    /// Returns the animation speed in 1/256 phase steps per update.
+    pub fn fine_speed(&self) -> u16 {
+        self.speed
+    }
+
+    /// Returns the minimum brightness.
+    pub fn min_brightness(&self) -> u8 {
+        self.min_brightness
+    }
+
+    /// Returns the maximum brightness.
+    pub fn max_brightness(&self) -> u8 {
+        self.max_brightness
+    }
+
+    /// Returns the waveform that drives the brightness.
+    pub fn waveform(&self) -> Waveform {
+        self.waveform
+    }
+
     /// Returns the number of LEDs this effect is configured for.
     pub fn num_leds(&self) -> usize {
         self.num_leds
     }
 
-    /// Computes the current brightness from the sine wave phase.
+    /// Computes the current brightness from the waveform phase.
     fn current_brightness(&self) -> u8 {
-        let sine_val = sine_wave(self.phase) as u16;
+        let wave = self.waveform.apply((self.phase >> 8) as u8);
+        let level = self.easing.apply(wave) as u16;
         let range = self.max_brightness as u16 - self.min_brightness as u16;
-        (self.min_brightness as u16 + (sine_val * range) / 255) as u8
+        (self.min_brightness as u16 + (level * range) / 255) as u8
     }
 
     /// Fills the buffer with the current pulse colors without advancing.
@@ -124,10 +242,22 @@ impl PulseEffect {
         Ok(())
     }
 
+    /// Advances the animation by `steps` updates without rendering.
+    pub fn advance(&mut self, steps: u32) -> Result<(), EffectError> {
+        let delta = (self.speed as u32).wrapping_mul(steps) as u16;
+        self.phase = self.phase.wrapping_add(delta);
+        Ok(())
+    }
+
     /// Resets the animation to its initial state.
     pub fn reset(&mut self) {
         self.phase = 0;
     }
+
+    /// Jumps to `phase` (0–255) of the waveform cycle; 0 is where a cycle starts.
+    pub fn set_phase(&mut self, phase: u8) {
+        self.phase = (phase as u16) << 8;
+    }
 }
 
 impl Effect for PulseEffect {
@@ -142,6 +272,24 @@ impl Effect for PulseEffect {
     fn reset(&mut self) {
         self.reset();
     }
+
+    fn advance(&mut self, steps: u32) -> Result<(), EffectError> {
+        self.advance(steps)
+    }
+
+    fn set_phase(&mut self, phase: u8) {
+        self.set_phase(phase);
+    }
+}
+
+impl PixelSource for PulseEffect {
+    fn num_leds(&self) -> usize {
+        self.num_leds
+    }
+
+    fn pixel(&self, _index: usize) -> RGB8 {
+        scale_brightness(self.color, self.current_brightness())
+    }
 }
 
 #[cfg(test)]
@@ -326,4 +474,166 @@ mod tests {
         // After advancing, colors may differ (unless the phase happens to land on the same sine value)
         // At least the trait call should not panic
     }
+
+    #[test]
+    fn test_with_fine_speed_zero_returns_error() {
+        let result = PulseEffect::new(12).unwrap().with_fine_speed(0);
+        assert_eq!(result.unwrap_err(), EffectError::ZeroStep);
+    }
+
+    #[test]
+    fn test_fine_speed_matches_whole_speed_over_time() {
+        let mut fine = PulseEffect::new(4).unwrap().with_fine_speed(128).unwrap();
+        let mut whole = PulseEffect::new(4).unwrap().with_speed(1).unwrap();
+        let mut buf_fine = [RGB8::default(); 4];
+        let mut buf_whole = [RGB8::default(); 4];
+
+        for _ in 0..20 {
+            fine.update(&mut buf_fine).unwrap();
+        }
+        for _ in 0..10 {
+            whole.update(&mut buf_whole).unwrap();
+        }
+        fine.current(&mut buf_fine).unwrap();
+        whole.current(&mut buf_whole).unwrap();
+        assert_eq!(buf_fine, buf_whole);
+    }
+
+    #[test]
+    fn test_set_color_preserves_phase() {
+        let mut effect = PulseEffect::new(4).unwrap();
+        let mut buffer = [RGB8::default(); 4];
+        for _ in 0..20 {
+            effect.update(&mut buffer).unwrap();
+        }
+        effect.current(&mut buffer).unwrap();
+        let brightness = buffer[0].r;
+
+        effect.set_color(RGB8::new(0, 255, 0));
+        effect.current(&mut buffer).unwrap();
+        assert_eq!(buffer[0], RGB8::new(0, brightness, 0));
+        assert_eq!(effect.color(), RGB8::new(0, 255, 0));
+    }
+
+    #[test]
+    fn test_easing_reshapes_brightness_curve() {
+        let mut linear = PulseEffect::new(1).unwrap();
+        let mut eased = PulseEffect::new(1).unwrap().with_easing(Easing::QuadIn);
+        let mut buf_linear = [RGB8::default(); 1];
+        let mut buf_eased = [RGB8::default(); 1];
+
+        // Partway up the sine hump eased brightness stays lower
+        for _ in 0..10 {
+            linear.update(&mut buf_linear).unwrap();
+            eased.update(&mut buf_eased).unwrap();
+        }
+        assert!(buf_eased[0].r < buf_linear[0].r);
+
+        // The peak is unchanged
+        for _ in 0..47 {
+            linear.update(&mut buf_linear).unwrap();
+            eased.update(&mut buf_eased).unwrap();
+        }
+        linear.current(&mut buf_linear).unwrap();
+        eased.current(&mut buf_eased).unwrap();
+        assert_eq!(buf_linear[0].r, 255);
+        assert_eq!(buf_eased[0].r, 255);
+    }
+
+    #[test]
+    fn test_half_sine_is_dark_at_end_of_cycle() {
+        let mut effect = PulseEffect::new(1).unwrap().with_speed(16).unwrap();
+        let mut buffer = [RGB8::default(); 1];
+        for _ in 0..15 {
+            effect.update(&mut buffer).unwrap();
+        }
+        effect.current(&mut buffer).unwrap();
+        assert_eq!(buffer[0], RGB8::default());
+
+        effect.set_waveform(Waveform::Sine);
+        effect.current(&mut buffer).unwrap();
+        assert!(buffer[0].r > 0);
+    }
+
+    #[test]
+    fn test_sine_waveform_stays_lit_in_second_half() {
+        let mut effect = PulseEffect::new(1)
+            .unwrap()
+            .with_waveform(Waveform::Sine)
+            .with_speed(16)
+            .unwrap();
+        assert_eq!(effect.waveform(), Waveform::Sine);
+
+        let mut buffer = [RGB8::default(); 1];
+        let mut levels = Vec::new();
+        for _ in 0..16 {
+            effect.update(&mut buffer).unwrap();
+            levels.push(buffer[0].r);
+        }
+        // Peak in the middle of the cycle, lit until close to the end
+        assert_eq!(levels.iter().position(|&l| l == 255), Some(8));
+        assert!(levels[8..15].iter().all(|&l| l > 0));
+    }
+
+    #[test]
+    fn test_triangle_peak_frame_reaches_max_brightness() {
+        let mut effect = PulseEffect::new(1)
+            .unwrap()
+            .with_color(RGB8::new(255, 255, 255))
+            .with_waveform(Waveform::Triangle)
+            .with_max_brightness(200)
+            .with_speed(1)
+            .unwrap();
+
+        let mut buffer = [RGB8::default(); 1];
+        let mut peak = 0;
+        for _ in 0..256 {
+            effect.update(&mut buffer).unwrap();
+            peak = peak.max(buffer[0].r);
+        }
+        assert_eq!(peak, 200);
+    }
+
+    #[test]
+    fn test_set_waveform_preserves_phase() {
+        let mut effect = PulseEffect::new(1).unwrap().with_speed(16).unwrap();
+        let mut buffer = [RGB8::default(); 1];
+        for _ in 0..4 {
+            effect.update(&mut buffer).unwrap();
+        }
+        effect.set_waveform(Waveform::Triangle);
+        effect.current(&mut buffer).unwrap();
+        assert_eq!(buffer[0].r, 128);
+    }
+
+    #[test]
+    fn test_advance_matches_repeated_update() {
+        let mut updated = PulseEffect::new(2).unwrap().with_fine_speed(300).unwrap();
+        let mut advanced = updated.clone();
+        let mut buf_updated = [RGB8::default(); 2];
+        let mut buf_advanced = [RGB8::default(); 2];
+
+        for _ in 0..77 {
+            updated.update(&mut buf_updated).unwrap();
+        }
+        advanced.advance(77).unwrap();
+
+        updated.current(&mut buf_updated).unwrap();
+        advanced.current(&mut buf_advanced).unwrap();
+        assert_eq!(buf_updated, buf_advanced);
+    }
+
+    #[test]
+    fn test_set_phase_jumps_within_cycle() {
+        let mut effect = PulseEffect::new(1).unwrap().with_speed(4).unwrap();
+        let mut expected = effect.clone();
+        expected.advance(32).unwrap();
+
+        effect.set_phase(128);
+        let mut buffer = [RGB8::default(); 1];
+        let mut expected_buffer = [RGB8::default(); 1];
+        effect.current(&mut buffer).unwrap();
+        expected.current(&mut expected_buffer).unwrap();
+        assert_eq!(buffer, expected_buffer);
+    }
 }
@@ -1,12 +1,22 @@
 //! Rainbow animation effect for LED rings.
 //!
-//! Creates smooth rainbow animations that cycle through the full color spectrum.
+//! Creates smooth rainbow animations that cycle through the color spectrum.
 //! Works with any LED ring size.
+//!
+//! By default the full hue wheel is spread once around the ring.
+//! The gradient can be restricted to part of the spectrum and/or repeated
+//! several times around the ring.
+//! Instead of the HSV wheel, the colors can also be sampled from a
+//! [`GradientPalette`].
 
 use crate::effect::{
-    validate_buffer, validate_num_leds, validate_speed, Direction, Effect, EffectError,
+    validate_buffer, validate_fine_speed, validate_num_leds, validate_speed, Direction, Effect,
+    EffectError,
 };
+use crate::frame_iter::PixelSource;
+use crate::gradient::GradientPalette;
 use crate::hsv::hsv_to_rgb;
+use crate::util::scale_brightness;
 use rgb::RGB8;
 
 /// A rainbow animation effect for LED rings.
@@ -29,11 +39,18 @@ use rgb::RGB8;
 #[derive(Debug, Clone)]
 pub struct RainbowEffect {
     num_leds: usize,
-    hue_offset: u8,
-    speed: u8,
+    /// Animation offset in 1/256 hue steps (Q8.8).
+    hue_offset: u16,
+    /// Hue increment per update in 1/256 hue steps (Q8.8).
+    speed: u16,
     brightness: u8,
     saturation: u8,
     direction: Direction,
+    hue_start: u8,
+    /// Number of hues covered by the gradient (1–256).
+    hue_span: u16,
+    repeat: u8,
+    palette: Option<GradientPalette>,
 }
 
 impl RainbowEffect {
@@ -46,6 +63,8 @@ impl RainbowEffect {
     /// # Errors
     ///
     /// Returns `EffectError::ZeroLeds` if `num_leds` is 0.
+    /// Returns `EffectError::TooManyLeds` if `num_leds` exceeds `MAX_LEDS`
+    /// (`MAX_LEDS_ALLOC` with the `alloc` feature).
     ///
     /// # Default Configuration
     ///
@@ -53,16 +72,23 @@ impl RainbowEffect {
     /// - Brightness: 255 (full)
     /// - Saturation: 255 (full)
     /// - Direction: Clockwise
+    /// - Hue range: 0–255 (full spectrum)
+    /// - Repeat: 1
+    /// - Colors: HSV wheel (no palette)
     pub fn new(num_leds: usize) -> Result<Self, EffectError> {
         validate_num_leds(num_leds)?;
 
         Ok(Self {
             num_leds,
             hue_offset: 0,
-            speed: 1,
+            speed: 256,
             brightness: 255,
             saturation: 255,
             direction: Direction::Clockwise,
+            hue_start: 0,
+            hue_span: 256,
+            repeat: 1,
+            palette: None,
         })
     }
 
@@ -74,8 +100,20 @@ impl RainbowEffect {
     ///
     /// Returns `EffectError::ZeroStep` if `speed` is 0.
     pub fn with_speed(mut self, speed: u8) -> Result<Self, EffectError> {
-        validate_speed(speed)?;
-        self.speed = speed;
+        self.set_speed(speed)?;
+        Ok(self)
+    }
+
+    /// Sets the animation speed in 1/256 hue steps per update.
+    ///
+    /// A value of 256 equals `with_speed(1)`; smaller values let the rainbow
+    /// rotate less than one hue step per update for slow ambient animations.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::ZeroStep` if `speed` is 0.
+    pub fn with_fine_speed(mut self, speed: u16) -> Result<Self, EffectError> {
+        self.set_fine_speed(speed)?;
         Ok(self)
     }
 
@@ -101,6 +139,109 @@ impl RainbowEffect {
         self
     }
 
+    /// Restricts the gradient to the hues from `start` to `end` (inclusive).
+    ///
+    /// If `end` is smaller than `start`, the range wraps through red
+    /// (e.g. `with_hue_range(213, 42)` covers magenta → red → yellow).
+    /// The animation rotates the colors within this range only.
+    pub fn with_hue_range(mut self, start: u8, end: u8) -> Self {
+        self.hue_start = start;
+        self.hue_span = end.wrapping_sub(start) as u16 + 1;
+        self
+    }
+
+    /// Repeats the gradient `repeat` times around the ring.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::ZeroRepeat` if `repeat` is 0.
+    pub fn with_repeat(mut self, repeat: u8) -> Result<Self, EffectError> {
+        if repeat == 0 {
+            return Err(EffectError::ZeroRepeat);
+        }
+        self.repeat = repeat;
+        Ok(self)
+    }
+
+    /// Samples colors from `palette` instead of the HSV wheel.
+    ///
+    /// Hue values become palette positions, so [`with_hue_range`](Self::with_hue_range)
+    /// and [`with_repeat`](Self::with_repeat) work the same way. Brightness still
+    /// applies; saturation is ignored.
+    pub fn with_palette(mut self, palette: GradientPalette) -> Self {
+        self.palette = Some(palette);
+        self
+    }
+
+    /// Changes the animation speed without restarting the animation.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::ZeroStep` if `speed` is 0.
+    pub fn set_speed(&mut self, speed: u8) -> Result<(), EffectError> {
+        validate_speed(speed)?;
+        self.speed = (speed as u16) << 8;
+        Ok(())
+    }
+
+    /// Changes the animation speed in 1/256 hue steps without restarting the animation.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::ZeroStep` if `speed` is 0.
+    pub fn set_fine_speed(&mut self, speed: u16) -> Result<(), EffectError> {
+        validate_fine_speed(speed)?;
+        self.speed = speed;
+        Ok(())
+    }
+
+    /// Changes the brightness level (0-255).
+    pub fn set_brightness(&mut self, brightness: u8) {
+        self.brightness = brightness;
+    }
+
+    /// Changes the saturation level (0-255).
+    pub fn set_saturation(&mut self, saturation: u8) {
+        self.saturation = saturation;
+    }
+
+    /// Changes the animation direction; the rainbow continues from its current colors.
+    pub fn set_direction(&mut self, direction: Direction) {
+        self.direction = direction;
+    }
+
+    /// Changes the palette; `None` switches back to the HSV wheel.
+    pub fn set_palette(&mut self, palette: Option<GradientPalette>) {
+        self.palette = palette;
+    }
+
+    /// Returns the animation speed in whole hue steps per update.
+    ///
+    /// Fractional speeds are truncated; see [`fine_speed`](Self::fine_speed).
+    pub fn speed(&self) -> u8 {
+        (self.speed >> 8) as u8
+    }
+
+    /// Returns the animation speed in 1/256 hue steps per update.
+    pub fn fine_speed(&self) -> u16 {
+        self.speed
+    }
+
+    /// Returns the brightness level.
+    pub fn brightness(&self) -> u8 {
+        self.brightness
+    }
+
+    /// Returns the saturation level.
+    pub fn saturation(&self) -> u8 {
+        self.saturation
+    }
+
+    /// Returns the animation direction.
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
     /// Returns the number of LEDs this effect is configured for.
     pub fn num_leds(&self) -> usize {
         self.num_leds
@@ -113,6 +254,16 @@ impl RainbowEffect {
         self.hue_offset = 0;
     }
 
+    /// Jumps to `phase` (0–255) of a full turn of the color wheel in the
+    /// configured direction.
+    pub fn set_phase(&mut self, phase: u8) {
+        let offset = (phase as u16) << 8;
+        self.hue_offset = match self.direction {
+            Direction::Clockwise => offset,
+            Direction::CounterClockwise => offset.wrapping_neg(),
+        };
+    }
+
     /// Fills the buffer with current rainbow colors without advancing the animation.
     ///
     /// Use this when you need to read the current state multiple times
@@ -125,18 +276,83 @@ impl RainbowEffect {
     pub fn current(&self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
         validate_buffer(buffer, self.num_leds)?;
 
-        for (i, pixel) in buffer.iter_mut().take(self.num_leds).enumerate() {
-            // Spread the full hue range (0-255) evenly across all LEDs.
-            // Multiply first to avoid integer division truncation issues.
-            let led_hue = ((i as u32 * 256) / self.num_leds as u32) as u8;
-            let hue = led_hue.wrapping_add(self.hue_offset);
-
-            *pixel = hsv_to_rgb(hue, self.saturation, self.brightness);
+        let n = self.num_leds as u32;
+        let span = self.hue_span as u32;
+        let offset = self.span_offset();
+
+        // Step the per-LED hue of `color_at` without dividing for every LED:
+        // LED i sits at floor(i * per_ring / n) = i * whole + carried remainders.
+        let per_ring = span * self.repeat as u32;
+        let (whole, remainder) = (per_ring / n, per_ring % n);
+        let mut led_hue = 0;
+        let mut carry = 0;
+
+        // Neighboring LEDs often share a hue on long strips, so reuse the last color
+        let mut last: Option<(u8, RGB8)> = None;
+
+        for pixel in buffer.iter_mut().take(self.num_leds) {
+            let hue = self
+                .hue_start
+                .wrapping_add(((led_hue + offset) % span) as u8);
+            let color = match last {
+                Some((last_hue, color)) if last_hue == hue => color,
+                _ => self.color_for_hue(hue),
+            };
+            last = Some((hue, color));
+            *pixel = color;
+
+            led_hue += whole;
+            carry += remainder;
+            if carry >= n {
+                carry -= n;
+                led_hue += 1;
+            }
         }
 
         Ok(())
     }
 
+    /// Returns the hue offset of the current frame within the hue span.
+    fn span_offset(&self) -> u32 {
+        // The animation offset covers the whole span once per 256 hue steps.
+        self.hue_offset as u32 * self.hue_span as u32 / 65536
+    }
+
+    /// Computes the color of LED `i` in the current frame.
+    fn color_at(&self, i: usize) -> RGB8 {
+        let span = self.hue_span as u32;
+        let repeat = self.repeat as u32;
+
+        // Spread the hue span (repeated) evenly across all LEDs.
+        // Multiply first to avoid integer division truncation issues.
+        let led_hue = (i as u32 * span * repeat) / self.num_leds as u32;
+        let hue = self
+            .hue_start
+            .wrapping_add(((led_hue + self.span_offset()) % span) as u8);
+        self.color_for_hue(hue)
+    }
+
+    /// Converts a hue to a color using the palette or the HSV wheel.
+    fn color_for_hue(&self, hue: u8) -> RGB8 {
+        match &self.palette {
+            Some(palette) => scale_brightness(palette.color_at(hue), self.brightness),
+            None => hsv_to_rgb(hue, self.saturation, self.brightness),
+        }
+    }
+
+    /// Rotates the hue offset by `steps` updates.
+    fn rotate(&mut self, steps: u32) {
+        let delta = (self.speed as u32).wrapping_mul(steps) as u16;
+        match self.direction {
+            Direction::Clockwise => {
+                self.hue_offset = self.hue_offset.wrapping_add(delta);
+            }
+            Direction::CounterClockwise => {
+                self.hue_offset = self.hue_offset.wrapping_sub(delta);
+            }
+        }
+    }
+
     /// Fills the buffer with rainbow colors and advances the animation.
     ///
     /// Call this method in your animation loop to update the LED colors.
@@ -148,17 +364,13 @@ impl RainbowEffect {
     /// elements than `num_leds`.
     pub fn update(&mut self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
         self.current(buffer)?;
+        self.rotate(1);
+        Ok(())
+    }
 
-        // Advance the animation
-        match self.direction {
-            Direction::Clockwise => {
-                self.hue_offset = self.hue_offset.wrapping_add(self.speed);
-            }
-            Direction::CounterClockwise => {
-                self.hue_offset = self.hue_offset.wrapping_sub(self.speed);
-            }
-        }
-
+    /// Advances the animation by `steps` updates without rendering.
+    pub fn advance(&mut self, steps: u32) -> Result<(), EffectError> {
+        self.rotate(steps);
         Ok(())
     }
 }
@@ -175,12 +387,30 @@ impl Effect for RainbowEffect {
     fn reset(&mut self) {
         self.reset();
     }
+
+    fn advance(&mut self, steps: u32) -> Result<(), EffectError> {
+        self.advance(steps)
+    }
+
+    fn set_phase(&mut self, phase: u8) {
+        self.set_phase(phase);
+    }
+}
+
+impl PixelSource for RainbowEffect {
+    fn num_leds(&self) -> usize {
+        self.num_leds
+    }
+
+    fn pixel(&self, index: usize) -> RGB8 {
+        self.color_at(index)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::effect::MAX_LEDS;
+    use crate::effect::{LED_LIMIT, MAX_LEDS};
 
     #[test]
     fn test_new_with_zero_leds_returns_error() {
@@ -202,12 +432,12 @@ mod tests {
 
     #[test]
     fn test_new_with_too_many_leds_returns_error() {
-        let result = RainbowEffect::new(MAX_LEDS + 1);
+        let result = RainbowEffect::new(LED_LIMIT + 1);
         assert_eq!(
             result.unwrap_err(),
             EffectError::TooManyLeds {
-                requested: MAX_LEDS + 1,
-                max: MAX_LEDS
+                requested: LED_LIMIT + 1,
+                max: LED_LIMIT
             }
         );
     }
@@ -342,6 +572,65 @@ mod tests {
         assert!(bright_max > dim_max);
     }
 
+    #[test]
+    fn test_with_repeat_zero_returns_error() {
+        let result = RainbowEffect::new(12).unwrap().with_repeat(0);
+        assert_eq!(result.unwrap_err(), EffectError::ZeroRepeat);
+    }
+
+    #[test]
+    fn test_default_matches_full_hue_range() {
+        let default = RainbowEffect::new(12).unwrap();
+        let full = RainbowEffect::new(12).unwrap().with_hue_range(0, 255);
+
+        let mut buf_default = [RGB8::default(); 12];
+        let mut buf_full = [RGB8::default(); 12];
+        default.current(&mut buf_default).unwrap();
+        full.current(&mut buf_full).unwrap();
+
+        assert_eq!(buf_default, buf_full);
+    }
+
+    #[test]
+    fn test_repeat_twice_repeats_pattern() {
+        let effect = RainbowEffect::new(12).unwrap().with_repeat(2).unwrap();
+        let mut buffer = [RGB8::default(); 12];
+        effect.current(&mut buffer).unwrap();
+
+        for i in 0..6 {
+            assert_eq!(buffer[i], buffer[i + 6], "LED {} should repeat", i);
+        }
+    }
+
+    #[test]
+    fn test_hue_range_stays_within_span() {
+        let mut effect = RainbowEffect::new(8)
+            .unwrap()
+            .with_hue_range(170, 200)
+            .with_speed(7)
+            .unwrap();
+
+        let allowed: Vec<RGB8> = (170..=200).map(|h| hsv_to_rgb(h, 255, 255)).collect();
+        let mut buffer = [RGB8::default(); 8];
+        for _ in 0..64 {
+            effect.update(&mut buffer).unwrap();
+            for led in &buffer {
+                assert!(allowed.contains(led), "{:?} is outside hue range", led);
+            }
+        }
+    }
+
+    #[test]
+    fn test_hue_range_wraps_through_red() {
+        let effect = RainbowEffect::new(4).unwrap().with_hue_range(250, 5);
+        let mut buffer = [RGB8::default(); 4];
+        effect.current(&mut buffer).unwrap();
+
+        assert_eq!(buffer[0], hsv_to_rgb(250, 255, 255));
+        // Span of 12 hues over 4 LEDs: 250, 253, 0, 3
+        assert_eq!(buffer[2], hsv_to_rgb(0, 255, 255));
+    }
+
     #[test]
     fn test_error_display() {
         assert_eq!(
@@ -400,6 +689,17 @@ mod tests {
         assert_ne!(buffer1[0], buffer2[0], "trait update should advance state");
     }
 
+    #[test]
+    fn test_trait_object_never_finishes() {
+        let mut effect = RainbowEffect::new(12).unwrap().with_speed(255).unwrap();
+        let mut buffer = [RGB8::default(); 12];
+        for _ in 0..10 {
+            effect.update(&mut buffer).unwrap();
+        }
+        let effect_ref: &dyn Effect = &effect;
+        assert!(!effect_ref.is_finished());
+    }
+
     #[test]
     fn test_trait_object_reset() {
         let mut effect = RainbowEffect::new(12).unwrap().with_speed(50).unwrap();
@@ -419,4 +719,128 @@ mod tests {
 
         assert_eq!(buffer_initial, buffer_after);
     }
+
+    #[test]
+    fn test_with_fine_speed_zero_returns_error() {
+        let result = RainbowEffect::new(12).unwrap().with_fine_speed(0);
+        assert_eq!(result.unwrap_err(), EffectError::ZeroStep);
+    }
+
+    #[test]
+    fn test_fine_speed_advances_one_hue_over_several_updates() {
+        let mut fine = RainbowEffect::new(12).unwrap().with_fine_speed(64).unwrap();
+        let mut whole = RainbowEffect::new(12).unwrap();
+        let mut buf_fine = [RGB8::default(); 12];
+        let mut buf_whole = [RGB8::default(); 12];
+
+        // A quarter hue step per update: nothing visible for the first 3 updates
+        for _ in 0..4 {
+            fine.update(&mut buf_fine).unwrap();
+        }
+        fine.current(&mut buf_fine).unwrap();
+        whole.update(&mut buf_whole).unwrap();
+        whole.current(&mut buf_whole).unwrap();
+        assert_eq!(buf_fine, buf_whole);
+    }
+
+    #[test]
+    fn test_setters_preserve_animation_state() {
+        let mut effect = RainbowEffect::new(12).unwrap().with_speed(10).unwrap();
+        let mut before = [RGB8::default(); 12];
+        let mut after = [RGB8::default(); 12];
+        for _ in 0..5 {
+            effect.update(&mut before).unwrap();
+        }
+        effect.current(&mut before).unwrap();
+
+        effect.set_speed(3).unwrap();
+        effect.set_direction(Direction::CounterClockwise);
+        effect.current(&mut after).unwrap();
+        assert_eq!(before, after, "changing speed must not restart the rainbow");
+        assert_eq!(effect.speed(), 3);
+        assert_eq!(effect.fine_speed(), 3 << 8);
+        assert_eq!(effect.direction(), Direction::CounterClockwise);
+
+        assert_eq!(effect.set_speed(0).unwrap_err(), EffectError::ZeroStep);
+        assert_eq!(effect.speed(), 3, "failed set must keep the old speed");
+    }
+
+    #[test]
+    fn test_palette_replaces_hsv_wheel() {
+        let palette = GradientPalette::linear(RGB8::new(255, 0, 0), RGB8::new(0, 0, 255));
+        let effect = RainbowEffect::new(4).unwrap().with_palette(palette);
+        let mut buffer = [RGB8::default(); 4];
+        effect.current(&mut buffer).unwrap();
+
+        for (i, led) in buffer.iter().enumerate() {
+            assert_eq!(*led, palette.color_at((i * 64) as u8));
+        }
+    }
+
+    #[test]
+    fn test_palette_rotates_and_respects_brightness() {
+        let mut effect = RainbowEffect::new(4)
+            .unwrap()
+            .with_palette(GradientPalette::HEAT)
+            .with_brightness(128)
+            .with_speed(64)
+            .unwrap();
+        let mut buffer = [RGB8::default(); 4];
+        effect.update(&mut buffer).unwrap();
+        effect.current(&mut buffer).unwrap();
+
+        // LED 0 now shows palette position 64
+        let expected = scale_brightness(GradientPalette::HEAT.color_at(64), 128);
+        assert_eq!(buffer[0], expected);
+
+        effect.set_palette(None);
+        effect.current(&mut buffer).unwrap();
+        assert_eq!(buffer[0], hsv_to_rgb(64, 255, 128));
+    }
+
+    #[test]
+    fn test_advance_matches_repeated_update() {
+        let mut updated = RainbowEffect::new(12)
+            .unwrap()
+            .with_fine_speed(700)
+            .unwrap();
+        let mut advanced = updated.clone();
+        let mut buf_updated = [RGB8::default(); 12];
+        let mut buf_advanced = [RGB8::default(); 12];
+
+        for _ in 0..300 {
+            updated.update(&mut buf_updated).unwrap();
+        }
+        advanced.advance(300).unwrap();
+
+        updated.current(&mut buf_updated).unwrap();
+        advanced.current(&mut buf_advanced).unwrap();
+        assert_eq!(buf_updated, buf_advanced);
+    }
+
+    #[test]
+    fn test_frame_matches_per_pixel_colors() {
+        for (n, start, end, repeat) in [
+            (12, 0, 255, 1),
+            (256, 0, 255, 1),
+            (7, 10, 109, 3),
+            (200, 230, 15, 2),
+        ] {
+            let mut effect = RainbowEffect::new(n)
+                .unwrap()
+                .with_hue_range(start, end)
+                .with_repeat(repeat)
+                .unwrap();
+            if n == 200 {
+                effect = effect.with_palette(GradientPalette::OCEAN);
+            }
+            effect.advance(37).unwrap();
+
+            let mut buffer = [RGB8::default(); MAX_LEDS];
+            effect.current(&mut buffer).unwrap();
+            for (i, (pixel, expected)) in buffer.iter().zip(effect.pixels()).enumerate() {
+                assert_eq!(*pixel, expected, "LED {} of {}", i, n);
+            }
+        }
+    }
 }
@@ -0,0 +1,185 @@
+//! RGBW colors for rings with a dedicated white LED.
+//!
+//! SK6812 RGBW rings have a fourth, white emitter per pixel. Effects keep
+//! rendering `RGB8` frames; [`rgb_to_rgbw`] moves the shared white part of each
+//! color onto the white channel before the frame is sent.
+//!
+//! [`RGBW8`] and [`rgb_to_rgbw`] are re-exported from `ws2812-pure`, so a
+//! converted frame can be handed to its packing functions as is.
+
+use crate::effect::EffectError;
+use crate::util::gamma8;
+use rgb::RGB8;
+
+pub use ws2812_pure::{rgb_to_rgbw, RGBW, RGBW8};
+
+/// Approximates an RGBW color on an RGB display by adding white to every channel.
+///
+/// Useful for previews and simulators; the result saturates at 255.
+pub fn rgbw_to_rgb(color: RGBW8) -> RGB8 {
+    RGB8::new(
+        color.r.saturating_add(color.w),
+        color.g.saturating_add(color.w),
+        color.b.saturating_add(color.w),
+    )
+}
+
+/// Converts an RGB frame into an RGBW frame with [`rgb_to_rgbw`].
+///
+/// # Errors
+///
+/// Returns `EffectError::BufferTooSmall` if `rgbw` is shorter than `rgb`.
+pub fn rgb_frame_to_rgbw(rgb: &[RGB8], rgbw: &mut [RGBW8]) -> Result<(), EffectError> {
+    if rgbw.len() < rgb.len() {
+        return Err(EffectError::BufferTooSmall {
+            required: rgb.len(),
+            actual: rgbw.len(),
+        });
+    }
+    for (out, &color) in rgbw.iter_mut().zip(rgb) {
+        *out = rgb_to_rgbw(color);
+    }
+    Ok(())
+}
+
+/// Scales all four channels of an RGBW color by a brightness factor (0–255).
+///
+/// The RGBW counterpart of [`scale_brightness`](crate::scale_brightness).
+pub fn scale_brightness_rgbw(color: RGBW8, brightness: u8) -> RGBW8 {
+    let b = brightness as u16;
+    let scale = |channel: u8| ((channel as u16 * b) / 255) as u8;
+    RGBW8::new(
+        scale(color.r),
+        scale(color.g),
+        scale(color.b),
+        scale(color.w),
+    )
+}
+
+/// Gamma-corrects every channel, including white, of each color in `buffer`.
+///
+/// The RGBW counterpart of [`gamma_correct`](crate::gamma_correct). Convert the
+/// frame to RGBW first, so the white part is extracted from linear values.
+pub fn gamma_correct_rgbw(buffer: &mut [RGBW8]) {
+    for pixel in buffer.iter_mut() {
+        *pixel = RGBW8::new(
+            gamma8(pixel.r),
+            gamma8(pixel.g),
+            gamma8(pixel.b),
+            gamma8(pixel.w),
+        );
+    }
+}
+
+/// Fills all elements of `buffer` with the given RGBW color.
+pub fn fill_solid_rgbw(buffer: &mut [RGBW8], color: RGBW8) {
+    buffer.fill(color);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rgb_to_rgbw_extracts_common_white() {
+        assert_eq!(
+            rgb_to_rgbw(RGB8::new(255, 255, 255)),
+            RGBW8::new(0, 0, 0, 255)
+        );
+        assert_eq!(rgb_to_rgbw(RGB8::new(40, 90, 40)), RGBW8::new(0, 50, 0, 40));
+    }
+
+    #[test]
+    fn test_saturated_colors_keep_white_off() {
+        for color in [
+            RGB8::new(255, 0, 0),
+            RGB8::new(0, 255, 128),
+            RGB8::new(10, 0, 200),
+        ] {
+            assert_eq!(rgb_to_rgbw(color).w, 0);
+            assert_eq!(rgb_to_rgbw(color).rgb(), color);
+        }
+    }
+
+    #[test]
+    fn test_round_trip_to_rgb() {
+        for color in [
+            RGB8::new(255, 200, 100),
+            RGB8::new(3, 3, 3),
+            RGB8::new(0, 0, 0),
+        ] {
+            assert_eq!(rgbw_to_rgb(rgb_to_rgbw(color)), color);
+        }
+    }
+
+    #[test]
+    fn test_rgbw_to_rgb_saturates() {
+        assert_eq!(
+            rgbw_to_rgb(RGBW8::new(200, 0, 0, 100)),
+            RGB8::new(255, 100, 100)
+        );
+    }
+
+    #[test]
+    fn test_from_rgb_leaves_white_off() {
+        assert_eq!(RGBW8::from(RGB8::new(1, 2, 3)), RGBW8::new(1, 2, 3, 0));
+    }
+
+    #[test]
+    fn test_frame_conversion() {
+        let rgb = [RGB8::new(255, 255, 255), RGB8::new(255, 0, 0)];
+        let mut rgbw = [RGBW8::default(); 3];
+        rgb_frame_to_rgbw(&rgb, &mut rgbw).unwrap();
+        assert_eq!(
+            rgbw,
+            [
+                RGBW8::new(0, 0, 0, 255),
+                RGBW8::new(255, 0, 0, 0),
+                RGBW8::default()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_frame_packs_with_ws2812_pure() {
+        let mut rgbw = [RGBW8::default(); 1];
+        rgb_frame_to_rgbw(&[RGB8::new(10, 20, 30)], &mut rgbw).unwrap();
+        assert_eq!(ws2812_pure::rgbw_to_grbw(rgbw[0]), 0x0A00140A);
+    }
+
+    #[test]
+    fn test_frame_conversion_too_small_returns_error() {
+        let rgb = [RGB8::default(); 4];
+        let mut rgbw = [RGBW8::default(); 2];
+        assert_eq!(
+            rgb_frame_to_rgbw(&rgb, &mut rgbw).unwrap_err(),
+            EffectError::BufferTooSmall {
+                required: 4,
+                actual: 2
+            }
+        );
+    }
+
+    #[test]
+    fn test_scale_brightness_includes_white() {
+        assert_eq!(
+            scale_brightness_rgbw(RGBW8::new(255, 0, 100, 255), 128),
+            RGBW8::new(128, 0, 50, 128)
+        );
+    }
+
+    #[test]
+    fn test_gamma_includes_white() {
+        let mut buffer = [RGBW8::new(0, 255, 128, 128)];
+        gamma_correct_rgbw(&mut buffer);
+        assert_eq!(buffer[0], RGBW8::new(0, 255, gamma8(128), gamma8(128)));
+        assert!(buffer[0].w < 128);
+    }
+
+    #[test]
+    fn test_fill_solid_rgbw() {
+        let mut buffer = [RGBW8::default(); 3];
+        fill_solid_rgbw(&mut buffer, RGBW8::new(0, 0, 0, 255));
+        assert_eq!(buffer, [RGBW8::new(0, 0, 0, 255); 3]);
+    }
+}
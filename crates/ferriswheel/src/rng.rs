@@ -0,0 +1,110 @@
+//! Random number source for randomized effects.
+//!
+//! Randomized effects take any [`Rng`], so tests can use a fixed seed and
+//! firmware can plug in a hardware random number generator.
+//! [`XorShift32`] is the built-in fallback.
+
+/// A source of pseudo-random or hardware random numbers.
+///
+/// Only [`next_u32`](Rng::next_u32) has to be implemented.
+///
+/// # Example
+///
+/// ```
+/// use ferriswheel::Rng;
+///
+/// /// Wraps a hardware RNG register.
+/// #[derive(Clone)]
+/// struct HardwareRng;
+///
+/// impl Rng for HardwareRng {
+///     fn next_u32(&mut self) -> u32 {
+///         // e.g. read the ESP32 RNG register here
+///         4 // chosen by fair dice roll
+///     }
+/// }
+/// ```
+pub trait Rng {
+    /// Returns the next random `u32`.
+    fn next_u32(&mut self) -> u32;
+
+    /// Returns the next random `u8`.
+    ///
+    /// Uses the upper bits of [`next_u32`](Rng::next_u32), which are the most
+    /// random ones for simple generators.
+    fn next_u8(&mut self) -> u8 {
+        (self.next_u32() >> 24) as u8
+    }
+}
+
+/// Xorshift32 pseudo-random number generator.
+///
+/// Fast, tiny, and deterministic for a given seed, which keeps randomized
+/// effects reproducible in tests. Not suitable for anything security related.
+#[derive(Debug, Clone)]
+pub struct XorShift32 {
+    state: u32,
+}
+
+impl XorShift32 {
+    /// Seed used when a seed of 0 is requested (xorshift gets stuck at 0).
+    const FALLBACK_SEED: u32 = 0x9E37_79B9;
+
+    /// Creates a generator from the given seed.
+    ///
+    /// A seed of 0 is replaced by a fixed non-zero seed.
+    pub fn new(seed: u32) -> Self {
+        let state = if seed == 0 { Self::FALLBACK_SEED } else { seed };
+        Self { state }
+    }
+}
+
+impl Rng for XorShift32 {
+    fn next_u32(&mut self) -> u32 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.state = x;
+        x
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_sequence() {
+        let mut a = XorShift32::new(42);
+        let mut b = XorShift32::new(42);
+        for _ in 0..100 {
+            assert_eq!(a.next_u32(), b.next_u32());
+        }
+    }
+
+    #[test]
+    fn test_zero_seed_does_not_get_stuck() {
+        let mut rng = XorShift32::new(0);
+        assert_ne!(rng.next_u32(), 0);
+        assert_ne!(rng.next_u32(), 0);
+    }
+
+    #[test]
+    fn test_next_u8_covers_range() {
+        let mut rng = XorShift32::new(1);
+        let mut seen = [false; 256];
+        for _ in 0..10_000 {
+            seen[rng.next_u8() as usize] = true;
+        }
+        assert!(seen.iter().all(|&s| s), "all byte values should appear");
+    }
+
+    #[test]
+    fn test_usable_as_trait_object() {
+        let mut rng = XorShift32::new(7);
+        let expected = XorShift32::new(7).next_u32();
+        let dyn_rng: &mut dyn Rng = &mut rng;
+        assert_eq!(dyn_rng.next_u32(), expected);
+    }
+}
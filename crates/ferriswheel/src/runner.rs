@@ -0,0 +1,466 @@
+//! Async render loop for embassy-style firmware.
+//!
+//! Most async projects wrap their effects in the same loop: render a frame,
+//! write it to the LEDs, wait for the next frame. [`EffectRunner`] provides that
+//! loop on top of the `embedded-hal-async` [`DelayNs`] trait, and an
+//! [`EffectSwitch`] lets other tasks choose which effect is shown.
+
+use crate::effect::{Effect, EffectError};
+use crate::writer::{AsyncFrameWriter, RunError};
+use core::convert::Infallible;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use embedded_hal_async::delay::DelayNs;
+use rgb::RGB8;
+
+/// Shared selection of the effect an [`EffectRunner`] renders.
+///
+/// The switch only uses atomic loads and stores, so it can live in a `static`
+/// and be written from other tasks or interrupt handlers, even on targets
+/// without compare-and-swap.
+///
+/// # Example
+///
+/// ```
+/// use ferriswheel::EffectSwitch;
+///
+/// static SWITCH: EffectSwitch = EffectSwitch::new();
+///
+/// // In a button handler
+/// SWITCH.select(1);
+/// assert_eq!(SWITCH.selected(), 1);
+/// ```
+#[derive(Debug, Default)]
+pub struct EffectSwitch {
+    selected: AtomicUsize,
+}
+
+impl EffectSwitch {
+    /// Creates a switch selecting the first effect.
+    pub const fn new() -> Self {
+        Self {
+            selected: AtomicUsize::new(0),
+        }
+    }
+
+    /// Selects the effect at `index`.
+    ///
+    /// The runner picks up the change before its next frame. Indices the runner
+    /// has no effect for are ignored.
+    pub fn select(&self, index: usize) {
+        self.selected.store(index, Ordering::Relaxed);
+    }
+
+    /// Returns the selected effect index.
+    pub fn selected(&self) -> usize {
+        self.selected.load(Ordering::Relaxed)
+    }
+}
+
+/// Renders effects into a buffer and writes them to a driver at a fixed interval.
+///
+/// The runner borrows up to `N` effects and shows one at a time. Switching, either
+/// with [`select`](EffectRunner::select) or through an [`EffectSwitch`],
+/// resets the newly selected effect so it starts from its first frame.
+///
+/// The delay runs after each write, so the frame period is the interval plus
+/// the time spent rendering and writing.
+///
+/// # Example
+///
+/// ```
+/// use ferriswheel::{AsyncFrameWriter, EffectRunner, PulseEffect, RainbowEffect};
+/// use embedded_hal_async::delay::DelayNs;
+/// use rgb::RGB8;
+///
+/// struct Strip;
+///
+/// impl AsyncFrameWriter for Strip {
+///     type Error = ();
+///
+///     async fn write_frame(&mut self, _frame: &[RGB8]) -> Result<(), ()> {
+///         Ok(())
+///     }
+/// }
+///
+/// async fn led_task(delay: impl DelayNs, mut strip: Strip) {
+///     let mut rainbow = RainbowEffect::new(12).unwrap();
+///     let mut pulse = PulseEffect::new(12).unwrap();
+///     let mut buffer = [RGB8::default(); 12];
+///
+///     // 50 frames per second
+///     let mut runner: EffectRunner<_, 2> = EffectRunner::new(&mut buffer, 20, delay).unwrap();
+///     runner.push(&mut rainbow).unwrap();
+///     runner.push(&mut pulse).unwrap();
+///
+///     let _error = runner.run(&mut strip).await;
+/// }
+/// ```
+pub struct EffectRunner<'a, D, const N: usize> {
+    effects: [Option<&'a mut dyn Effect>; N],
+    len: usize,
+    active: usize,
+    switch: Option<&'a EffectSwitch>,
+    switch_seen: usize,
+    buffer: &'a mut [RGB8],
+    frame_ms: u32,
+    delay: D,
+}
+
+impl<'a, D: DelayNs, const N: usize> EffectRunner<'a, D, N> {
+    /// Creates a runner that renders into `buffer` and waits `frame_ms` between frames.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::ZeroLeds` if `buffer` is empty.
+    /// Returns `EffectError::ZeroStep` if `frame_ms` is 0.
+    pub fn new(buffer: &'a mut [RGB8], frame_ms: u32, delay: D) -> Result<Self, EffectError> {
+        if buffer.is_empty() {
+            return Err(EffectError::ZeroLeds);
+        }
+        if frame_ms == 0 {
+            return Err(EffectError::ZeroStep);
+        }
+
+        Ok(Self {
+            effects: core::array::from_fn(|_| None),
+            len: 0,
+            active: 0,
+            switch: None,
+            switch_seen: usize::MAX,
+            buffer,
+            frame_ms,
+            delay,
+        })
+    }
+
+    /// Follows `switch` to decide which effect is shown.
+    ///
+    /// The runner applies the switch's selection before the first frame and
+    /// again whenever it changes, so [`select`](EffectRunner::select) still works
+    /// in between.
+    pub fn with_switch(mut self, switch: &'a EffectSwitch) -> Self {
+        self.switch = Some(switch);
+        self
+    }
+
+    /// Adds an effect and returns its index.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::TooManyEffects` if the runner already holds `N` effects.
+    pub fn push(&mut self, effect: &'a mut dyn Effect) -> Result<usize, EffectError> {
+        if self.len == N {
+            return Err(EffectError::TooManyEffects { max: N });
+        }
+        self.effects[self.len] = Some(effect);
+        self.len += 1;
+        Ok(self.len - 1)
+    }
+
+    /// Returns the number of effects.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no effects were added.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the index of the effect currently shown.
+    pub fn active(&self) -> usize {
+        self.active
+    }
+
+    /// Switches to the effect at `index` and resets it.
+    ///
+    /// Returns `false` and keeps the current effect if there is no effect at `index`.
+    pub fn select(&mut self, index: usize) -> bool {
+        match self.effects[..self.len].get_mut(index) {
+            Some(Some(effect)) => {
+                effect.reset();
+                self.active = index;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Changes the delay between frames.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::ZeroStep` if `frame_ms` is 0.
+    pub fn set_frame_ms(&mut self, frame_ms: u32) -> Result<(), EffectError> {
+        if frame_ms == 0 {
+            return Err(EffectError::ZeroStep);
+        }
+        self.frame_ms = frame_ms;
+        Ok(())
+    }
+
+    /// Returns the delay between frames in milliseconds.
+    pub fn frame_ms(&self) -> u32 {
+        self.frame_ms
+    }
+
+    /// Renders one frame, writes it to `driver` and waits for the frame interval.
+    ///
+    /// Without effects the buffer is written unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RunError::Effect` if the effect fails to render, or
+    /// `RunError::Write` if the driver fails to write.
+    pub async fn run_frame<W: AsyncFrameWriter>(
+        &mut self,
+        driver: &mut W,
+    ) -> Result<(), RunError<W::Error>> {
+        if let Some(switch) = self.switch {
+            let selected = switch.selected();
+            if selected != self.switch_seen {
+                self.switch_seen = selected;
+                self.select(selected);
+            }
+        }
+
+        if let Some(Some(effect)) = self.effects[..self.len].get_mut(self.active) {
+            effect.update(self.buffer)?;
+        }
+        driver
+            .write_frame(self.buffer)
+            .await
+            .map_err(RunError::Write)?;
+        self.delay.delay_ms(self.frame_ms).await;
+        Ok(())
+    }
+
+    /// Runs the render loop until an error occurs.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error reported by [`run_frame`](EffectRunner::run_frame).
+    pub async fn run<W: AsyncFrameWriter>(
+        &mut self,
+        driver: &mut W,
+    ) -> Result<Infallible, RunError<W::Error>> {
+        loop {
+            self.run_frame(driver).await?;
+        }
+    }
+}
+
+impl<D, const N: usize> core::fmt::Debug for EffectRunner<'_, D, N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("EffectRunner")
+            .field("len", &self.len)
+            .field("active", &self.active)
+            .field("num_leds", &self.buffer.len())
+            .field("frame_ms", &self.frame_ms)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FlashEffect, SpinnerEffect};
+    use core::future::Future;
+    use core::pin::pin;
+    use core::task::{Context, Poll, Waker};
+
+    const RED: RGB8 = RGB8::new(255, 0, 0);
+    const GREEN: RGB8 = RGB8::new(0, 255, 0);
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        let mut cx = Context::from_waker(Waker::noop());
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+        }
+    }
+
+    #[derive(Default)]
+    struct MockDelay {
+        total_ns: u64,
+    }
+
+    impl DelayNs for MockDelay {
+        async fn delay_ns(&mut self, ns: u32) {
+            self.total_ns += ns as u64;
+        }
+    }
+
+    /// Records written frames and fails once `limit` frames were written.
+    struct MockStrip {
+        frames: Vec<Vec<RGB8>>,
+        limit: usize,
+    }
+
+    impl MockStrip {
+        fn new(limit: usize) -> Self {
+            Self {
+                frames: Vec::new(),
+                limit,
+            }
+        }
+    }
+
+    impl AsyncFrameWriter for MockStrip {
+        type Error = &'static str;
+
+        async fn write_frame(&mut self, frame: &[RGB8]) -> Result<(), Self::Error> {
+            if self.frames.len() == self.limit {
+                return Err("full");
+            }
+            self.frames.push(frame.to_vec());
+            Ok(())
+        }
+    }
+
+    fn solid(n: usize, color: RGB8) -> FlashEffect {
+        FlashEffect::new(n)
+            .unwrap()
+            .with_color(color)
+            .with_duty(255, 1)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_new_with_empty_buffer_returns_error() {
+        let mut buffer: [RGB8; 0] = [];
+        let result: Result<EffectRunner<_, 1>, _> =
+            EffectRunner::new(&mut buffer, 20, MockDelay::default());
+        assert_eq!(result.unwrap_err(), EffectError::ZeroLeds);
+    }
+
+    #[test]
+    fn test_new_with_zero_interval_returns_error() {
+        let mut buffer = [RGB8::default(); 4];
+        let result: Result<EffectRunner<_, 1>, _> =
+            EffectRunner::new(&mut buffer, 0, MockDelay::default());
+        assert_eq!(result.unwrap_err(), EffectError::ZeroStep);
+    }
+
+    #[test]
+    fn test_too_many_effects_returns_error() {
+        let mut a = solid(4, RED);
+        let mut b = solid(4, GREEN);
+        let mut buffer = [RGB8::default(); 4];
+        let mut runner: EffectRunner<_, 1> =
+            EffectRunner::new(&mut buffer, 20, MockDelay::default()).unwrap();
+        assert_eq!(runner.push(&mut a), Ok(0));
+        assert_eq!(
+            runner.push(&mut b).unwrap_err(),
+            EffectError::TooManyEffects { max: 1 }
+        );
+    }
+
+    #[test]
+    fn test_run_frame_renders_writes_and_waits() {
+        let mut effect = solid(4, RED);
+        let mut buffer = [RGB8::default(); 4];
+        let mut runner: EffectRunner<_, 1> =
+            EffectRunner::new(&mut buffer, 20, MockDelay::default()).unwrap();
+        runner.push(&mut effect).unwrap();
+
+        let mut strip = MockStrip::new(usize::MAX);
+        block_on(runner.run_frame(&mut strip)).unwrap();
+        block_on(runner.run_frame(&mut strip)).unwrap();
+
+        assert_eq!(strip.frames.len(), 2);
+        assert_eq!(strip.frames[0], [RED; 4]);
+        assert_eq!(runner.delay.total_ns, 40_000_000);
+    }
+
+    #[test]
+    fn test_run_advances_effect_every_frame() {
+        let mut spinner = SpinnerEffect::new(4).unwrap().with_tail_length(0);
+        let mut buffer = [RGB8::default(); 4];
+        let mut runner: EffectRunner<_, 1> =
+            EffectRunner::new(&mut buffer, 10, MockDelay::default()).unwrap();
+        runner.push(&mut spinner).unwrap();
+
+        let mut strip = MockStrip::new(3);
+        let result = block_on(runner.run(&mut strip));
+
+        assert_eq!(result.unwrap_err(), RunError::Write("full"));
+        let lit: Vec<usize> = strip
+            .frames
+            .iter()
+            .map(|frame| {
+                frame
+                    .iter()
+                    .position(|&led| led != RGB8::default())
+                    .unwrap()
+            })
+            .collect();
+        assert_eq!(lit, [0, 1, 2]);
+    }
+
+    #[test]
+    fn test_effect_error_stops_run() {
+        let mut effect = solid(8, RED);
+        let mut buffer = [RGB8::default(); 4];
+        let mut runner: EffectRunner<_, 1> =
+            EffectRunner::new(&mut buffer, 10, MockDelay::default()).unwrap();
+        runner.push(&mut effect).unwrap();
+
+        let mut strip = MockStrip::new(usize::MAX);
+        assert_eq!(
+            block_on(runner.run(&mut strip)).unwrap_err(),
+            RunError::Effect(EffectError::BufferTooSmall {
+                required: 8,
+                actual: 4
+            })
+        );
+        assert!(strip.frames.is_empty());
+    }
+
+    #[test]
+    fn test_select_switches_and_resets_effect() {
+        let mut red = solid(4, RED);
+        let mut spinner = SpinnerEffect::new(4).unwrap().with_tail_length(0);
+        spinner.advance(2).unwrap();
+        let mut buffer = [RGB8::default(); 4];
+        let mut runner: EffectRunner<_, 2> =
+            EffectRunner::new(&mut buffer, 10, MockDelay::default()).unwrap();
+        runner.push(&mut red).unwrap();
+        runner.push(&mut spinner).unwrap();
+
+        assert!(!runner.select(2));
+        assert_eq!(runner.active(), 0);
+        assert!(runner.select(1));
+
+        let mut strip = MockStrip::new(usize::MAX);
+        block_on(runner.run_frame(&mut strip)).unwrap();
+        assert_ne!(strip.frames[0][0], RGB8::default());
+    }
+
+    #[test]
+    fn test_switch_changes_effect_before_next_frame() {
+        let switch = EffectSwitch::new();
+        let mut red = solid(2, RED);
+        let mut green = solid(2, GREEN);
+        let mut buffer = [RGB8::default(); 2];
+        let mut runner: EffectRunner<_, 2> =
+            EffectRunner::new(&mut buffer, 10, MockDelay::default())
+                .unwrap()
+                .with_switch(&switch);
+        runner.push(&mut red).unwrap();
+        runner.push(&mut green).unwrap();
+
+        let mut strip = MockStrip::new(usize::MAX);
+        block_on(runner.run_frame(&mut strip)).unwrap();
+        switch.select(1);
+        block_on(runner.run_frame(&mut strip)).unwrap();
+        // Out-of-range selections keep the current effect
+        switch.select(5);
+        block_on(runner.run_frame(&mut strip)).unwrap();
+
+        assert_eq!(strip.frames, [[RED; 2], [GREEN; 2], [GREEN; 2]]);
+        assert_eq!(runner.active(), 1);
+    }
+}
@@ -0,0 +1,476 @@
+//! Effects selected by application state.
+//!
+//! Status rings show one effect per application state: connecting, connected,
+//! error, and so on. [`SceneManager`] maps those states to pre-configured
+//! effects, so firmware only reports the state and keeps rendering.
+
+use crate::effect::{validate_buffer, validate_speed, Direction, Effect, EffectError};
+use crate::transition::{blend_transition, TransitionMode};
+use rgb::RGB8;
+
+struct Scene<'a, K> {
+    key: K,
+    effect: &'a mut dyn Effect,
+}
+
+/// Switches between up to `N` effects identified by keys of type `K`.
+///
+/// Keys are usually a fieldless application enum or a `u8`. Each scene borrows
+/// its effect; selecting a scene resets its effect so it starts from the first
+/// frame. Selecting the scene that is already active does nothing, so status
+/// updates can be forwarded without filtering duplicates.
+///
+/// Scene changes are instant unless a transition is configured with
+/// [`with_transition`](SceneManager::with_transition). During a transition both
+/// effects keep animating and are blended like a
+/// [`WipeTransitionEffect`](crate::WipeTransitionEffect).
+///
+/// # Example
+///
+/// ```
+/// use ferriswheel::{PulseEffect, SceneManager, SpinnerEffect, TransitionMode};
+/// use rgb::RGB8;
+///
+/// #[derive(Clone, Copy, PartialEq)]
+/// enum Status {
+///     Connecting,
+///     Connected,
+/// }
+///
+/// let mut spinner = SpinnerEffect::new(12).unwrap();
+/// let mut pulse = PulseEffect::new(12).unwrap();
+/// let mut scratch = [RGB8::default(); 12];
+///
+/// let mut scenes: SceneManager<Status, 2> = SceneManager::new()
+///     .with_transition(TransitionMode::Crossfade, 16, &mut scratch)
+///     .unwrap();
+/// scenes.add(Status::Connecting, &mut spinner).unwrap();
+/// scenes.add(Status::Connected, &mut pulse).unwrap();
+///
+/// let mut buffer = [RGB8::default(); 12];
+/// scenes.set_scene(Status::Connecting).unwrap();
+/// scenes.update(&mut buffer).unwrap();
+///
+/// // Crossfades to the pulse over the next 16 updates
+/// scenes.set_scene(Status::Connected).unwrap();
+/// scenes.update(&mut buffer).unwrap();
+/// ```
+pub struct SceneManager<'a, K, const N: usize> {
+    scenes: [Option<Scene<'a, K>>; N],
+    len: usize,
+    active: Option<usize>,
+    previous: Option<usize>,
+    scratch: Option<&'a mut [RGB8]>,
+    mode: TransitionMode,
+    direction: Direction,
+    speed: u8,
+    progress: u8,
+}
+
+impl<'a, K: Copy + PartialEq, const N: usize> SceneManager<'a, K, N> {
+    /// Creates a manager without scenes and with instant scene changes.
+    pub fn new() -> Self {
+        Self {
+            scenes: core::array::from_fn(|_| None),
+            len: 0,
+            active: None,
+            previous: None,
+            scratch: None,
+            mode: TransitionMode::Wipe,
+            direction: Direction::Clockwise,
+            speed: 8,
+            progress: 0,
+        }
+    }
+
+    /// Enables transitions between scenes.
+    ///
+    /// `speed` is the progress increment out of 255 per update, as for
+    /// [`WipeTransitionEffect`](crate::WipeTransitionEffect). The outgoing scene
+    /// renders into `scratch`, which must be at least as long as the buffers
+    /// passed to [`update`](SceneManager::update).
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::ZeroStep` if `speed` is 0.
+    pub fn with_transition(
+        mut self,
+        mode: TransitionMode,
+        speed: u8,
+        scratch: &'a mut [RGB8],
+    ) -> Result<Self, EffectError> {
+        validate_speed(speed)?;
+        self.mode = mode;
+        self.speed = speed;
+        self.scratch = Some(scratch);
+        Ok(self)
+    }
+
+    /// Sets the direction wipe transitions travel around the ring.
+    pub fn with_direction(mut self, direction: Direction) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Adds a scene shown for `key`.
+    ///
+    /// If `key` already has a scene, its effect is replaced.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::TooManyScenes` if the manager already holds `N` scenes.
+    pub fn add(&mut self, key: K, effect: &'a mut dyn Effect) -> Result<(), EffectError> {
+        if let Some(index) = self.index_of(key) {
+            if let Some(scene) = self.scenes[index].as_mut() {
+                scene.effect = effect;
+            }
+            return Ok(());
+        }
+        if self.len == N {
+            return Err(EffectError::TooManyScenes { max: N });
+        }
+        self.scenes[self.len] = Some(Scene { key, effect });
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Returns the number of scenes.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no scenes were added.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn index_of(&self, key: K) -> Option<usize> {
+        self.scenes[..self.len]
+            .iter()
+            .position(|scene| scene.as_ref().is_some_and(|scene| scene.key == key))
+    }
+
+    /// Shows the scene for `key`.
+    ///
+    /// Starts a transition from the current scene if one is configured. If a
+    /// transition is already running, the scene it was moving to becomes the
+    /// outgoing scene.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::UnknownScene` if no scene was added for `key`.
+    pub fn set_scene(&mut self, key: K) -> Result<(), EffectError> {
+        let index = self.index_of(key).ok_or(EffectError::UnknownScene)?;
+        if self.active == Some(index) {
+            return Ok(());
+        }
+
+        if let Some(scene) = self.scenes[index].as_mut() {
+            scene.effect.reset();
+        }
+        self.previous = match self.scratch {
+            Some(_) => self.active,
+            None => None,
+        };
+        self.progress = 0;
+        self.active = Some(index);
+        Ok(())
+    }
+
+    /// Returns the key of the active scene, or `None` before the first
+    /// [`set_scene`](SceneManager::set_scene).
+    pub fn active_scene(&self) -> Option<K> {
+        self.active
+            .and_then(|index| self.scenes[index].as_ref())
+            .map(|scene| scene.key)
+    }
+
+    /// Returns `true` while a transition between two scenes is running.
+    pub fn is_transitioning(&self) -> bool {
+        self.previous.is_some()
+    }
+
+    /// Renders the active scene and advances it.
+    ///
+    /// Before a scene is selected the buffer is left untouched.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::BufferTooSmall` if a transition is running and the
+    /// scratch buffer is shorter than `buffer`, or the first error reported by
+    /// a scene's effect.
+    pub fn update(&mut self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        let Some(active) = self.active else {
+            return Ok(());
+        };
+
+        if let (Some(previous), Some(scratch)) = (self.previous, self.scratch.as_deref_mut()) {
+            validate_buffer(scratch, buffer.len())?;
+            let scratch = &mut scratch[..buffer.len()];
+            if let Some(scene) = self.scenes[previous].as_mut() {
+                scene.effect.update(scratch)?;
+            }
+            if let Some(scene) = self.scenes[active].as_mut() {
+                scene.effect.update(buffer)?;
+            }
+            blend_transition(scratch, buffer, self.mode, self.direction, self.progress);
+
+            self.progress = self.progress.saturating_add(self.speed);
+            if self.progress == u8::MAX {
+                self.previous = None;
+            }
+            return Ok(());
+        }
+
+        match self.scenes[active].as_mut() {
+            Some(scene) => scene.effect.update(buffer),
+            None => Ok(()),
+        }
+    }
+
+    /// Restarts the active scene's effect and ends a running transition.
+    pub fn reset(&mut self) {
+        self.previous = None;
+        if let Some(scene) = self.active.and_then(|index| self.scenes[index].as_mut()) {
+            scene.effect.reset();
+        }
+    }
+}
+
+impl<K: Copy + PartialEq, const N: usize> Default for SceneManager<'_, K, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Copy + PartialEq + core::fmt::Debug, const N: usize> core::fmt::Debug
+    for SceneManager<'_, K, N>
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SceneManager")
+            .field("len", &self.len)
+            .field("active_scene", &self.active_scene())
+            .field("transitioning", &self.is_transitioning())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FlashEffect, SpinnerEffect};
+
+    const RED: RGB8 = RGB8::new(255, 0, 0);
+    const BLUE: RGB8 = RGB8::new(0, 0, 255);
+    const MARK: RGB8 = RGB8::new(1, 2, 3);
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Status {
+        Idle,
+        Busy,
+        Error,
+    }
+
+    fn solid(n: usize, color: RGB8) -> FlashEffect {
+        FlashEffect::new(n)
+            .unwrap()
+            .with_color(color)
+            .with_duty(255, 1)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_too_many_scenes_returns_error() {
+        let mut a = solid(4, RED);
+        let mut b = solid(4, BLUE);
+        let mut scenes: SceneManager<u8, 1> = SceneManager::new();
+        scenes.add(0, &mut a).unwrap();
+        assert_eq!(
+            scenes.add(1, &mut b).unwrap_err(),
+            EffectError::TooManyScenes { max: 1 }
+        );
+    }
+
+    #[test]
+    fn test_unknown_scene_returns_error() {
+        let mut a = solid(4, RED);
+        let mut scenes: SceneManager<Status, 2> = SceneManager::new();
+        scenes.add(Status::Idle, &mut a).unwrap();
+        assert_eq!(
+            scenes.set_scene(Status::Error).unwrap_err(),
+            EffectError::UnknownScene
+        );
+        assert_eq!(scenes.active_scene(), None);
+    }
+
+    #[test]
+    fn test_no_active_scene_leaves_buffer_untouched() {
+        let mut a = solid(2, RED);
+        let mut scenes: SceneManager<u8, 1> = SceneManager::new();
+        scenes.add(0, &mut a).unwrap();
+
+        let mut buffer = [MARK; 2];
+        scenes.update(&mut buffer).unwrap();
+        assert_eq!(buffer, [MARK; 2]);
+    }
+
+    #[test]
+    fn test_instant_switch() {
+        let mut idle = solid(2, RED);
+        let mut busy = solid(2, BLUE);
+        let mut scenes: SceneManager<Status, 2> = SceneManager::new();
+        scenes.add(Status::Idle, &mut idle).unwrap();
+        scenes.add(Status::Busy, &mut busy).unwrap();
+
+        let mut buffer = [RGB8::default(); 2];
+        scenes.set_scene(Status::Idle).unwrap();
+        scenes.update(&mut buffer).unwrap();
+        assert_eq!(buffer, [RED; 2]);
+
+        scenes.set_scene(Status::Busy).unwrap();
+        assert!(!scenes.is_transitioning());
+        scenes.update(&mut buffer).unwrap();
+        assert_eq!(buffer, [BLUE; 2]);
+        assert_eq!(scenes.active_scene(), Some(Status::Busy));
+    }
+
+    #[test]
+    fn test_add_existing_key_replaces_effect() {
+        let mut first = solid(2, RED);
+        let mut second = solid(2, BLUE);
+        let mut scenes: SceneManager<u8, 1> = SceneManager::new();
+        scenes.add(7, &mut first).unwrap();
+        scenes.add(7, &mut second).unwrap();
+        assert_eq!(scenes.len(), 1);
+
+        let mut buffer = [RGB8::default(); 2];
+        scenes.set_scene(7).unwrap();
+        scenes.update(&mut buffer).unwrap();
+        assert_eq!(buffer, [BLUE; 2]);
+    }
+
+    #[test]
+    fn test_set_scene_resets_effect_unless_already_active() {
+        let mut spinner = SpinnerEffect::new(4).unwrap().with_tail_length(0);
+        let mut scenes: SceneManager<u8, 1> = SceneManager::new();
+        scenes.add(0, &mut spinner).unwrap();
+
+        let mut buffer = [RGB8::default(); 4];
+        scenes.set_scene(0).unwrap();
+        scenes.update(&mut buffer).unwrap();
+        scenes.update(&mut buffer).unwrap();
+        // Repeating the active scene keeps the animation going
+        scenes.set_scene(0).unwrap();
+        scenes.update(&mut buffer).unwrap();
+        assert_ne!(buffer[2], RGB8::default());
+    }
+
+    #[test]
+    fn test_crossfade_transition() {
+        let mut idle = solid(2, RED);
+        let mut busy = solid(2, BLUE);
+        let mut scratch = [RGB8::default(); 2];
+        let mut scenes: SceneManager<Status, 2> = SceneManager::new()
+            .with_transition(TransitionMode::Crossfade, 128, &mut scratch)
+            .unwrap();
+        scenes.add(Status::Idle, &mut idle).unwrap();
+        scenes.add(Status::Busy, &mut busy).unwrap();
+
+        let mut buffer = [RGB8::default(); 2];
+        scenes.set_scene(Status::Idle).unwrap();
+        // The first scene has nothing to transition from
+        assert!(!scenes.is_transitioning());
+        scenes.update(&mut buffer).unwrap();
+
+        scenes.set_scene(Status::Busy).unwrap();
+        assert!(scenes.is_transitioning());
+        let mut frames = Vec::new();
+        while scenes.is_transitioning() {
+            scenes.update(&mut buffer).unwrap();
+            frames.push(buffer[0]);
+        }
+        assert_eq!(frames[0], RED);
+        assert!(frames[1] != RED && frames[1] != BLUE);
+        assert_eq!(frames.len(), 2);
+
+        scenes.update(&mut buffer).unwrap();
+        assert_eq!(buffer, [BLUE; 2]);
+    }
+
+    #[test]
+    fn test_wipe_transition_reveals_led_by_led() {
+        let mut idle = solid(4, RED);
+        let mut busy = solid(4, BLUE);
+        let mut scratch = [RGB8::default(); 4];
+        let mut scenes: SceneManager<Status, 2> = SceneManager::new()
+            .with_transition(TransitionMode::Wipe, 128, &mut scratch)
+            .unwrap();
+        scenes.add(Status::Idle, &mut idle).unwrap();
+        scenes.add(Status::Busy, &mut busy).unwrap();
+
+        let mut buffer = [RGB8::default(); 4];
+        scenes.set_scene(Status::Idle).unwrap();
+        scenes.update(&mut buffer).unwrap();
+        scenes.set_scene(Status::Busy).unwrap();
+        scenes.update(&mut buffer).unwrap();
+        scenes.update(&mut buffer).unwrap();
+        // Half way: two LEDs revealed
+        assert_eq!(buffer[0], BLUE);
+        assert_eq!(buffer[1], BLUE);
+        assert_eq!(buffer[3], RED);
+    }
+
+    #[test]
+    fn test_scratch_too_small_returns_error() {
+        let mut idle = solid(4, RED);
+        let mut busy = solid(4, BLUE);
+        let mut scratch = [RGB8::default(); 2];
+        let mut scenes: SceneManager<u8, 2> = SceneManager::new()
+            .with_transition(TransitionMode::Wipe, 8, &mut scratch)
+            .unwrap();
+        scenes.add(0, &mut idle).unwrap();
+        scenes.add(1, &mut busy).unwrap();
+
+        let mut buffer = [RGB8::default(); 4];
+        scenes.set_scene(0).unwrap();
+        scenes.update(&mut buffer).unwrap();
+        scenes.set_scene(1).unwrap();
+        assert_eq!(
+            scenes.update(&mut buffer).unwrap_err(),
+            EffectError::BufferTooSmall {
+                required: 4,
+                actual: 2
+            }
+        );
+    }
+
+    #[test]
+    fn test_zero_transition_speed_returns_error() {
+        let mut scratch = [RGB8::default(); 2];
+        let result: Result<SceneManager<u8, 1>, _> =
+            SceneManager::new().with_transition(TransitionMode::Wipe, 0, &mut scratch);
+        assert_eq!(result.unwrap_err(), EffectError::ZeroStep);
+    }
+
+    #[test]
+    fn test_reset_ends_transition() {
+        let mut idle = solid(2, RED);
+        let mut busy = solid(2, BLUE);
+        let mut scratch = [RGB8::default(); 2];
+        let mut scenes: SceneManager<u8, 2> = SceneManager::new()
+            .with_transition(TransitionMode::Crossfade, 8, &mut scratch)
+            .unwrap();
+        scenes.add(0, &mut idle).unwrap();
+        scenes.add(1, &mut busy).unwrap();
+
+        scenes.set_scene(0).unwrap();
+        scenes.set_scene(1).unwrap();
+        assert!(scenes.is_transitioning());
+        scenes.reset();
+        assert!(!scenes.is_transitioning());
+
+        let mut buffer = [RGB8::default(); 2];
+        scenes.update(&mut buffer).unwrap();
+        assert_eq!(buffer, [BLUE; 2]);
+    }
+}
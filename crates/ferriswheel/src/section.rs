@@ -3,23 +3,54 @@
 //! Splits an LED ring into colored sections, each sized proportionally
 //! to its weight.
 //! Sections are driven externally via [`set_sections`](SectionEffect::set_sections).
-
-use crate::effect::{validate_buffer, validate_num_leds, Effect, EffectError};
+//! Each section is drawn according to the effect's [`SectionRenderMode`].
+//! The whole layout can start at any LED and optionally rotate around the ring.
+//! Optional gap LEDs visually separate adjacent sections.
+//! Per-section brightness and blink modifiers highlight single sections.
+
+use crate::effect::{
+    advance_position, validate_buffer, validate_duty, validate_num_leds_up_to, Direction, Effect,
+    EffectError, MAX_LEDS,
+};
 use crate::palette::ColorPalette;
-use crate::util::fill_solid;
+use crate::util::{fill_solid, lerp_color, scale_brightness};
 use rgb::RGB8;
 
-/// Maximum number of sections supported by [`SectionEffect`].
+/// Default section capacity of [`SectionEffect`].
 pub const MAX_SECTIONS: usize = 8;
 
+/// How [`SectionEffect`] draws the LEDs within each section.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SectionRenderMode {
+    /// Every LED in the section uses the palette's primary color.
+    #[default]
+    Solid,
+    /// The section fades from primary (first LED) to secondary (last LED).
+    Gradient,
+    /// LEDs alternate between primary and secondary, starting with primary.
+    Alternating,
+}
+
 /// An effect that divides the ring into weighted color sections.
 ///
 /// Each section is a [`ColorPalette`] paired with a weight.
 /// LEDs are distributed proportionally across sections based on their weights.
-/// The primary color of each palette is used for rendering.
+/// By default the primary color of each palette is used for rendering;
+/// see [`with_render_mode`](SectionEffect::with_render_mode) for other modes.
 ///
 /// Like [`ProgressEffect`](crate::ProgressEffect), this effect is externally
-/// driven — `update()` renders the current state without advancing animation.
+/// driven — `update()` renders the current state without advancing animation,
+/// unless a rotation speed is configured or a section blinks.
+///
+/// Individual sections can be dimmed with
+/// [`set_section_brightness`](SectionEffect::set_section_brightness) or made to
+/// blink with [`set_section_blink`](SectionEffect::set_section_blink) while the
+/// rest of the layout stays static.
+///
+/// Up to `S` sections can be active at once. The default capacity is
+/// [`MAX_SECTIONS`]; use [`new_fixed`](SectionEffect::new_fixed)
+/// for more.
 ///
 /// # Example
 ///
@@ -36,32 +67,132 @@ pub const MAX_SECTIONS: usize = 8;
 /// effect.update(&mut buffer).unwrap();
 /// ```
 #[derive(Debug, Clone)]
-pub struct SectionEffect {
+pub struct SectionEffect<const S: usize = MAX_SECTIONS> {
     num_leds: usize,
-    sections: [(ColorPalette, u8); MAX_SECTIONS],
+    sections: [(ColorPalette, u8); S],
     count: usize,
+    render_mode: SectionRenderMode,
+    start_offset: u8,
+    rotation: u8,
+    rotation_speed: u8,
+    gap: u8,
+    gap_color: RGB8,
+    brightness: [u8; S],
+    blink: [bool; S],
+    blink_on: u8,
+    blink_off: u8,
+    blink_tick: u16,
 }
 
 impl SectionEffect {
     /// Creates a new section effect for the specified number of LEDs.
     ///
     /// Starts with no sections (ring is dark).
+    /// The first section starts at LED 0, the layout does not rotate,
+    /// and sections are drawn without gaps.
     ///
     /// # Errors
     ///
     /// Returns `EffectError::ZeroLeds` if `num_leds` is 0.
-    /// Returns `EffectError::TooManyLeds` if `num_leds` exceeds `MAX_LEDS`.
+    /// Returns `EffectError::TooManyLeds` if `num_leds` exceeds `MAX_LEDS`, even with the `alloc` feature.
     pub fn new(num_leds: usize) -> Result<Self, EffectError> {
-        validate_num_leds(num_leds)?;
+        Self::new_fixed(num_leds)
+    }
+}
+
+impl<const S: usize> SectionEffect<S> {
+    /// Creates a section effect that holds up to `S` sections.
+    ///
+    /// Otherwise identical to [`SectionEffect::new`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::ZeroLeds` if `num_leds` is 0.
+    /// Returns `EffectError::TooManyLeds` if `num_leds` exceeds `MAX_LEDS`, even with the `alloc` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ferriswheel::{ColorPalette, SectionEffect};
+    /// use rgb::RGB8;
+    ///
+    /// // One section per hour on a 24-LED scheduling wheel
+    /// let mut wheel: SectionEffect<24> = SectionEffect::new_fixed(24).unwrap();
+    /// let hour = ColorPalette::mono(RGB8::new(0, 64, 255));
+    /// wheel.set_sections(&[(hour, 1); 24]).unwrap();
+    /// ```
+    pub fn new_fixed(num_leds: usize) -> Result<Self, EffectError> {
+        validate_num_leds_up_to(num_leds, MAX_LEDS)?;
 
         let default_palette = ColorPalette::mono(RGB8::default());
         Ok(Self {
             num_leds,
-            sections: [(default_palette, 0); MAX_SECTIONS],
+            sections: [(default_palette, 0); S],
             count: 0,
+            render_mode: SectionRenderMode::Solid,
+            start_offset: 0,
+            rotation: 0,
+            rotation_speed: 0,
+            gap: 0,
+            gap_color: RGB8::new(0, 0, 0),
+            brightness: [u8::MAX; S],
+            blink: [false; S],
+            blink_on: 8,
+            blink_off: 8,
+            blink_tick: 0,
         })
     }
 
+    /// Sets how the LEDs within each section are drawn.
+    pub fn with_render_mode(mut self, render_mode: SectionRenderMode) -> Self {
+        self.render_mode = render_mode;
+        self
+    }
+
+    /// Sets the LED index where the first section begins.
+    ///
+    /// Indices past the end of the ring wrap around.
+    pub fn with_start_offset(mut self, led_index: usize) -> Self {
+        self.start_offset = (led_index % self.num_leds) as u8;
+        self
+    }
+
+    /// Rotates the whole layout clockwise by `speed` LEDs per update.
+    ///
+    /// A speed of 0 (the default) keeps the layout static.
+    pub fn with_rotation_speed(mut self, speed: u8) -> Self {
+        self.rotation_speed = speed;
+        self
+    }
+
+    /// Inserts `gap` LEDs after every section when two or more sections are active.
+    ///
+    /// Because the ring is closed, the last section is also separated from the first.
+    /// Gap LEDs are taken from the ring before the remaining LEDs are distributed
+    /// by weight.
+    pub fn with_gap(mut self, gap: u8) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /// Sets the color of gap LEDs (default: off).
+    pub fn with_gap_color(mut self, color: RGB8) -> Self {
+        self.gap_color = color;
+        self
+    }
+
+    /// Sets how many updates blinking sections stay on and off (default: 8 and 8).
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::ZeroDuty` if either value is 0.
+    pub fn with_blink_duty(mut self, on_ticks: u8, off_ticks: u8) -> Result<Self, EffectError> {
+        validate_duty(on_ticks, off_ticks)?;
+        self.blink_on = on_ticks;
+        self.blink_off = off_ticks;
+        Ok(self)
+    }
+
     /// Sets the active sections.
     ///
     /// Each entry is a `(ColorPalette, weight)` pair.
@@ -71,12 +202,12 @@ impl SectionEffect {
     ///
     /// # Errors
     ///
-    /// Returns `EffectError::TooManySections` if `sections.len()` exceeds `MAX_SECTIONS`.
+    /// Returns `EffectError::TooManySections` if `sections.len()` exceeds the capacity `S`.
     pub fn set_sections(&mut self, sections: &[(ColorPalette, u8)]) -> Result<(), EffectError> {
-        if sections.len() > MAX_SECTIONS {
+        if sections.len() > S {
             return Err(EffectError::TooManySections {
                 requested: sections.len(),
-                max: MAX_SECTIONS,
+                max: S,
             });
         }
 
@@ -93,6 +224,56 @@ impl SectionEffect {
         self.count = 0;
     }
 
+    fn validate_section_index(&self, index: usize) -> Result<(), EffectError> {
+        if index >= S {
+            return Err(EffectError::InvalidSectionIndex { index, capacity: S });
+        }
+        Ok(())
+    }
+
+    /// Scales the section at `index` by `brightness` (0–255, default 255).
+    ///
+    /// Modifiers belong to the section position and are kept when
+    /// [`set_sections`](Self::set_sections) changes the layout, so a section can
+    /// be dimmed or pulsed without rebuilding the layout every frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::InvalidSectionIndex` if `index` is not below the capacity `S`.
+    pub fn set_section_brightness(
+        &mut self,
+        index: usize,
+        brightness: u8,
+    ) -> Result<(), EffectError> {
+        self.validate_section_index(index)?;
+        self.brightness[index] = brightness;
+        Ok(())
+    }
+
+    /// Makes the section at `index` blink with the configured blink duty.
+    ///
+    /// All blinking sections share one blink phase, which advances with `update()`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::InvalidSectionIndex` if `index` is not below the capacity `S`.
+    pub fn set_section_blink(&mut self, index: usize, blink: bool) -> Result<(), EffectError> {
+        self.validate_section_index(index)?;
+        self.blink[index] = blink;
+        Ok(())
+    }
+
+    /// Restores full brightness and turns off blinking for every section.
+    pub fn clear_section_modifiers(&mut self) {
+        self.brightness = [u8::MAX; S];
+        self.blink = [false; S];
+    }
+
+    /// Returns `true` while blinking sections are in their on phase.
+    fn blink_is_on(&self) -> bool {
+        self.blink_tick < self.blink_on as u16
+    }
+
     /// Returns the number of active sections.
     pub fn count(&self) -> usize {
         self.count
@@ -118,50 +299,117 @@ impl SectionEffect {
             .sum();
 
         // If all weights are zero, treat each section as weight 1
-        let (effective_weights, effective_total) = if total_weight == 0 {
-            ([1u32; MAX_SECTIONS], self.count as u32)
+        let equal_weights = total_weight == 0;
+        let effective_total = if equal_weights {
+            self.count as u32
         } else {
-            let mut weights = [0u32; MAX_SECTIONS];
-            for (i, &(_, w)) in self.sections[..self.count].iter().enumerate() {
-                weights[i] = w as u32;
-            }
-            (weights, total_weight)
+            total_weight
+        };
+
+        // Reserve gap LEDs first; a single section has no neighbour to separate
+        let gap = if self.count > 1 {
+            (self.gap as usize).min(self.num_leds / self.count)
+        } else {
+            0
         };
+        let available = self.num_leds - gap * self.count;
 
         let mut led_idx = 0;
-        for (i, (&weight, &(palette, _))) in effective_weights[..self.count]
-            .iter()
-            .zip(self.sections[..self.count].iter())
-            .enumerate()
-        {
+        let mut used = 0;
+        for (i, &(palette, weight)) in self.sections[..self.count].iter().enumerate() {
+            let weight = if equal_weights { 1 } else { weight as u32 };
             let leds_for_section = if i == self.count - 1 {
                 // Last section absorbs rounding remainder
-                self.num_leds - led_idx
+                available - used
             } else {
-                (weight * self.num_leds as u32 / effective_total) as usize
+                (weight * available as u32 / effective_total) as usize
             };
 
-            for led in buffer[led_idx..led_idx + leds_for_section].iter_mut() {
-                *led = palette.primary;
-            }
+            let level = if self.blink[i] && !self.blink_is_on() {
+                0
+            } else {
+                self.brightness[i]
+            };
+            self.render_section(
+                &mut buffer[led_idx..led_idx + leds_for_section],
+                &palette,
+                level,
+            );
             led_idx += leds_for_section;
+            used += leds_for_section;
+
+            fill_solid(&mut buffer[led_idx..led_idx + gap], self.gap_color);
+            led_idx += gap;
         }
 
+        // Move the layout to its physical start position
+        let shift = (self.start_offset as usize + self.rotation as usize) % self.num_leds;
+        buffer[..self.num_leds].rotate_right(shift);
+
         Ok(())
     }
 
-    /// Renders the current sections (same as `current` — sections are externally driven).
+    /// Draws a single section according to the render mode, scaled by `level`.
+    fn render_section(&self, leds: &mut [RGB8], palette: &ColorPalette, level: u8) {
+        let len = leds.len();
+        for (i, led) in leds.iter_mut().enumerate() {
+            let color = match self.render_mode {
+                SectionRenderMode::Solid => palette.primary,
+                SectionRenderMode::Gradient if len > 1 => {
+                    let t = (i * 255 / (len - 1)) as u8;
+                    lerp_color(palette.primary, palette.secondary, t)
+                }
+                SectionRenderMode::Gradient => palette.primary,
+                SectionRenderMode::Alternating if i % 2 == 0 => palette.primary,
+                SectionRenderMode::Alternating => palette.secondary,
+            };
+            *led = if level == u8::MAX {
+                color
+            } else {
+                scale_brightness(color, level)
+            };
+        }
+    }
+
+    /// Renders the current sections and advances the rotation and blink phase.
     pub fn update(&mut self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
-        self.current(buffer)
+        self.current(buffer)?;
+        self.advance_blink(1);
+        if self.rotation_speed > 0 {
+            self.rotation = advance_position(
+                self.rotation,
+                self.rotation_speed,
+                self.num_leds,
+                Direction::Clockwise,
+            );
+        }
+        Ok(())
     }
 
-    /// Resets the effect by clearing all sections.
+    fn advance_blink(&mut self, steps: u32) {
+        let period = self.blink_on as u32 + self.blink_off as u32;
+        self.blink_tick = ((self.blink_tick as u32 + steps % period) % period) as u16;
+    }
+
+    /// Advances the rotation and blink phase by `steps` updates without rendering.
+    pub fn advance(&mut self, steps: u32) -> Result<(), EffectError> {
+        self.advance_blink(steps);
+        let distance = self.rotation_speed as u64 * steps as u64 % self.num_leds as u64;
+        self.rotation = ((self.rotation as u64 + distance) % self.num_leds as u64) as u8;
+        Ok(())
+    }
+
+    /// Resets the effect by clearing all sections, section modifiers, the rotation
+    /// and the blink phase.
     pub fn reset(&mut self) {
         self.clear();
+        self.clear_section_modifiers();
+        self.rotation = 0;
+        self.blink_tick = 0;
     }
 }
 
-impl Effect for SectionEffect {
+impl<const S: usize> Effect for SectionEffect<S> {
     fn update(&mut self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
         self.update(buffer)
     }
@@ -173,6 +421,10 @@ impl Effect for SectionEffect {
     fn reset(&mut self) {
         self.reset();
     }
+
+    fn advance(&mut self, steps: u32) -> Result<(), EffectError> {
+        self.advance(steps)
+    }
 }
 
 #[cfg(test)]
@@ -254,6 +506,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::needless_range_loop)]
     fn test_two_equal_weight_sections_split_evenly() {
         let mut effect = SectionEffect::new(8).unwrap();
         effect
@@ -421,6 +674,176 @@ mod tests {
         }
     }
 
+    fn red_blue_palette() -> ColorPalette {
+        ColorPalette::new(RGB8::new(255, 0, 0), RGB8::new(0, 0, 255), RGB8::default())
+    }
+
+    #[test]
+    fn test_default_render_mode_is_solid() {
+        assert_eq!(SectionRenderMode::default(), SectionRenderMode::Solid);
+
+        let mut effect = SectionEffect::new(4).unwrap();
+        effect.set_sections(&[(red_blue_palette(), 1)]).unwrap();
+
+        let mut buffer = [RGB8::default(); 4];
+        effect.current(&mut buffer).unwrap();
+        for led in &buffer {
+            assert_eq!(*led, RGB8::new(255, 0, 0));
+        }
+    }
+
+    #[test]
+    fn test_gradient_mode_fades_primary_to_secondary() {
+        let mut effect = SectionEffect::new(8)
+            .unwrap()
+            .with_render_mode(SectionRenderMode::Gradient);
+        effect
+            .set_sections(&[(red_blue_palette(), 1), (red_blue_palette(), 1)])
+            .unwrap();
+
+        let mut buffer = [RGB8::default(); 8];
+        effect.current(&mut buffer).unwrap();
+
+        // Each 4-LED section starts at primary and ends at secondary
+        for start in [0, 4] {
+            assert_eq!(buffer[start], RGB8::new(255, 0, 0));
+            assert_eq!(buffer[start + 3], RGB8::new(0, 0, 255));
+            assert!(buffer[start + 1].r > buffer[start + 2].r);
+            assert!(buffer[start + 1].b < buffer[start + 2].b);
+        }
+    }
+
+    #[test]
+    fn test_gradient_mode_single_led_section_uses_primary() {
+        let mut effect = SectionEffect::new(1)
+            .unwrap()
+            .with_render_mode(SectionRenderMode::Gradient);
+        effect.set_sections(&[(red_blue_palette(), 1)]).unwrap();
+
+        let mut buffer = [RGB8::default(); 1];
+        effect.current(&mut buffer).unwrap();
+        assert_eq!(buffer[0], RGB8::new(255, 0, 0));
+    }
+
+    #[test]
+    fn test_alternating_mode_restarts_per_section() {
+        let mut effect = SectionEffect::new(6)
+            .unwrap()
+            .with_render_mode(SectionRenderMode::Alternating);
+        effect
+            .set_sections(&[(red_blue_palette(), 1), (red_blue_palette(), 1)])
+            .unwrap();
+
+        let mut buffer = [RGB8::default(); 6];
+        effect.current(&mut buffer).unwrap();
+
+        let red = RGB8::new(255, 0, 0);
+        let blue = RGB8::new(0, 0, 255);
+        assert_eq!(buffer, [red, blue, red, red, blue, red]);
+    }
+
+    #[test]
+    fn test_start_offset_moves_layout() {
+        let mut effect = SectionEffect::new(8).unwrap().with_start_offset(6);
+        effect
+            .set_sections(&[(red_palette(), 1), (blue_palette(), 1)])
+            .unwrap();
+
+        let mut buffer = [RGB8::default(); 8];
+        effect.current(&mut buffer).unwrap();
+
+        let red = RGB8::new(255, 0, 0);
+        let blue = RGB8::new(0, 0, 255);
+        assert_eq!(buffer, [red, red, blue, blue, blue, blue, red, red]);
+    }
+
+    #[test]
+    fn test_start_offset_wraps() {
+        let effect = SectionEffect::new(8).unwrap().with_start_offset(10);
+        assert_eq!(effect.start_offset, 2);
+    }
+
+    #[test]
+    fn test_rotation_advances_each_update() {
+        let mut effect = SectionEffect::new(4).unwrap().with_rotation_speed(1);
+        effect
+            .set_sections(&[(red_palette(), 1), (blue_palette(), 3)])
+            .unwrap();
+
+        let mut buffer = [RGB8::default(); 4];
+        for expected_red in 0..6 {
+            effect.update(&mut buffer).unwrap();
+            assert_eq!(buffer[expected_red % 4], RGB8::new(255, 0, 0));
+        }
+    }
+
+    #[test]
+    fn test_reset_clears_rotation() {
+        let mut effect = SectionEffect::new(4).unwrap().with_rotation_speed(1);
+        let mut buffer = [RGB8::default(); 4];
+        effect.update(&mut buffer).unwrap();
+        effect.reset();
+        assert_eq!(effect.rotation, 0);
+    }
+
+    #[test]
+    fn test_gap_separates_sections() {
+        let mut effect = SectionEffect::new(8).unwrap().with_gap(1);
+        effect
+            .set_sections(&[(red_palette(), 1), (blue_palette(), 1)])
+            .unwrap();
+
+        let mut buffer = [RGB8::default(); 8];
+        effect.current(&mut buffer).unwrap();
+
+        let red = RGB8::new(255, 0, 0);
+        let blue = RGB8::new(0, 0, 255);
+        let off = RGB8::new(0, 0, 0);
+        assert_eq!(buffer, [red, red, red, off, blue, blue, blue, off]);
+    }
+
+    #[test]
+    fn test_gap_color() {
+        let divider = RGB8::new(10, 10, 10);
+        let mut effect = SectionEffect::new(6)
+            .unwrap()
+            .with_gap(1)
+            .with_gap_color(divider);
+        effect
+            .set_sections(&[(red_palette(), 1), (blue_palette(), 1)])
+            .unwrap();
+
+        let mut buffer = [RGB8::default(); 6];
+        effect.current(&mut buffer).unwrap();
+        assert_eq!(buffer[2], divider);
+        assert_eq!(buffer[5], divider);
+    }
+
+    #[test]
+    fn test_gap_ignored_for_single_section() {
+        let mut effect = SectionEffect::new(4).unwrap().with_gap(2);
+        effect.set_sections(&[(red_palette(), 1)]).unwrap();
+
+        let mut buffer = [RGB8::default(); 4];
+        effect.current(&mut buffer).unwrap();
+        for led in &buffer {
+            assert_eq!(*led, RGB8::new(255, 0, 0));
+        }
+    }
+
+    #[test]
+    fn test_oversized_gap_does_not_overflow_ring() {
+        let mut effect = SectionEffect::new(5).unwrap().with_gap(200);
+        effect
+            .set_sections(&[(red_palette(), 1), (blue_palette(), 1)])
+            .unwrap();
+
+        let mut buffer = [RGB8::default(); 5];
+        effect.current(&mut buffer).unwrap();
+        // 2 gaps of 2 LEDs each, the last section keeps the single leftover LED
+        assert_eq!(buffer.iter().filter(|led| led.b == 255).count(), 1);
+    }
+
     #[test]
     fn test_max_sections_allowed() {
         let mut effect = SectionEffect::new(16).unwrap();
@@ -429,4 +852,166 @@ mod tests {
         assert!(effect.set_sections(&sections).is_ok());
         assert_eq!(effect.count(), MAX_SECTIONS);
     }
+
+    #[test]
+    fn test_new_beyond_max_leds_returns_error() {
+        assert_eq!(
+            SectionEffect::new(MAX_LEDS + 1).unwrap_err(),
+            EffectError::TooManyLeds {
+                requested: MAX_LEDS + 1,
+                max: MAX_LEDS
+            }
+        );
+    }
+
+    #[test]
+    fn test_advance_matches_repeated_update() {
+        let mut updated = SectionEffect::new(5).unwrap().with_rotation_speed(2);
+        updated
+            .set_sections(&[(red_palette(), 1), (blue_palette(), 4)])
+            .unwrap();
+        let mut advanced = updated.clone();
+
+        let mut buffer = [RGB8::default(); 5];
+        for _ in 0..13 {
+            updated.update(&mut buffer).unwrap();
+        }
+        advanced.advance(13).unwrap();
+        assert_eq!(advanced.rotation, updated.rotation);
+    }
+
+    #[test]
+    fn test_custom_capacity_holds_more_sections() {
+        let mut effect: SectionEffect<24> = SectionEffect::new_fixed(24).unwrap();
+        let sections: Vec<(ColorPalette, u8)> = (0..24)
+            .map(|i| {
+                if i % 2 == 0 {
+                    (red_palette(), 1)
+                } else {
+                    (blue_palette(), 1)
+                }
+            })
+            .collect();
+        effect.set_sections(&sections).unwrap();
+        assert_eq!(effect.count(), 24);
+
+        let mut buffer = [RGB8::default(); 24];
+        effect.update(&mut buffer).unwrap();
+        assert_eq!(buffer[0], RGB8::new(255, 0, 0));
+        assert_eq!(buffer[1], RGB8::new(0, 0, 255));
+        assert_eq!(buffer[23], RGB8::new(0, 0, 255));
+    }
+
+    #[test]
+    fn test_custom_capacity_limit() {
+        let mut effect: SectionEffect<2> = SectionEffect::new_fixed(8).unwrap();
+        assert_eq!(
+            effect.set_sections(&[(red_palette(), 1); 3]).unwrap_err(),
+            EffectError::TooManySections {
+                requested: 3,
+                max: 2
+            }
+        );
+    }
+
+    #[test]
+    fn test_section_brightness_dims_one_section() {
+        let mut effect = SectionEffect::new(4).unwrap();
+        effect
+            .set_sections(&[(red_palette(), 1), (blue_palette(), 1)])
+            .unwrap();
+        effect.set_section_brightness(1, 128).unwrap();
+
+        let mut buffer = [RGB8::default(); 4];
+        effect.update(&mut buffer).unwrap();
+        assert_eq!(buffer[0], RGB8::new(255, 0, 0));
+        assert_eq!(buffer[2], RGB8::new(0, 0, 128));
+    }
+
+    #[test]
+    fn test_section_blink_follows_duty() {
+        let mut effect = SectionEffect::new(4)
+            .unwrap()
+            .with_blink_duty(2, 1)
+            .unwrap();
+        effect
+            .set_sections(&[(red_palette(), 1), (blue_palette(), 1)])
+            .unwrap();
+        effect.set_section_blink(0, true).unwrap();
+
+        let mut buffer = [RGB8::default(); 4];
+        let mut first = Vec::new();
+        for _ in 0..6 {
+            effect.update(&mut buffer).unwrap();
+            first.push(buffer[0]);
+            // The static section is unaffected
+            assert_eq!(buffer[2], RGB8::new(0, 0, 255));
+        }
+        let red = RGB8::new(255, 0, 0);
+        let off = RGB8::default();
+        assert_eq!(first, [red, red, off, red, red, off]);
+    }
+
+    #[test]
+    fn test_modifiers_survive_set_sections() {
+        let mut effect = SectionEffect::new(2).unwrap();
+        effect.set_section_brightness(0, 0).unwrap();
+        effect.set_sections(&[(red_palette(), 1)]).unwrap();
+
+        let mut buffer = [RGB8::new(1, 1, 1); 2];
+        effect.current(&mut buffer).unwrap();
+        assert_eq!(buffer, [RGB8::default(); 2]);
+
+        effect.clear_section_modifiers();
+        effect.current(&mut buffer).unwrap();
+        assert_eq!(buffer, [RGB8::new(255, 0, 0); 2]);
+    }
+
+    #[test]
+    fn test_section_modifier_index_out_of_range_returns_error() {
+        let mut effect = SectionEffect::new(4).unwrap();
+        assert_eq!(
+            effect.set_section_blink(MAX_SECTIONS, true).unwrap_err(),
+            EffectError::InvalidSectionIndex {
+                index: MAX_SECTIONS,
+                capacity: MAX_SECTIONS
+            }
+        );
+    }
+
+    #[test]
+    fn test_section_brightness_index_out_of_range_returns_error() {
+        let mut effect: SectionEffect<2> = SectionEffect::new_fixed(4).unwrap();
+        assert_eq!(
+            effect.set_section_brightness(2, 128).unwrap_err(),
+            EffectError::InvalidSectionIndex {
+                index: 2,
+                capacity: 2
+            }
+        );
+    }
+
+    #[test]
+    fn test_zero_blink_duty_returns_error() {
+        let result = SectionEffect::new(4).unwrap().with_blink_duty(0, 4);
+        assert_eq!(result.unwrap_err(), EffectError::ZeroDuty);
+    }
+
+    #[test]
+    fn test_advance_moves_blink_phase() {
+        let mut updated = SectionEffect::new(2)
+            .unwrap()
+            .with_blink_duty(3, 2)
+            .unwrap();
+        updated.set_sections(&[(red_palette(), 1)]).unwrap();
+        updated.set_section_blink(0, true).unwrap();
+        let mut advanced = updated.clone();
+
+        let mut buffer = [RGB8::default(); 2];
+        for _ in 0..7 {
+            updated.update(&mut buffer).unwrap();
+        }
+        advanced.advance(7).unwrap();
+        assert_eq!(advanced.blink_tick, updated.blink_tick);
+    }
 }
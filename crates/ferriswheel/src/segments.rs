@@ -0,0 +1,335 @@
+//! Independent effects on parts of one LED strip.
+//!
+//! Several rings or bars often share one data line. [`Segments`] splits the
+//! physical buffer into ranges and lets each range run its own effect.
+
+use crate::effect::{validate_buffer, Effect, EffectError};
+use rgb::RGB8;
+
+struct Segment<'a> {
+    start: usize,
+    len: usize,
+    effect: &'a mut dyn Effect,
+}
+
+/// Drives up to `N` effects on separate ranges of one buffer.
+///
+/// Each segment borrows its effect and renders into `buffer[start..start + len]`,
+/// so every effect sees its own zero-based slice. LEDs outside all segments
+/// are left untouched. If segments overlap, later segments overwrite earlier ones.
+///
+/// `Segments` implements [`Effect`], so the whole strip can be handed to code
+/// that expects a single effect.
+///
+/// # Example
+///
+/// ```
+/// use ferriswheel::{Effect, ProgressEffect, RainbowEffect, Segments};
+/// use rgb::RGB8;
+///
+/// // A 24-LED ring followed by an 8-LED bar on the same data line
+/// let mut ring = RainbowEffect::new(24).unwrap();
+/// let mut bar = ProgressEffect::new(8).unwrap();
+///
+/// let mut segments: Segments<2> = Segments::new();
+/// segments.add(0, 24, &mut ring).unwrap();
+/// segments.add(24, 8, &mut bar).unwrap();
+///
+/// let mut buffer = [RGB8::default(); 32];
+/// segments.update(&mut buffer).unwrap();
+/// ```
+pub struct Segments<'a, const N: usize> {
+    segments: [Option<Segment<'a>>; N],
+    count: usize,
+}
+
+impl<'a, const N: usize> Segments<'a, N> {
+    /// Creates a controller without segments.
+    pub fn new() -> Self {
+        Self {
+            segments: core::array::from_fn(|_| None),
+            count: 0,
+        }
+    }
+
+    /// Adds a segment covering `len` LEDs starting at physical index `start`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::ZeroLeds` if `len` is 0.
+    /// Returns `EffectError::TooManySegments` if `N` segments were already added.
+    pub fn add(
+        &mut self,
+        start: usize,
+        len: usize,
+        effect: &'a mut dyn Effect,
+    ) -> Result<(), EffectError> {
+        if len == 0 {
+            return Err(EffectError::ZeroLeds);
+        }
+        if self.count == N {
+            return Err(EffectError::TooManySegments { max: N });
+        }
+        self.segments[self.count] = Some(Segment { start, len, effect });
+        self.count += 1;
+        Ok(())
+    }
+
+    /// Returns the number of segments.
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Returns `true` if no segments were added.
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Returns the number of LEDs needed to hold every segment.
+    pub fn required_len(&self) -> usize {
+        self.iter().map(|s| s.start + s.len).max().unwrap_or(0)
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &Segment<'a>> {
+        self.segments[..self.count].iter().flatten()
+    }
+
+    /// Fills each segment's range with its current frame without advancing.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::BufferTooSmall` if the buffer does not cover every segment,
+    /// or the first error reported by a segment's effect.
+    pub fn current(&self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        validate_buffer(buffer, self.required_len())?;
+
+        for segment in self.iter() {
+            let range = segment.start..segment.start + segment.len;
+            segment.effect.current(&mut buffer[range])?;
+        }
+        Ok(())
+    }
+
+    /// Fills each segment's range and advances every effect.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::BufferTooSmall` if the buffer does not cover every segment,
+    /// or the first error reported by a segment's effect.
+    pub fn update(&mut self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        validate_buffer(buffer, self.required_len())?;
+
+        for segment in self.segments[..self.count].iter_mut().flatten() {
+            let range = segment.start..segment.start + segment.len;
+            segment.effect.update(&mut buffer[range])?;
+        }
+        Ok(())
+    }
+
+    /// Advances every segment's effect by `steps` updates without rendering.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error reported by a segment's effect.
+    pub fn advance(&mut self, steps: u32) -> Result<(), EffectError> {
+        for segment in self.segments[..self.count].iter_mut().flatten() {
+            segment.effect.advance(steps)?;
+        }
+        Ok(())
+    }
+
+    /// Resets every segment's effect.
+    pub fn reset(&mut self) {
+        for segment in self.segments[..self.count].iter_mut().flatten() {
+            segment.effect.reset();
+        }
+    }
+
+    /// Jumps every segment's effect to `phase` (0–255) of its own cycle.
+    pub fn set_phase(&mut self, phase: u8) {
+        for segment in self.segments[..self.count].iter_mut().flatten() {
+            segment.effect.set_phase(phase);
+        }
+    }
+
+    /// Returns `true` once every segment's effect has finished.
+    pub fn is_finished(&self) -> bool {
+        self.iter().all(|segment| segment.effect.is_finished())
+    }
+}
+
+impl<const N: usize> Default for Segments<'_, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> core::fmt::Debug for Segments<'_, N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Segments")
+            .field("count", &self.count)
+            .field("required_len", &self.required_len())
+            .finish()
+    }
+}
+
+impl<const N: usize> Effect for Segments<'_, N> {
+    fn update(&mut self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        self.update(buffer)
+    }
+
+    fn current(&self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        self.current(buffer)
+    }
+
+    fn reset(&mut self) {
+        self.reset();
+    }
+
+    fn advance(&mut self, steps: u32) -> Result<(), EffectError> {
+        self.advance(steps)
+    }
+
+    fn is_finished(&self) -> bool {
+        self.is_finished()
+    }
+
+    fn set_phase(&mut self, phase: u8) {
+        self.set_phase(phase);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FlashEffect, SpinnerEffect};
+
+    const RED: RGB8 = RGB8::new(255, 0, 0);
+    const GREEN: RGB8 = RGB8::new(0, 255, 0);
+    const WHITE: RGB8 = RGB8::new(255, 255, 255);
+    const MARK: RGB8 = RGB8::new(1, 2, 3);
+
+    fn solid(n: usize, color: RGB8) -> FlashEffect {
+        FlashEffect::new(n)
+            .unwrap()
+            .with_color(color)
+            .with_duty(255, 1)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_zero_length_segment_returns_error() {
+        let mut effect = solid(1, RED);
+        let mut segments: Segments<1> = Segments::new();
+        assert_eq!(
+            segments.add(0, 0, &mut effect).unwrap_err(),
+            EffectError::ZeroLeds
+        );
+    }
+
+    #[test]
+    fn test_too_many_segments_returns_error() {
+        let mut a = solid(1, RED);
+        let mut b = solid(1, GREEN);
+        let mut segments: Segments<1> = Segments::new();
+        segments.add(0, 1, &mut a).unwrap();
+        assert_eq!(
+            segments.add(1, 1, &mut b).unwrap_err(),
+            EffectError::TooManySegments { max: 1 }
+        );
+    }
+
+    #[test]
+    fn test_segments_render_into_their_ranges() {
+        let mut ring = solid(3, RED);
+        let mut bar = solid(2, GREEN);
+        let mut segments: Segments<2> = Segments::new();
+        segments.add(0, 3, &mut ring).unwrap();
+        segments.add(4, 2, &mut bar).unwrap();
+        assert_eq!(segments.required_len(), 6);
+
+        let mut buffer = [MARK; 7];
+        segments.update(&mut buffer).unwrap();
+        assert_eq!(buffer, [RED, RED, RED, MARK, GREEN, GREEN, MARK]);
+    }
+
+    #[test]
+    fn test_effects_advance_independently() {
+        let mut a = SpinnerEffect::new(4).unwrap().with_tail_length(0);
+        let mut b = SpinnerEffect::new(4)
+            .unwrap()
+            .with_tail_length(0)
+            .with_speed(2)
+            .unwrap();
+        let mut segments: Segments<2> = Segments::new();
+        segments.add(0, 4, &mut a).unwrap();
+        segments.add(4, 4, &mut b).unwrap();
+
+        let mut buffer = [RGB8::default(); 8];
+        segments.update(&mut buffer).unwrap();
+        segments.current(&mut buffer).unwrap();
+        assert_eq!(buffer[1], WHITE);
+        assert_eq!(buffer[4 + 2], WHITE);
+    }
+
+    #[test]
+    fn test_buffer_too_small_returns_error() {
+        let mut bar = solid(4, GREEN);
+        let mut segments: Segments<1> = Segments::new();
+        segments.add(4, 4, &mut bar).unwrap();
+
+        let mut buffer = [RGB8::default(); 6];
+        assert_eq!(
+            segments.update(&mut buffer).unwrap_err(),
+            EffectError::BufferTooSmall {
+                required: 8,
+                actual: 6
+            }
+        );
+    }
+
+    #[test]
+    fn test_reset_resets_every_effect() {
+        let mut a = SpinnerEffect::new(4).unwrap().with_tail_length(0);
+        let mut segments: Segments<1> = Segments::new();
+        segments.add(0, 4, &mut a).unwrap();
+
+        let mut buffer = [RGB8::default(); 4];
+        segments.update(&mut buffer).unwrap();
+        segments.reset();
+        segments.current(&mut buffer).unwrap();
+        assert_eq!(buffer[0], WHITE);
+    }
+
+    #[test]
+    fn test_set_phase_reaches_every_segment() {
+        let mut a = SpinnerEffect::new(4).unwrap().with_tail_length(0);
+        let mut b = SpinnerEffect::new(8).unwrap().with_tail_length(0);
+        let mut segments: Segments<2> = Segments::new();
+        segments.add(0, 4, &mut a).unwrap();
+        segments.add(4, 8, &mut b).unwrap();
+
+        let mut buffer = [RGB8::default(); 12];
+        segments.update(&mut buffer).unwrap();
+        let effect_ref: &mut dyn Effect = &mut segments;
+        effect_ref.set_phase(128);
+        effect_ref.current(&mut buffer).unwrap();
+        // Half a revolution on each ring, not a restart at LED 0
+        assert_eq!(buffer[2], WHITE);
+        assert_eq!(buffer[4 + 4], WHITE);
+        assert_eq!(buffer[0], RGB8::default());
+    }
+
+    #[test]
+    fn test_trait_object_update() {
+        let mut a = solid(2, RED);
+        let mut segments: Segments<1> = Segments::new();
+        segments.add(1, 2, &mut a).unwrap();
+
+        let effect_ref: &mut dyn Effect = &mut segments;
+        let mut buffer = [MARK; 3];
+        effect_ref.update(&mut buffer).unwrap();
+        assert_eq!(buffer, [MARK, RED, RED]);
+        assert!(!effect_ref.is_finished());
+    }
+}
@@ -0,0 +1,503 @@
+//! Plays a list of effects one after another.
+//!
+//! The sequencer borrows its effects, so no allocator is needed.
+//! Each step runs for a fixed number of updates or until its effect reports
+//! [`is_finished`](crate::Effect::is_finished).
+
+use crate::effect::{Effect, EffectError};
+use rgb::RGB8;
+
+/// How long a sequencer step lasts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum StepDuration {
+    /// Advance after this many updates.
+    Ticks(u32),
+    /// Advance once the step's effect reports it is finished.
+    UntilFinished,
+}
+
+struct Step<'a> {
+    effect: &'a mut dyn Effect,
+    duration: StepDuration,
+}
+
+/// A fixed-capacity sequence of effects with per-step durations.
+///
+/// Steps are played in the order they were pushed. When the last step ends the
+/// sequencer either starts over (looping) or keeps rendering the last step's
+/// final frame and reports [`is_finished`](EffectSequencer::is_finished).
+///
+/// The sequencer implements [`Effect`] itself, so it can be nested or used
+/// wherever a single effect is expected.
+///
+/// # Example
+///
+/// ```
+/// use ferriswheel::{Effect, EffectSequencer, FlashEffect, RainbowEffect, StepDuration};
+/// use rgb::RGB8;
+///
+/// let mut flash = FlashEffect::new(12).unwrap();
+/// let mut rainbow = RainbowEffect::new(12).unwrap();
+///
+/// let mut sequencer: EffectSequencer<4> = EffectSequencer::new().with_looping(true);
+/// sequencer.push(&mut flash, StepDuration::Ticks(16)).unwrap();
+/// sequencer.push(&mut rainbow, StepDuration::Ticks(256)).unwrap();
+///
+/// let mut buffer = [RGB8::default(); 12];
+/// sequencer.update(&mut buffer).unwrap();
+/// ```
+pub struct EffectSequencer<'a, const N: usize> {
+    steps: [Option<Step<'a>>; N],
+    len: usize,
+    index: usize,
+    ticks: u32,
+    looping: bool,
+    done: bool,
+}
+
+impl<'a, const N: usize> EffectSequencer<'a, N> {
+    /// Creates an empty, non-looping sequencer.
+    pub fn new() -> Self {
+        Self {
+            steps: core::array::from_fn(|_| None),
+            len: 0,
+            index: 0,
+            ticks: 0,
+            looping: false,
+            done: false,
+        }
+    }
+
+    /// Sets whether the sequence starts over after the last step.
+    pub fn with_looping(mut self, looping: bool) -> Self {
+        self.looping = looping;
+        self
+    }
+
+    /// Appends a step to the sequence.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::TooManySteps` if the sequencer already holds `N` steps.
+    pub fn push(
+        &mut self,
+        effect: &'a mut dyn Effect,
+        duration: StepDuration,
+    ) -> Result<(), EffectError> {
+        if self.len == N {
+            return Err(EffectError::TooManySteps { max: N });
+        }
+        self.steps[self.len] = Some(Step { effect, duration });
+        self.len += 1;
+        self.done = false;
+        Ok(())
+    }
+
+    /// Returns the number of steps in the sequence.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the sequence has no steps.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the index of the step currently playing.
+    pub fn current_step(&self) -> usize {
+        self.index
+    }
+
+    /// Returns `true` once a non-looping sequence has played its last step.
+    ///
+    /// An empty sequence is always finished.
+    pub fn is_finished(&self) -> bool {
+        self.len == 0 || self.done
+    }
+
+    /// Renders the current step without advancing.
+    ///
+    /// An empty sequence leaves the buffer untouched.
+    pub fn current(&self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        match &self.steps[..self.len].get(self.index) {
+            Some(Some(step)) => step.effect.current(buffer),
+            _ => Ok(()),
+        }
+    }
+
+    /// Renders and advances the current step, moving on when it is over.
+    ///
+    /// An empty sequence leaves the buffer untouched.
+    pub fn update(&mut self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        if self.len == 0 {
+            return Ok(());
+        }
+        if self.done {
+            return self.current(buffer);
+        }
+
+        let Some(step) = self.steps[self.index].as_mut() else {
+            return Ok(());
+        };
+        step.effect.update(buffer)?;
+        self.ticks = self.ticks.saturating_add(1);
+
+        let step_over = match step.duration {
+            StepDuration::Ticks(ticks) => self.ticks >= ticks,
+            StepDuration::UntilFinished => step.effect.is_finished(),
+        };
+        if step_over {
+            self.advance_step();
+        }
+
+        Ok(())
+    }
+
+    /// Advances the sequence by `steps` updates without rendering.
+    ///
+    /// Steps with a fixed duration are skipped in one jump; steps that run
+    /// until their effect finishes are advanced one update at a time.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error reported by a step's effect.
+    pub fn advance(&mut self, mut steps: u32) -> Result<(), EffectError> {
+        while steps > 0 && self.len > 0 && !self.done {
+            let Some(step) = self.steps[self.index].as_mut() else {
+                return Ok(());
+            };
+            let n = match step.duration {
+                StepDuration::Ticks(ticks) => ticks.saturating_sub(self.ticks).clamp(1, steps),
+                StepDuration::UntilFinished => 1,
+            };
+            step.effect.advance(n)?;
+            self.ticks = self.ticks.saturating_add(n);
+            steps -= n;
+
+            let step_over = match step.duration {
+                StepDuration::Ticks(ticks) => self.ticks >= ticks,
+                StepDuration::UntilFinished => step.effect.is_finished(),
+            };
+            if step_over {
+                self.advance_step();
+            }
+        }
+        Ok(())
+    }
+
+    /// Moves to the next step, wrapping or finishing at the end.
+    fn advance_step(&mut self) {
+        let next = self.index + 1;
+        if next < self.len {
+            self.index = next;
+        } else if self.looping {
+            self.index = 0;
+        } else {
+            self.done = true;
+            return;
+        }
+        self.ticks = 0;
+        if let Some(step) = self.steps[self.index].as_mut() {
+            step.effect.reset();
+        }
+    }
+
+    /// Jumps the current step's effect to `phase` (0–255) of its cycle.
+    ///
+    /// The sequence stays on the current step and keeps its tick count, so
+    /// beats do not restart the sequence. An empty sequence ignores it.
+    pub fn set_phase(&mut self, phase: u8) {
+        if let Some(step) = self.steps[..self.len]
+            .get_mut(self.index)
+            .and_then(Option::as_mut)
+        {
+            step.effect.set_phase(phase);
+        }
+    }
+
+    /// Restarts the sequence from the first step.
+    pub fn reset(&mut self) {
+        self.index = 0;
+        self.ticks = 0;
+        self.done = false;
+        if let Some(step) = self.steps[0..self.len].first_mut().and_then(Option::as_mut) {
+            step.effect.reset();
+        }
+    }
+}
+
+impl<const N: usize> Default for EffectSequencer<'_, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> core::fmt::Debug for EffectSequencer<'_, N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("EffectSequencer")
+            .field("len", &self.len)
+            .field("index", &self.index)
+            .field("ticks", &self.ticks)
+            .field("looping", &self.looping)
+            .field("done", &self.done)
+            .finish()
+    }
+}
+
+impl<const N: usize> Effect for EffectSequencer<'_, N> {
+    fn update(&mut self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        self.update(buffer)
+    }
+
+    fn current(&self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        self.current(buffer)
+    }
+
+    fn reset(&mut self) {
+        self.reset();
+    }
+
+    fn advance(&mut self, steps: u32) -> Result<(), EffectError> {
+        self.advance(steps)
+    }
+
+    fn is_finished(&self) -> bool {
+        self.is_finished()
+    }
+
+    fn set_phase(&mut self, phase: u8) {
+        self.set_phase(phase);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FlashEffect, SpinnerEffect, WipeTransitionEffect};
+
+    const RED: RGB8 = RGB8::new(255, 0, 0);
+    const GREEN: RGB8 = RGB8::new(0, 255, 0);
+    const BLUE: RGB8 = RGB8::new(0, 0, 255);
+
+    /// Solid flash effect that never turns off, used as a recognizable step.
+    fn solid(color: RGB8) -> FlashEffect {
+        FlashEffect::new(2)
+            .unwrap()
+            .with_color(color)
+            .with_duty(255, 1)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_empty_sequencer_is_finished_and_untouched() {
+        let mut sequencer: EffectSequencer<2> = EffectSequencer::new();
+        assert!(sequencer.is_empty());
+        assert!(sequencer.is_finished());
+
+        let mut buffer = [RED; 2];
+        sequencer.update(&mut buffer).unwrap();
+        assert_eq!(buffer, [RED; 2]);
+    }
+
+    #[test]
+    fn test_push_beyond_capacity_returns_error() {
+        let mut a = solid(RED);
+        let mut b = solid(GREEN);
+        let mut sequencer: EffectSequencer<1> = EffectSequencer::new();
+        sequencer.push(&mut a, StepDuration::Ticks(1)).unwrap();
+        assert_eq!(
+            sequencer.push(&mut b, StepDuration::Ticks(1)).unwrap_err(),
+            EffectError::TooManySteps { max: 1 }
+        );
+        assert_eq!(sequencer.len(), 1);
+    }
+
+    #[test]
+    fn test_steps_play_for_their_tick_count() {
+        let mut a = solid(RED);
+        let mut b = solid(GREEN);
+        let mut sequencer: EffectSequencer<2> = EffectSequencer::new();
+        sequencer.push(&mut a, StepDuration::Ticks(2)).unwrap();
+        sequencer.push(&mut b, StepDuration::Ticks(3)).unwrap();
+
+        let mut buffer = [RGB8::default(); 2];
+        let mut frames = Vec::new();
+        for _ in 0..6 {
+            sequencer.update(&mut buffer).unwrap();
+            frames.push(buffer[0]);
+        }
+
+        assert_eq!(frames, [RED, RED, GREEN, GREEN, GREEN, GREEN]);
+        assert!(sequencer.is_finished());
+        assert_eq!(sequencer.current_step(), 1);
+    }
+
+    #[test]
+    fn test_looping_starts_over() {
+        let mut a = solid(RED);
+        let mut b = solid(GREEN);
+        let mut sequencer: EffectSequencer<2> = EffectSequencer::new().with_looping(true);
+        sequencer.push(&mut a, StepDuration::Ticks(1)).unwrap();
+        sequencer.push(&mut b, StepDuration::Ticks(1)).unwrap();
+
+        let mut buffer = [RGB8::default(); 2];
+        let mut frames = Vec::new();
+        for _ in 0..5 {
+            sequencer.update(&mut buffer).unwrap();
+            frames.push(buffer[0]);
+        }
+
+        assert_eq!(frames, [RED, GREEN, RED, GREEN, RED]);
+        assert!(!sequencer.is_finished());
+    }
+
+    #[test]
+    fn test_until_finished_waits_for_one_shot_effect() {
+        let mut wipe = WipeTransitionEffect::new(2)
+            .unwrap()
+            .with_speed(128)
+            .unwrap();
+        wipe.set_frames(&[RED; 2], &[BLUE; 2]).unwrap();
+        let mut after = solid(GREEN);
+
+        let mut sequencer: EffectSequencer<2> = EffectSequencer::new();
+        sequencer
+            .push(&mut wipe, StepDuration::UntilFinished)
+            .unwrap();
+        sequencer.push(&mut after, StepDuration::Ticks(1)).unwrap();
+
+        let mut buffer = [RGB8::default(); 2];
+        // Progress 0, 128, 255 → finished after the second update
+        sequencer.update(&mut buffer).unwrap();
+        assert_eq!(sequencer.current_step(), 0);
+        sequencer.update(&mut buffer).unwrap();
+        assert_eq!(sequencer.current_step(), 1);
+        sequencer.update(&mut buffer).unwrap();
+        assert_eq!(buffer, [GREEN; 2]);
+    }
+
+    #[test]
+    fn test_next_step_is_reset_on_entry() {
+        let mut a = solid(RED);
+        let mut b = FlashEffect::new(2).unwrap().with_duty(1, 1).unwrap();
+        // Leave b in its off phase before it is sequenced
+        b.update(&mut [RGB8::default(); 2]).unwrap();
+        assert!(!b.is_on());
+
+        let mut sequencer: EffectSequencer<2> = EffectSequencer::new();
+        sequencer.push(&mut a, StepDuration::Ticks(1)).unwrap();
+        sequencer.push(&mut b, StepDuration::Ticks(1)).unwrap();
+
+        let mut buffer = [RGB8::default(); 2];
+        sequencer.update(&mut buffer).unwrap();
+        sequencer.update(&mut buffer).unwrap();
+        assert_eq!(buffer, [RGB8::new(255, 255, 255); 2]);
+    }
+
+    #[test]
+    fn test_reset_restarts_from_first_step() {
+        let mut a = solid(RED);
+        let mut b = solid(GREEN);
+        let mut sequencer: EffectSequencer<2> = EffectSequencer::new();
+        sequencer.push(&mut a, StepDuration::Ticks(1)).unwrap();
+        sequencer.push(&mut b, StepDuration::Ticks(1)).unwrap();
+
+        let mut buffer = [RGB8::default(); 2];
+        for _ in 0..3 {
+            sequencer.update(&mut buffer).unwrap();
+        }
+        assert!(sequencer.is_finished());
+
+        sequencer.reset();
+        assert!(!sequencer.is_finished());
+        sequencer.current(&mut buffer).unwrap();
+        assert_eq!(buffer, [RED; 2]);
+    }
+
+    #[test]
+    fn test_set_phase_stays_on_current_step() {
+        let mut a = solid(RED);
+        let mut b = SpinnerEffect::new(4).unwrap().with_tail_length(0);
+        let mut sequencer: EffectSequencer<2> = EffectSequencer::new();
+        sequencer.push(&mut a, StepDuration::Ticks(1)).unwrap();
+        sequencer.push(&mut b, StepDuration::Ticks(10)).unwrap();
+
+        let mut buffer = [RGB8::default(); 4];
+        sequencer.update(&mut buffer).unwrap();
+        sequencer.update(&mut buffer).unwrap();
+        assert_eq!(sequencer.current_step(), 1);
+
+        let effect_ref: &mut dyn Effect = &mut sequencer;
+        effect_ref.set_phase(192);
+        effect_ref.current(&mut buffer).unwrap();
+        assert_eq!(sequencer.current_step(), 1);
+        assert_eq!(buffer[3], RGB8::new(255, 255, 255));
+
+        // The step still ends after its ten ticks
+        for _ in 0..9 {
+            sequencer.update(&mut buffer).unwrap();
+        }
+        assert!(sequencer.is_finished());
+    }
+
+    #[test]
+    fn test_set_phase_on_empty_sequence_is_ignored() {
+        let mut sequencer: EffectSequencer<1> = EffectSequencer::new();
+        sequencer.set_phase(100);
+        assert!(sequencer.is_finished());
+    }
+
+    #[test]
+    fn test_trait_object_usage() {
+        let mut a = solid(RED);
+        let mut sequencer: EffectSequencer<1> = EffectSequencer::new();
+        sequencer.push(&mut a, StepDuration::Ticks(1)).unwrap();
+
+        let effect_ref: &mut dyn Effect = &mut sequencer;
+        let mut buffer = [RGB8::default(); 2];
+        effect_ref.update(&mut buffer).unwrap();
+        assert_eq!(buffer, [RED; 2]);
+        assert!(effect_ref.is_finished());
+    }
+
+    #[test]
+    fn test_advance_skips_through_steps() {
+        let mut a = solid(RED);
+        let mut b = solid(GREEN);
+        let mut c = solid(BLUE);
+        let mut sequencer: EffectSequencer<3> = EffectSequencer::new().with_looping(true);
+        sequencer.push(&mut a, StepDuration::Ticks(2)).unwrap();
+        sequencer.push(&mut b, StepDuration::Ticks(3)).unwrap();
+        sequencer.push(&mut c, StepDuration::Ticks(100)).unwrap();
+
+        // 2 + 3 + 100 ticks per loop; 2 loops plus 4 ticks lands on the second step
+        sequencer.advance(2 * 105 + 4).unwrap();
+        assert_eq!(sequencer.current_step(), 1);
+
+        let mut buffer = [RGB8::default(); 2];
+        sequencer.current(&mut buffer).unwrap();
+        assert_eq!(buffer, [GREEN; 2]);
+    }
+
+    #[test]
+    fn test_advance_waits_for_one_shot_steps() {
+        let mut wipe = WipeTransitionEffect::new(2)
+            .unwrap()
+            .with_speed(128)
+            .unwrap();
+        let mut b = solid(GREEN);
+        let mut sequencer: EffectSequencer<2> = EffectSequencer::new();
+        sequencer
+            .push(&mut wipe, StepDuration::UntilFinished)
+            .unwrap();
+        sequencer.push(&mut b, StepDuration::Ticks(1)).unwrap();
+
+        sequencer.advance(1).unwrap();
+        assert_eq!(sequencer.current_step(), 0);
+        sequencer.advance(1).unwrap();
+        assert_eq!(sequencer.current_step(), 1);
+        sequencer.advance(10).unwrap();
+        assert!(sequencer.is_finished());
+    }
+}
@@ -1,10 +1,12 @@
 //! Rotating dot with a fading tail effect for LED rings.
 //!
 //! A single bright LED rotates around the ring with a fading tail behind it.
+//! An optional leading tail in front of the head turns the spinner into a
+//! comet with a halo.
 
 use crate::effect::{
-    advance_position, validate_buffer, validate_num_leds, validate_speed, Direction, Effect,
-    EffectError,
+    advance_position_fine, validate_buffer, validate_fine_speed, validate_num_leds, validate_speed,
+    Direction, Effect, EffectError,
 };
 use crate::util::scale_brightness;
 use rgb::RGB8;
@@ -13,6 +15,7 @@ use rgb::RGB8;
 ///
 /// A bright head LED rotates around the ring, followed by a tail of LEDs
 /// with linearly decreasing brightness.
+/// A separate leading tail can fade out ahead of the head as well.
 ///
 /// # Example
 ///
@@ -31,9 +34,12 @@ use rgb::RGB8;
 pub struct SpinnerEffect {
     num_leds: usize,
     color: RGB8,
-    position: u8,
-    speed: u8,
+    /// Head position in 1/256 LED steps (Q8.8).
+    position: u32,
+    /// Position increment per update in 1/256 LED steps (Q8.8).
+    speed: u16,
     tail_length: u8,
+    leading_tail_length: u8,
     direction: Direction,
 }
 
@@ -43,13 +49,15 @@ impl SpinnerEffect {
     /// # Errors
     ///
     /// Returns `EffectError::ZeroLeds` if `num_leds` is 0.
-    /// Returns `EffectError::TooManyLeds` if `num_leds` exceeds `MAX_LEDS`.
+    /// Returns `EffectError::TooManyLeds` if `num_leds` exceeds `MAX_LEDS`
+    /// (`MAX_LEDS_ALLOC` with the `alloc` feature).
     ///
     /// # Default Configuration
     ///
     /// - Color: white (255, 255, 255)
     /// - Speed: 1
     /// - Tail length: 2
+    /// - Leading tail length: 0
     /// - Direction: Clockwise
     pub fn new(num_leds: usize) -> Result<Self, EffectError> {
         validate_num_leds(num_leds)?;
@@ -58,8 +66,9 @@ impl SpinnerEffect {
             num_leds,
             color: RGB8::new(255, 255, 255),
             position: 0,
-            speed: 1,
+            speed: 256,
             tail_length: 2,
+            leading_tail_length: 0,
             direction: Direction::Clockwise,
         })
     }
@@ -76,23 +85,121 @@ impl SpinnerEffect {
     ///
     /// Returns `EffectError::ZeroStep` if `speed` is 0.
     pub fn with_speed(mut self, speed: u8) -> Result<Self, EffectError> {
-        validate_speed(speed)?;
-        self.speed = speed;
+        self.set_speed(speed)?;
+        Ok(self)
+    }
+
+    /// Sets the animation speed in 1/256 LED steps per update.
+    ///
+    /// A value of 256 equals `with_speed(1)`; smaller values make the head
+    /// move to the next LED only every few updates.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::ZeroStep` if `speed` is 0.
+    pub fn with_fine_speed(mut self, speed: u16) -> Result<Self, EffectError> {
+        self.set_fine_speed(speed)?;
         Ok(self)
     }
 
     /// Sets the number of LEDs in the fading tail behind the head.
+    ///
+    /// A tail longer than the ring wraps around onto itself; the head is
+    /// always drawn last, so it stays at full brightness.
     pub fn with_tail_length(mut self, tail_length: u8) -> Self {
         self.tail_length = tail_length;
         self
     }
 
+    /// Sets the number of LEDs in the fading tail ahead of the head.
+    ///
+    /// The leading tail is configured independently of the trailing tail;
+    /// set both to the same length for a symmetric halo.
+    pub fn with_leading_tail_length(mut self, leading_tail_length: u8) -> Self {
+        self.leading_tail_length = leading_tail_length;
+        self
+    }
+
     /// Sets the rotation direction.
     pub fn with_direction(mut self, direction: Direction) -> Self {
         self.direction = direction;
         self
     }
 
+    /// Changes the spinner color.
+    pub fn set_color(&mut self, color: RGB8) {
+        self.color = color;
+    }
+
+    /// Changes the animation speed without restarting the animation.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::ZeroStep` if `speed` is 0.
+    pub fn set_speed(&mut self, speed: u8) -> Result<(), EffectError> {
+        validate_speed(speed)?;
+        self.speed = (speed as u16) << 8;
+        Ok(())
+    }
+
+    /// Changes the animation speed in 1/256 LED steps without restarting the animation.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::ZeroStep` if `speed` is 0.
+    pub fn set_fine_speed(&mut self, speed: u16) -> Result<(), EffectError> {
+        validate_fine_speed(speed)?;
+        self.speed = speed;
+        Ok(())
+    }
+
+    /// Changes the trailing tail length.
+    pub fn set_tail_length(&mut self, tail_length: u8) {
+        self.tail_length = tail_length;
+    }
+
+    /// Changes the leading tail length.
+    pub fn set_leading_tail_length(&mut self, leading_tail_length: u8) {
+        self.leading_tail_length = leading_tail_length;
+    }
+
+    /// Changes the rotation direction; the head continues from its current position.
+    pub fn set_direction(&mut self, direction: Direction) {
+        self.direction = direction;
+    }
+
+    /// Returns the spinner color.
+    pub fn color(&self) -> RGB8 {
+        self.color
+    }
+
+    /// Returns the animation speed in whole LED steps per update.
+    ///
+    /// Fractional speeds are truncated; see [`fine_speed`](Self::fine_speed).
+    pub fn speed(&self) -> u8 {
+        (self.speed >> 8) as u8
+    }
+
+    /// Returns the animation speed in 1/256 LED steps per update.
+    pub fn fine_speed(&self) -> u16 {
+        self.speed
+    }
+
+    /// Returns the trailing tail length.
+    pub fn tail_length(&self) -> u8 {
+        self.tail_length
+    }
+
+    /// Returns the leading tail length.
+    pub fn leading_tail_length(&self) -> u8 {
+        self.leading_tail_length
+    }
+
+    /// Returns the rotation direction.
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
     /// Returns the number of LEDs this effect is configured for.
     pub fn num_leds(&self) -> usize {
         self.num_leds
@@ -103,21 +210,29 @@ impl SpinnerEffect {
         validate_buffer(buffer, self.num_leds)?;
 
         let n = self.num_leds;
-        let head = self.position as usize % n;
+        let head = (self.position >> 8) as usize % n;
 
         // Clear all LEDs
         for led in buffer.iter_mut().take(n) {
             *led = RGB8::new(0, 0, 0);
         }
 
-        // Head at full brightness
-        buffer[head] = self.color;
+        // Leading tail ahead of the head, fading the same way
+        let total = self.leading_tail_length as usize + 1; // head + leading tail
+        for i in 1..=self.leading_tail_length as usize {
+            let lead_idx = match self.direction {
+                Direction::Clockwise => (head + i) % n,
+                Direction::CounterClockwise => (head + n - i % n) % n,
+            };
+            let brightness = (255 * (total - i) / total) as u8;
+            buffer[lead_idx] = scale_brightness(self.color, brightness);
+        }
 
         // Tail with linearly decreasing brightness
         let total = self.tail_length as usize + 1; // head + tail
         for i in 1..=self.tail_length as usize {
             let tail_idx = match self.direction {
-                Direction::Clockwise => (head + n - i) % n,
+                Direction::Clockwise => (head + n - i % n) % n,
                 Direction::CounterClockwise => (head + i) % n,
             };
             // Linear fade: tail LED 1 is brightest, last is dimmest
@@ -125,13 +240,23 @@ impl SpinnerEffect {
             buffer[tail_idx] = scale_brightness(self.color, brightness);
         }
 
+        // Head at full brightness (drawn last so tails never cover it)
+        buffer[head] = self.color;
+
         Ok(())
     }
 
     /// Fills the buffer with spinner state and advances the animation.
     pub fn update(&mut self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
         self.current(buffer)?;
-        self.position = advance_position(self.position, self.speed, self.num_leds, self.direction);
+        self.advance(1)
+    }
+
+    /// Advances the animation by `steps` updates without rendering.
+    pub fn advance(&mut self, steps: u32) -> Result<(), EffectError> {
+        let distance = self.speed as u64 * steps as u64;
+        self.position =
+            advance_position_fine(self.position, distance, self.num_leds, self.direction);
         Ok(())
     }
 
@@ -139,6 +264,14 @@ impl SpinnerEffect {
     pub fn reset(&mut self) {
         self.position = 0;
     }
+
+    /// Jumps to `phase` (0–255) of a full revolution in the configured direction.
+    ///
+    /// Phase 64 on a 12-LED ring puts the head at LED 3 clockwise.
+    pub fn set_phase(&mut self, phase: u8) {
+        let distance = phase as u64 * self.num_leds as u64;
+        self.position = advance_position_fine(0, distance, self.num_leds, self.direction);
+    }
 }
 
 impl Effect for SpinnerEffect {
@@ -153,6 +286,14 @@ impl Effect for SpinnerEffect {
     fn reset(&mut self) {
         self.reset();
     }
+
+    fn advance(&mut self, steps: u32) -> Result<(), EffectError> {
+        self.advance(steps)
+    }
+
+    fn set_phase(&mut self, phase: u8) {
+        self.set_phase(phase);
+    }
 }
 
 #[cfg(test)]
@@ -235,6 +376,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::needless_range_loop)]
     fn test_non_tail_leds_are_off() {
         let effect = SpinnerEffect::new(8)
             .unwrap()
@@ -250,6 +392,132 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_leading_tail_default_is_off() {
+        let effect = SpinnerEffect::new(8)
+            .unwrap()
+            .with_color(RGB8::new(255, 0, 0))
+            .with_tail_length(2);
+
+        let mut buffer = [RGB8::default(); 8];
+        effect.current(&mut buffer).unwrap();
+
+        assert_eq!(buffer[1], RGB8::new(0, 0, 0));
+    }
+
+    #[test]
+    fn test_leading_tail_fades_ahead_of_head() {
+        let effect = SpinnerEffect::new(8)
+            .unwrap()
+            .with_color(RGB8::new(255, 255, 255))
+            .with_tail_length(0)
+            .with_leading_tail_length(2);
+
+        let mut buffer = [RGB8::default(); 8];
+        effect.current(&mut buffer).unwrap();
+
+        // Head at 0, leading tail at 1, 2 (clockwise, ahead of head)
+        assert_eq!(buffer[0].r, 255);
+        assert!(
+            buffer[1].r > buffer[2].r,
+            "closer leading LED should be brighter"
+        );
+        assert!(buffer[2].r > 0);
+        assert_eq!(buffer[3], RGB8::new(0, 0, 0));
+        assert_eq!(buffer[7], RGB8::new(0, 0, 0));
+    }
+
+    #[test]
+    fn test_symmetric_tails_mirror_brightness() {
+        let effect = SpinnerEffect::new(12)
+            .unwrap()
+            .with_tail_length(3)
+            .with_leading_tail_length(3);
+
+        let mut buffer = [RGB8::default(); 12];
+        effect.current(&mut buffer).unwrap();
+
+        for i in 1..=3 {
+            assert_eq!(buffer[i], buffer[12 - i], "LED {} should mirror", i);
+        }
+    }
+
+    #[test]
+    fn test_leading_tail_counter_clockwise() {
+        let effect = SpinnerEffect::new(8)
+            .unwrap()
+            .with_tail_length(0)
+            .with_leading_tail_length(1)
+            .with_direction(Direction::CounterClockwise);
+
+        let mut buffer = [RGB8::default(); 8];
+        effect.current(&mut buffer).unwrap();
+
+        // Moving counter-clockwise, "ahead" is index 7
+        assert!(buffer[7].r > 0);
+        assert_eq!(buffer[1], RGB8::new(0, 0, 0));
+    }
+
+    #[test]
+    fn test_long_tails_keep_head_bright() {
+        let effect = SpinnerEffect::new(4)
+            .unwrap()
+            .with_color(RGB8::new(0, 255, 0))
+            .with_tail_length(10)
+            .with_leading_tail_length(10);
+
+        let mut buffer = [RGB8::default(); 4];
+        effect.current(&mut buffer).unwrap();
+
+        assert_eq!(buffer[0], RGB8::new(0, 255, 0));
+    }
+
+    #[test]
+    fn test_tail_longer_than_ring_does_not_panic() {
+        for direction in [Direction::Clockwise, Direction::CounterClockwise] {
+            let mut effect = SpinnerEffect::new(3)
+                .unwrap()
+                .with_tail_length(255)
+                .with_leading_tail_length(3)
+                .with_direction(direction);
+            let mut buffer = [RGB8::default(); 3];
+            for _ in 0..3 {
+                effect.update(&mut buffer).unwrap();
+                assert_eq!(
+                    buffer
+                        .iter()
+                        .filter(|&&led| led == RGB8::new(255, 255, 255))
+                        .count(),
+                    1
+                );
+                assert!(buffer.iter().all(|led| led.r > 0));
+            }
+        }
+    }
+
+    #[test]
+    fn test_head_is_drawn_over_wrapping_tail() {
+        // A tail of exactly `num_leds` ends on the head itself
+        for direction in [Direction::Clockwise, Direction::CounterClockwise] {
+            let mut effect = SpinnerEffect::new(5)
+                .unwrap()
+                .with_color(RGB8::new(200, 0, 0))
+                .with_tail_length(5)
+                .with_direction(direction);
+            effect.advance(2).unwrap();
+            let head = (effect.position >> 8) as usize;
+
+            let mut buffer = [RGB8::default(); 5];
+            effect.current(&mut buffer).unwrap();
+            assert_eq!(buffer[head], RGB8::new(200, 0, 0));
+            for (i, led) in buffer.iter().enumerate() {
+                if i != head {
+                    assert!(led.r < 200, "LED {} should be dimmer than the head", i);
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_clockwise_advances_position() {
         let mut effect = SpinnerEffect::new(8)
@@ -342,4 +610,118 @@ mod tests {
 
         assert_ne!(buf1, buf2, "spinner should advance between updates");
     }
+
+    #[test]
+    fn test_with_fine_speed_zero_returns_error() {
+        let result = SpinnerEffect::new(12).unwrap().with_fine_speed(0);
+        assert_eq!(result.unwrap_err(), EffectError::ZeroStep);
+    }
+
+    #[test]
+    fn test_fine_speed_moves_head_every_few_updates() {
+        let mut effect = SpinnerEffect::new(8)
+            .unwrap()
+            .with_tail_length(0)
+            .with_fine_speed(96)
+            .unwrap();
+        let mut buffer = [RGB8::default(); 8];
+
+        let mut heads = Vec::new();
+        for _ in 0..6 {
+            effect.update(&mut buffer).unwrap();
+            heads.push(buffer.iter().position(|&led| led != RGB8::default()));
+        }
+        // Positions 0, 96, 192, 288, 384, 480 (1/256 LED)
+        assert_eq!(
+            heads,
+            [Some(0), Some(0), Some(0), Some(1), Some(1), Some(1)]
+        );
+    }
+
+    #[test]
+    fn test_setters_preserve_position() {
+        let mut effect = SpinnerEffect::new(8).unwrap().with_tail_length(0);
+        let mut buffer = [RGB8::default(); 8];
+        for _ in 0..3 {
+            effect.update(&mut buffer).unwrap();
+        }
+
+        let red = RGB8::new(255, 0, 0);
+        effect.set_color(red);
+        effect.set_speed(2).unwrap();
+        effect.current(&mut buffer).unwrap();
+        assert_eq!(buffer[3], red, "head should stay where it was");
+        assert_eq!(effect.color(), red);
+        assert_eq!(effect.speed(), 2);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_alloc_spinner_wraps_on_long_strip() {
+        let mut effect = SpinnerEffect::new(1000)
+            .unwrap()
+            .with_tail_length(0)
+            .with_speed(250)
+            .unwrap();
+        let mut buffer = vec![RGB8::default(); 1000];
+        for _ in 0..5 {
+            effect.update(&mut buffer).unwrap();
+        }
+        effect.current(&mut buffer).unwrap();
+        // 5 × 250 = 1250, wrapped to 250
+        assert_ne!(buffer[250], RGB8::default());
+    }
+
+    #[test]
+    fn test_advance_matches_repeated_update() {
+        let mut updated = SpinnerEffect::new(12)
+            .unwrap()
+            .with_fine_speed(100)
+            .unwrap();
+        let mut advanced = updated.clone();
+        let mut buf_updated = [RGB8::default(); 12];
+        let mut buf_advanced = [RGB8::default(); 12];
+
+        for _ in 0..200 {
+            updated.update(&mut buf_updated).unwrap();
+        }
+        advanced.advance(200).unwrap();
+
+        updated.current(&mut buf_updated).unwrap();
+        advanced.current(&mut buf_advanced).unwrap();
+        assert_eq!(buf_updated, buf_advanced);
+    }
+
+    #[test]
+    fn test_golden_comet_frames() {
+        let mut spinner = SpinnerEffect::new(6)
+            .unwrap()
+            .with_color(RGB8::new(0, 200, 100))
+            .with_tail_length(3)
+            .with_leading_tail_length(1);
+        crate::golden::assert_golden(
+            &mut spinner,
+            "
+            # head, leading tail, dark, tail x3
+            00c864 006331 000000 003118 006331 00954a
+            00954a 00c864 006331 000000 003118 006331
+            006331 00954a 00c864 006331 000000 003118
+            003118 006331 00954a 00c864 006331 000000
+            ",
+        );
+    }
+
+    #[test]
+    fn test_set_phase_follows_direction() {
+        let mut effect = SpinnerEffect::new(12).unwrap().with_tail_length(0);
+        effect.set_phase(64);
+        let mut buffer = [RGB8::default(); 12];
+        effect.current(&mut buffer).unwrap();
+        assert_ne!(buffer[3], RGB8::default());
+
+        let mut effect = effect.with_direction(Direction::CounterClockwise);
+        effect.set_phase(64);
+        effect.current(&mut buffer).unwrap();
+        assert_ne!(buffer[9], RGB8::default());
+    }
 }
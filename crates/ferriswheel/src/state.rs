@@ -0,0 +1,81 @@
+//! Per-LED state storage for stateful effects.
+//!
+//! State for up to `N` LEDs lives inline in an `[T; N]` array. With the
+//! `alloc` feature, runtime-sized effects with more LEDs than that keep their
+//! state in a heap allocation of exactly `num_leds` entries, which lets them
+//! go beyond [`MAX_LEDS`](crate::MAX_LEDS). Fixed-size effects built with
+//! `new_fixed()` always stay inline.
+
+use core::ops::{Deref, DerefMut};
+
+#[cfg(feature = "alloc")]
+use alloc::{vec, vec::Vec};
+
+/// Storage for one value per LED.
+#[derive(Debug, Clone)]
+pub(crate) enum LedState<T, const N: usize> {
+    /// Up to `N` values in place.
+    Inline([T; N]),
+    /// More than `N` values on the heap.
+    #[cfg(feature = "alloc")]
+    Heap(Vec<T>),
+}
+
+impl<T: Copy, const N: usize> LedState<T, N> {
+    /// Creates storage for `num_leds` values set to `value`.
+    ///
+    /// Without `alloc`, `num_leds` must not exceed `N`; callers validate this.
+    /// With `alloc`, only counts above `N` are allocated.
+    pub(crate) fn new(num_leds: usize, value: T) -> Self {
+        #[cfg(feature = "alloc")]
+        if num_leds > N {
+            return Self::Heap(vec![value; num_leds]);
+        }
+        debug_assert!(num_leds <= N);
+        Self::Inline([value; N])
+    }
+}
+
+impl<T, const N: usize> Deref for LedState<T, N> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        match self {
+            Self::Inline(values) => values,
+            #[cfg(feature = "alloc")]
+            Self::Heap(values) => values,
+        }
+    }
+}
+
+impl<T, const N: usize> DerefMut for LedState<T, N> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        match self {
+            Self::Inline(values) => values,
+            #[cfg(feature = "alloc")]
+            Self::Heap(values) => values,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fitting_state_is_inline() {
+        let state: LedState<u8, 8> = LedState::new(8, 7);
+        assert!(matches!(state, LedState::Inline(_)));
+        assert_eq!(&state[..], &[7; 8]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_larger_state_is_on_heap() {
+        let mut state: LedState<u8, 8> = LedState::new(300, 1);
+        assert!(matches!(state, LedState::Heap(_)));
+        assert_eq!(state.len(), 300);
+        state[299] = 2;
+        assert_eq!(state[299], 2);
+    }
+}
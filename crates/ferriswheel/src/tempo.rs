@@ -0,0 +1,352 @@
+//! Speed changes over time for any effect.
+//!
+//! Effects advance by a fixed step per update. [`TempoEnvelope`] changes how
+//! many steps the wrapped effect advances per update along a keyframe list,
+//! e.g. to make a "connecting" spinner speed up the longer it waits.
+
+use crate::effect::{Effect, EffectError};
+use rgb::RGB8;
+
+/// Maximum number of keyframes in a [`TempoEnvelope`].
+pub const MAX_TEMPO_KEYFRAMES: usize = 8;
+
+/// Tempo of the wrapped effect at its original speed, in percent.
+const NORMAL_TEMPO: u16 = 100;
+
+/// Returns the whole steps to advance for one update at `tempo` percent.
+///
+/// Fractional steps are kept in `carry` for the following updates.
+pub(crate) fn tempo_steps(carry: &mut u32, tempo: u16) -> u32 {
+    let total = *carry + tempo as u32;
+    *carry = total % NORMAL_TEMPO as u32;
+    total / NORMAL_TEMPO as u32
+}
+
+/// Wraps an effect and modulates its speed along a keyframe list.
+///
+/// Each keyframe is an `(update, tempo)` pair: after `update` updates since the
+/// envelope started, the wrapped effect runs at `tempo` percent of its original
+/// speed. Between keyframes the tempo is interpolated linearly; before the
+/// first keyframe it holds the first tempo and after the last one the last
+/// tempo, unless [`with_looping`](TempoEnvelope::with_looping) repeats the envelope.
+///
+/// A tempo of 100 advances the wrapped effect once per update, 250 two or three
+/// times, and 50 every other update. Fractional steps are carried over, so the
+/// average speed is exact.
+///
+/// # Example
+///
+/// ```
+/// use ferriswheel::{Effect, SpinnerEffect, TempoEnvelope};
+/// use rgb::RGB8;
+///
+/// // Start at half speed and reach triple speed after 200 frames
+/// let spinner = SpinnerEffect::new(12).unwrap();
+/// let mut connecting = TempoEnvelope::new(spinner, &[(0, 50), (200, 300)]).unwrap();
+/// let mut buffer = [RGB8::default(); 12];
+///
+/// connecting.update(&mut buffer).unwrap();
+///
+/// // Nearly done: slow down again
+/// connecting.set_keyframes(&[(0, 300), (50, 80)]).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct TempoEnvelope<E> {
+    effect: E,
+    keyframes: [(u32, u16); MAX_TEMPO_KEYFRAMES],
+    count: usize,
+    looping: bool,
+    elapsed: u32,
+    carry: u32,
+}
+
+impl<E: Effect> TempoEnvelope<E> {
+    /// Wraps `effect` and starts the envelope described by `keyframes`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::InvalidKeyframes` if `keyframes` is empty, has more
+    /// than [`MAX_TEMPO_KEYFRAMES`] entries, or its updates are not in ascending order.
+    ///
+    /// # Default Configuration
+    ///
+    /// - Looping: off (the last tempo is held)
+    pub fn new(effect: E, keyframes: &[(u32, u16)]) -> Result<Self, EffectError> {
+        let mut envelope = Self {
+            effect,
+            keyframes: [(0, NORMAL_TEMPO); MAX_TEMPO_KEYFRAMES],
+            count: 0,
+            looping: false,
+            elapsed: 0,
+            carry: 0,
+        };
+        envelope.set_keyframes(keyframes)?;
+        Ok(envelope)
+    }
+
+    /// Repeats the envelope after the last keyframe instead of holding its tempo.
+    pub fn with_looping(mut self, looping: bool) -> Self {
+        self.looping = looping;
+        self
+    }
+
+    /// Replaces the keyframes and restarts the envelope.
+    ///
+    /// The wrapped effect keeps its animation state.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::InvalidKeyframes` if `keyframes` is empty, has more
+    /// than [`MAX_TEMPO_KEYFRAMES`] entries, or its updates are not in ascending order.
+    pub fn set_keyframes(&mut self, keyframes: &[(u32, u16)]) -> Result<(), EffectError> {
+        if keyframes.is_empty()
+            || keyframes.len() > MAX_TEMPO_KEYFRAMES
+            || keyframes.windows(2).any(|pair| pair[0].0 > pair[1].0)
+        {
+            return Err(EffectError::InvalidKeyframes);
+        }
+        self.keyframes[..keyframes.len()].copy_from_slice(keyframes);
+        self.count = keyframes.len();
+        self.restart();
+        Ok(())
+    }
+
+    /// Restarts the envelope from its first keyframe.
+    pub fn restart(&mut self) {
+        self.elapsed = 0;
+        self.carry = 0;
+    }
+
+    /// Returns the number of updates since the envelope started.
+    pub fn elapsed(&self) -> u32 {
+        self.elapsed
+    }
+
+    /// Returns the tempo of the next update, in percent of the original speed.
+    pub fn tempo(&self) -> u16 {
+        let keyframes = &self.keyframes[..self.count];
+        let last = keyframes[self.count - 1];
+        let t = if self.looping && last.0 > 0 {
+            self.elapsed % last.0
+        } else {
+            self.elapsed
+        };
+
+        match keyframes.iter().rposition(|&(at, _)| at <= t) {
+            None => keyframes[0].1,
+            Some(i) if i == self.count - 1 => last.1,
+            Some(i) => {
+                let (from_at, from) = keyframes[i];
+                let (to_at, to) = keyframes[i + 1];
+                let span = (to_at - from_at) as i64;
+                let delta = to as i64 - from as i64;
+                (from as i64 + delta * (t - from_at) as i64 / span) as u16
+            }
+        }
+    }
+
+    /// Returns a reference to the wrapped effect.
+    pub fn inner(&self) -> &E {
+        &self.effect
+    }
+
+    /// Returns a mutable reference to the wrapped effect.
+    pub fn inner_mut(&mut self) -> &mut E {
+        &mut self.effect
+    }
+
+    /// Consumes the wrapper and returns the wrapped effect.
+    pub fn into_inner(self) -> E {
+        self.effect
+    }
+
+    /// Moves the envelope on by one update and returns the wrapped effect's steps.
+    fn step(&mut self) -> u32 {
+        let tempo = self.tempo();
+        let steps = tempo_steps(&mut self.carry, tempo);
+        self.elapsed = self.elapsed.saturating_add(1);
+        steps
+    }
+
+    /// Fills the buffer without advancing.
+    pub fn current(&self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        self.effect.current(buffer)
+    }
+
+    /// Fills the buffer and advances the wrapped effect at the current tempo.
+    pub fn update(&mut self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        match self.step() {
+            0 => self.effect.current(buffer),
+            steps => {
+                self.effect.update(buffer)?;
+                self.effect.advance(steps - 1)
+            }
+        }
+    }
+
+    /// Advances the envelope and the wrapped effect by `steps` updates without rendering.
+    pub fn advance(&mut self, steps: u32) -> Result<(), EffectError> {
+        let mut total: u32 = 0;
+        for _ in 0..steps {
+            total = total.saturating_add(self.step());
+        }
+        self.effect.advance(total)
+    }
+
+    /// Resets the wrapped effect and restarts the envelope.
+    pub fn reset(&mut self) {
+        self.effect.reset();
+        self.restart();
+    }
+}
+
+impl<E: Effect> Effect for TempoEnvelope<E> {
+    fn update(&mut self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        self.update(buffer)
+    }
+
+    fn current(&self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        self.current(buffer)
+    }
+
+    fn reset(&mut self) {
+        self.reset();
+    }
+
+    fn advance(&mut self, steps: u32) -> Result<(), EffectError> {
+        self.advance(steps)
+    }
+
+    fn is_finished(&self) -> bool {
+        self.effect.is_finished()
+    }
+
+    fn set_phase(&mut self, phase: u8) {
+        self.effect.set_phase(phase);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SpinnerEffect;
+
+    fn spinner() -> SpinnerEffect {
+        SpinnerEffect::new(64).unwrap().with_tail_length(0)
+    }
+
+    fn head(buffer: &[RGB8]) -> usize {
+        buffer
+            .iter()
+            .position(|&led| led != RGB8::default())
+            .unwrap()
+    }
+
+    fn heads(envelope: &mut TempoEnvelope<SpinnerEffect>, updates: usize) -> Vec<usize> {
+        let mut buffer = [RGB8::default(); 64];
+        (0..updates)
+            .map(|_| {
+                envelope.update(&mut buffer).unwrap();
+                head(&buffer)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_normal_tempo_matches_plain_effect() {
+        let mut envelope = TempoEnvelope::new(spinner(), &[(0, 100)]).unwrap();
+        assert_eq!(heads(&mut envelope, 4), [0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_half_tempo_holds_frames() {
+        let mut envelope = TempoEnvelope::new(spinner(), &[(0, 50)]).unwrap();
+        assert_eq!(heads(&mut envelope, 6), [0, 0, 1, 1, 2, 2]);
+    }
+
+    #[test]
+    fn test_double_tempo_skips_frames() {
+        let mut envelope = TempoEnvelope::new(spinner(), &[(0, 200)]).unwrap();
+        assert_eq!(heads(&mut envelope, 4), [0, 2, 4, 6]);
+    }
+
+    #[test]
+    fn test_tempo_interpolates_between_keyframes() {
+        let mut envelope = TempoEnvelope::new(spinner(), &[(10, 100), (20, 300)]).unwrap();
+        assert_eq!(envelope.tempo(), 100);
+        envelope.advance(15).unwrap();
+        assert_eq!(envelope.tempo(), 200);
+        envelope.advance(5).unwrap();
+        assert_eq!(envelope.tempo(), 300);
+        envelope.advance(100).unwrap();
+        assert_eq!(envelope.tempo(), 300);
+    }
+
+    #[test]
+    fn test_looping_repeats_envelope() {
+        let mut envelope = TempoEnvelope::new(spinner(), &[(0, 100), (10, 200)])
+            .unwrap()
+            .with_looping(true);
+        envelope.advance(5).unwrap();
+        let mid = envelope.tempo();
+        envelope.advance(10).unwrap();
+        assert_eq!(envelope.tempo(), mid);
+        assert_eq!(envelope.elapsed(), 15);
+    }
+
+    #[test]
+    fn test_advance_matches_update() {
+        let keyframes = [(0, 30), (12, 170), (20, 90)];
+        let mut updated = TempoEnvelope::new(spinner(), &keyframes).unwrap();
+        let mut advanced = updated.clone();
+        let mut buffer = [RGB8::default(); 64];
+        for _ in 0..25 {
+            updated.update(&mut buffer).unwrap();
+        }
+        advanced.advance(25).unwrap();
+
+        let mut expected = [RGB8::default(); 64];
+        updated.current(&mut expected).unwrap();
+        advanced.current(&mut buffer).unwrap();
+        assert_eq!(buffer, expected);
+    }
+
+    #[test]
+    fn test_set_keyframes_restarts_envelope() {
+        let mut envelope = TempoEnvelope::new(spinner(), &[(0, 100)]).unwrap();
+        heads(&mut envelope, 3);
+        envelope.set_keyframes(&[(0, 0)]).unwrap();
+        assert_eq!(envelope.elapsed(), 0);
+        // The spinner keeps its position and stops
+        assert_eq!(heads(&mut envelope, 2), [3, 3]);
+    }
+
+    #[test]
+    fn test_invalid_keyframes_return_error() {
+        for keyframes in [&[][..], &[(5, 100), (2, 100)][..], &[(0, 100); 9][..]] {
+            assert_eq!(
+                TempoEnvelope::new(spinner(), keyframes).unwrap_err(),
+                EffectError::InvalidKeyframes
+            );
+        }
+    }
+
+    #[test]
+    fn test_reset_restarts_effect_and_envelope() {
+        let mut envelope = TempoEnvelope::new(spinner(), &[(0, 100), (4, 400)]).unwrap();
+        heads(&mut envelope, 6);
+        envelope.reset();
+        assert_eq!(envelope.elapsed(), 0);
+        assert_eq!(heads(&mut envelope, 2), [0, 1]);
+    }
+
+    #[test]
+    fn test_trait_object_update() {
+        let mut envelope = TempoEnvelope::new(spinner(), &[(0, 300)]).unwrap();
+        let effect_ref: &mut dyn Effect = &mut envelope;
+        let mut buffer = [RGB8::default(); 64];
+        effect_ref.update(&mut buffer).unwrap();
+        effect_ref.update(&mut buffer).unwrap();
+        assert_eq!(head(&buffer), 3);
+    }
+}
@@ -0,0 +1,311 @@
+//! Time-based driving of tick-based effects.
+//!
+//! Effects advance by a fixed step per `update()` call, so their speed depends on
+//! the frame rate. [`TimedEffect`] decouples the two: it accumulates elapsed
+//! milliseconds and advances the wrapped effect once per configured tick interval.
+
+use crate::effect::{Effect, EffectError};
+use rgb::RGB8;
+
+/// Wraps an effect so its speed is defined in milliseconds.
+///
+/// Call [`update_with_dt`](TimedEffect::update_with_dt) with the time elapsed
+/// since the previous frame. The wrapped effect advances once for every
+/// `tick_ms` that has passed, no matter how often frames are rendered.
+/// When frames come faster than `tick_ms`, the current frame is rendered again
+/// without advancing.
+///
+/// After a long stall (e.g. a blocking Wi-Fi operation) the number of ticks
+/// caught up in a single call is limited by
+/// [`with_max_catch_up`](TimedEffect::with_max_catch_up); the rest is dropped.
+///
+/// # Example
+///
+/// ```
+/// use ferriswheel::{PulseEffect, TimedEffect};
+/// use rgb::RGB8;
+///
+/// let pulse = PulseEffect::new(12).unwrap();
+/// // Advance the pulse every 10 ms, regardless of the frame rate
+/// let mut timed = TimedEffect::new(pulse, 10).unwrap();
+/// let mut buffer = [RGB8::default(); 12];
+///
+/// timed.update_with_dt(&mut buffer, 16).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct TimedEffect<E> {
+    effect: E,
+    tick_ms: u32,
+    elapsed_ms: u32,
+    max_catch_up: u32,
+}
+
+impl<E: Effect> TimedEffect<E> {
+    /// Wraps `effect`, advancing it once every `tick_ms` milliseconds.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::ZeroStep` if `tick_ms` is 0.
+    ///
+    /// # Default Configuration
+    ///
+    /// - Max catch-up: 8 ticks per call
+    pub fn new(effect: E, tick_ms: u32) -> Result<Self, EffectError> {
+        if tick_ms == 0 {
+            return Err(EffectError::ZeroStep);
+        }
+
+        Ok(Self {
+            effect,
+            tick_ms,
+            elapsed_ms: 0,
+            max_catch_up: 8,
+        })
+    }
+
+    /// Sets the maximum number of ticks advanced in a single call.
+    ///
+    /// Values below 1 are clamped to 1.
+    pub fn with_max_catch_up(mut self, ticks: u32) -> Self {
+        self.max_catch_up = ticks.max(1);
+        self
+    }
+
+    /// Returns the tick interval in milliseconds.
+    pub fn tick_ms(&self) -> u32 {
+        self.tick_ms
+    }
+
+    /// Returns a reference to the wrapped effect.
+    pub fn inner(&self) -> &E {
+        &self.effect
+    }
+
+    /// Returns a mutable reference to the wrapped effect.
+    pub fn inner_mut(&mut self) -> &mut E {
+        &mut self.effect
+    }
+
+    /// Consumes the wrapper and returns the wrapped effect.
+    pub fn into_inner(self) -> E {
+        self.effect
+    }
+
+    /// Fills the buffer and advances the effect by the ticks elapsed in `dt_ms`.
+    pub fn update_with_dt(&mut self, buffer: &mut [RGB8], dt_ms: u32) -> Result<(), EffectError> {
+        self.elapsed_ms = self.elapsed_ms.saturating_add(dt_ms);
+        let ticks = (self.elapsed_ms / self.tick_ms).min(self.max_catch_up);
+        self.elapsed_ms %= self.tick_ms;
+
+        if ticks == 0 {
+            return self.effect.current(buffer);
+        }
+        for _ in 0..ticks {
+            self.effect.update(buffer)?;
+        }
+        Ok(())
+    }
+
+    /// Advances the effect by the ticks elapsed in `dt_ms` without rendering.
+    ///
+    /// Unlike [`update_with_dt`](Self::update_with_dt), the catch-up limit does
+    /// not apply: after a long pause the animation jumps to where it would be
+    /// had it kept running. Leftover time counts towards the next tick.
+    pub fn advance_with_dt(&mut self, dt_ms: u32) -> Result<(), EffectError> {
+        let total = self.elapsed_ms as u64 + dt_ms as u64;
+        let ticks = total / self.tick_ms as u64;
+        self.elapsed_ms = (total % self.tick_ms as u64) as u32;
+        self.effect.advance(ticks.min(u32::MAX as u64) as u32)
+    }
+
+    /// Fills the buffer without advancing.
+    pub fn current(&self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        self.effect.current(buffer)
+    }
+
+    /// Fills the buffer and advances the effect by exactly one tick.
+    pub fn update(&mut self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        self.effect.update(buffer)
+    }
+
+    /// Resets the wrapped effect and discards accumulated time.
+    pub fn reset(&mut self) {
+        self.elapsed_ms = 0;
+        self.effect.reset();
+    }
+}
+
+impl<E: Effect> Effect for TimedEffect<E> {
+    fn update(&mut self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        self.update(buffer)
+    }
+
+    fn current(&self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        self.current(buffer)
+    }
+
+    fn reset(&mut self) {
+        self.reset();
+    }
+
+    fn advance(&mut self, steps: u32) -> Result<(), EffectError> {
+        self.effect.advance(steps)
+    }
+
+    fn is_finished(&self) -> bool {
+        self.effect.is_finished()
+    }
+
+    fn set_phase(&mut self, phase: u8) {
+        self.effect.set_phase(phase);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SpinnerEffect;
+
+    fn spinner() -> SpinnerEffect {
+        SpinnerEffect::new(8).unwrap().with_tail_length(0)
+    }
+
+    fn head(buffer: &[RGB8]) -> usize {
+        buffer
+            .iter()
+            .position(|&led| led != RGB8::default())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_zero_tick_returns_error() {
+        assert_eq!(
+            TimedEffect::new(spinner(), 0).unwrap_err(),
+            EffectError::ZeroStep
+        );
+    }
+
+    #[test]
+    fn test_short_frames_accumulate() {
+        let mut timed = TimedEffect::new(spinner(), 10).unwrap();
+        let mut buffer = [RGB8::default(); 8];
+
+        // 4 ms frames: the spinner advances on the 3rd and 5th call (12 ms, 20 ms)
+        let mut heads = Vec::new();
+        for _ in 0..5 {
+            timed.update_with_dt(&mut buffer, 4).unwrap();
+            timed.current(&mut buffer).unwrap();
+            heads.push(head(&buffer));
+        }
+        assert_eq!(heads, [0, 0, 1, 1, 2]);
+    }
+
+    #[test]
+    fn test_long_frame_advances_several_ticks() {
+        let mut timed = TimedEffect::new(spinner(), 10).unwrap();
+        let mut buffer = [RGB8::default(); 8];
+
+        timed.update_with_dt(&mut buffer, 35).unwrap();
+        timed.current(&mut buffer).unwrap();
+        assert_eq!(head(&buffer), 3);
+
+        // The leftover 5 ms count towards the next tick
+        timed.update_with_dt(&mut buffer, 5).unwrap();
+        timed.current(&mut buffer).unwrap();
+        assert_eq!(head(&buffer), 4);
+    }
+
+    #[test]
+    fn test_catch_up_is_limited() {
+        let mut timed = TimedEffect::new(spinner(), 10)
+            .unwrap()
+            .with_max_catch_up(2);
+        let mut buffer = [RGB8::default(); 8];
+
+        timed.update_with_dt(&mut buffer, 1_000).unwrap();
+        timed.current(&mut buffer).unwrap();
+        assert_eq!(head(&buffer), 2);
+    }
+
+    #[test]
+    fn test_same_elapsed_time_same_frame() {
+        let mut fast = TimedEffect::new(spinner(), 10).unwrap();
+        let mut slow = TimedEffect::new(spinner(), 10).unwrap();
+        let mut buf_fast = [RGB8::default(); 8];
+        let mut buf_slow = [RGB8::default(); 8];
+
+        for _ in 0..12 {
+            fast.update_with_dt(&mut buf_fast, 5).unwrap();
+        }
+        for _ in 0..2 {
+            slow.update_with_dt(&mut buf_slow, 30).unwrap();
+        }
+        fast.current(&mut buf_fast).unwrap();
+        slow.current(&mut buf_slow).unwrap();
+        assert_eq!(buf_fast, buf_slow);
+    }
+
+    #[test]
+    fn test_buffer_too_small_returns_error() {
+        let mut timed = TimedEffect::new(spinner(), 10).unwrap();
+        let mut buffer = [RGB8::default(); 4];
+        assert_eq!(
+            timed.update_with_dt(&mut buffer, 1).unwrap_err(),
+            EffectError::BufferTooSmall {
+                required: 8,
+                actual: 4
+            }
+        );
+    }
+
+    #[test]
+    fn test_reset_discards_elapsed_time() {
+        let mut timed = TimedEffect::new(spinner(), 10).unwrap();
+        let mut buffer = [RGB8::default(); 8];
+        timed.update_with_dt(&mut buffer, 29).unwrap();
+
+        timed.reset();
+        timed.update_with_dt(&mut buffer, 9).unwrap();
+        timed.current(&mut buffer).unwrap();
+        assert_eq!(head(&buffer), 0);
+    }
+
+    #[test]
+    fn test_trait_object_update() {
+        let mut timed = TimedEffect::new(spinner(), 10).unwrap();
+        let effect_ref: &mut dyn Effect = &mut timed;
+        let mut buffer = [RGB8::default(); 8];
+
+        effect_ref.update(&mut buffer).unwrap();
+        effect_ref.current(&mut buffer).unwrap();
+        assert_eq!(head(&buffer), 1);
+    }
+
+    #[test]
+    fn test_advance_with_dt_ignores_catch_up_limit() {
+        let mut timed = TimedEffect::new(spinner(), 10)
+            .unwrap()
+            .with_max_catch_up(2);
+        let mut buffer = [RGB8::default(); 8];
+
+        // 10 ticks of sleep, plus 5 ms that carry over
+        timed.advance_with_dt(105).unwrap();
+        timed.current(&mut buffer).unwrap();
+        assert_eq!(head(&buffer), 2);
+
+        timed.update_with_dt(&mut buffer, 5).unwrap();
+        timed.current(&mut buffer).unwrap();
+        assert_eq!(head(&buffer), 3);
+    }
+
+    #[test]
+    fn test_trait_advance_forwards_to_inner() {
+        let mut timed = TimedEffect::new(spinner(), 10).unwrap();
+        let effect_ref: &mut dyn Effect = &mut timed;
+        effect_ref.advance(3).unwrap();
+
+        let mut buffer = [RGB8::default(); 8];
+        effect_ref.current(&mut buffer).unwrap();
+        assert_eq!(head(&buffer), 3);
+    }
+}
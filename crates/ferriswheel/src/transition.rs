@@ -0,0 +1,511 @@
+//! One-shot transition between two frames.
+//!
+//! The transition reveals a target frame over a source frame, either LED by LED
+//! around the ring (wipe) or by blending all LEDs at once (crossfade).
+//! It is the building block for clean scene changes on status rings.
+
+use crate::easing::Easing;
+use crate::effect::{
+    validate_buffer, validate_num_leds, validate_speed, Direction, Effect, EffectError, MAX_LEDS,
+};
+use crate::state::LedState;
+use crate::util::lerp_color;
+use rgb::RGB8;
+
+/// How [`WipeTransitionEffect`] moves from one frame to the other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum TransitionMode {
+    /// Reveal the target frame LED by LED around the ring.
+    #[default]
+    Wipe,
+    /// Blend every LED from the source to the target color at the same time.
+    Crossfade,
+}
+
+/// A one-shot effect that transitions from one frame to another.
+///
+/// Both frames are copied into the effect with
+/// [`set_frames`](WipeTransitionEffect::set_frames).
+/// Each `update()` advances the transition by the configured speed until
+/// [`is_finished`](WipeTransitionEffect::is_finished) returns `true`;
+/// after that the target frame is rendered unchanged.
+///
+/// Both frames are stored in arrays of `N` colors. The default `N` is
+/// [`MAX_LEDS`]; use [`new_fixed`](WipeTransitionEffect::new_fixed) to size
+/// them exactly to the ring.
+///
+/// # Example
+///
+/// ```
+/// use ferriswheel::{Effect, WipeTransitionEffect};
+/// use rgb::RGB8;
+///
+/// let from = [RGB8::new(255, 0, 0); 12];
+/// let to = [RGB8::new(0, 0, 255); 12];
+///
+/// let mut wipe = WipeTransitionEffect::new(12).unwrap().with_speed(16).unwrap();
+/// wipe.set_frames(&from, &to).unwrap();
+///
+/// let mut buffer = [RGB8::default(); 12];
+/// while !wipe.is_finished() {
+///     wipe.update(&mut buffer).unwrap();
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct WipeTransitionEffect<const N: usize = MAX_LEDS> {
+    num_leds: usize,
+    from: LedState<RGB8, N>,
+    to: LedState<RGB8, N>,
+    mode: TransitionMode,
+    direction: Direction,
+    speed: u8,
+    progress: u8,
+    easing: Easing,
+}
+
+impl WipeTransitionEffect {
+    /// Creates a new transition for the specified number of LEDs.
+    ///
+    /// Both frames start out black until [`set_frames`](Self::set_frames) is called.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::ZeroLeds` if `num_leds` is 0.
+    /// Returns `EffectError::TooManyLeds` if `num_leds` exceeds `MAX_LEDS`
+    /// (`MAX_LEDS_ALLOC` with the `alloc` feature).
+    ///
+    /// # Default Configuration
+    ///
+    /// - Mode: [`TransitionMode::Wipe`]
+    /// - Direction: Clockwise
+    /// - Speed: 8 (the transition completes after 32 updates)
+    /// - Easing: linear
+    pub fn new(num_leds: usize) -> Result<Self, EffectError> {
+        validate_num_leds(num_leds)?;
+        Ok(Self::with_len(num_leds))
+    }
+}
+
+impl<const N: usize> WipeTransitionEffect<N> {
+    /// Creates a transition for exactly `N` LEDs.
+    ///
+    /// Both frames hold `N` colors, so small rings don't pay for
+    /// [`MAX_LEDS`] entries and `N` may exceed it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::ZeroLeds` if `N` is 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ferriswheel::WipeTransitionEffect;
+    ///
+    /// let wipe: WipeTransitionEffect<8> = WipeTransitionEffect::new_fixed().unwrap();
+    /// assert_eq!(wipe.num_leds(), 8);
+    /// ```
+    pub fn new_fixed() -> Result<Self, EffectError> {
+        if N == 0 {
+            return Err(EffectError::ZeroLeds);
+        }
+        Ok(Self::with_len(N))
+    }
+
+    fn with_len(num_leds: usize) -> Self {
+        Self {
+            num_leds,
+            from: LedState::new(num_leds, RGB8::default()),
+            to: LedState::new(num_leds, RGB8::default()),
+            mode: TransitionMode::Wipe,
+            direction: Direction::Clockwise,
+            speed: 8,
+            progress: 0,
+            easing: Easing::Linear,
+        }
+    }
+
+    /// Sets how the target frame is revealed.
+    pub fn with_mode(mut self, mode: TransitionMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets the direction the wipe travels around the ring.
+    ///
+    /// Ignored in [`TransitionMode::Crossfade`].
+    pub fn with_direction(mut self, direction: Direction) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Sets the transition speed (progress increment out of 255 per update).
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::ZeroStep` if `speed` is 0.
+    pub fn with_speed(mut self, speed: u8) -> Result<Self, EffectError> {
+        validate_speed(speed)?;
+        self.speed = speed;
+        Ok(self)
+    }
+
+    /// Sets the easing curve applied to the transition progress.
+    ///
+    /// The number of updates stays the same; only the pace within the transition changes.
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Copies the source and target frames and restarts the transition.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::BufferTooSmall` if either frame has fewer
+    /// elements than `num_leds`.
+    pub fn set_frames(&mut self, from: &[RGB8], to: &[RGB8]) -> Result<(), EffectError> {
+        validate_buffer(from, self.num_leds)?;
+        validate_buffer(to, self.num_leds)?;
+
+        self.from[..self.num_leds].copy_from_slice(&from[..self.num_leds]);
+        self.to[..self.num_leds].copy_from_slice(&to[..self.num_leds]);
+        self.progress = 0;
+        Ok(())
+    }
+
+    /// Returns the transition progress (0 = source frame, 255 = target frame).
+    pub fn progress(&self) -> u8 {
+        self.progress
+    }
+
+    /// Returns `true` once the target frame is fully revealed.
+    pub fn is_finished(&self) -> bool {
+        self.progress == u8::MAX
+    }
+
+    /// Returns the number of LEDs this effect is configured for.
+    pub fn num_leds(&self) -> usize {
+        self.num_leds
+    }
+
+    /// Fills the buffer with the current transition state without advancing.
+    pub fn current(&self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        validate_buffer(buffer, self.num_leds)?;
+
+        let n = self.num_leds;
+        buffer[..n].copy_from_slice(&self.to[..n]);
+        blend_transition(
+            &self.from[..n],
+            &mut buffer[..n],
+            self.mode,
+            self.direction,
+            self.easing.apply(self.progress),
+        );
+
+        Ok(())
+    }
+
+    /// Fills the buffer with the transition state and advances it.
+    pub fn update(&mut self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        self.current(buffer)?;
+        self.advance(1)
+    }
+
+    /// Advances the animation by `steps` updates without rendering.
+    pub fn advance(&mut self, steps: u32) -> Result<(), EffectError> {
+        let delta = (self.speed as u32)
+            .saturating_mul(steps)
+            .min(u8::MAX as u32) as u8;
+        self.progress = self.progress.saturating_add(delta);
+        Ok(())
+    }
+
+    /// Restarts the transition from the source frame.
+    pub fn reset(&mut self) {
+        self.progress = 0;
+    }
+}
+
+impl<const N: usize> Effect for WipeTransitionEffect<N> {
+    fn update(&mut self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        self.update(buffer)
+    }
+
+    fn current(&self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        self.current(buffer)
+    }
+
+    fn reset(&mut self) {
+        self.reset();
+    }
+
+    fn advance(&mut self, steps: u32) -> Result<(), EffectError> {
+        self.advance(steps)
+    }
+
+    fn is_finished(&self) -> bool {
+        self.is_finished()
+    }
+}
+
+/// Blends `from` into `buffer`, which holds the target frame, at `progress`
+/// (0 = source frame, 255 = target frame).
+///
+/// `from` must be at least as long as `buffer`.
+pub(crate) fn blend_transition(
+    from: &[RGB8],
+    buffer: &mut [RGB8],
+    mode: TransitionMode,
+    direction: Direction,
+    progress: u8,
+) {
+    let n = buffer.len();
+
+    match mode {
+        TransitionMode::Crossfade => {
+            for (led, &source) in buffer.iter_mut().zip(from) {
+                *led = lerp_color(source, *led, progress);
+            }
+        }
+        TransitionMode::Wipe => {
+            // Same sub-LED scaling as ProgressEffect: the LED at the wipe
+            // front is blended between both frames.
+            let fill_255 = progress as u32 * n as u32;
+            let revealed = (fill_255 / 255) as usize;
+            let fractional = (fill_255 % 255) as u8;
+
+            for (i, (led, &source)) in buffer.iter_mut().zip(from).enumerate() {
+                let step = match direction {
+                    Direction::Clockwise => i,
+                    Direction::CounterClockwise => (n - i) % n,
+                };
+                if step == revealed {
+                    *led = lerp_color(source, *led, fractional);
+                } else if step > revealed {
+                    *led = source;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: RGB8 = RGB8::new(255, 0, 0);
+    const BLUE: RGB8 = RGB8::new(0, 0, 255);
+
+    fn wipe(n: usize) -> WipeTransitionEffect {
+        let mut effect = WipeTransitionEffect::new(n).unwrap();
+        effect.set_frames(&[RED; 8][..n], &[BLUE; 8][..n]).unwrap();
+        effect
+    }
+
+    #[test]
+    fn test_new_with_zero_leds_returns_error() {
+        assert_eq!(
+            WipeTransitionEffect::new(0).unwrap_err(),
+            EffectError::ZeroLeds
+        );
+    }
+
+    #[test]
+    fn test_with_speed_zero_returns_error() {
+        let result = WipeTransitionEffect::new(8).unwrap().with_speed(0);
+        assert_eq!(result.unwrap_err(), EffectError::ZeroStep);
+    }
+
+    #[test]
+    fn test_set_frames_too_small_returns_error() {
+        let mut effect = WipeTransitionEffect::new(8).unwrap();
+        let small = [RGB8::default(); 4];
+        let full = [RGB8::default(); 8];
+        assert_eq!(
+            effect.set_frames(&small, &full).unwrap_err(),
+            EffectError::BufferTooSmall {
+                required: 8,
+                actual: 4
+            }
+        );
+        assert_eq!(
+            effect.set_frames(&full, &small).unwrap_err(),
+            EffectError::BufferTooSmall {
+                required: 8,
+                actual: 4
+            }
+        );
+    }
+
+    #[test]
+    fn test_starts_with_source_frame() {
+        let effect = wipe(8);
+        let mut buffer = [RGB8::default(); 8];
+        effect.current(&mut buffer).unwrap();
+        assert_eq!(buffer, [RED; 8]);
+        assert!(!effect.is_finished());
+    }
+
+    #[test]
+    fn test_wipe_reveals_led_by_led() {
+        let mut effect = wipe(8).with_speed(32).unwrap();
+        let mut buffer = [RGB8::default(); 8];
+
+        // Progress 96 → 3 LEDs revealed
+        for _ in 0..3 {
+            effect.update(&mut buffer).unwrap();
+        }
+        effect.current(&mut buffer).unwrap();
+        assert_eq!(&buffer[..3], &[BLUE; 3]);
+        assert_eq!(&buffer[4..], &[RED; 4]);
+    }
+
+    #[test]
+    fn test_wipe_counter_clockwise_starts_at_zero_then_wraps() {
+        let mut effect = wipe(8)
+            .with_speed(64)
+            .unwrap()
+            .with_direction(Direction::CounterClockwise);
+        let mut buffer = [RGB8::default(); 8];
+
+        effect.update(&mut buffer).unwrap();
+        effect.current(&mut buffer).unwrap();
+        // Progress 64 → 2 LEDs: index 0 and 7
+        assert_eq!(buffer[0], BLUE);
+        assert_eq!(buffer[7], BLUE);
+        assert_eq!(buffer[1], RED);
+    }
+
+    #[test]
+    fn test_crossfade_blends_all_leds() {
+        let mut effect = wipe(4).with_mode(TransitionMode::Crossfade);
+        let mut buffer = [RGB8::default(); 4];
+
+        for _ in 0..16 {
+            effect.update(&mut buffer).unwrap();
+        }
+        effect.current(&mut buffer).unwrap();
+        let expected = lerp_color(RED, BLUE, 128);
+        assert_eq!(buffer, [expected; 4]);
+    }
+
+    #[test]
+    fn test_finishes_on_target_frame() {
+        for mode in [TransitionMode::Wipe, TransitionMode::Crossfade] {
+            let mut effect = wipe(8).with_mode(mode).with_speed(100).unwrap();
+            let mut buffer = [RGB8::default(); 8];
+            for _ in 0..3 {
+                effect.update(&mut buffer).unwrap();
+            }
+            assert!(effect.is_finished());
+            effect.update(&mut buffer).unwrap();
+            assert_eq!(buffer, [BLUE; 8], "{:?} should end on target", mode);
+        }
+    }
+
+    #[test]
+    fn test_set_frames_restarts() {
+        let mut effect = wipe(8).with_speed(255).unwrap();
+        let mut buffer = [RGB8::default(); 8];
+        effect.update(&mut buffer).unwrap();
+        assert!(effect.is_finished());
+
+        effect.set_frames(&[BLUE; 8], &[RED; 8]).unwrap();
+        assert_eq!(effect.progress(), 0);
+    }
+
+    #[test]
+    fn test_reset_restores_initial_state() {
+        let mut effect = wipe(8);
+        let mut buffer = [RGB8::default(); 8];
+        for _ in 0..10 {
+            effect.update(&mut buffer).unwrap();
+        }
+        effect.reset();
+        effect.current(&mut buffer).unwrap();
+        assert_eq!(buffer, [RED; 8]);
+    }
+
+    #[test]
+    fn test_trait_object_is_finished() {
+        let mut effect = wipe(8).with_speed(255).unwrap();
+        let effect_ref: &mut dyn Effect = &mut effect;
+        assert!(!effect_ref.is_finished());
+
+        let mut buffer = [RGB8::default(); 8];
+        effect_ref.update(&mut buffer).unwrap();
+        assert!(effect_ref.is_finished());
+
+        effect_ref.reset();
+        assert!(!effect_ref.is_finished());
+    }
+
+    #[test]
+    fn test_trait_object_update() {
+        let mut effect = wipe(8).with_speed(64).unwrap();
+        let effect_ref: &mut dyn Effect = &mut effect;
+
+        let mut buf1 = [RGB8::default(); 8];
+        let mut buf2 = [RGB8::default(); 8];
+        effect_ref.update(&mut buf1).unwrap();
+        effect_ref.update(&mut buf2).unwrap();
+
+        assert_ne!(buf1, buf2, "transition should advance between updates");
+    }
+
+    #[test]
+    fn test_easing_changes_pace_not_duration() {
+        let mut effect = wipe(4)
+            .with_mode(TransitionMode::Crossfade)
+            .with_easing(Easing::CubicIn);
+        let mut buffer = [RGB8::default(); 4];
+
+        // Halfway through, a cubic ease-in is still close to the source frame
+        for _ in 0..16 {
+            effect.update(&mut buffer).unwrap();
+        }
+        effect.current(&mut buffer).unwrap();
+        assert!(buffer[0].b < lerp_color(RED, BLUE, 128).b);
+
+        for _ in 0..16 {
+            effect.update(&mut buffer).unwrap();
+        }
+        assert!(effect.is_finished());
+        effect.current(&mut buffer).unwrap();
+        assert_eq!(buffer, [BLUE; 4]);
+    }
+
+    #[test]
+    fn test_new_fixed_with_zero_leds_returns_error() {
+        let result: Result<WipeTransitionEffect<0>, _> = WipeTransitionEffect::new_fixed();
+        assert_eq!(result.unwrap_err(), EffectError::ZeroLeds);
+    }
+
+    #[test]
+    fn test_new_fixed_matches_runtime_sized() {
+        let mut fixed: WipeTransitionEffect<4> = WipeTransitionEffect::new_fixed().unwrap();
+        fixed.set_frames(&[RED; 4], &[BLUE; 4]).unwrap();
+        let mut sized = wipe(4);
+        #[cfg(not(feature = "alloc"))]
+        assert!(core::mem::size_of_val(&fixed) < core::mem::size_of_val(&sized));
+
+        let mut buf_fixed = [RGB8::default(); 4];
+        let mut buf_sized = [RGB8::default(); 4];
+        while !sized.is_finished() {
+            fixed.update(&mut buf_fixed).unwrap();
+            sized.update(&mut buf_sized).unwrap();
+            assert_eq!(buf_fixed, buf_sized);
+        }
+        assert!(fixed.is_finished());
+    }
+
+    #[test]
+    fn test_advance_saturates_at_target() {
+        let mut effect = wipe(4);
+        effect.advance(3).unwrap();
+        assert_eq!(effect.progress(), 24);
+        effect.advance(u32::MAX).unwrap();
+        assert!(effect.is_finished());
+    }
+}
@@ -0,0 +1,556 @@
+//! Random twinkling effect for LED rings.
+//!
+//! Random LEDs light up with a color drawn from a [`ColorPalette`] or a
+//! [`GradientPalette`], fade in,
+//! and fade out again. Every twinkle has its own fade speed, so lifetimes
+//! differ from pixel to pixel.
+
+use crate::effect::{
+    validate_buffer, validate_num_leds, validate_speed, Effect, EffectError, MAX_LEDS,
+};
+use crate::gradient::GradientPalette;
+use crate::palette::ColorPalette;
+use crate::rng::{Rng, XorShift32};
+use crate::state::LedState;
+use crate::util::scale_brightness;
+use rgb::RGB8;
+
+/// A twinkling effect where random LEDs fade in and out.
+///
+/// Each update, a new twinkle starts on a random idle LED with a probability
+/// set by [`with_density`](TwinkleEffect::with_density).
+/// Its color is picked at random from the palette's primary, secondary and accent
+/// colors, and its fade speed is picked at random between 1 and the configured speed.
+///
+/// Randomness comes from an [`Rng`]; the built-in [`XorShift32`] is used
+/// by default and is deterministic for a given seed.
+/// Use [`with_rng`](TwinkleEffect::with_rng) to plug in a hardware generator.
+///
+/// Per-LED state is stored in arrays of `N` entries. The default `N` is
+/// [`MAX_LEDS`]; use [`new_fixed`](TwinkleEffect::new_fixed) to size the
+/// state exactly to the ring.
+///
+/// # Example
+///
+/// ```
+/// use ferriswheel::{ColorPalette, Effect, TwinkleEffect};
+/// use rgb::RGB8;
+///
+/// let palette = ColorPalette::new(
+///     RGB8::new(255, 180, 0),
+///     RGB8::new(255, 255, 255),
+///     RGB8::new(0, 120, 255),
+/// );
+/// let mut twinkle = TwinkleEffect::new(12).unwrap()
+///     .with_palette(palette)
+///     .with_seed(0xC0FFEE);
+/// let mut buffer = [RGB8::default(); 12];
+///
+/// twinkle.update(&mut buffer).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct TwinkleEffect<R = XorShift32, const N: usize = MAX_LEDS> {
+    num_leds: usize,
+    palette: ColorPalette,
+    gradient: Option<GradientPalette>,
+    speed: u8,
+    density: u8,
+    /// Generator state restored by [`reset`](TwinkleEffect::reset).
+    initial_rng: R,
+    rng: R,
+    /// Position in each LED's fade-in/fade-out envelope.
+    phases: LedState<u8, N>,
+    /// Envelope increment per update; 0 marks an idle LED.
+    steps: LedState<u8, N>,
+    colors: LedState<RGB8, N>,
+}
+
+/// Seed used unless [`with_seed`](TwinkleEffect::with_seed) is called.
+const DEFAULT_SEED: u32 = 0x2545_F491;
+
+impl TwinkleEffect {
+    /// Creates a new twinkle effect for the specified number of LEDs.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::ZeroLeds` if `num_leds` is 0.
+    /// Returns `EffectError::TooManyLeds` if `num_leds` exceeds `MAX_LEDS`
+    /// (`MAX_LEDS_ALLOC` with the `alloc` feature).
+    ///
+    /// # Default Configuration
+    ///
+    /// - Palette: white (255, 255, 255)
+    /// - Speed: 8
+    /// - Density: 64 (a new twinkle roughly every fourth update)
+    pub fn new(num_leds: usize) -> Result<Self, EffectError> {
+        validate_num_leds(num_leds)?;
+        Ok(Self::with_len(num_leds))
+    }
+}
+
+impl<const N: usize> TwinkleEffect<XorShift32, N> {
+    /// Creates a twinkle effect for exactly `N` LEDs.
+    ///
+    /// The state arrays hold `N` entries, so small rings don't pay for
+    /// [`MAX_LEDS`] entries and `N` may exceed it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::ZeroLeds` if `N` is 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ferriswheel::{Effect, TwinkleEffect, XorShift32};
+    /// use rgb::RGB8;
+    ///
+    /// let mut twinkle: TwinkleEffect<XorShift32, 8> = TwinkleEffect::new_fixed().unwrap();
+    /// let mut buffer = [RGB8::default(); 8];
+    ///
+    /// twinkle.update(&mut buffer).unwrap();
+    /// ```
+    pub fn new_fixed() -> Result<Self, EffectError> {
+        if N == 0 {
+            return Err(EffectError::ZeroLeds);
+        }
+        Ok(Self::with_len(N))
+    }
+
+    fn with_len(num_leds: usize) -> Self {
+        Self {
+            num_leds,
+            palette: ColorPalette::mono(RGB8::new(255, 255, 255)),
+            gradient: None,
+            speed: 8,
+            density: 64,
+            initial_rng: XorShift32::new(DEFAULT_SEED),
+            rng: XorShift32::new(DEFAULT_SEED),
+            phases: LedState::new(num_leds, 0),
+            steps: LedState::new(num_leds, 0),
+            colors: LedState::new(num_leds, RGB8::default()),
+        }
+    }
+
+    /// Sets the seed of the built-in random generator and restarts the effect.
+    pub fn with_seed(self, seed: u32) -> Self {
+        self.with_rng(XorShift32::new(seed))
+    }
+}
+
+impl<R: Rng + Clone, const N: usize> TwinkleEffect<R, N> {
+    /// Replaces the random number source and restarts the effect.
+    ///
+    /// [`reset`](Self::reset) restores a clone of `rng` as given here.
+    pub fn with_rng<R2: Rng + Clone>(self, rng: R2) -> TwinkleEffect<R2, N> {
+        let mut effect = TwinkleEffect {
+            num_leds: self.num_leds,
+            palette: self.palette,
+            gradient: self.gradient,
+            speed: self.speed,
+            density: self.density,
+            initial_rng: rng.clone(),
+            rng,
+            phases: self.phases,
+            steps: self.steps,
+            colors: self.colors,
+        };
+        effect.reset();
+        effect
+    }
+
+    /// Sets the palette that twinkle colors are drawn from.
+    ///
+    /// Replaces a gradient set with [`with_gradient`](Self::with_gradient).
+    pub fn with_palette(mut self, palette: ColorPalette) -> Self {
+        self.palette = palette;
+        self.gradient = None;
+        self
+    }
+
+    /// Draws twinkle colors from a random position in `gradient` instead of the palette.
+    pub fn with_gradient(mut self, gradient: GradientPalette) -> Self {
+        self.gradient = Some(gradient);
+        self
+    }
+
+    /// Sets the maximum fade speed (envelope increment per update).
+    ///
+    /// Each twinkle picks its own speed between 1 and `speed`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EffectError::ZeroStep` if `speed` is 0.
+    pub fn with_speed(mut self, speed: u8) -> Result<Self, EffectError> {
+        validate_speed(speed)?;
+        self.speed = speed;
+        Ok(self)
+    }
+
+    /// Sets the chance (out of 256) that a new twinkle starts on each update.
+    pub fn with_density(mut self, density: u8) -> Self {
+        self.density = density;
+        self
+    }
+
+    /// Returns the number of LEDs this effect is configured for.
+    pub fn num_leds(&self) -> usize {
+        self.num_leds
+    }
+
+    /// Returns the number of LEDs currently twinkling.
+    pub fn active_count(&self) -> usize {
+        self.steps[..self.num_leds]
+            .iter()
+            .filter(|&&step| step > 0)
+            .count()
+    }
+
+    /// Maps an envelope phase to brightness: fade in, then fade out.
+    fn envelope(phase: u8) -> u8 {
+        if phase < 128 {
+            phase * 2
+        } else {
+            (255 - phase) * 2
+        }
+    }
+
+    /// Fills the buffer with the current twinkles without advancing.
+    pub fn current(&self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        validate_buffer(buffer, self.num_leds)?;
+
+        for (i, led) in buffer.iter_mut().take(self.num_leds).enumerate() {
+            *led = if self.steps[i] == 0 {
+                RGB8::default()
+            } else {
+                scale_brightness(self.colors[i], Self::envelope(self.phases[i]))
+            };
+        }
+
+        Ok(())
+    }
+
+    /// Fills the buffer with the current twinkles, then fades and spawns twinkles.
+    pub fn update(&mut self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        self.current(buffer)?;
+        self.tick();
+        Ok(())
+    }
+
+    /// Advances the animation by `steps` updates without rendering.
+    ///
+    /// Twinkles are random, so every skipped update is still simulated,
+    /// just without rendering.
+    pub fn advance(&mut self, steps: u32) -> Result<(), EffectError> {
+        for _ in 0..steps {
+            self.tick();
+        }
+        Ok(())
+    }
+
+    /// Fades running twinkles and maybe spawns a new one.
+    fn tick(&mut self) {
+        for i in 0..self.num_leds {
+            if self.steps[i] == 0 {
+                continue;
+            }
+            match self.phases[i].checked_add(self.steps[i]) {
+                Some(phase) => self.phases[i] = phase,
+                None => self.steps[i] = 0,
+            }
+        }
+
+        if self.rng.next_u8() < self.density {
+            let idx = (self.rng.next_u32() % self.num_leds as u32) as usize;
+            if self.steps[idx] == 0 {
+                let pick = self.rng.next_u8();
+                self.colors[idx] = match &self.gradient {
+                    Some(gradient) => gradient.color_at(pick),
+                    None => match pick % 3 {
+                        0 => self.palette.primary,
+                        1 => self.palette.secondary,
+                        _ => self.palette.accent,
+                    },
+                };
+                self.steps[idx] = 1 + self.rng.next_u8() % self.speed;
+                self.phases[idx] = 0;
+            }
+        }
+    }
+
+    /// Clears all twinkles and restarts the random sequence.
+    pub fn reset(&mut self) {
+        self.rng = self.initial_rng.clone();
+        self.phases.fill(0);
+        self.steps.fill(0);
+    }
+}
+
+impl<R: Rng + Clone, const N: usize> Effect for TwinkleEffect<R, N> {
+    fn update(&mut self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        self.update(buffer)
+    }
+
+    fn current(&self, buffer: &mut [RGB8]) -> Result<(), EffectError> {
+        self.current(buffer)
+    }
+
+    fn reset(&mut self) {
+        self.reset();
+    }
+
+    fn advance(&mut self, steps: u32) -> Result<(), EffectError> {
+        self.advance(steps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_with_zero_leds_returns_error() {
+        assert_eq!(TwinkleEffect::new(0).unwrap_err(), EffectError::ZeroLeds);
+    }
+
+    #[test]
+    fn test_with_speed_zero_returns_error() {
+        let result = TwinkleEffect::new(12).unwrap().with_speed(0);
+        assert_eq!(result.unwrap_err(), EffectError::ZeroStep);
+    }
+
+    #[test]
+    fn test_buffer_too_small_returns_error() {
+        let effect = TwinkleEffect::new(12).unwrap();
+        let mut buffer = [RGB8::default(); 8];
+        assert_eq!(
+            effect.current(&mut buffer).unwrap_err(),
+            EffectError::BufferTooSmall {
+                required: 12,
+                actual: 8
+            }
+        );
+    }
+
+    #[test]
+    fn test_starts_dark() {
+        let effect = TwinkleEffect::new(8).unwrap();
+        let mut buffer = [RGB8::new(9, 9, 9); 8];
+        effect.current(&mut buffer).unwrap();
+        assert_eq!(buffer, [RGB8::default(); 8]);
+    }
+
+    #[test]
+    fn test_zero_density_stays_dark() {
+        let mut effect = TwinkleEffect::new(8).unwrap().with_density(0);
+        let mut buffer = [RGB8::default(); 8];
+        for _ in 0..100 {
+            effect.update(&mut buffer).unwrap();
+        }
+        assert_eq!(buffer, [RGB8::default(); 8]);
+    }
+
+    #[test]
+    fn test_twinkles_use_palette_colors() {
+        let palette = ColorPalette::new(
+            RGB8::new(255, 0, 0),
+            RGB8::new(0, 255, 0),
+            RGB8::new(0, 0, 255),
+        );
+        let mut effect = TwinkleEffect::new(16)
+            .unwrap()
+            .with_palette(palette)
+            .with_density(255);
+
+        let mut buffer = [RGB8::default(); 16];
+        let mut lit = 0;
+        for _ in 0..200 {
+            effect.update(&mut buffer).unwrap();
+            for led in &buffer {
+                let channels = [led.r, led.g, led.b];
+                assert!(
+                    channels.iter().filter(|&&c| c > 0).count() <= 1,
+                    "{:?} is not a scaled palette color",
+                    led
+                );
+                if *led != RGB8::default() {
+                    lit += 1;
+                }
+            }
+        }
+        assert!(lit > 0, "some twinkles should have been visible");
+    }
+
+    #[test]
+    fn test_twinkle_fades_in_and_out() {
+        let mut effect = TwinkleEffect::new(1).unwrap().with_density(0);
+        effect.colors[0] = RGB8::new(255, 255, 255);
+        effect.steps[0] = 4;
+
+        let mut buffer = [RGB8::default(); 1];
+        let mut levels = Vec::new();
+        while effect.active_count() == 1 {
+            effect.update(&mut buffer).unwrap();
+            levels.push(buffer[0].r);
+        }
+
+        // 64 updates: 32 rising, 32 falling
+        assert_eq!(levels.len(), 64);
+        assert!(levels[..32].windows(2).all(|w| w[0] < w[1]));
+        assert!(levels[32..].windows(2).all(|w| w[0] > w[1]));
+    }
+
+    #[test]
+    fn test_same_seed_is_deterministic() {
+        let mut a = TwinkleEffect::new(12).unwrap().with_seed(7);
+        let mut b = TwinkleEffect::new(12).unwrap().with_seed(7);
+        let mut buf_a = [RGB8::default(); 12];
+        let mut buf_b = [RGB8::default(); 12];
+        for _ in 0..50 {
+            a.update(&mut buf_a).unwrap();
+            b.update(&mut buf_b).unwrap();
+            assert_eq!(buf_a, buf_b);
+        }
+    }
+
+    #[test]
+    fn test_reset_restores_initial_state() {
+        let mut effect = TwinkleEffect::new(12).unwrap().with_density(200);
+        let mut first = Vec::new();
+        let mut buffer = [RGB8::default(); 12];
+        for _ in 0..20 {
+            effect.update(&mut buffer).unwrap();
+            first.push(buffer);
+        }
+
+        effect.reset();
+        for frame in &first {
+            effect.update(&mut buffer).unwrap();
+            assert_eq!(&buffer, frame);
+        }
+    }
+
+    #[test]
+    fn test_trait_object_update() {
+        let mut effect = TwinkleEffect::new(8).unwrap().with_density(255);
+        let effect_ref: &mut dyn Effect = &mut effect;
+
+        let mut buffer = [RGB8::default(); 8];
+        for _ in 0..10 {
+            effect_ref.update(&mut buffer).unwrap();
+        }
+        assert_ne!(buffer, [RGB8::default(); 8]);
+    }
+
+    #[derive(Clone)]
+    struct Constant(u32);
+
+    impl Rng for Constant {
+        fn next_u32(&mut self) -> u32 {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_custom_rng() {
+        // next_u8() = 0 < density, LED 0, primary color, step 1
+        let palette = ColorPalette::mono(RGB8::new(0, 0, 255));
+        let mut effect = TwinkleEffect::new(4)
+            .unwrap()
+            .with_palette(palette)
+            .with_rng(Constant(0));
+
+        let mut buffer = [RGB8::default(); 4];
+        effect.update(&mut buffer).unwrap();
+        assert_eq!(effect.active_count(), 1);
+        effect.current(&mut buffer).unwrap();
+        assert_eq!(buffer[1..], [RGB8::default(); 3]);
+    }
+
+    #[test]
+    fn test_gradient_colors() {
+        let gradient = GradientPalette::linear(RGB8::new(255, 0, 0), RGB8::new(0, 0, 255));
+        let mut effect = TwinkleEffect::new(16)
+            .unwrap()
+            .with_gradient(gradient)
+            .with_density(255);
+
+        let mut buffer = [RGB8::default(); 16];
+        for _ in 0..200 {
+            effect.update(&mut buffer).unwrap();
+            for led in &buffer {
+                assert_eq!(led.g, 0, "{:?} is not on the red-blue gradient", led);
+            }
+        }
+        assert!(effect.colors[..16].iter().any(|c| c.r > 0 && c.b > 0));
+    }
+
+    #[test]
+    fn test_new_fixed_with_zero_leds_returns_error() {
+        let result: Result<TwinkleEffect<XorShift32, 0>, _> = TwinkleEffect::new_fixed();
+        assert_eq!(result.unwrap_err(), EffectError::ZeroLeds);
+    }
+
+    #[test]
+    fn test_new_fixed_matches_runtime_sized() {
+        let mut fixed: TwinkleEffect<XorShift32, 8> = TwinkleEffect::new_fixed().unwrap();
+        let mut sized = TwinkleEffect::new(8).unwrap();
+        assert_eq!(fixed.num_leds(), 8);
+        assert!(core::mem::size_of_val(&fixed) < core::mem::size_of_val(&sized));
+
+        let mut buf_fixed = [RGB8::default(); 8];
+        let mut buf_sized = [RGB8::default(); 8];
+        for _ in 0..50 {
+            fixed.update(&mut buf_fixed).unwrap();
+            sized.update(&mut buf_sized).unwrap();
+            assert_eq!(buf_fixed, buf_sized);
+        }
+    }
+
+    #[test]
+    fn test_new_fixed_keeps_state_inline() {
+        // Also with `alloc`: a fixed-size effect never touches the heap
+        let twinkle: TwinkleEffect<XorShift32, 300> = TwinkleEffect::new_fixed().unwrap();
+        assert!(matches!(twinkle.phases, LedState::Inline(_)));
+        assert!(matches!(twinkle.colors, LedState::Inline(_)));
+    }
+
+    #[test]
+    fn test_new_fixed_allows_more_than_max_leds() {
+        let mut twinkle: TwinkleEffect<XorShift32, 300> =
+            TwinkleEffect::new_fixed().unwrap().with_density(255);
+        let mut buffer = [RGB8::default(); 300];
+        for _ in 0..10 {
+            twinkle.update(&mut buffer).unwrap();
+        }
+        assert!(twinkle.active_count() > 0);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_alloc_allows_long_strips() {
+        let mut twinkle = TwinkleEffect::new(1000).unwrap().with_density(255);
+        let mut buffer = vec![RGB8::default(); 1000];
+        for _ in 0..10 {
+            twinkle.update(&mut buffer).unwrap();
+        }
+        assert!(twinkle.active_count() > 0);
+        assert!(TwinkleEffect::new(crate::MAX_LEDS_ALLOC + 1).is_err());
+    }
+
+    #[test]
+    fn test_advance_matches_repeated_update() {
+        let mut updated = TwinkleEffect::new(8).unwrap().with_density(200);
+        let mut advanced = updated.clone();
+        let mut buf_updated = [RGB8::default(); 8];
+        let mut buf_advanced = [RGB8::default(); 8];
+
+        for _ in 0..60 {
+            updated.update(&mut buf_updated).unwrap();
+        }
+        advanced.advance(60).unwrap();
+
+        updated.current(&mut buf_updated).unwrap();
+        advanced.current(&mut buf_advanced).unwrap();
+        assert_eq!(buf_updated, buf_advanced);
+    }
+}
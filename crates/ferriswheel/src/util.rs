@@ -2,8 +2,12 @@
 //!
 //! These helpers are used by multiple effects and are useful for custom effects too.
 
+use crate::easing::Easing;
 use rgb::RGB8;
 
+// Shared with the drivers; see `ws2812-pure`.
+pub use ws2812_pure::{add_color, gamma8};
+
 /// 256-entry sine lookup table.
 ///
 /// Maps a phase angle (0–255) to amplitude (0–255).
@@ -40,6 +44,23 @@ pub fn sine_wave(phase: u8) -> u8 {
     SINE_TABLE[phase as usize]
 }
 
+/// Returns a full-cycle sine value for the given phase.
+///
+/// Unlike [`sine_wave`], the curve spans the whole cycle: it rises from 0 at
+/// phase 0 to 255 around phase 128 and falls back to 0, so a pulse never
+/// sits dark for half its period.
+pub fn full_sine_wave(phase: u8) -> u8 {
+    Easing::SineInOut.apply(triangle_wave(phase))
+}
+
+/// Returns a triangle-wave value for the given phase.
+///
+/// Ramps linearly from 0 at phase 0 to 255 at phases 127 and 128, then back down.
+pub fn triangle_wave(phase: u8) -> u8 {
+    let half = if phase < 128 { phase } else { 255 - phase };
+    (half as u16 * 255 / 127) as u8
+}
+
 /// Scales a single color channel by a brightness factor (0–255).
 ///
 /// Uses integer math: `(channel * brightness) / 255`.
@@ -65,6 +86,56 @@ pub fn lerp_color(a: RGB8, b: RGB8, t: u8) -> RGB8 {
     )
 }
 
+/// Blends `overlay` into `existing` in place.
+///
+/// `amount` ranges from 0 (keep `existing`) to 255 (replace with `overlay`).
+/// This is the in-place counterpart of [`lerp_color`].
+pub fn nblend(existing: &mut RGB8, overlay: RGB8, amount: u8) {
+    *existing = lerp_color(*existing, overlay, amount);
+}
+
+/// Dims every color in `buffer` by `amount` (0 = unchanged, 255 = black).
+///
+/// Calling this on each frame before drawing leaves fading trails behind
+/// moving pixels.
+pub fn fade_to_black_by(buffer: &mut [RGB8], amount: u8) {
+    let keep = 255 - amount;
+    for pixel in buffer.iter_mut() {
+        *pixel = scale_brightness(*pixel, keep);
+    }
+}
+
+/// Blurs `buffer` by letting each LED bleed into its neighbors.
+///
+/// `amount` (0–255) is how much of each LED is spread out; half of it goes to
+/// each neighbor. Light that would leave the ends of the buffer is lost.
+pub fn blur1d(buffer: &mut [RGB8], amount: u8) {
+    let keep = 255 - amount;
+    let seep = amount >> 1;
+    let mut carry = RGB8::default();
+
+    for i in 0..buffer.len() {
+        let color = buffer[i];
+        let part = scale_brightness(color, seep);
+        let kept = scale_brightness(color, keep);
+
+        if i > 0 {
+            buffer[i - 1] = add_color(buffer[i - 1], part);
+        }
+        buffer[i] = add_color(kept, carry);
+        carry = part;
+    }
+}
+
+/// Gamma-corrects every color in `buffer` in place with [`gamma8`].
+///
+/// Uses the gamma 2.2 table of `ws2812-pure`, so effects and drivers agree
+/// on the curve. Apply this once to the finished frame, right before sending
+/// it to the LEDs.
+pub fn gamma_correct(buffer: &mut [RGB8]) {
+    ws2812_pure::gamma_correct_slice(buffer);
+}
+
 /// Fills all elements of `buffer` with the given color.
 pub fn fill_solid(buffer: &mut [RGB8], color: RGB8) {
     for pixel in buffer.iter_mut() {
@@ -201,4 +272,106 @@ mod tests {
         fill_solid(&mut buffer, RGB8::new(10, 20, 30));
         // Should not panic
     }
+
+    #[test]
+    fn test_gamma8_endpoints() {
+        assert_eq!(gamma8(0), 0);
+        assert_eq!(gamma8(255), 255);
+    }
+
+    #[test]
+    fn test_gamma8_monotonic_and_below_linear() {
+        for i in 0..255u8 {
+            assert!(gamma8(i) <= gamma8(i + 1));
+            assert!(gamma8(i) <= i);
+        }
+        assert_eq!(gamma8(128), 56);
+    }
+
+    #[test]
+    fn test_gamma_correct_applies_per_channel() {
+        let mut buffer = [RGB8::new(255, 128, 0), RGB8::new(0, 0, 255)];
+        gamma_correct(&mut buffer);
+        assert_eq!(buffer, [RGB8::new(255, 56, 0), RGB8::new(0, 0, 255)]);
+    }
+
+    #[test]
+    fn test_full_sine_wave_is_symmetric() {
+        assert_eq!(full_sine_wave(0), 0);
+        assert_eq!(full_sine_wave(127), 255);
+        for phase in 0..128u8 {
+            assert_eq!(full_sine_wave(phase), full_sine_wave(255 - phase));
+        }
+    }
+
+    #[test]
+    fn test_full_sine_wave_rises_then_falls() {
+        for phase in 0..127u8 {
+            assert!(full_sine_wave(phase) <= full_sine_wave(phase + 1));
+        }
+        for phase in 128..255u8 {
+            assert!(full_sine_wave(phase) >= full_sine_wave(phase + 1));
+        }
+    }
+
+    #[test]
+    fn test_triangle_wave() {
+        assert_eq!(triangle_wave(0), 0);
+        assert_eq!(triangle_wave(64), 128);
+        assert_eq!(triangle_wave(127), 255);
+        assert_eq!(triangle_wave(128), 255);
+        assert_eq!(triangle_wave(255), 0);
+    }
+
+    #[test]
+    fn test_add_color_saturates() {
+        assert_eq!(
+            add_color(RGB8::new(200, 10, 0), RGB8::new(100, 20, 0)),
+            RGB8::new(255, 30, 0)
+        );
+    }
+
+    #[test]
+    fn test_nblend() {
+        let mut color = RGB8::new(0, 0, 0);
+        nblend(&mut color, RGB8::new(255, 255, 255), 0);
+        assert_eq!(color, RGB8::new(0, 0, 0));
+        nblend(&mut color, RGB8::new(255, 0, 255), 255);
+        assert_eq!(color, RGB8::new(255, 0, 255));
+        nblend(&mut color, RGB8::new(0, 0, 0), 128);
+        assert_eq!(color, RGB8::new(127, 0, 127));
+    }
+
+    #[test]
+    fn test_fade_to_black_by() {
+        let mut buffer = [RGB8::new(255, 128, 0); 2];
+        fade_to_black_by(&mut buffer, 0);
+        assert_eq!(buffer, [RGB8::new(255, 128, 0); 2]);
+        fade_to_black_by(&mut buffer, 128);
+        assert_eq!(buffer, [RGB8::new(127, 63, 0); 2]);
+        fade_to_black_by(&mut buffer, 255);
+        assert_eq!(buffer, [RGB8::default(); 2]);
+    }
+
+    #[test]
+    fn test_blur1d_spreads_to_neighbors() {
+        let mut buffer = [RGB8::default(); 5];
+        buffer[2] = RGB8::new(255, 0, 0);
+        blur1d(&mut buffer, 128);
+
+        assert_eq!(buffer[0], RGB8::default());
+        assert_eq!(buffer[4], RGB8::default());
+        assert_eq!(buffer[1], buffer[3]);
+        assert!(buffer[1].r > 0);
+        assert!(buffer[2].r > buffer[1].r);
+        assert!(buffer[2].r < 255);
+    }
+
+    #[test]
+    fn test_blur1d_zero_amount_is_identity() {
+        let mut buffer = [RGB8::new(1, 2, 3), RGB8::new(200, 100, 50)];
+        let expected = buffer;
+        blur1d(&mut buffer, 0);
+        assert_eq!(buffer, expected);
+    }
 }
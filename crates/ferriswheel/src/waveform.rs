@@ -0,0 +1,66 @@
+//! Periodic waveforms for brightness modulation.
+
+use crate::util::{full_sine_wave, sine_wave, triangle_wave};
+
+/// The shape of a periodic brightness curve.
+///
+/// Every waveform maps a phase (0–255, one full cycle) to an amplitude (0–255)
+/// and starts at 0 on phase 0.
+///
+/// # Example
+///
+/// ```
+/// use ferriswheel::Waveform;
+///
+/// // The half-rectified sine is dark at the end of the cycle
+/// assert_eq!(Waveform::HalfSine.apply(240), 0);
+/// assert!(Waveform::Sine.apply(240) > 0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Waveform {
+    /// A single sine hump followed by a dark tail.
+    ///
+    /// See [`sine_wave`].
+    #[default]
+    HalfSine,
+    /// A raised sine spanning the whole cycle, peaking at phase 128.
+    ///
+    /// See [`full_sine_wave`].
+    Sine,
+    /// A linear ramp up to phase 128 and back down.
+    ///
+    /// See [`triangle_wave`].
+    Triangle,
+}
+
+impl Waveform {
+    /// Returns the amplitude at `phase` (0–255).
+    pub fn apply(self, phase: u8) -> u8 {
+        match self {
+            Waveform::HalfSine => sine_wave(phase),
+            Waveform::Sine => full_sine_wave(phase),
+            Waveform::Triangle => triangle_wave(phase),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_all_waveforms_start_dark() {
+        for waveform in [Waveform::HalfSine, Waveform::Sine, Waveform::Triangle] {
+            assert_eq!(waveform.apply(0), 0, "{:?}", waveform);
+        }
+    }
+
+    #[test]
+    fn test_full_waveforms_are_never_dark_mid_cycle() {
+        for phase in 16..=240 {
+            assert!(Waveform::Sine.apply(phase) > 0, "phase {}", phase);
+            assert!(Waveform::Triangle.apply(phase) > 0, "phase {}", phase);
+        }
+    }
+}
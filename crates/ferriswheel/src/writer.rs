@@ -0,0 +1,62 @@
+//! Output side of the render loops.
+//!
+//! [`Animator`](crate::Animator) and the async `EffectRunner` hand every rendered
+//! frame to a driver through [`FrameWriter`] or `AsyncFrameWriter`, and report
+//! failures as a [`RunError`].
+
+use crate::effect::EffectError;
+#[cfg(feature = "async")]
+use core::future::Future;
+use rgb::RGB8;
+
+/// An LED driver that writes a whole frame.
+///
+/// Closures taking a frame and returning a `Result` implement this trait,
+/// so a driver call can be passed without a wrapper type.
+pub trait FrameWriter {
+    /// Error reported by the driver.
+    type Error;
+
+    /// Writes `frame` to the LEDs.
+    fn write_frame(&mut self, frame: &[RGB8]) -> Result<(), Self::Error>;
+}
+
+impl<F, E> FrameWriter for F
+where
+    F: FnMut(&[RGB8]) -> Result<(), E>,
+{
+    type Error = E;
+
+    fn write_frame(&mut self, frame: &[RGB8]) -> Result<(), E> {
+        self(frame)
+    }
+}
+
+/// An LED driver that writes a whole frame asynchronously.
+///
+/// Implement this for the driver of your strip; implementations are usually
+/// written as `async fn write_frame`.
+#[cfg(feature = "async")]
+pub trait AsyncFrameWriter {
+    /// Error reported by the driver.
+    type Error;
+
+    /// Writes `frame` to the LEDs.
+    fn write_frame(&mut self, frame: &[RGB8]) -> impl Future<Output = Result<(), Self::Error>>;
+}
+
+/// Error returned by a render loop.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum RunError<E> {
+    /// The effect failed to render.
+    Effect(EffectError),
+    /// The driver failed to write a frame.
+    Write(E),
+}
+
+impl<E> From<EffectError> for RunError<E> {
+    fn from(error: EffectError) -> Self {
+        RunError::Effect(error)
+    }
+}
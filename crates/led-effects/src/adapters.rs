@@ -0,0 +1,195 @@
+//! Wrappers that change colors on their way to a status LED.
+//!
+//! The wrappers implement [`StatusLed`] themselves, so they apply to every
+//! caller of `set_color` and can be stacked: an onboard NeoPixel that is far
+//! too bright is dimmed once, where it is created, instead of at every call
+//! site.
+
+use crate::{DimmableLed, StatusLed};
+use rgb::RGB8;
+
+/// Scales every color by a fixed factor before forwarding it.
+///
+/// A scale of 255 passes colors through unchanged, 128 halves every channel
+/// and 0 keeps the LED off.
+///
+/// # Example
+///
+/// ```
+/// use led_effects::{Dimmed, StatusLed};
+/// use rgb::RGB8;
+///
+/// # struct Led;
+/// # impl StatusLed for Led {
+/// #     type Error = ();
+/// #     fn set_color(&mut self, _color: RGB8) -> Result<(), ()> { Ok(()) }
+/// # }
+/// // Everyone writing to `led` now gets a quarter of the brightness
+/// let mut led = Dimmed::new(Led, 64);
+/// led.set_color(RGB8::new(0, 255, 0)).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Dimmed<L> {
+    led: L,
+    scale: u8,
+}
+
+impl<L: StatusLed> Dimmed<L> {
+    /// Wraps `led`, scaling every color by `scale` (0-255, 255 = unchanged).
+    pub fn new(led: L, scale: u8) -> Self {
+        Self { led, scale }
+    }
+
+    /// Changes the scale; takes effect with the next color.
+    pub fn set_scale(&mut self, scale: u8) {
+        self.scale = scale;
+    }
+
+    /// Returns the scale applied to every color.
+    pub fn scale(&self) -> u8 {
+        self.scale
+    }
+
+    /// Returns a reference to the wrapped LED.
+    pub fn inner(&self) -> &L {
+        &self.led
+    }
+
+    /// Returns a mutable reference to the wrapped LED.
+    pub fn inner_mut(&mut self) -> &mut L {
+        &mut self.led
+    }
+
+    /// Consumes the wrapper and returns the wrapped LED.
+    pub fn into_inner(self) -> L {
+        self.led
+    }
+}
+
+impl<L: StatusLed> StatusLed for Dimmed<L> {
+    type Error = L::Error;
+
+    fn set_color(&mut self, color: RGB8) -> Result<(), Self::Error> {
+        let scale = |value: u8| (value as u16 * self.scale as u16 / 255) as u8;
+        self.led
+            .set_color(RGB8::new(scale(color.r), scale(color.g), scale(color.b)))
+    }
+}
+
+/// The scale is the brightness.
+impl<L: StatusLed> DimmableLed for Dimmed<L> {
+    fn set_brightness(&mut self, brightness: u8) {
+        self.scale = brightness;
+    }
+
+    fn brightness(&self) -> u8 {
+        self.scale
+    }
+}
+
+/// Inverts every channel (`255 - value`) before forwarding the color.
+///
+/// Black turns the wrapped LED fully on and white turns it off, e.g. for a
+/// common-anode RGB LED driven by a plain PWM or GPIO adapter.
+///
+/// # Example
+///
+/// ```
+/// use led_effects::{Inverted, StatusLed};
+/// use rgb::RGB8;
+///
+/// # struct Led;
+/// # impl StatusLed for Led {
+/// #     type Error = ();
+/// #     fn set_color(&mut self, _color: RGB8) -> Result<(), ()> { Ok(()) }
+/// # }
+/// let mut led = Inverted::new(Led);
+/// led.set_color(RGB8::new(255, 0, 0)).unwrap(); // the wrapped LED gets cyan
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Inverted<L> {
+    led: L,
+}
+
+impl<L: StatusLed> Inverted<L> {
+    /// Wraps `led`.
+    pub fn new(led: L) -> Self {
+        Self { led }
+    }
+
+    /// Returns a reference to the wrapped LED.
+    pub fn inner(&self) -> &L {
+        &self.led
+    }
+
+    /// Returns a mutable reference to the wrapped LED.
+    pub fn inner_mut(&mut self) -> &mut L {
+        &mut self.led
+    }
+
+    /// Consumes the wrapper and returns the wrapped LED.
+    pub fn into_inner(self) -> L {
+        self.led
+    }
+}
+
+impl<L: StatusLed> StatusLed for Inverted<L> {
+    type Error = L::Error;
+
+    fn set_color(&mut self, color: RGB8) -> Result<(), Self::Error> {
+        self.led.set_color(RGB8::new(!color.r, !color.g, !color.b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::RecordingLed;
+    use crate::StatusLedExt;
+
+    #[test]
+    fn test_dimmed_scales_channels() {
+        let mut led = Dimmed::new(RecordingLed::new(), 128);
+        led.set_color(RGB8::new(255, 100, 0)).unwrap();
+        assert_eq!(led.inner().colors, [RGB8::new(128, 50, 0)]);
+    }
+
+    #[test]
+    fn test_dimmed_full_and_zero_scale() {
+        let mut led = Dimmed::new(RecordingLed::new(), 255);
+        led.set_rgb(10, 20, 30).unwrap();
+        led.set_scale(0);
+        led.set_rgb(255, 255, 255).unwrap();
+        assert_eq!(
+            led.into_inner().colors,
+            [RGB8::new(10, 20, 30), RGB8::new(0, 0, 0)]
+        );
+    }
+
+    #[test]
+    fn test_dimmed_brightness_is_scale() {
+        let mut led = Dimmed::new(RecordingLed::new(), 255);
+        led.set_brightness(51);
+        assert_eq!(led.scale(), 51);
+        led.set_rgb(255, 0, 0).unwrap();
+        assert_eq!(led.inner().colors, [RGB8::new(51, 0, 0)]);
+    }
+
+    #[test]
+    fn test_inverted_flips_channels() {
+        let mut led = Inverted::new(RecordingLed::new());
+        led.set_rgb(255, 0, 55).unwrap();
+        led.off().unwrap();
+        assert_eq!(
+            led.inner().colors,
+            [RGB8::new(0, 255, 200), RGB8::new(255, 255, 255)]
+        );
+    }
+
+    #[test]
+    fn test_wrappers_stack() {
+        let mut led = Dimmed::new(Inverted::new(RecordingLed::new()), 51);
+        led.set_rgb(255, 0, 0).unwrap();
+        assert_eq!(led.inner().inner().colors, [RGB8::new(204, 255, 255)]);
+    }
+}
@@ -0,0 +1,137 @@
+//! Async counterpart of [`StatusLed`] for drivers with async writes.
+//!
+//! Drivers that push colors out over DMA or RMT futures implement
+//! [`AsyncStatusLed`] instead of blocking inside `set_color`. Blocking
+//! drivers take part through the [`Blocking`] adapter.
+
+use crate::StatusLed;
+use core::future::Future;
+use rgb::RGB8;
+
+/// Trait for status LEDs whose writes are async.
+///
+/// Implementations are usually written as `async fn set_color`.
+///
+/// # Example
+///
+/// ```
+/// use led_effects::AsyncStatusLed;
+/// use rgb::RGB8;
+///
+/// struct DmaLed;
+///
+/// impl AsyncStatusLed for DmaLed {
+///     type Error = ();
+///
+///     async fn set_color(&mut self, _color: RGB8) -> Result<(), Self::Error> {
+///         // Start the transfer and await its completion
+///         Ok(())
+///     }
+/// }
+/// ```
+pub trait AsyncStatusLed {
+    /// The error type returned by LED operations.
+    type Error;
+
+    /// Sets the LED to the specified color.
+    fn set_color(&mut self, color: RGB8) -> impl Future<Output = Result<(), Self::Error>>;
+}
+
+/// Uses a blocking [`StatusLed`] where an [`AsyncStatusLed`] is expected.
+///
+/// The returned future completes on its first poll, after the blocking write
+/// has finished.
+///
+/// # Example
+///
+/// ```
+/// use led_effects::{AsyncStatusLed, Blocking, StatusLed};
+/// use rgb::RGB8;
+///
+/// # struct GpioLed;
+/// # impl StatusLed for GpioLed {
+/// #     type Error = ();
+/// #     fn set_color(&mut self, _color: RGB8) -> Result<(), ()> { Ok(()) }
+/// # }
+/// async fn show_connected(led: &mut impl AsyncStatusLed) {
+///     let _ = led.set_color(RGB8::new(0, 255, 0)).await;
+/// }
+///
+/// let mut led = Blocking::new(GpioLed);
+/// let _ = show_connected(&mut led);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Blocking<L> {
+    led: L,
+}
+
+impl<L: StatusLed> Blocking<L> {
+    /// Wraps a blocking LED.
+    pub fn new(led: L) -> Self {
+        Self { led }
+    }
+
+    /// Returns a reference to the wrapped LED.
+    pub fn inner(&self) -> &L {
+        &self.led
+    }
+
+    /// Returns a mutable reference to the wrapped LED.
+    pub fn inner_mut(&mut self) -> &mut L {
+        &mut self.led
+    }
+
+    /// Consumes the adapter and returns the wrapped LED.
+    pub fn into_inner(self) -> L {
+        self.led
+    }
+}
+
+impl<L: StatusLed> AsyncStatusLed for Blocking<L> {
+    type Error = L::Error;
+
+    async fn set_color(&mut self, color: RGB8) -> Result<(), Self::Error> {
+        self.led.set_color(color)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::RecordingLed;
+    use core::pin::pin;
+    use core::task::{Context, Poll, Waker};
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        let mut cx = Context::from_waker(Waker::noop());
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+        }
+    }
+
+    async fn blink<L: AsyncStatusLed>(led: &mut L) -> Result<(), L::Error> {
+        led.set_color(RGB8::new(255, 0, 0)).await?;
+        led.set_color(RGB8::new(0, 0, 0)).await
+    }
+
+    #[test]
+    fn test_blocking_adapter_forwards_colors() {
+        let mut led = Blocking::new(RecordingLed::new());
+        block_on(blink(&mut led)).unwrap();
+        assert_eq!(
+            led.into_inner().colors,
+            [RGB8::new(255, 0, 0), RGB8::new(0, 0, 0)]
+        );
+    }
+
+    #[test]
+    fn test_blocking_adapter_completes_on_first_poll() {
+        let mut led = Blocking::new(RecordingLed::new());
+        let mut future = pin!(led.set_color(RGB8::new(0, 0, 255)));
+        let mut cx = Context::from_waker(Waker::noop());
+        assert!(future.as_mut().poll(&mut cx).is_ready());
+    }
+}
@@ -0,0 +1,218 @@
+//! Numeric error codes as blink sequences.
+//!
+//! Headless devices report errors by blinking a code: error 3 blinks three
+//! times, pauses, and starts over. [`ErrorBlinkEncoder`] generates that
+//! sequence one tick at a time.
+
+use crate::colors::STATUS_ERROR;
+use crate::StatusLed;
+use rgb::RGB8;
+
+/// Blinks an error code on a [`StatusLed`]: `code` blinks, a pause, repeat.
+///
+/// Code 0 shows the color steadily instead of blinking. The LED is only
+/// written when its color changes.
+///
+/// # Default Configuration
+///
+/// - Color: red
+/// - On: 6 ticks, off: 6 ticks, pause: 24 ticks (300 ms / 300 ms / 1.2 s at
+///   50 ms per tick)
+///
+/// # Example
+///
+/// ```
+/// use led_effects::{ErrorBlinkEncoder, StatusLed};
+/// use rgb::RGB8;
+///
+/// # struct Led;
+/// # impl StatusLed for Led {
+/// #     type Error = ();
+/// #     fn set_color(&mut self, _color: RGB8) -> Result<(), ()> { Ok(()) }
+/// # }
+/// # let mut led = Led;
+/// let mut encoder = ErrorBlinkEncoder::new(3).with_timing(4, 4, 20);
+/// assert_eq!(encoder.cycle_ticks(), 3 * 8 + 20);
+///
+/// // In the main loop
+/// encoder.tick(&mut led).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct ErrorBlinkEncoder {
+    code: u8,
+    color: RGB8,
+    on_ticks: u16,
+    off_ticks: u16,
+    pause_ticks: u16,
+    position: u32,
+    written: Option<RGB8>,
+}
+
+impl ErrorBlinkEncoder {
+    /// Creates an encoder blinking `code` in [`STATUS_ERROR`] red.
+    pub fn new(code: u8) -> Self {
+        Self {
+            code,
+            color: STATUS_ERROR,
+            on_ticks: 6,
+            off_ticks: 6,
+            pause_ticks: 24,
+            position: 0,
+            written: None,
+        }
+    }
+
+    /// Sets the blink color.
+    pub fn with_color(mut self, color: RGB8) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Sets how many ticks each blink is on and off, and how long the pause
+    /// after the last blink lasts. Values below 1 count as 1.
+    pub fn with_timing(mut self, on_ticks: u16, off_ticks: u16, pause_ticks: u16) -> Self {
+        self.on_ticks = on_ticks.max(1);
+        self.off_ticks = off_ticks.max(1);
+        self.pause_ticks = pause_ticks.max(1);
+        self
+    }
+
+    /// Changes the code and starts its sequence from the first blink.
+    pub fn set_code(&mut self, code: u8) {
+        self.code = code;
+        self.position = 0;
+    }
+
+    /// Changes the blink color.
+    pub fn set_color(&mut self, color: RGB8) {
+        self.color = color;
+    }
+
+    /// Returns the code being blinked.
+    pub fn code(&self) -> u8 {
+        self.code
+    }
+
+    /// Returns the blink color.
+    pub fn color(&self) -> RGB8 {
+        self.color
+    }
+
+    /// Returns the length of one sequence, blinks plus pause, in ticks.
+    pub fn cycle_ticks(&self) -> u32 {
+        self.code as u32 * (self.on_ticks as u32 + self.off_ticks as u32) + self.pause_ticks as u32
+    }
+
+    /// Restarts the sequence from the first blink.
+    pub fn reset(&mut self) {
+        self.position = 0;
+    }
+
+    /// Forgets the last written color, so the next tick writes the LED even
+    /// if the color did not change.
+    pub fn invalidate(&mut self) {
+        self.written = None;
+    }
+
+    /// Returns the color for this tick and moves one tick ahead.
+    pub fn next_color(&mut self) -> RGB8 {
+        if self.code == 0 {
+            return self.color;
+        }
+
+        let blink = self.on_ticks as u32 + self.off_ticks as u32;
+        let blinks_end = self.code as u32 * blink;
+        let lit = self.position < blinks_end && self.position % blink < self.on_ticks as u32;
+        self.position = (self.position + 1) % self.cycle_ticks();
+
+        if lit {
+            self.color
+        } else {
+            RGB8::default()
+        }
+    }
+
+    /// Writes the color for this tick to `led` and moves one tick ahead.
+    ///
+    /// # Errors
+    ///
+    /// Returns the LED's error if writing the color fails.
+    pub fn tick<L: StatusLed>(&mut self, led: &mut L) -> Result<(), L::Error> {
+        let color = self.next_color();
+        if self.written != Some(color) {
+            led.set_color(color)?;
+            self.written = Some(color);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::RecordingLed;
+
+    const RED: RGB8 = RGB8::new(255, 0, 0);
+    const OFF: RGB8 = RGB8::new(0, 0, 0);
+
+    fn sequence(encoder: &mut ErrorBlinkEncoder, ticks: usize) -> Vec<bool> {
+        (0..ticks).map(|_| encoder.next_color() != OFF).collect()
+    }
+
+    #[test]
+    fn test_blinks_code_then_pauses() {
+        let mut encoder = ErrorBlinkEncoder::new(2).with_timing(1, 1, 3);
+        assert_eq!(encoder.cycle_ticks(), 7);
+        assert_eq!(
+            sequence(&mut encoder, 14),
+            [
+                true, false, true, false, false, false, false, //
+                true, false, true, false, false, false, false,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_longer_on_and_off() {
+        let mut encoder = ErrorBlinkEncoder::new(1).with_timing(2, 3, 1);
+        assert_eq!(
+            sequence(&mut encoder, 6),
+            [true, true, false, false, false, false]
+        );
+        assert!(encoder.next_color() != OFF);
+    }
+
+    #[test]
+    fn test_code_zero_is_steady() {
+        let mut encoder = ErrorBlinkEncoder::new(0);
+        assert!(sequence(&mut encoder, 50).iter().all(|&lit| lit));
+    }
+
+    #[test]
+    fn test_zero_timing_counts_as_one() {
+        let mut encoder = ErrorBlinkEncoder::new(1).with_timing(0, 0, 0);
+        assert_eq!(encoder.cycle_ticks(), 3);
+        assert_eq!(sequence(&mut encoder, 3), [true, false, false]);
+    }
+
+    #[test]
+    fn test_set_code_restarts() {
+        let mut encoder = ErrorBlinkEncoder::new(3).with_timing(1, 1, 1);
+        sequence(&mut encoder, 3);
+        encoder.set_code(1);
+        assert_eq!(encoder.code(), 1);
+        assert_eq!(sequence(&mut encoder, 3), [true, false, false]);
+    }
+
+    #[test]
+    fn test_tick_writes_only_changes() {
+        let mut led = RecordingLed::new();
+        let mut encoder = ErrorBlinkEncoder::new(2)
+            .with_timing(2, 1, 2)
+            .with_color(RED);
+        for _ in 0..8 {
+            encoder.tick(&mut led).unwrap();
+        }
+        assert_eq!(led.colors, [RED, OFF, RED, OFF]);
+    }
+}
@@ -0,0 +1,321 @@
+//! One status sink for several LEDs.
+//!
+//! Arrays and pairs of status LEDs implement [`StatusLed`] themselves and
+//! stop at the first LED that fails. [`Broadcast`] makes the error handling
+//! explicit: with [`ErrorStrategy::WriteAll`] every LED is written and all
+//! errors are collected.
+
+use crate::StatusLed;
+use rgb::RGB8;
+
+impl<L: StatusLed + ?Sized> StatusLed for &mut L {
+    type Error = L::Error;
+
+    fn set_color(&mut self, color: RGB8) -> Result<(), Self::Error> {
+        (**self).set_color(color)
+    }
+}
+
+/// Writes the color to every LED in order and stops at the first error.
+impl<L: StatusLed, const N: usize> StatusLed for [L; N] {
+    type Error = L::Error;
+
+    fn set_color(&mut self, color: RGB8) -> Result<(), Self::Error> {
+        for led in self.iter_mut() {
+            led.set_color(color)?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes the color to both LEDs and stops at the first error.
+impl<A: StatusLed, B: StatusLed> StatusLed for (A, B) {
+    type Error = PairError<A::Error, B::Error>;
+
+    fn set_color(&mut self, color: RGB8) -> Result<(), Self::Error> {
+        self.0.set_color(color).map_err(PairError::First)?;
+        self.1.set_color(color).map_err(PairError::Second)
+    }
+}
+
+/// Error of a pair of LEDs with different error types.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PairError<A, B> {
+    /// The first LED failed.
+    First(A),
+    /// The second LED failed.
+    Second(B),
+    /// Both LEDs failed; only reported with [`ErrorStrategy::WriteAll`].
+    Both(A, B),
+}
+
+/// What a [`Broadcast`] does when one of its LEDs fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ErrorStrategy {
+    /// Stop writing and report the failing LED.
+    #[default]
+    StopOnFirst,
+    /// Write every LED anyway and report all failures.
+    WriteAll,
+}
+
+/// Errors collected by a [`Broadcast`] over an array of LEDs.
+///
+/// Holds one entry per LED; entries of LEDs that were written successfully,
+/// or not written at all, are `None`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BroadcastError<E, const N: usize> {
+    errors: [Option<E>; N],
+}
+
+impl<E, const N: usize> BroadcastError<E, N> {
+    /// Returns the error of LED `index`, if it failed.
+    pub fn get(&self, index: usize) -> Option<&E> {
+        self.errors.get(index)?.as_ref()
+    }
+
+    /// Returns the index and error of the first LED that failed.
+    pub fn first(&self) -> Option<(usize, &E)> {
+        self.iter().next()
+    }
+
+    /// Returns the index and error of every LED that failed.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &E)> {
+        self.errors
+            .iter()
+            .enumerate()
+            .filter_map(|(index, error)| Some((index, error.as_ref()?)))
+    }
+
+    /// Returns the number of LEDs that failed.
+    pub fn count(&self) -> usize {
+        self.iter().count()
+    }
+
+    /// Returns the per-LED errors.
+    pub fn into_inner(self) -> [Option<E>; N] {
+        self.errors
+    }
+}
+
+/// Forwards every color to several LEDs with a configurable [`ErrorStrategy`].
+///
+/// Wraps an array of LEDs of one type or a pair of LEDs of different types.
+///
+/// # Example
+///
+/// ```
+/// use led_effects::{Broadcast, ErrorStrategy, StatusLed};
+/// use rgb::RGB8;
+///
+/// # struct RgbLed;
+/// # impl StatusLed for RgbLed {
+/// #     type Error = ();
+/// #     fn set_color(&mut self, _color: RGB8) -> Result<(), ()> { Ok(()) }
+/// # }
+/// # struct GpioLed;
+/// # impl StatusLed for GpioLed {
+/// #     type Error = ();
+/// #     fn set_color(&mut self, _color: RGB8) -> Result<(), ()> { Ok(()) }
+/// # }
+/// let mut status = Broadcast::new((RgbLed, GpioLed)).with_strategy(ErrorStrategy::WriteAll);
+/// status.set_color(RGB8::new(0, 255, 0)).unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Broadcast<T> {
+    leds: T,
+    strategy: ErrorStrategy,
+}
+
+impl<T> Broadcast<T> {
+    /// Wraps `leds`, stopping at the first error.
+    pub fn new(leds: T) -> Self {
+        Self {
+            leds,
+            strategy: ErrorStrategy::StopOnFirst,
+        }
+    }
+
+    /// Sets what happens when one of the LEDs fails.
+    pub fn with_strategy(mut self, strategy: ErrorStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Returns what happens when one of the LEDs fails.
+    pub fn strategy(&self) -> ErrorStrategy {
+        self.strategy
+    }
+
+    /// Returns a reference to the wrapped LEDs.
+    pub fn inner(&self) -> &T {
+        &self.leds
+    }
+
+    /// Returns a mutable reference to the wrapped LEDs.
+    pub fn inner_mut(&mut self) -> &mut T {
+        &mut self.leds
+    }
+
+    /// Consumes the wrapper and returns the wrapped LEDs.
+    pub fn into_inner(self) -> T {
+        self.leds
+    }
+}
+
+impl<L: StatusLed, const N: usize> StatusLed for Broadcast<[L; N]> {
+    type Error = BroadcastError<L::Error, N>;
+
+    fn set_color(&mut self, color: RGB8) -> Result<(), Self::Error> {
+        let mut errors: [Option<L::Error>; N] = core::array::from_fn(|_| None);
+        let mut failed = false;
+        for (led, error) in self.leds.iter_mut().zip(errors.iter_mut()) {
+            if let Err(e) = led.set_color(color) {
+                *error = Some(e);
+                failed = true;
+                if self.strategy == ErrorStrategy::StopOnFirst {
+                    break;
+                }
+            }
+        }
+
+        if failed {
+            Err(BroadcastError { errors })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<A: StatusLed, B: StatusLed> StatusLed for Broadcast<(A, B)> {
+    type Error = PairError<A::Error, B::Error>;
+
+    fn set_color(&mut self, color: RGB8) -> Result<(), Self::Error> {
+        if self.strategy == ErrorStrategy::StopOnFirst {
+            return self.leds.set_color(color);
+        }
+        match (self.leds.0.set_color(color), self.leds.1.set_color(color)) {
+            (Ok(()), Ok(())) => Ok(()),
+            (Err(a), Ok(())) => Err(PairError::First(a)),
+            (Ok(()), Err(b)) => Err(PairError::Second(b)),
+            (Err(a), Err(b)) => Err(PairError::Both(a, b)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::RecordingLed;
+
+    const RED: RGB8 = RGB8::new(255, 0, 0);
+
+    /// Records colors like `RecordingLed` but fails every write when broken.
+    #[derive(Default)]
+    struct FlakyLed {
+        broken: bool,
+        colors: Vec<RGB8>,
+    }
+
+    impl FlakyLed {
+        fn broken() -> Self {
+            Self {
+                broken: true,
+                colors: Vec::new(),
+            }
+        }
+    }
+
+    impl StatusLed for FlakyLed {
+        type Error = &'static str;
+
+        fn set_color(&mut self, color: RGB8) -> Result<(), Self::Error> {
+            if self.broken {
+                return Err("broken");
+            }
+            self.colors.push(color);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_array_writes_every_led() {
+        let mut leds = [RecordingLed::new(), RecordingLed::new()];
+        leds.set_color(RED).unwrap();
+        assert_eq!(leds[0].colors, [RED]);
+        assert_eq!(leds[1].colors, [RED]);
+    }
+
+    #[test]
+    fn test_array_stops_at_first_error() {
+        let mut leds = [FlakyLed::broken(), FlakyLed::default()];
+        assert_eq!(leds.set_color(RED), Err("broken"));
+        assert!(leds[1].colors.is_empty());
+    }
+
+    #[test]
+    fn test_pair_of_different_leds() {
+        let mut pair = (RecordingLed::new(), FlakyLed::default());
+        pair.set_color(RED).unwrap();
+        assert_eq!(pair.0.colors, [RED]);
+        assert_eq!(pair.1.colors, [RED]);
+
+        let mut pair = (FlakyLed::default(), FlakyLed::broken());
+        assert_eq!(pair.set_color(RED), Err(PairError::Second("broken")));
+    }
+
+    #[test]
+    fn test_array_of_borrowed_leds() {
+        let mut a = RecordingLed::new();
+        let mut b = RecordingLed::new();
+        [&mut a, &mut b].set_color(RED).unwrap();
+        assert_eq!(a.colors, [RED]);
+        assert_eq!(b.colors, [RED]);
+    }
+
+    #[test]
+    fn test_broadcast_stop_on_first() {
+        let mut leds =
+            Broadcast::new([FlakyLed::default(), FlakyLed::broken(), FlakyLed::default()]);
+        let error = leds.set_color(RED).unwrap_err();
+        assert_eq!(error.first(), Some((1, &"broken")));
+        assert_eq!(error.count(), 1);
+        assert!(leds.inner()[2].colors.is_empty());
+    }
+
+    #[test]
+    fn test_broadcast_write_all_collects_errors() {
+        let mut leds =
+            Broadcast::new([FlakyLed::broken(), FlakyLed::default(), FlakyLed::broken()])
+                .with_strategy(ErrorStrategy::WriteAll);
+        let error = leds.set_color(RED).unwrap_err();
+        assert_eq!(error.iter().map(|(i, _)| i).collect::<Vec<_>>(), [0, 2]);
+        assert_eq!(error.get(1), None);
+        assert_eq!(leds.inner()[1].colors, [RED]);
+    }
+
+    #[test]
+    fn test_broadcast_pair_write_all() {
+        let mut pair = Broadcast::new((FlakyLed::broken(), FlakyLed::broken()))
+            .with_strategy(ErrorStrategy::WriteAll);
+        assert_eq!(
+            pair.set_color(RED),
+            Err(PairError::Both("broken", "broken"))
+        );
+
+        let mut pair = Broadcast::new((FlakyLed::broken(), FlakyLed::default()))
+            .with_strategy(ErrorStrategy::WriteAll);
+        assert_eq!(pair.set_color(RED), Err(PairError::First("broken")));
+        assert_eq!(pair.inner().1.colors, [RED]);
+    }
+
+    #[test]
+    fn test_broadcast_pair_stop_on_first() {
+        let mut pair = Broadcast::new((FlakyLed::broken(), FlakyLed::default()));
+        assert_eq!(pair.set_color(RED), Err(PairError::First("broken")));
+        assert!(pair.inner().1.colors.is_empty());
+    }
+}
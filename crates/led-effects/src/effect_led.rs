@@ -0,0 +1,177 @@
+//! Ferriswheel effects on a single status LED.
+//!
+//! The ring effects of `ferriswheel` render into a frame buffer, while status
+//! LEDs take one color at a time. [`EffectLed`] bridges the two: it renders a
+//! one-LED frame per tick and writes the pixel to a [`StatusLed`].
+
+use crate::StatusLed;
+use ferriswheel::{Effect, RunError};
+use rgb::RGB8;
+
+/// Runs a `ferriswheel` [`Effect`] configured for one LED on a [`StatusLed`].
+///
+/// Every [`tick`](EffectLed::tick) advances the effect by one frame. The LED
+/// is only written when the pixel's color changes.
+///
+/// # Example
+///
+/// ```
+/// use ferriswheel::PulseEffect;
+/// use led_effects::{EffectLed, StatusLed};
+/// use rgb::RGB8;
+///
+/// # struct Led;
+/// # impl StatusLed for Led {
+/// #     type Error = ();
+/// #     fn set_color(&mut self, _color: RGB8) -> Result<(), ()> { Ok(()) }
+/// # }
+/// let pulse = PulseEffect::new(1).unwrap().with_color(RGB8::new(0, 0, 255));
+/// let mut status = EffectLed::new(pulse, Led);
+///
+/// // In the main loop
+/// status.tick().unwrap();
+/// ```
+#[derive(Debug)]
+pub struct EffectLed<E, L> {
+    effect: E,
+    led: L,
+    written: Option<RGB8>,
+}
+
+impl<E: Effect, L: StatusLed> EffectLed<E, L> {
+    /// Creates an adapter that plays `effect` on `led`.
+    pub fn new(effect: E, led: L) -> Self {
+        Self {
+            effect,
+            led,
+            written: None,
+        }
+    }
+
+    /// Renders the next frame of the effect and writes its pixel to the LED.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RunError::Effect` if the effect fails to render, e.g. with
+    /// `EffectError::BufferTooSmall` when it is configured for more than one
+    /// LED, and `RunError::Write` if writing the color fails.
+    pub fn tick(&mut self) -> Result<(), RunError<L::Error>> {
+        let mut pixel = [RGB8::default()];
+        self.effect.update(&mut pixel)?;
+        if self.written != Some(pixel[0]) {
+            self.led.set_color(pixel[0]).map_err(RunError::Write)?;
+            self.written = Some(pixel[0]);
+        }
+        Ok(())
+    }
+
+    /// Replaces the effect; the next tick writes its first frame.
+    pub fn set_effect(&mut self, effect: E) {
+        self.effect = effect;
+        self.written = None;
+    }
+
+    /// Restarts the effect from its first frame.
+    pub fn reset(&mut self) {
+        self.effect.reset();
+    }
+
+    /// Returns `true` once a one-shot effect has completed.
+    pub fn is_finished(&self) -> bool {
+        self.effect.is_finished()
+    }
+
+    /// Forgets the last written color, so the next tick writes the LED even
+    /// if the color did not change.
+    pub fn invalidate(&mut self) {
+        self.written = None;
+    }
+
+    /// Returns a reference to the effect.
+    pub fn effect(&self) -> &E {
+        &self.effect
+    }
+
+    /// Returns a mutable reference to the effect.
+    pub fn effect_mut(&mut self) -> &mut E {
+        &mut self.effect
+    }
+
+    /// Returns a reference to the LED.
+    pub fn led(&self) -> &L {
+        &self.led
+    }
+
+    /// Returns a mutable reference to the LED.
+    ///
+    /// The next tick writes the LED again, in case it was changed directly.
+    pub fn led_mut(&mut self) -> &mut L {
+        self.written = None;
+        &mut self.led
+    }
+
+    /// Consumes the adapter and returns the effect and the LED.
+    pub fn into_parts(self) -> (E, L) {
+        (self.effect, self.led)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::RecordingLed;
+    use ferriswheel::{EffectError, FlashEffect, PulseEffect, RainbowEffect};
+
+    const RED: RGB8 = RGB8::new(255, 0, 0);
+    const OFF: RGB8 = RGB8::new(0, 0, 0);
+
+    #[test]
+    fn test_writes_the_effect_pixel() {
+        let rainbow = RainbowEffect::new(1).unwrap();
+        let mut expected = [RGB8::default()];
+        rainbow.current(&mut expected).unwrap();
+
+        let mut status = EffectLed::new(rainbow, RecordingLed::new());
+        status.tick().unwrap();
+        assert_eq!(status.led().colors, expected);
+    }
+
+    #[test]
+    fn test_writes_only_changes() {
+        let flash = FlashEffect::new(1)
+            .unwrap()
+            .with_color(RED)
+            .with_duty(2, 2)
+            .unwrap();
+        let mut status = EffectLed::new(flash, RecordingLed::new());
+        for _ in 0..8 {
+            status.tick().unwrap();
+        }
+        assert_eq!(status.led().colors, [RED, OFF, RED, OFF]);
+    }
+
+    #[test]
+    fn test_effect_for_several_leds_fails() {
+        let pulse = PulseEffect::new(3).unwrap();
+        let mut status = EffectLed::new(pulse, RecordingLed::new());
+        assert_eq!(
+            status.tick(),
+            Err(RunError::Effect(EffectError::BufferTooSmall {
+                required: 3,
+                actual: 1
+            }))
+        );
+        assert!(status.led().colors.is_empty());
+    }
+
+    #[test]
+    fn test_led_mut_forces_rewrite() {
+        let flash = FlashEffect::new(1).unwrap().with_color(RED);
+        let mut status = EffectLed::new(flash, RecordingLed::new());
+        status.tick().unwrap();
+        status.led_mut();
+        status.reset();
+        status.tick().unwrap();
+        assert_eq!(status.led().colors, [RED, RED]);
+    }
+}
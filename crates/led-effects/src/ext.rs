@@ -0,0 +1,132 @@
+//! Convenience methods for every [`StatusLed`].
+//!
+//! [`StatusLedExt`] is implemented for all status LEDs, so the calls most
+//! firmware writes by hand live in one place while the core trait keeps its
+//! single required method.
+
+use crate::StatusLed;
+use rgb::RGB8;
+
+/// Convenience methods built on [`StatusLed::set_color`].
+///
+/// # Example
+///
+/// ```
+/// use led_effects::{StatusLed, StatusLedExt};
+/// use rgb::RGB8;
+///
+/// # struct Led;
+/// # impl StatusLed for Led {
+/// #     type Error = ();
+/// #     fn set_color(&mut self, _color: RGB8) -> Result<(), ()> { Ok(()) }
+/// # }
+/// let mut led = Led;
+/// led.set_rgb(0, 0, 255).unwrap();
+///
+/// // Lit for three ticks, then off
+/// let mut flash = led.flash_once(RGB8::new(255, 255, 255), 3).unwrap();
+/// while !flash.is_finished() {
+///     flash.tick().unwrap();
+/// }
+/// ```
+pub trait StatusLedExt: StatusLed {
+    /// Turns the LED off.
+    fn off(&mut self) -> Result<(), Self::Error> {
+        self.set_color(RGB8::new(0, 0, 0))
+    }
+
+    /// Sets the LED to the color given by its red, green and blue channels.
+    fn set_rgb(&mut self, r: u8, g: u8, b: u8) -> Result<(), Self::Error> {
+        self.set_color(RGB8::new(r, g, b))
+    }
+
+    /// Lights the LED in `color` and returns a [`FlashOnce`] that turns it off
+    /// again after `ticks` calls to [`FlashOnce::tick`]; values below 1 count as 1.
+    fn flash_once(&mut self, color: RGB8, ticks: u16) -> Result<FlashOnce<'_, Self>, Self::Error>
+    where
+        Self: Sized,
+    {
+        self.set_color(color)?;
+        Ok(FlashOnce {
+            led: self,
+            remaining: ticks.max(1),
+        })
+    }
+}
+
+impl<L: StatusLed + ?Sized> StatusLedExt for L {}
+
+/// A single flash started by [`StatusLedExt::flash_once`].
+#[derive(Debug)]
+pub struct FlashOnce<'a, L: StatusLed> {
+    led: &'a mut L,
+    remaining: u16,
+}
+
+impl<L: StatusLed> FlashOnce<'_, L> {
+    /// Counts one tick and turns the LED off once the flash is over.
+    ///
+    /// Further ticks after that do nothing.
+    pub fn tick(&mut self) -> Result<(), L::Error> {
+        if self.remaining == 0 {
+            return Ok(());
+        }
+        self.remaining -= 1;
+        if self.remaining == 0 {
+            self.led.off()?;
+        }
+        Ok(())
+    }
+
+    /// Returns `true` once the LED has been turned off again.
+    pub fn is_finished(&self) -> bool {
+        self.remaining == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::RecordingLed;
+
+    const OFF: RGB8 = RGB8::new(0, 0, 0);
+    const WHITE: RGB8 = RGB8::new(255, 255, 255);
+
+    #[test]
+    fn test_off_and_set_rgb() {
+        let mut led = RecordingLed::new();
+        led.set_rgb(1, 2, 3).unwrap();
+        led.off().unwrap();
+        assert_eq!(led.colors, [RGB8::new(1, 2, 3), OFF]);
+    }
+
+    #[test]
+    fn test_flash_once_turns_off_after_ticks() {
+        let mut led = RecordingLed::new();
+        let mut flash = led.flash_once(WHITE, 2).unwrap();
+        flash.tick().unwrap();
+        assert!(!flash.is_finished());
+        flash.tick().unwrap();
+        assert!(flash.is_finished());
+        flash.tick().unwrap();
+        assert_eq!(led.colors, [WHITE, OFF]);
+    }
+
+    #[test]
+    fn test_zero_tick_flash_lasts_one_tick() {
+        let mut led = RecordingLed::new();
+        let mut flash = led.flash_once(WHITE, 0).unwrap();
+        assert!(!flash.is_finished());
+        flash.tick().unwrap();
+        assert!(flash.is_finished());
+        assert_eq!(led.colors, [WHITE, OFF]);
+    }
+
+    #[test]
+    fn test_ext_on_trait_object() {
+        let mut led = RecordingLed::new();
+        let dyn_led: &mut dyn StatusLed<Error = core::convert::Infallible> = &mut led;
+        dyn_led.off().unwrap();
+        assert_eq!(led.colors, [OFF]);
+    }
+}
@@ -0,0 +1,225 @@
+//! Smooth color changes on status LEDs.
+//!
+//! A status LED that snaps from one color to the next looks nervous.
+//! [`TransitionFader`] remembers the color it last showed and, when given a
+//! new target, blends towards it over a fixed number of ticks.
+
+use crate::StatusLed;
+use rgb::RGB8;
+
+/// Blends the current color towards a target color over a number of ticks.
+///
+/// Use it on its own with [`tick`](TransitionFader::tick), or enable it in a
+/// [`StatusIndicator`](crate::StatusIndicator) with
+/// [`with_fade`](crate::StatusIndicator::with_fade). The LED is only written
+/// when its color changes.
+///
+/// # Example
+///
+/// ```
+/// use led_effects::{StatusLed, TransitionFader};
+/// use rgb::RGB8;
+///
+/// # struct Led;
+/// # impl StatusLed for Led {
+/// #     type Error = ();
+/// #     fn set_color(&mut self, _color: RGB8) -> Result<(), ()> { Ok(()) }
+/// # }
+/// # let mut led = Led;
+/// let mut fader = TransitionFader::new(10);
+/// fader.set_target(RGB8::new(0, 255, 0));
+///
+/// while fader.is_fading() {
+///     fader.tick(&mut led).unwrap();
+/// }
+/// assert_eq!(fader.current(), RGB8::new(0, 255, 0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct TransitionFader {
+    duration: u16,
+    from: RGB8,
+    current: RGB8,
+    target: RGB8,
+    elapsed: u16,
+    written: Option<RGB8>,
+}
+
+impl TransitionFader {
+    /// Creates a fader that takes `duration` ticks per transition, starting from black.
+    ///
+    /// A duration of 0 switches colors immediately.
+    pub fn new(duration: u16) -> Self {
+        Self {
+            duration,
+            from: RGB8::default(),
+            current: RGB8::default(),
+            target: RGB8::default(),
+            elapsed: 0,
+            written: None,
+        }
+    }
+
+    /// Changes the number of ticks per transition; a running transition keeps its progress.
+    pub fn set_duration(&mut self, duration: u16) {
+        self.duration = duration;
+        self.elapsed = self.elapsed.min(duration);
+    }
+
+    /// Returns the number of ticks per transition.
+    pub fn duration(&self) -> u16 {
+        self.duration
+    }
+
+    /// Starts a transition from the current color to `target`.
+    ///
+    /// Setting the target that is already being approached does not restart
+    /// the transition.
+    pub fn set_target(&mut self, target: RGB8) {
+        if target != self.target {
+            self.from = self.current;
+            self.target = target;
+            self.elapsed = 0;
+        }
+    }
+
+    /// Switches to `color` immediately, without a transition.
+    pub fn jump_to(&mut self, color: RGB8) {
+        self.from = color;
+        self.current = color;
+        self.target = color;
+        self.elapsed = self.duration;
+    }
+
+    /// Returns the color being approached.
+    pub fn target(&self) -> RGB8 {
+        self.target
+    }
+
+    /// Returns the color shown at the last tick.
+    pub fn current(&self) -> RGB8 {
+        self.current
+    }
+
+    /// Returns `true` while the current color has not reached the target.
+    pub fn is_fading(&self) -> bool {
+        self.current != self.target
+    }
+
+    /// Forgets the last written color, so the next tick writes the LED even
+    /// if the color did not change.
+    pub fn invalidate(&mut self) {
+        self.written = None;
+    }
+
+    /// Moves one tick towards the target and returns the new color.
+    pub fn next_color(&mut self) -> RGB8 {
+        if self.elapsed < self.duration {
+            self.elapsed += 1;
+            let amount = (self.elapsed as u32 * 255 / self.duration as u32) as u8;
+            self.current = lerp(self.from, self.target, amount);
+        } else {
+            self.current = self.target;
+        }
+        self.current
+    }
+
+    /// Writes the color for this tick to `led` and moves one tick ahead.
+    ///
+    /// # Errors
+    ///
+    /// Returns the LED's error if writing the color fails.
+    pub fn tick<L: StatusLed>(&mut self, led: &mut L) -> Result<(), L::Error> {
+        let color = self.next_color();
+        if self.written != Some(color) {
+            led.set_color(color)?;
+            self.written = Some(color);
+        }
+        Ok(())
+    }
+}
+
+/// Blends `from` towards `to` by `amount` (0 = `from`, 255 = `to`).
+fn lerp(from: RGB8, to: RGB8, amount: u8) -> RGB8 {
+    let channel = |a: u8, b: u8| {
+        let (a, b, t) = (a as i32, b as i32, amount as i32);
+        (a + (b - a) * t / 255) as u8
+    };
+    RGB8::new(
+        channel(from.r, to.r),
+        channel(from.g, to.g),
+        channel(from.b, to.b),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::RecordingLed;
+
+    const WHITE: RGB8 = RGB8::new(255, 255, 255);
+    const BLACK: RGB8 = RGB8::new(0, 0, 0);
+
+    #[test]
+    fn test_fades_over_duration() {
+        let mut fader = TransitionFader::new(4);
+        fader.set_target(RGB8::new(200, 0, 100));
+        let colors: Vec<RGB8> = (0..5).map(|_| fader.next_color()).collect();
+        assert_eq!(
+            colors,
+            [
+                RGB8::new(49, 0, 24),
+                RGB8::new(99, 0, 49),
+                RGB8::new(149, 0, 74),
+                RGB8::new(200, 0, 100),
+                RGB8::new(200, 0, 100),
+            ]
+        );
+        assert!(!fader.is_fading());
+    }
+
+    #[test]
+    fn test_fades_down() {
+        let mut fader = TransitionFader::new(2);
+        fader.jump_to(WHITE);
+        fader.set_target(BLACK);
+        assert_eq!(fader.next_color(), RGB8::new(128, 128, 128));
+        assert_eq!(fader.next_color(), BLACK);
+    }
+
+    #[test]
+    fn test_zero_duration_switches_immediately() {
+        let mut fader = TransitionFader::new(0);
+        fader.set_target(WHITE);
+        assert_eq!(fader.next_color(), WHITE);
+    }
+
+    #[test]
+    fn test_retarget_starts_from_current_color() {
+        let mut fader = TransitionFader::new(2);
+        fader.set_target(WHITE);
+        fader.next_color();
+        fader.set_target(BLACK);
+        assert_eq!(fader.next_color(), RGB8::new(64, 64, 64));
+        assert_eq!(fader.next_color(), BLACK);
+    }
+
+    #[test]
+    fn test_same_target_does_not_restart() {
+        let mut fader = TransitionFader::new(2);
+        fader.set_target(WHITE);
+        fader.next_color();
+        fader.set_target(WHITE);
+        assert_eq!(fader.next_color(), WHITE);
+    }
+
+    #[test]
+    fn test_tick_writes_only_changes() {
+        let mut led = RecordingLed::new();
+        let mut fader = TransitionFader::new(2);
+        fader.set_target(WHITE);
+        for _ in 0..4 {
+            fader.tick(&mut led).unwrap();
+        }
+        assert_eq!(led.colors, [RGB8::new(127, 127, 127), WHITE]);
+    }
+}
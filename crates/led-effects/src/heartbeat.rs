@@ -0,0 +1,177 @@
+//! "Firmware is alive" heartbeat.
+//!
+//! A double blink followed by a pause is the usual sign that the main loop
+//! is still running. [`Heartbeat`] generates it one tick at a time, so a
+//! stuck loop shows up as a frozen LED.
+
+use crate::StatusLed;
+use rgb::RGB8;
+
+/// Blinks twice, pauses and starts over, like a heartbeat.
+///
+/// The LED is only written when its color changes.
+///
+/// # Default Configuration
+///
+/// - Beats: 2 ticks on, 3 ticks apart
+/// - Pause: 18 ticks (a 1.25 s cycle at 50 ms per tick)
+///
+/// # Example
+///
+/// ```
+/// use led_effects::{Heartbeat, StatusLed};
+/// use rgb::RGB8;
+///
+/// # struct Led;
+/// # impl StatusLed for Led {
+/// #     type Error = ();
+/// #     fn set_color(&mut self, _color: RGB8) -> Result<(), ()> { Ok(()) }
+/// # }
+/// # let mut led = Led;
+/// let mut heartbeat = Heartbeat::new(RGB8::new(0, 64, 0));
+///
+/// loop {
+///     // ... the actual work of the main loop ...
+///     heartbeat.tick(&mut led).unwrap();
+/// #   break;
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Heartbeat {
+    color: RGB8,
+    beat_ticks: u16,
+    gap_ticks: u16,
+    pause_ticks: u16,
+    position: u32,
+    written: Option<RGB8>,
+}
+
+impl Heartbeat {
+    /// Creates a heartbeat in `color` with the default timing.
+    pub fn new(color: RGB8) -> Self {
+        Self {
+            color,
+            beat_ticks: 2,
+            gap_ticks: 3,
+            pause_ticks: 18,
+            position: 0,
+            written: None,
+        }
+    }
+
+    /// Sets how many ticks each beat is on, how long the gap between the two
+    /// beats lasts and how long the pause after the second beat lasts.
+    /// Values below 1 count as 1.
+    pub fn with_timing(mut self, beat_ticks: u16, gap_ticks: u16, pause_ticks: u16) -> Self {
+        self.beat_ticks = beat_ticks.max(1);
+        self.gap_ticks = gap_ticks.max(1);
+        self.pause_ticks = pause_ticks.max(1);
+        self
+    }
+
+    /// Changes the beat color.
+    pub fn set_color(&mut self, color: RGB8) {
+        self.color = color;
+    }
+
+    /// Returns the beat color.
+    pub fn color(&self) -> RGB8 {
+        self.color
+    }
+
+    /// Returns the length of one heartbeat, both beats plus pause, in ticks.
+    pub fn cycle_ticks(&self) -> u32 {
+        2 * self.beat_ticks as u32 + self.gap_ticks as u32 + self.pause_ticks as u32
+    }
+
+    /// Restarts at the first beat.
+    pub fn reset(&mut self) {
+        self.position = 0;
+    }
+
+    /// Forgets the last written color, so the next tick writes the LED even
+    /// if the color did not change.
+    pub fn invalidate(&mut self) {
+        self.written = None;
+    }
+
+    /// Returns the color for this tick and moves one tick ahead.
+    pub fn next_color(&mut self) -> RGB8 {
+        let beat = self.beat_ticks as u32;
+        let second_beat = beat + self.gap_ticks as u32;
+        let lit =
+            self.position < beat || (second_beat..second_beat + beat).contains(&self.position);
+        self.position = (self.position + 1) % self.cycle_ticks();
+
+        if lit {
+            self.color
+        } else {
+            RGB8::default()
+        }
+    }
+
+    /// Writes the color for this tick to `led` and moves one tick ahead.
+    ///
+    /// # Errors
+    ///
+    /// Returns the LED's error if writing the color fails.
+    pub fn tick<L: StatusLed>(&mut self, led: &mut L) -> Result<(), L::Error> {
+        let color = self.next_color();
+        if self.written != Some(color) {
+            led.set_color(color)?;
+            self.written = Some(color);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::RecordingLed;
+
+    const GREEN: RGB8 = RGB8::new(0, 255, 0);
+    const OFF: RGB8 = RGB8::new(0, 0, 0);
+
+    fn sequence(heartbeat: &mut Heartbeat, ticks: usize) -> Vec<bool> {
+        (0..ticks).map(|_| heartbeat.next_color() != OFF).collect()
+    }
+
+    #[test]
+    fn test_double_blink_then_pause() {
+        let mut heartbeat = Heartbeat::new(GREEN).with_timing(1, 2, 3);
+        assert_eq!(heartbeat.cycle_ticks(), 7);
+        assert_eq!(
+            sequence(&mut heartbeat, 14),
+            [
+                true, false, false, true, false, false, false, //
+                true, false, false, true, false, false, false,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_default_timing() {
+        let mut heartbeat = Heartbeat::new(GREEN);
+        assert_eq!(heartbeat.cycle_ticks(), 25);
+        let lit = sequence(&mut heartbeat, 25);
+        assert_eq!(lit.iter().filter(|&&on| on).count(), 4);
+        assert_eq!(&lit[..7], [true, true, false, false, false, true, true]);
+    }
+
+    #[test]
+    fn test_zero_timing_counts_as_one() {
+        let mut heartbeat = Heartbeat::new(GREEN).with_timing(0, 0, 0);
+        assert_eq!(sequence(&mut heartbeat, 4), [true, false, true, false]);
+    }
+
+    #[test]
+    fn test_tick_writes_only_changes() {
+        let mut led = RecordingLed::new();
+        let mut heartbeat = Heartbeat::new(GREEN).with_timing(2, 1, 2);
+        for _ in 0..7 {
+            heartbeat.tick(&mut led).unwrap();
+        }
+        assert_eq!(led.colors, [GREEN, OFF, GREEN, OFF]);
+    }
+}
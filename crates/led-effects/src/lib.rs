@@ -1,8 +1,9 @@
-#![cfg_attr(not(test), no_std)]
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
 //! LED animation effects for embedded projects.
 //!
 //! This crate provides reusable animation effects that work with RGB LEDs.
-//! It is `no_std` compatible for embedded use.
+//! It is `no_std` compatible for embedded use; the optional `std` feature
+//! only adds test helpers.
 //!
 //! # StatusLed Trait
 //!
@@ -10,6 +11,22 @@
 //! display status colors. This enables crates like `esp32-wifi-manager` to
 //! show connection status without depending on a specific LED implementation.
 //!
+//! [`StatusLedExt`] adds `off()`, `set_rgb()` and `flash_once()` to every
+//! `StatusLed`. Arrays and pairs of LEDs are status LEDs too; [`Broadcast`]
+//! forwards colors to several LEDs with an explicit [`ErrorStrategy`].
+//! [`Dimmed`] and [`Inverted`] wrap an LED and transform every color written
+//! to it. Drivers with an adjustable brightness also implement
+//! [`DimmableLed`].
+//!
+//! For host tests of status logic, the `std` feature adds `MockStatusLed`,
+//! which records every color and offers assertions on the sequence.
+//!
+//! # AsyncStatusLed (requires `async` feature)
+//!
+//! Drivers whose writes are futures (DMA, RMT) implement `AsyncStatusLed`
+//! instead. Wrap a blocking `StatusLed` in `Blocking` to pass it to code
+//! written against the async trait.
+//!
 //! # SimpleLed (requires `hal` feature, enabled by default)
 //!
 //! For simple on/off GPIO LEDs (not RGB), use the [`SimpleLed`] adapter which
@@ -17,25 +34,116 @@
 //! It is generic over [`embedded_hal::digital::OutputPin`], so it works with
 //! any HAL or test mock.
 //!
+//! Discrete RGB LEDs with one pin per channel use [`RgbGpioLed`] instead;
+//! both support active-low wiring through [`Polarity`]. [`PwmLed`] dims a
+//! single LED through `embedded_hal::pwm::SetDutyCycle`, and [`PwmRgbLed`]
+//! mixes any color on a discrete RGB LED with three PWM channels.
+//!
+//! # Blink Patterns
+//!
+//! A [`BlinkPattern`] describes a sequence of colors and durations, e.g.
+//! "three red blinks", and a [`PatternPlayer`] plays it on any `StatusLed`
+//! one tick at a time. [`ErrorBlinkEncoder`] blinks numeric error codes and
+//! [`Heartbeat`] shows that the firmware is alive. [`MorsePattern`] blinks
+//! short diagnostics in Morse code.
+//!
+//! # StatusIndicator
+//!
+//! [`StatusIndicator`] shows a connection [`Status`] (connecting, connected,
+//! error code, ...) with built-in colors and blink timings. Temporary overlays
+//! with a [`Priority`] interrupt the base status and revert after a timeout.
+//! On a [`DimmableLed`] the indicator's brightness can be adjusted as well.
+//! A [`TransitionFader`] blends between colors, inside the indicator or on its own.
+//!
+//! For plain notifications, [`TimedStatus`] shows a color for a number of
+//! ticks and then reverts to an idle color. [`ColorThresholds`] turns a
+//! measured value, such as the battery voltage, into a status color with
+//! hysteresis.
+//!
+//! # Colors
+//!
+//! The [`colors`] module names the basic web colors and the status colors
+//! (`STATUS_OK`, `STATUS_ERROR`, ...) used by the indicator. It is a
+//! re-export of `ws2812_pure::colors`, so drivers share the same constants.
+//!
 //! # PulseEffect
 //!
-//! The [`PulseEffect`] creates smooth pulsing brightness animations.
+//! The [`PulseEffect`] creates smooth pulsing brightness animations, with a
+//! triangle or sine [`PulseShape`], stepped per call or driven by elapsed time.
+//!
+//! With the `ferriswheel` feature, `EffectLed` plays any `ferriswheel` ring
+//! effect configured for one LED on a status LED.
+//!
+//! # Prelude
+//!
+//! `use led_effects::prelude::*;` imports the trait, the effect and the
+//! brightness helpers in one line; see [`prelude`].
 
 use rgb::RGB8;
-
+use ws2812_pure::QUARTER_SINE;
+
+mod adapters;
+#[cfg(feature = "async")]
+mod async_status;
+mod blink_code;
+mod broadcast;
+#[cfg(feature = "ferriswheel")]
+mod effect_led;
+mod ext;
+mod fader;
+mod heartbeat;
+#[cfg(feature = "std")]
+mod mock;
+mod morse;
+mod pattern;
+pub mod prelude;
+#[cfg(feature = "hal")]
+mod pwm_led;
+#[cfg(feature = "hal")]
+mod rgb_gpio_led;
 #[cfg(feature = "hal")]
 mod simple_led;
-
+mod status;
+#[cfg(test)]
+mod testing;
+mod thresholds;
+mod timed;
+
+pub use adapters::{Dimmed, Inverted};
+#[cfg(feature = "async")]
+pub use async_status::{AsyncStatusLed, Blocking};
+pub use blink_code::ErrorBlinkEncoder;
+pub use broadcast::{Broadcast, BroadcastError, ErrorStrategy, PairError};
+#[cfg(feature = "ferriswheel")]
+pub use effect_led::EffectLed;
+pub use ext::{FlashOnce, StatusLedExt};
+pub use fader::TransitionFader;
+pub use heartbeat::Heartbeat;
+#[cfg(feature = "std")]
+pub use mock::{MockStatusLed, Recorded};
+pub use morse::MorsePattern;
+pub use pattern::{BlinkPattern, BlinkStep, PatternPlayer, Repeat};
+#[cfg(feature = "hal")]
+pub use pwm_led::{PwmLed, PwmRgbLed};
+#[cfg(feature = "hal")]
+pub use rgb_gpio_led::RgbGpioLed;
 #[cfg(feature = "hal")]
 pub use simple_led::SimpleLed;
+pub use status::{Priority, Status, StatusIndicator};
+pub use thresholds::{ColorThresholds, ThresholdError};
+pub use timed::TimedStatus;
+pub use ws2812_pure::colors;
 
 /// Error type for PulseEffect configuration.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum PulseEffectError {
     /// min must be less than max
     InvalidRange { min: u8, max: u8 },
     /// step must be greater than 0
     ZeroStep,
+    /// period must be greater than 0
+    ZeroPeriod,
 }
 
 impl core::fmt::Display for PulseEffectError {
@@ -51,6 +159,9 @@ impl core::fmt::Display for PulseEffectError {
             PulseEffectError::ZeroStep => {
                 write!(f, "step must be greater than 0")
             }
+            PulseEffectError::ZeroPeriod => {
+                write!(f, "period must be greater than 0")
+            }
         }
     }
 }
@@ -85,6 +196,63 @@ pub trait StatusLed {
     fn set_color(&mut self, color: RGB8) -> Result<(), Self::Error>;
 }
 
+/// Status LED with an adjustable overall brightness.
+///
+/// This is a separate trait so existing `StatusLed` implementations keep
+/// working unchanged. The brightness scales every color written afterwards;
+/// the color currently shown is not rewritten.
+///
+/// PWM LEDs and [`Dimmed`] dim for real. On/off LEDs such as `SimpleLed`
+/// implement the trait as well, so they fit the same code, but ignore the
+/// brightness and report `false` from
+/// [`is_dimmable`](DimmableLed::is_dimmable). Wrap a WS2812 driver in
+/// [`Dimmed`] to make it dimmable.
+///
+/// # Example
+///
+/// ```
+/// use led_effects::{DimmableLed, Dimmed, StatusLed};
+/// use rgb::RGB8;
+///
+/// # struct Led;
+/// # impl StatusLed for Led {
+/// #     type Error = ();
+/// #     fn set_color(&mut self, _color: RGB8) -> Result<(), ()> { Ok(()) }
+/// # }
+/// fn night_mode<L: DimmableLed>(led: &mut L) {
+///     led.set_brightness(16);
+/// }
+///
+/// let mut led = Dimmed::new(Led, 255);
+/// night_mode(&mut led);
+/// assert_eq!(led.brightness(), 16);
+/// ```
+pub trait DimmableLed: StatusLed {
+    /// Sets the brightness applied to the following colors (0-255, 255 = full).
+    fn set_brightness(&mut self, brightness: u8);
+
+    /// Returns the brightness applied to colors.
+    fn brightness(&self) -> u8;
+
+    /// Returns `true` if the brightness has a visible effect.
+    fn is_dimmable(&self) -> bool {
+        true
+    }
+}
+
+/// Which pin level turns a GPIO-driven LED on.
+///
+/// Many boards sink the LED into the pin, so driving the pin low turns it on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Polarity {
+    /// A high pin level turns the LED on.
+    #[default]
+    ActiveHigh,
+    /// A low pin level turns the LED on.
+    ActiveLow,
+}
+
 /// Default brightness threshold for simple on/off LED decisions.
 pub const DEFAULT_BRIGHTNESS_THRESHOLD: u8 = 10;
 
@@ -107,6 +275,47 @@ pub fn max_channel_brightness(color: RGB8) -> u8 {
     color.r.max(color.g).max(color.b)
 }
 
+/// Calculates the approximate perceived brightness of an RGB color.
+///
+/// Weighs the channels by how bright they appear to the eye (Rec. 709
+/// coefficients), so pure green is far brighter than pure blue.
+///
+/// # Example
+///
+/// ```
+/// use led_effects::luminance;
+/// use rgb::RGB8;
+///
+/// assert_eq!(luminance(RGB8::new(255, 255, 255)), 255);
+/// assert!(luminance(RGB8::new(0, 255, 0)) > luminance(RGB8::new(0, 0, 255)));
+/// ```
+#[inline]
+pub fn luminance(color: RGB8) -> u8 {
+    // 54 + 183 + 19 = 256, so white maps to exactly 255
+    ((54 * color.r as u16 + 183 * color.g as u16 + 19 * color.b as u16) >> 8) as u8
+}
+
+/// How a color is reduced to a single brightness level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum BrightnessMode {
+    /// The brightest channel, see [`max_channel_brightness`].
+    #[default]
+    MaxChannel,
+    /// The perceived brightness, see [`luminance`].
+    Luminance,
+}
+
+impl BrightnessMode {
+    /// Returns the brightness level of `color` in this mode.
+    pub fn level(self, color: RGB8) -> u8 {
+        match self {
+            BrightnessMode::MaxChannel => max_channel_brightness(color),
+            BrightnessMode::Luminance => luminance(color),
+        }
+    }
+}
+
 /// Determines if an RGB color exceeds a brightness threshold.
 ///
 /// Returns `true` if any channel is strictly greater than the threshold.
@@ -128,18 +337,49 @@ pub fn exceeds_threshold(color: RGB8, threshold: u8) -> bool {
     max_channel_brightness(color) > threshold
 }
 
+/// Shape of the brightness curve of a [`PulseEffect`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PulseShape {
+    /// Brightness ramps linearly up and down.
+    #[default]
+    Triangle,
+    /// Brightness eases in and out along a sine curve, lingering briefly at
+    /// the dim and bright ends like breathing.
+    Sine,
+}
+
+/// Eases a position `t` (0-255) along `(1 - cos(t * 180°)) / 2`.
+fn sine_ease(t: u8) -> u8 {
+    // (1 - cos(x)) / 2 = sin²(x / 2)
+    let s = QUARTER_SINE[(t as usize * 64 + 127) / 255] as u16;
+    (s * s / 255) as u8
+}
+
 /// A pulsing brightness effect that smoothly oscillates between dim and bright.
 ///
+/// [`update`](PulseEffect::update) moves the brightness by a fixed step per
+/// call, so the speed depends on the frame rate.
+/// [`update_dt`](PulseEffect::update_dt) instead advances by the elapsed
+/// milliseconds and completes one pulse per configured period.
+///
 /// # Example
 ///
 /// ```
-/// use led_effects::PulseEffect;
+/// use led_effects::{PulseEffect, PulseShape};
 ///
 /// let mut pulse = PulseEffect::new();
 /// let base_color = (255, 0, 0); // Red
 ///
 /// // Call update() in your main loop to get the next animation frame
 /// let current_color = pulse.update(base_color);
+///
+/// // Or breathe once every 3 seconds, independent of the frame rate
+/// let mut breathing = PulseEffect::new()
+///     .with_shape(PulseShape::Sine)
+///     .with_period_ms(3_000)
+///     .unwrap();
+/// let current_color = breathing.update_dt(base_color, 20);
 /// ```
 #[derive(Debug)]
 pub struct PulseEffect {
@@ -148,6 +388,12 @@ pub struct PulseEffect {
     min_brightness: u8,
     max_brightness: u8,
     step: u8,
+    shape: PulseShape,
+    period_ms: u32,
+    phase_ms: u32,
+    peak_dwell: u16,
+    min_dwell: u16,
+    holding: u16,
 }
 
 impl Default for PulseEffect {
@@ -159,7 +405,8 @@ impl Default for PulseEffect {
 impl PulseEffect {
     /// Creates a new pulse effect with default parameters.
     ///
-    /// Default range: 0-30 brightness, step size: 2
+    /// Default range: 0-30 brightness, step size: 2, triangle shape, no
+    /// dwell, period for [`update_dt`](Self::update_dt): 2000 ms
     pub fn new() -> Self {
         Self {
             brightness: 0,
@@ -167,6 +414,12 @@ impl PulseEffect {
             min_brightness: 2,
             max_brightness: 30,
             step: 2,
+            shape: PulseShape::Triangle,
+            period_ms: 2_000,
+            phase_ms: 0,
+            peak_dwell: 0,
+            min_dwell: 0,
+            holding: 0,
         }
     }
 
@@ -193,13 +446,89 @@ impl PulseEffect {
 
         Ok(Self {
             brightness: min,
-            increasing: true,
             min_brightness: min,
             max_brightness: max,
             step,
+            ..Self::new()
         })
     }
 
+    /// Sets the shape of the brightness curve.
+    pub fn with_shape(mut self, shape: PulseShape) -> Self {
+        self.shape = shape;
+        self
+    }
+
+    /// Holds the brightness for extra [`update`](Self::update) calls when it
+    /// reaches the peak and the minimum, before turning around.
+    ///
+    /// A short pause at full brightness makes status "breathing" look calm
+    /// instead of bouncing off the ends.
+    pub fn with_dwell(mut self, peak_ticks: u16, min_ticks: u16) -> Self {
+        self.peak_dwell = peak_ticks;
+        self.min_dwell = min_ticks;
+        self
+    }
+
+    /// Sets the duration of one full pulse (dim, bright, dim) for
+    /// [`update_dt`](Self::update_dt).
+    ///
+    /// # Errors
+    ///
+    /// Returns `PulseEffectError::ZeroPeriod` if `period_ms` is 0.
+    pub fn with_period_ms(mut self, period_ms: u32) -> Result<Self, PulseEffectError> {
+        if period_ms == 0 {
+            return Err(PulseEffectError::ZeroPeriod);
+        }
+        self.period_ms = period_ms;
+        self.phase_ms %= period_ms;
+        Ok(self)
+    }
+
+    /// Returns the shape of the brightness curve.
+    pub fn shape(&self) -> PulseShape {
+        self.shape
+    }
+
+    /// Returns the duration of one full pulse for [`update_dt`](Self::update_dt).
+    pub fn period_ms(&self) -> u32 {
+        self.period_ms
+    }
+
+    /// Returns the number of extra updates held at the peak.
+    pub fn peak_dwell(&self) -> u16 {
+        self.peak_dwell
+    }
+
+    /// Returns the number of extra updates held at the minimum.
+    pub fn min_dwell(&self) -> u16 {
+        self.min_dwell
+    }
+
+    /// Advances the pulse by `dt_ms` milliseconds and returns the color at
+    /// the new position.
+    ///
+    /// Unlike [`update`](Self::update), the speed does not depend on how
+    /// often this is called: one full pulse takes the configured period.
+    pub fn update_dt(&mut self, rgb: (u8, u8, u8), dt_ms: u32) -> RGB8 {
+        self.phase_ms = ((self.phase_ms as u64 + dt_ms as u64) % self.period_ms as u64) as u32;
+
+        // Rise during the first half of the period, fall during the second
+        let range = (self.max_brightness - self.min_brightness) as u64;
+        let period = self.period_ms as u64;
+        let half = (period / 2).max(1);
+        let phase = self.phase_ms as u64;
+        self.increasing = phase < half;
+        let offset = if self.increasing {
+            phase * range / half
+        } else {
+            (period - phase) * range / (period - half)
+        };
+
+        self.brightness = self.min_brightness + offset as u8;
+        self.scale(rgb)
+    }
+
     /// Updates the effect state and returns the next color frame.
     ///
     /// Call this method repeatedly in your animation loop.
@@ -212,20 +541,20 @@ impl PulseEffect {
     ///
     /// The color is scaled by the current brightness level
     pub fn update(&mut self, rgb: (u8, u8, u8)) -> RGB8 {
-        let color = RGB8::new(
-            ((rgb.0 as u16 * self.brightness as u16) / 255) as u8,
-            ((rgb.1 as u16 * self.brightness as u16) / 255) as u8,
-            ((rgb.2 as u16 * self.brightness as u16) / 255) as u8,
-        );
+        let color = self.scale(rgb);
 
-        if self.increasing {
+        if self.holding > 0 {
+            self.holding -= 1;
+        } else if self.increasing {
             if self.brightness >= self.max_brightness {
                 self.increasing = false;
+                self.holding = self.peak_dwell;
             } else {
                 self.brightness = self.brightness.saturating_add(self.step);
             }
         } else if self.brightness <= self.min_brightness {
             self.increasing = true;
+            self.holding = self.min_dwell;
         } else {
             self.brightness = self.brightness.saturating_sub(self.step);
         }
@@ -236,9 +565,27 @@ impl PulseEffect {
     /// Returns the current brightness level (0-255).
     ///
     /// This value oscillates between `min_brightness` and `max_brightness`
-    /// as `update()` is called repeatedly.
+    /// as `update()` is called repeatedly, following the configured shape.
     pub fn brightness(&self) -> u8 {
-        self.brightness
+        match self.shape {
+            PulseShape::Triangle => self.brightness,
+            PulseShape::Sine => {
+                let (min, max) = (self.min_brightness, self.max_brightness);
+                let range = (max - min) as u16;
+                let t = (self.brightness.clamp(min, max) - min) as u16 * 255 / range;
+                min + (sine_ease(t as u8) as u16 * range / 255) as u8
+            }
+        }
+    }
+
+    /// Scales `rgb` by the current brightness.
+    fn scale(&self, rgb: (u8, u8, u8)) -> RGB8 {
+        let brightness = self.brightness() as u16;
+        RGB8::new(
+            ((rgb.0 as u16 * brightness) / 255) as u8,
+            ((rgb.1 as u16 * brightness) / 255) as u8,
+            ((rgb.2 as u16 * brightness) / 255) as u8,
+        )
     }
 
     /// Resets the effect to its initial state.
@@ -246,11 +593,14 @@ impl PulseEffect {
     /// After calling this method:
     /// - Brightness is set to `min_brightness`
     /// - Direction is set to increasing
+    /// - The [`update_dt`](Self::update_dt) clock starts over
     ///
     /// Use this to restart the pulse animation from the beginning.
     pub fn reset(&mut self) {
         self.brightness = self.min_brightness;
         self.increasing = true;
+        self.phase_ms = 0;
+        self.holding = 0;
     }
 }
 
@@ -315,12 +665,54 @@ mod tests {
             assert!(led.pin.is_high);
         }
 
+        #[test]
+        fn test_simple_led_active_low_drives_pin_low_when_on() {
+            let mut led = SimpleLed::new_active_low(MockPin::new());
+            assert_eq!(led.polarity(), Polarity::ActiveLow);
+            led.set_color(RGB8::new(0, 255, 0)).unwrap();
+            assert!(!led.pin.is_high);
+            led.set_color(RGB8::new(0, 0, 0)).unwrap();
+            assert!(led.pin.is_high);
+        }
+
+        #[test]
+        fn test_simple_led_set_polarity() {
+            let mut led = SimpleLed::with_threshold(MockPin::new(), 100);
+            led.set_polarity(Polarity::ActiveLow);
+            led.set_color(RGB8::new(50, 50, 50)).unwrap();
+            assert!(led.pin.is_high);
+        }
+
+        #[test]
+        fn test_simple_led_luminance_threshold() {
+            let mut led = SimpleLed::with_luminance_threshold(MockPin::new(), 20);
+            assert_eq!(led.mode(), BrightnessMode::Luminance);
+
+            // Dim blue is hard to see and stays off
+            led.set_color(RGB8::new(0, 0, 60)).unwrap();
+            assert!(!led.pin.is_high);
+
+            // Dark yellow with a lower max channel is clearly visible
+            led.set_color(RGB8::new(40, 40, 0)).unwrap();
+            assert!(led.pin.is_high);
+        }
+
         #[test]
         fn test_simple_led_at_threshold_stays_off() {
             let mut led = SimpleLed::with_threshold(MockPin::new(), 100);
             led.set_color(RGB8::new(100, 100, 100)).unwrap();
             assert!(!led.pin.is_high);
         }
+
+        #[test]
+        fn test_simple_led_ignores_brightness() {
+            let mut led = SimpleLed::new(MockPin::new());
+            assert!(!led.is_dimmable());
+            led.set_brightness(0);
+            assert_eq!(led.brightness(), 255);
+            led.set_color(RGB8::new(255, 0, 0)).unwrap();
+            assert!(led.pin.is_high);
+        }
     }
 
     #[test]
@@ -345,6 +737,22 @@ mod tests {
         assert_eq!(max_channel_brightness(RGB8::new(255, 255, 255)), 255);
     }
 
+    #[test]
+    fn test_luminance_weights_channels() {
+        assert_eq!(luminance(RGB8::new(0, 0, 0)), 0);
+        assert_eq!(luminance(RGB8::new(255, 255, 255)), 255);
+        assert_eq!(luminance(RGB8::new(255, 0, 0)), 53);
+        assert_eq!(luminance(RGB8::new(0, 255, 0)), 182);
+        assert_eq!(luminance(RGB8::new(0, 0, 255)), 18);
+    }
+
+    #[test]
+    fn test_brightness_mode_level() {
+        let color = RGB8::new(0, 0, 200);
+        assert_eq!(BrightnessMode::MaxChannel.level(color), 200);
+        assert_eq!(BrightnessMode::Luminance.level(color), luminance(color));
+    }
+
     #[test]
     fn test_exceeds_threshold_above() {
         assert!(exceeds_threshold(RGB8::new(0, 0, 15), 10));
@@ -401,6 +809,85 @@ mod tests {
         assert_eq!(err, PulseEffectError::ZeroStep);
     }
 
+    #[test]
+    fn test_sine_shape_eases_at_the_ends() {
+        let mut pulse = PulseEffect::with_range(0, 255, 16)
+            .unwrap()
+            .with_shape(PulseShape::Sine);
+        let levels: Vec<u8> = (0..17)
+            .map(|_| {
+                pulse.update((255, 255, 255));
+                pulse.brightness()
+            })
+            .collect();
+
+        // Small changes near the ends, large ones in the middle
+        assert!(levels[1] - levels[0] < 10);
+        assert!(levels[8] - levels[7] > 20);
+        assert_eq!(*levels.last().unwrap(), 255);
+    }
+
+    #[test]
+    fn test_sine_and_triangle_share_endpoints() {
+        let sine = PulseEffect::with_range(10, 200, 5)
+            .unwrap()
+            .with_shape(PulseShape::Sine);
+        assert_eq!(sine.brightness(), 10);
+        assert_eq!(sine_ease(0), 0);
+        assert_eq!(sine_ease(255), 255);
+        assert_eq!(sine_ease(128), 127);
+    }
+
+    #[test]
+    fn test_dwell_holds_peak_and_min() {
+        let mut pulse = PulseEffect::with_range(0, 20, 10).unwrap().with_dwell(3, 1);
+        assert_eq!((pulse.peak_dwell(), pulse.min_dwell()), (3, 1));
+        let levels: Vec<u8> = (0..13).map(|_| pulse.update((255, 0, 0)).r).collect();
+        assert_eq!(levels, [0, 10, 20, 20, 20, 20, 20, 10, 0, 0, 0, 10, 20]);
+    }
+
+    #[test]
+    fn test_reset_clears_dwell() {
+        let mut pulse = PulseEffect::with_range(0, 20, 10).unwrap().with_dwell(5, 0);
+        for _ in 0..4 {
+            pulse.update((255, 0, 0));
+        }
+        pulse.reset();
+        assert_eq!(pulse.update((255, 0, 0)).r, 0);
+        assert_eq!(pulse.update((255, 0, 0)).r, 10);
+    }
+
+    #[test]
+    fn test_update_dt_follows_period() {
+        let mut pulse = PulseEffect::with_range(0, 200, 1)
+            .unwrap()
+            .with_period_ms(1_000)
+            .unwrap();
+        assert_eq!(pulse.update_dt((255, 255, 255), 250).r, 100);
+        assert_eq!(pulse.update_dt((255, 255, 255), 250).r, 200);
+        assert_eq!(pulse.update_dt((255, 255, 255), 250).r, 100);
+        assert_eq!(pulse.update_dt((255, 255, 255), 250).r, 0);
+    }
+
+    #[test]
+    fn test_update_dt_is_frame_rate_independent() {
+        let mut fast = PulseEffect::new().with_period_ms(800).unwrap();
+        let mut slow = PulseEffect::new().with_period_ms(800).unwrap();
+        for _ in 0..30 {
+            fast.update_dt((255, 0, 0), 10);
+        }
+        for _ in 0..3 {
+            slow.update_dt((255, 0, 0), 100);
+        }
+        assert_eq!(fast.brightness(), slow.brightness());
+    }
+
+    #[test]
+    fn test_with_period_rejects_zero() {
+        let err = PulseEffect::new().with_period_ms(0).unwrap_err();
+        assert_eq!(err, PulseEffectError::ZeroPeriod);
+    }
+
     #[test]
     fn test_error_display() {
         let err = PulseEffectError::InvalidRange { min: 100, max: 10 };
@@ -411,5 +898,8 @@ mod tests {
 
         let err = PulseEffectError::ZeroStep;
         assert_eq!(format!("{}", err), "step must be greater than 0");
+
+        let err = PulseEffectError::ZeroPeriod;
+        assert_eq!(format!("{}", err), "period must be greater than 0");
     }
 }
@@ -0,0 +1,196 @@
+//! Test double for code that drives a status LED.
+//!
+//! [`MockStatusLed`] records every color written to it, so status logic can
+//! be unit tested on the host without an LED.
+
+use crate::StatusLed;
+use core::convert::Infallible;
+use rgb::RGB8;
+
+/// A color written to a [`MockStatusLed`] and the tick it was written at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Recorded {
+    /// Value of the mock's tick counter when the color was written.
+    pub tick: u32,
+    /// The color that was written.
+    pub color: RGB8,
+}
+
+/// Status LED that records every color set on it.
+///
+/// The mock keeps a tick counter that the test advances with
+/// [`advance`](MockStatusLed::advance); every write is stored together with
+/// the tick it happened at.
+///
+/// # Example
+///
+/// ```
+/// use led_effects::colors::{BLACK, RED};
+/// use led_effects::{MockStatusLed, StatusLed, StatusLedExt};
+///
+/// let mut led = MockStatusLed::new();
+/// led.set_color(RED).unwrap();
+/// led.advance(10);
+/// led.off().unwrap();
+///
+/// led.assert_sequence(&[RED, BLACK]);
+/// assert_eq!(led.last_color(), Some(BLACK));
+/// assert_eq!(led.color_at(5), Some(RED));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MockStatusLed {
+    history: Vec<Recorded>,
+    tick: u32,
+}
+
+impl MockStatusLed {
+    /// Creates a mock with an empty history at tick 0.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Moves the tick counter ahead by `ticks`.
+    pub fn advance(&mut self, ticks: u32) {
+        self.tick = self.tick.saturating_add(ticks);
+    }
+
+    /// Returns the current value of the tick counter.
+    pub fn current_tick(&self) -> u32 {
+        self.tick
+    }
+
+    /// Returns every write in order.
+    pub fn history(&self) -> &[Recorded] {
+        &self.history
+    }
+
+    /// Returns the written colors in order, without their ticks.
+    pub fn colors(&self) -> Vec<RGB8> {
+        self.history.iter().map(|record| record.color).collect()
+    }
+
+    /// Returns the last color written, or `None` if nothing was written.
+    pub fn last_color(&self) -> Option<RGB8> {
+        self.history.last().map(|record| record.color)
+    }
+
+    /// Returns the color the LED showed at `tick`: the last color written at
+    /// or before it.
+    pub fn color_at(&self, tick: u32) -> Option<RGB8> {
+        self.history
+            .iter()
+            .take_while(|record| record.tick <= tick)
+            .last()
+            .map(|record| record.color)
+    }
+
+    /// Returns the number of writes.
+    pub fn write_count(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Forgets all writes; the tick counter keeps running.
+    pub fn clear(&mut self) {
+        self.history.clear();
+    }
+
+    /// Asserts that exactly `expected` was written, in order.
+    ///
+    /// # Panics
+    ///
+    /// Panics with the expected and the written colors if they differ.
+    #[track_caller]
+    pub fn assert_sequence(&self, expected: &[RGB8]) {
+        let colors = self.colors();
+        assert!(
+            colors == expected,
+            "unexpected status LED colors\n  expected: {:?}\n   written: {:?}",
+            expected,
+            colors
+        );
+    }
+}
+
+impl StatusLed for MockStatusLed {
+    type Error = Infallible;
+
+    fn set_color(&mut self, color: RGB8) -> Result<(), Self::Error> {
+        self.history.push(Recorded {
+            tick: self.tick,
+            color,
+        });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::colors::{BLUE, LIME, RED};
+    use crate::{Status, StatusIndicator};
+
+    #[test]
+    fn test_records_colors_with_ticks() {
+        let mut led = MockStatusLed::new();
+        led.set_color(RED).unwrap();
+        led.advance(3);
+        led.set_color(BLUE).unwrap();
+        assert_eq!(
+            led.history(),
+            [
+                Recorded {
+                    tick: 0,
+                    color: RED
+                },
+                Recorded {
+                    tick: 3,
+                    color: BLUE
+                },
+            ]
+        );
+        assert_eq!(led.write_count(), 2);
+        assert_eq!(led.current_tick(), 3);
+    }
+
+    #[test]
+    fn test_color_at() {
+        let mut led = MockStatusLed::new();
+        led.advance(2);
+        assert_eq!(led.color_at(5), None);
+        led.set_color(RED).unwrap();
+        led.advance(5);
+        led.set_color(BLUE).unwrap();
+        assert_eq!(led.color_at(1), None);
+        assert_eq!(led.color_at(2), Some(RED));
+        assert_eq!(led.color_at(6), Some(RED));
+        assert_eq!(led.color_at(7), Some(BLUE));
+    }
+
+    #[test]
+    fn test_clear_keeps_tick() {
+        let mut led = MockStatusLed::new();
+        led.set_color(RED).unwrap();
+        led.advance(4);
+        led.clear();
+        assert_eq!(led.last_color(), None);
+        assert_eq!(led.current_tick(), 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "unexpected status LED colors")]
+    fn test_assert_sequence_fails_on_mismatch() {
+        let mut led = MockStatusLed::new();
+        led.set_color(RED).unwrap();
+        led.assert_sequence(&[BLUE]);
+    }
+
+    #[test]
+    fn test_drives_status_indicator() {
+        let mut indicator = StatusIndicator::new(MockStatusLed::new());
+        indicator.set_status(Status::Connected);
+        for _ in 0..5 {
+            indicator.tick().unwrap();
+        }
+        indicator.led().assert_sequence(&[LIME]);
+    }
+}
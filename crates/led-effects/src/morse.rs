@@ -0,0 +1,346 @@
+//! Short messages in Morse code.
+//!
+//! Devices without a display can still report short diagnostics, such as the
+//! last octet of their IP address, by blinking them in Morse code.
+//! [`MorsePattern`] turns a string into dits and dahs with standard spacing
+//! and plays it one tick at a time.
+
+use crate::pattern::Repeat;
+use crate::StatusLed;
+use rgb::RGB8;
+
+/// Dits (`.`) and dahs (`-`) of the letters A-Z.
+const LETTERS: [&str; 26] = [
+    ".-", "-...", "-.-.", "-..", ".", "..-.", "--.", "....", "..", ".---", "-.-", ".-..", "--",
+    "-.", "---", ".--.", "--.-", ".-.", "...", "-", "..-", "...-", ".--", "-..-", "-.--", "--..",
+];
+
+/// Dits and dahs of the digits 0-9.
+const DIGITS: [&str; 10] = [
+    "-----", ".----", "..---", "...--", "....-", ".....", "-....", "--...", "---..", "----.",
+];
+
+/// Returns the dits and dahs of `c`, or `None` if it has no Morse code.
+fn code(c: char) -> Option<&'static str> {
+    match c.to_ascii_uppercase() {
+        c @ 'A'..='Z' => Some(LETTERS[c as usize - 'A' as usize]),
+        c @ '0'..='9' => Some(DIGITS[c as usize - '0' as usize]),
+        '.' => Some(".-.-.-"),
+        ',' => Some("--..--"),
+        '?' => Some("..--.."),
+        '/' => Some("-..-."),
+        '-' => Some("-....-"),
+        '=' => Some("-...-"),
+        _ => None,
+    }
+}
+
+/// Blinks a string in Morse code on a [`StatusLed`].
+///
+/// Timing follows the standard in units of [`with_unit`](Self::with_unit)
+/// ticks: a dit is lit for 1 unit and a dah for 3, with 1 unit between the
+/// elements of a character, 3 between characters and 7 between words.
+/// Letters are case-insensitive; characters without a Morse code are skipped.
+/// Every pass ends with a word gap, so repeats stay readable.
+///
+/// The LED is only written when its color changes, and is turned off once the
+/// message has been repeated the configured number of times.
+///
+/// # Default Configuration
+///
+/// - Color: white
+/// - Unit: 4 ticks (200 ms at 50 ms per tick, about 6 words per minute)
+/// - Repeat: forever
+///
+/// # Example
+///
+/// ```
+/// use led_effects::{MorsePattern, Repeat, StatusLed};
+/// use rgb::RGB8;
+///
+/// # struct Led;
+/// # impl StatusLed for Led {
+/// #     type Error = ();
+/// #     fn set_color(&mut self, _color: RGB8) -> Result<(), ()> { Ok(()) }
+/// # }
+/// # let mut led = Led;
+/// let mut morse = MorsePattern::new("IP 42")
+///     .with_color(RGB8::new(0, 0, 255))
+///     .with_repeat(Repeat::Times(2));
+///
+/// while !morse.is_finished() {
+///     morse.tick(&mut led).unwrap();
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct MorsePattern<'a> {
+    text: &'a str,
+    color: RGB8,
+    unit_ticks: u16,
+    repeat: Repeat,
+    /// Byte offset of the character being sent.
+    position: usize,
+    /// Index of the next element of that character.
+    element: usize,
+    lit: bool,
+    remaining: u32,
+    passes: u16,
+    /// Set when the last pass has started its final gap.
+    finished: bool,
+    written: Option<RGB8>,
+}
+
+impl<'a> MorsePattern<'a> {
+    /// Creates a pattern that blinks `text` in white, repeating forever.
+    pub fn new(text: &'a str) -> Self {
+        let mut pattern = Self {
+            text,
+            color: RGB8::new(255, 255, 255),
+            unit_ticks: 4,
+            repeat: Repeat::Forever,
+            position: 0,
+            element: 0,
+            lit: false,
+            remaining: 0,
+            passes: 0,
+            finished: false,
+            written: None,
+        };
+        pattern.reset();
+        pattern
+    }
+
+    /// Sets the blink color.
+    pub fn with_color(mut self, color: RGB8) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Sets the length of one unit (a dit) in ticks. Values below 1 count as 1.
+    pub fn with_unit(mut self, unit_ticks: u16) -> Self {
+        self.unit_ticks = unit_ticks.max(1);
+        self
+    }
+
+    /// Sets how often the message is sent.
+    pub fn with_repeat(mut self, repeat: Repeat) -> Self {
+        self.repeat = repeat;
+        self.reset();
+        self
+    }
+
+    /// Returns the message.
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    /// Returns the blink color.
+    pub fn color(&self) -> RGB8 {
+        self.color
+    }
+
+    /// Returns the length of one unit in ticks.
+    pub fn unit_ticks(&self) -> u16 {
+        self.unit_ticks
+    }
+
+    /// Returns `true` once the message has been sent as often as configured.
+    ///
+    /// Messages without any Morse characters finish immediately.
+    pub fn is_finished(&self) -> bool {
+        self.finished && self.remaining == 0
+    }
+
+    /// Starts the message over from its first character.
+    pub fn reset(&mut self) {
+        self.position = 0;
+        self.element = 0;
+        self.lit = false;
+        self.remaining = 0;
+        self.passes = 0;
+        self.finished =
+            self.repeat == Repeat::Times(0) || !self.text.chars().any(|c| code(c).is_some());
+    }
+
+    /// Forgets the last written color, so the next tick writes the LED even
+    /// if the color did not change.
+    pub fn invalidate(&mut self) {
+        self.written = None;
+    }
+
+    /// Returns the color for this tick and moves one tick ahead.
+    ///
+    /// Returns `None` once the message has finished.
+    pub fn next_color(&mut self) -> Option<RGB8> {
+        while self.remaining == 0 {
+            if self.finished {
+                return None;
+            }
+            self.next_segment();
+        }
+
+        self.remaining -= 1;
+        Some(if self.lit {
+            self.color
+        } else {
+            RGB8::default()
+        })
+    }
+
+    /// Writes the color for this tick to `led` and moves one tick ahead.
+    ///
+    /// # Errors
+    ///
+    /// Returns the LED's error if writing the color fails.
+    pub fn tick<L: StatusLed>(&mut self, led: &mut L) -> Result<(), L::Error> {
+        let color = self.next_color().unwrap_or_default();
+        if self.written != Some(color) {
+            led.set_color(color)?;
+            self.written = Some(color);
+        }
+        Ok(())
+    }
+
+    /// Starts the next lit element or the gap after the current one.
+    fn next_segment(&mut self) {
+        let (lit, units) = if self.lit {
+            (false, self.gap_units())
+        } else {
+            let symbol = self.next_element();
+            (true, if symbol == b'-' { 3 } else { 1 })
+        };
+        self.lit = lit;
+        self.remaining = units * self.unit_ticks as u32;
+    }
+
+    /// Returns the next dit or dah, moving on to the next character and the
+    /// next pass as needed.
+    fn next_element(&mut self) -> u8 {
+        loop {
+            let Some(c) = self.text[self.position..].chars().next() else {
+                // `reset` made sure that the text contains a Morse character
+                self.position = 0;
+                self.element = 0;
+                continue;
+            };
+            match code(c).map(str::as_bytes) {
+                Some(symbols) if self.element < symbols.len() => {
+                    self.element += 1;
+                    return symbols[self.element - 1];
+                }
+                _ => {
+                    self.position += c.len_utf8();
+                    self.element = 0;
+                }
+            }
+        }
+    }
+
+    /// Returns the units of darkness after the element that was just sent.
+    fn gap_units(&mut self) -> u32 {
+        let mut rest = self.text[self.position..].chars();
+        let current = rest.next().and_then(code).unwrap_or_default();
+        if self.element < current.len() {
+            return 1;
+        }
+
+        let mut word_gap = false;
+        for c in rest {
+            if c.is_whitespace() {
+                word_gap = true;
+            } else if code(c).is_some() {
+                return if word_gap { 7 } else { 3 };
+            }
+        }
+
+        // End of the message
+        self.passes = self.passes.saturating_add(1);
+        if let Repeat::Times(times) = self.repeat {
+            self.finished = self.passes >= times;
+        }
+        self.position = self.text.len();
+        7
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::RecordingLed;
+
+    const WHITE: RGB8 = RGB8::new(255, 255, 255);
+    const OFF: RGB8 = RGB8::new(0, 0, 0);
+
+    /// Renders one pass with a unit of 1 tick as `#` (lit) and `_` (dark).
+    fn render(text: &str) -> String {
+        let mut morse = MorsePattern::new(text)
+            .with_unit(1)
+            .with_repeat(Repeat::Times(1));
+        let mut out = String::new();
+        while let Some(color) = morse.next_color() {
+            out.push(if color == OFF { '_' } else { '#' });
+        }
+        out
+    }
+
+    #[test]
+    fn test_letter_timing() {
+        // A: dit, dah, then the word gap that ends every pass
+        assert_eq!(render("A"), "#_###_______");
+    }
+
+    #[test]
+    fn test_character_and_word_gaps() {
+        assert_eq!(render("EE"), "#___#_______");
+        assert_eq!(render("E E"), "#_______#_______");
+        assert_eq!(render("e  e"), render("E E"));
+    }
+
+    #[test]
+    fn test_digits() {
+        assert_eq!(render("5"), "#_#_#_#_#_______");
+        assert_eq!(render("0"), "###_###_###_###_###_______");
+    }
+
+    #[test]
+    fn test_unknown_characters_are_skipped() {
+        assert_eq!(render("E#E"), render("EE"));
+        assert!(MorsePattern::new("#~").is_finished());
+        assert_eq!(MorsePattern::new("").next_color(), None);
+    }
+
+    #[test]
+    fn test_unit_scales_timing() {
+        let mut morse = MorsePattern::new("T")
+            .with_unit(2)
+            .with_repeat(Repeat::Times(1));
+        let colors: Vec<RGB8> = core::iter::from_fn(|| morse.next_color()).collect();
+        assert_eq!(colors.len(), 2 * (3 + 7));
+        assert_eq!(
+            &colors[..7],
+            [WHITE, WHITE, WHITE, WHITE, WHITE, WHITE, OFF]
+        );
+    }
+
+    #[test]
+    fn test_repeats_forever_by_default() {
+        let mut morse = MorsePattern::new("E").with_unit(1);
+        for _ in 0..100 {
+            assert!(morse.next_color().is_some());
+        }
+        assert!(!morse.is_finished());
+    }
+
+    #[test]
+    fn test_tick_turns_led_off_when_finished() {
+        let mut led = RecordingLed::new();
+        let mut morse = MorsePattern::new("I")
+            .with_unit(1)
+            .with_repeat(Repeat::Times(2));
+        for _ in 0..30 {
+            morse.tick(&mut led).unwrap();
+        }
+        assert!(morse.is_finished());
+        assert_eq!(led.colors, [WHITE, OFF, WHITE, OFF, WHITE, OFF, WHITE, OFF]);
+    }
+}
@@ -0,0 +1,345 @@
+//! Blink patterns for status LEDs.
+//!
+//! A [`BlinkPattern`] is a list of colors, each shown for a number of ticks,
+//! optionally repeated a fixed number of times. A [`PatternPlayer`] walks
+//! through the pattern one tick at a time and writes the colors to any
+//! [`StatusLed`], e.g. "three red blinks" for a wrong Wi-Fi password.
+//!
+//! Patterns borrow their steps, so they can live in `static`s without an
+//! allocator.
+
+use crate::StatusLed;
+use rgb::RGB8;
+
+/// One step of a [`BlinkPattern`]: a color held for a number of ticks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BlinkStep {
+    /// Color shown during this step.
+    pub color: RGB8,
+    /// Number of ticks the color is shown; steps with 0 ticks are skipped.
+    pub ticks: u16,
+}
+
+impl BlinkStep {
+    /// Creates a step showing `color` for `ticks` ticks.
+    pub const fn new(color: RGB8, ticks: u16) -> Self {
+        Self { color, ticks }
+    }
+
+    /// Creates a step with the LED off for `ticks` ticks.
+    pub const fn off(ticks: u16) -> Self {
+        Self::new(RGB8::new(0, 0, 0), ticks)
+    }
+}
+
+/// How often a [`BlinkPattern`] is played.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Repeat {
+    /// Loop until another pattern is played or the player is stopped.
+    #[default]
+    Forever,
+    /// Play the pattern this many times, then turn the LED off.
+    Times(u16),
+}
+
+/// A sequence of colored steps, repeated forever or a fixed number of times.
+///
+/// # Example
+///
+/// ```
+/// use led_effects::{BlinkPattern, BlinkStep, Repeat};
+/// use rgb::RGB8;
+///
+/// const RED: RGB8 = RGB8::new(255, 0, 0);
+///
+/// // Three short red blinks
+/// static WRONG_PASSWORD: BlinkPattern =
+///     BlinkPattern::new(&[BlinkStep::new(RED, 5), BlinkStep::off(5)]).with_repeat(Repeat::Times(3));
+///
+/// assert_eq!(WRONG_PASSWORD.cycle_ticks(), 10);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BlinkPattern<'a> {
+    steps: &'a [BlinkStep],
+    repeat: Repeat,
+}
+
+impl<'a> BlinkPattern<'a> {
+    /// Creates a pattern from `steps` that loops forever.
+    pub const fn new(steps: &'a [BlinkStep]) -> Self {
+        Self {
+            steps,
+            repeat: Repeat::Forever,
+        }
+    }
+
+    /// Sets how often the pattern is played.
+    pub const fn with_repeat(mut self, repeat: Repeat) -> Self {
+        self.repeat = repeat;
+        self
+    }
+
+    /// Returns the steps of one cycle.
+    pub fn steps(&self) -> &'a [BlinkStep] {
+        self.steps
+    }
+
+    /// Returns how often the pattern is played.
+    pub fn repeat(&self) -> Repeat {
+        self.repeat
+    }
+
+    /// Returns the length of one cycle in ticks.
+    pub fn cycle_ticks(&self) -> u32 {
+        self.steps.iter().map(|step| step.ticks as u32).sum()
+    }
+}
+
+/// Plays a [`BlinkPattern`] on a [`StatusLed`], one step per tick.
+///
+/// Call [`tick`](PatternPlayer::tick) at a fixed rate from the main loop. The
+/// LED is only written when its color changes. Once a pattern with a repeat
+/// count has finished, or when nothing is playing, the LED is turned off.
+///
+/// # Example
+///
+/// ```
+/// use led_effects::{BlinkPattern, BlinkStep, PatternPlayer, Repeat, StatusLed};
+/// use rgb::RGB8;
+///
+/// # struct Led;
+/// # impl StatusLed for Led {
+/// #     type Error = ();
+/// #     fn set_color(&mut self, _color: RGB8) -> Result<(), ()> { Ok(()) }
+/// # }
+/// # let mut led = Led;
+/// static BLINK: [BlinkStep; 2] = [BlinkStep::new(RGB8::new(255, 0, 0), 5), BlinkStep::off(5)];
+///
+/// let mut player = PatternPlayer::new();
+/// player.play(BlinkPattern::new(&BLINK).with_repeat(Repeat::Times(3)));
+///
+/// while player.is_playing() {
+///     player.tick(&mut led).unwrap();
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PatternPlayer<'a> {
+    pattern: Option<BlinkPattern<'a>>,
+    step: usize,
+    elapsed: u16,
+    cycles: u16,
+    written: Option<RGB8>,
+}
+
+impl<'a> PatternPlayer<'a> {
+    /// Creates a player with nothing to play.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts playing `pattern` from its first step.
+    ///
+    /// Empty patterns and patterns whose steps all last 0 ticks finish immediately.
+    pub fn play(&mut self, pattern: BlinkPattern<'a>) {
+        self.pattern = Some(pattern);
+        self.step = 0;
+        self.elapsed = 0;
+        self.cycles = 0;
+        if pattern.cycle_ticks() == 0 || pattern.repeat == Repeat::Times(0) {
+            self.pattern = None;
+        }
+    }
+
+    /// Stops playback; the LED is turned off on the next tick.
+    pub fn stop(&mut self) {
+        self.pattern = None;
+    }
+
+    /// Returns `true` while a pattern is playing.
+    pub fn is_playing(&self) -> bool {
+        self.pattern.is_some()
+    }
+
+    /// Returns the pattern being played.
+    pub fn pattern(&self) -> Option<BlinkPattern<'a>> {
+        self.pattern
+    }
+
+    /// Forgets the last written color, so the next tick writes the LED even
+    /// if the color did not change.
+    ///
+    /// Use this after something else has written to the LED.
+    pub fn invalidate(&mut self) {
+        self.written = None;
+    }
+
+    /// Returns the color for this tick and moves one tick ahead.
+    ///
+    /// Returns `None` once the pattern has finished or when nothing is
+    /// playing. Use this instead of [`tick`](Self::tick) to drive something
+    /// other than a [`StatusLed`].
+    pub fn next_color(&mut self) -> Option<RGB8> {
+        let pattern = self.pattern?;
+
+        // `play` rejects patterns without any ticks, so this finds a step
+        while pattern.steps[self.step].ticks == 0 {
+            self.next_step(pattern);
+            // Skipping a trailing step can finish the last cycle
+            self.pattern?;
+        }
+
+        let color = pattern.steps[self.step].color;
+        self.elapsed += 1;
+        if self.elapsed >= pattern.steps[self.step].ticks {
+            self.next_step(pattern);
+        }
+        Some(color)
+    }
+
+    /// Writes the color for this tick to `led` and moves one tick ahead.
+    ///
+    /// # Errors
+    ///
+    /// Returns the LED's error if writing the color fails.
+    pub fn tick<L: StatusLed>(&mut self, led: &mut L) -> Result<(), L::Error> {
+        let color = self.next_color().unwrap_or_default();
+        if self.written != Some(color) {
+            led.set_color(color)?;
+            self.written = Some(color);
+        }
+        Ok(())
+    }
+
+    fn next_step(&mut self, pattern: BlinkPattern<'a>) {
+        self.elapsed = 0;
+        self.step += 1;
+        if self.step < pattern.steps.len() {
+            return;
+        }
+
+        self.step = 0;
+        self.cycles = self.cycles.saturating_add(1);
+        if let Repeat::Times(times) = pattern.repeat {
+            if self.cycles >= times {
+                self.pattern = None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::RecordingLed;
+
+    const RED: RGB8 = RGB8::new(255, 0, 0);
+    const GREEN: RGB8 = RGB8::new(0, 255, 0);
+    const OFF: RGB8 = RGB8::new(0, 0, 0);
+
+    static BLINK: [BlinkStep; 2] = [BlinkStep::new(RED, 2), BlinkStep::off(1)];
+
+    fn colors(player: &mut PatternPlayer, ticks: usize) -> Vec<Option<RGB8>> {
+        (0..ticks).map(|_| player.next_color()).collect()
+    }
+
+    #[test]
+    fn test_forever_loops() {
+        let mut player = PatternPlayer::new();
+        player.play(BlinkPattern::new(&BLINK));
+        assert_eq!(
+            colors(&mut player, 7),
+            [RED, RED, OFF, RED, RED, OFF, RED].map(Some)
+        );
+        assert!(player.is_playing());
+    }
+
+    #[test]
+    fn test_repeat_count_finishes() {
+        let mut player = PatternPlayer::new();
+        player.play(BlinkPattern::new(&BLINK).with_repeat(Repeat::Times(2)));
+        let played = colors(&mut player, 7);
+        assert_eq!(played[..6], [RED, RED, OFF, RED, RED, OFF].map(Some));
+        assert_eq!(played[6], None);
+        assert!(!player.is_playing());
+    }
+
+    #[test]
+    fn test_zero_tick_steps_are_skipped() {
+        let steps = [
+            BlinkStep::new(GREEN, 0),
+            BlinkStep::new(RED, 1),
+            BlinkStep::new(GREEN, 0),
+        ];
+        let mut player = PatternPlayer::new();
+        player.play(BlinkPattern::new(&steps));
+        assert_eq!(colors(&mut player, 3), [RED, RED, RED].map(Some));
+    }
+
+    #[test]
+    fn test_trailing_zero_tick_step_finishes_on_time() {
+        let steps = [BlinkStep::new(RED, 1), BlinkStep::off(0)];
+        let mut player = PatternPlayer::new();
+        player.play(BlinkPattern::new(&steps).with_repeat(Repeat::Times(2)));
+        assert_eq!(colors(&mut player, 3), [Some(RED), Some(RED), None]);
+        assert!(!player.is_playing());
+    }
+
+    #[test]
+    fn test_empty_pattern_finishes_immediately() {
+        let silent = [BlinkStep::new(RED, 0)];
+        let mut player = PatternPlayer::new();
+        player.play(BlinkPattern::new(&silent));
+        assert!(!player.is_playing());
+        player.play(BlinkPattern::new(&BLINK).with_repeat(Repeat::Times(0)));
+        assert!(!player.is_playing());
+        assert_eq!(player.next_color(), None);
+    }
+
+    #[test]
+    fn test_tick_writes_only_changes() {
+        let mut led = RecordingLed::new();
+        let mut player = PatternPlayer::new();
+        player.play(BlinkPattern::new(&BLINK).with_repeat(Repeat::Times(1)));
+        for _ in 0..5 {
+            player.tick(&mut led).unwrap();
+        }
+        assert_eq!(led.colors, [RED, OFF]);
+    }
+
+    #[test]
+    fn test_idle_player_turns_led_off_once() {
+        let mut led = RecordingLed::new();
+        let mut player = PatternPlayer::new();
+        player.tick(&mut led).unwrap();
+        player.tick(&mut led).unwrap();
+        assert_eq!(led.colors, [OFF]);
+
+        player.invalidate();
+        player.tick(&mut led).unwrap();
+        assert_eq!(led.colors, [OFF, OFF]);
+    }
+
+    #[test]
+    fn test_play_restarts_and_stop_turns_off() {
+        let mut led = RecordingLed::new();
+        let mut player = PatternPlayer::new();
+        player.play(BlinkPattern::new(&BLINK));
+        player.tick(&mut led).unwrap();
+        player.tick(&mut led).unwrap();
+        player.play(BlinkPattern::new(&BLINK));
+        assert_eq!(player.next_color(), Some(RED));
+
+        player.stop();
+        player.tick(&mut led).unwrap();
+        assert_eq!(led.colors, [RED, OFF]);
+    }
+
+    #[test]
+    fn test_cycle_ticks() {
+        assert_eq!(BlinkPattern::new(&BLINK).cycle_ticks(), 3);
+        assert_eq!(BlinkPattern::new(&[]).cycle_ticks(), 0);
+    }
+}
@@ -0,0 +1,39 @@
+//! Commonly used items in one import.
+//!
+//! `use led_effects::prelude::*;` brings into scope:
+//!
+//! - the [`StatusLed`] trait and its [`StatusLedExt`] convenience methods
+//! - blink patterns and the [`StatusIndicator`]
+//! - the [`PulseEffect`] and the brightness helpers
+//! - `RGB8` and the named [`colors`]
+//! - the GPIO LED adapters with the `hal` feature, and the async LED trait with the
+//!   `async` feature
+//!
+//! # Example
+//!
+//! ```
+//! use led_effects::prelude::*;
+//!
+//! fn show_error<L: StatusLed>(led: &mut L) -> Result<(), L::Error> {
+//!     led.set_color(RGB8::new(255, 0, 0))
+//! }
+//!
+//! let mut pulse = PulseEffect::new();
+//! let color = pulse.update((0, 0, 255));
+//! assert!(!exceeds_threshold(color, DEFAULT_BRIGHTNESS_THRESHOLD));
+//! ```
+
+pub use crate::colors;
+#[cfg(feature = "ferriswheel")]
+pub use crate::EffectLed;
+pub use crate::{
+    exceeds_threshold, luminance, max_channel_brightness, BlinkPattern, BlinkStep, BrightnessMode,
+    Dimmed, ErrorBlinkEncoder, FlashOnce, Heartbeat, Inverted, MorsePattern, PatternPlayer,
+    Polarity, Priority, PulseEffect, PulseEffectError, PulseShape, Repeat, Status, StatusIndicator,
+    StatusLed, StatusLedExt, TimedStatus, TransitionFader, DEFAULT_BRIGHTNESS_THRESHOLD,
+};
+#[cfg(feature = "async")]
+pub use crate::{AsyncStatusLed, Blocking};
+#[cfg(feature = "hal")]
+pub use crate::{PwmLed, PwmRgbLed, RgbGpioLed, SimpleLed};
+pub use rgb::RGB8;
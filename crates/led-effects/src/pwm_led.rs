@@ -0,0 +1,376 @@
+//! Dimmable LEDs on PWM channels.
+//!
+//! Unlike [`SimpleLed`](crate::SimpleLed), which can only switch the LED,
+//! [`PwmLed`] maps the brightness of each color to a duty cycle, so pulses and
+//! fades look smooth on an ordinary indicator LED. [`PwmRgbLed`] drives a
+//! discrete RGB LED with one channel per color for true color mixing.
+
+use crate::{BrightnessMode, DimmableLed, Polarity, StatusLed};
+use embedded_hal::pwm::SetDutyCycle;
+use rgb::RGB8;
+
+/// Scales `value` by `brightness` (255 = unchanged).
+fn scale(value: u8, brightness: u8) -> u8 {
+    (value as u16 * brightness as u16 / 255) as u8
+}
+
+/// Converts a brightness level to a duty cycle out of `max_duty`.
+///
+/// With `gamma` enabled the level follows a cubic curve (gamma 3.0), which
+/// makes equal steps in level look like equal steps in brightness. It is
+/// computed at the full duty resolution instead of through the 8-bit gamma
+/// 2.2 table of `ws2812-pure`, which would leave the dimmest levels at 0.
+pub(crate) fn level_to_duty(level: u8, max_duty: u16, gamma: bool, polarity: Polarity) -> u16 {
+    let level = level as u64;
+    let duty = if gamma {
+        max_duty as u64 * level * level * level / (255 * 255 * 255)
+    } else {
+        max_duty as u64 * level / 255
+    } as u16;
+    match polarity {
+        Polarity::ActiveHigh => duty,
+        Polarity::ActiveLow => max_duty - duty,
+    }
+}
+
+/// Single-color LED on a PWM channel that implements StatusLed.
+///
+/// The color is reduced to one brightness level, by default the brightest
+/// channel, and written as a duty cycle.
+///
+/// # Example
+///
+/// ```
+/// use core::convert::Infallible;
+/// use embedded_hal::pwm::{ErrorType, SetDutyCycle};
+/// use led_effects::{BrightnessMode, PwmLed, StatusLed};
+/// use rgb::RGB8;
+///
+/// # struct Channel;
+/// # impl ErrorType for Channel { type Error = Infallible; }
+/// # impl SetDutyCycle for Channel {
+/// #     fn max_duty_cycle(&self) -> u16 { 1023 }
+/// #     fn set_duty_cycle(&mut self, _duty: u16) -> Result<(), Infallible> { Ok(()) }
+/// # }
+/// let mut led = PwmLed::new(Channel)
+///     .with_mode(BrightnessMode::Luminance)
+///     .with_gamma(true);
+///
+/// led.set_color(RGB8::new(0, 64, 0)).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct PwmLed<P> {
+    pwm: P,
+    mode: BrightnessMode,
+    gamma: bool,
+    polarity: Polarity,
+    brightness: u8,
+}
+
+impl<P: SetDutyCycle> PwmLed<P> {
+    /// Creates an active-high LED using the brightest channel, without gamma correction.
+    pub fn new(pwm: P) -> Self {
+        Self {
+            pwm,
+            mode: BrightnessMode::MaxChannel,
+            gamma: false,
+            polarity: Polarity::ActiveHigh,
+            brightness: 255,
+        }
+    }
+
+    /// Sets how a color is reduced to a brightness level.
+    pub fn with_mode(mut self, mode: BrightnessMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Enables gamma correction of the brightness level.
+    pub fn with_gamma(mut self, gamma: bool) -> Self {
+        self.gamma = gamma;
+        self
+    }
+
+    /// Sets which output level turns the LED on; active-low inverts the duty cycle.
+    pub fn with_polarity(mut self, polarity: Polarity) -> Self {
+        self.polarity = polarity;
+        self
+    }
+
+    /// Returns how a color is reduced to a brightness level.
+    pub fn mode(&self) -> BrightnessMode {
+        self.mode
+    }
+
+    /// Returns `true` if gamma correction is enabled.
+    pub fn gamma(&self) -> bool {
+        self.gamma
+    }
+
+    /// Returns which output level turns the LED on.
+    pub fn polarity(&self) -> Polarity {
+        self.polarity
+    }
+
+    /// Consumes the LED and returns the PWM channel.
+    pub fn into_inner(self) -> P {
+        self.pwm
+    }
+}
+
+impl<P: SetDutyCycle> StatusLed for PwmLed<P> {
+    type Error = P::Error;
+
+    fn set_color(&mut self, color: RGB8) -> Result<(), Self::Error> {
+        let duty = level_to_duty(
+            scale(self.mode.level(color), self.brightness),
+            self.pwm.max_duty_cycle(),
+            self.gamma,
+            self.polarity,
+        );
+        self.pwm.set_duty_cycle(duty)
+    }
+}
+
+impl<P: SetDutyCycle> DimmableLed for PwmLed<P> {
+    fn set_brightness(&mut self, brightness: u8) {
+        self.brightness = brightness;
+    }
+
+    fn brightness(&self) -> u8 {
+        self.brightness
+    }
+}
+
+/// Discrete RGB LED on three PWM channels that implements StatusLed.
+///
+/// Each channel's duty cycle follows the matching color channel. The three
+/// dies of a discrete RGB LED are rarely equally bright; per-channel
+/// calibration scales each channel down (255 = full) so white looks white.
+///
+/// # Example
+///
+/// ```
+/// use core::convert::Infallible;
+/// use embedded_hal::pwm::{ErrorType, SetDutyCycle};
+/// use led_effects::{PwmRgbLed, StatusLed};
+/// use rgb::RGB8;
+///
+/// # struct Channel;
+/// # impl ErrorType for Channel { type Error = Infallible; }
+/// # impl SetDutyCycle for Channel {
+/// #     fn max_duty_cycle(&self) -> u16 { 1023 }
+/// #     fn set_duty_cycle(&mut self, _duty: u16) -> Result<(), Infallible> { Ok(()) }
+/// # }
+/// // Green is the brightest die on this LED
+/// let mut led = PwmRgbLed::new(Channel, Channel, Channel).with_calibration(255, 160, 230);
+///
+/// led.set_color(RGB8::new(255, 128, 0)).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct PwmRgbLed<R, G, B> {
+    red: R,
+    green: G,
+    blue: B,
+    calibration: RGB8,
+    gamma: bool,
+    polarity: Polarity,
+    brightness: u8,
+}
+
+impl<R, G, B> PwmRgbLed<R, G, B>
+where
+    R: SetDutyCycle,
+    G: SetDutyCycle<Error = R::Error>,
+    B: SetDutyCycle<Error = R::Error>,
+{
+    /// Creates an active-high, uncalibrated RGB LED without gamma correction.
+    pub fn new(red: R, green: G, blue: B) -> Self {
+        Self {
+            red,
+            green,
+            blue,
+            calibration: RGB8::new(255, 255, 255),
+            gamma: false,
+            polarity: Polarity::ActiveHigh,
+            brightness: 255,
+        }
+    }
+
+    /// Sets the scale of each channel (0-255, 255 = full brightness).
+    pub fn with_calibration(mut self, red: u8, green: u8, blue: u8) -> Self {
+        self.calibration = RGB8::new(red, green, blue);
+        self
+    }
+
+    /// Enables gamma correction of each channel.
+    pub fn with_gamma(mut self, gamma: bool) -> Self {
+        self.gamma = gamma;
+        self
+    }
+
+    /// Sets which output level turns a channel on; active-low inverts the duty cycles.
+    pub fn with_polarity(mut self, polarity: Polarity) -> Self {
+        self.polarity = polarity;
+        self
+    }
+
+    /// Returns the scale of each channel.
+    pub fn calibration(&self) -> RGB8 {
+        self.calibration
+    }
+
+    /// Returns `true` if gamma correction is enabled.
+    pub fn gamma(&self) -> bool {
+        self.gamma
+    }
+
+    /// Returns which output level turns a channel on.
+    pub fn polarity(&self) -> Polarity {
+        self.polarity
+    }
+
+    /// Consumes the LED and returns the red, green and blue channels.
+    pub fn into_channels(self) -> (R, G, B) {
+        (self.red, self.green, self.blue)
+    }
+}
+
+/// Writes the level of one color channel to a PWM channel.
+fn set_channel<P: SetDutyCycle>(
+    pwm: &mut P,
+    level: u8,
+    gamma: bool,
+    polarity: Polarity,
+) -> Result<(), P::Error> {
+    let duty = level_to_duty(level, pwm.max_duty_cycle(), gamma, polarity);
+    pwm.set_duty_cycle(duty)
+}
+
+impl<R, G, B> StatusLed for PwmRgbLed<R, G, B>
+where
+    R: SetDutyCycle,
+    G: SetDutyCycle<Error = R::Error>,
+    B: SetDutyCycle<Error = R::Error>,
+{
+    type Error = R::Error;
+
+    fn set_color(&mut self, color: RGB8) -> Result<(), Self::Error> {
+        let (gamma, polarity) = (self.gamma, self.polarity);
+        let level = |value: u8, calibration: u8| scale(scale(value, calibration), self.brightness);
+        let (r, g, b) = (
+            level(color.r, self.calibration.r),
+            level(color.g, self.calibration.g),
+            level(color.b, self.calibration.b),
+        );
+        set_channel(&mut self.red, r, gamma, polarity)?;
+        set_channel(&mut self.green, g, gamma, polarity)?;
+        set_channel(&mut self.blue, b, gamma, polarity)
+    }
+}
+
+impl<R, G, B> DimmableLed for PwmRgbLed<R, G, B>
+where
+    R: SetDutyCycle,
+    G: SetDutyCycle<Error = R::Error>,
+    B: SetDutyCycle<Error = R::Error>,
+{
+    fn set_brightness(&mut self, brightness: u8) {
+        self.brightness = brightness;
+    }
+
+    fn brightness(&self) -> u8 {
+        self.brightness
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::MockPwm;
+
+    #[test]
+    fn test_duty_follows_brightest_channel() {
+        let mut led = PwmLed::new(MockPwm::new(1000));
+        led.set_color(RGB8::new(0, 255, 51)).unwrap();
+        assert_eq!(led.pwm.duty, 1000);
+        led.set_color(RGB8::new(51, 0, 0)).unwrap();
+        assert_eq!(led.pwm.duty, 200);
+        led.set_color(RGB8::new(0, 0, 0)).unwrap();
+        assert_eq!(led.pwm.duty, 0);
+    }
+
+    #[test]
+    fn test_luminance_mode_dims_blue() {
+        let mut led = PwmLed::new(MockPwm::new(255)).with_mode(BrightnessMode::Luminance);
+        led.set_color(RGB8::new(0, 0, 255)).unwrap();
+        assert_eq!(led.pwm.duty, 18);
+    }
+
+    #[test]
+    fn test_gamma_darkens_midtones() {
+        let mut led = PwmLed::new(MockPwm::new(u16::MAX)).with_gamma(true);
+        led.set_color(RGB8::new(128, 0, 0)).unwrap();
+        assert!(led.pwm.duty < u16::MAX / 4);
+        led.set_color(RGB8::new(255, 0, 0)).unwrap();
+        assert_eq!(led.pwm.duty, u16::MAX);
+    }
+
+    #[test]
+    fn test_active_low_inverts_duty() {
+        let mut led = PwmLed::new(MockPwm::new(1000)).with_polarity(Polarity::ActiveLow);
+        led.set_color(RGB8::new(0, 0, 0)).unwrap();
+        assert_eq!(led.pwm.duty, 1000);
+        led.set_color(RGB8::new(51, 0, 0)).unwrap();
+        assert_eq!(led.pwm.duty, 800);
+    }
+
+    #[test]
+    fn test_brightness_scales_duty() {
+        let mut led = PwmLed::new(MockPwm::new(1000));
+        led.set_brightness(51);
+        led.set_color(RGB8::new(255, 0, 0)).unwrap();
+        assert_eq!(led.pwm.duty, 200);
+        assert!(led.is_dimmable());
+    }
+
+    fn rgb_led() -> PwmRgbLed<MockPwm, MockPwm, MockPwm> {
+        PwmRgbLed::new(MockPwm::new(255), MockPwm::new(255), MockPwm::new(1000))
+    }
+
+    fn duties(led: PwmRgbLed<MockPwm, MockPwm, MockPwm>) -> (u16, u16, u16) {
+        let (r, g, b) = led.into_channels();
+        (r.duty, g.duty, b.duty)
+    }
+
+    #[test]
+    fn test_rgb_channels_follow_color() {
+        let mut led = rgb_led();
+        led.set_color(RGB8::new(255, 100, 51)).unwrap();
+        assert_eq!(duties(led), (255, 100, 200));
+    }
+
+    #[test]
+    fn test_rgb_calibration_scales_channels() {
+        let mut led = rgb_led().with_calibration(255, 128, 0);
+        led.set_color(RGB8::new(255, 255, 255)).unwrap();
+        assert_eq!(duties(led), (255, 128, 0));
+    }
+
+    #[test]
+    fn test_rgb_brightness_on_top_of_calibration() {
+        let mut led = rgb_led().with_calibration(255, 128, 255);
+        led.set_brightness(128);
+        led.set_color(RGB8::new(255, 255, 255)).unwrap();
+        assert_eq!(duties(led), (128, 64, 501));
+    }
+
+    #[test]
+    fn test_rgb_gamma_and_active_low() {
+        let mut led = rgb_led()
+            .with_gamma(true)
+            .with_polarity(Polarity::ActiveLow);
+        led.set_color(RGB8::new(255, 0, 0)).unwrap();
+        assert_eq!(duties(led), (0, 255, 1000));
+    }
+}
@@ -0,0 +1,170 @@
+//! Discrete RGB LED on three GPIO pins.
+//!
+//! Boards with a common-anode or common-cathode RGB LED instead of a WS2812
+//! can still show eight colors: each channel is switched on when its value
+//! exceeds that channel's threshold.
+
+use crate::simple_led::drive_pin;
+use crate::{DimmableLed, Polarity, StatusLed, DEFAULT_BRIGHTNESS_THRESHOLD};
+use embedded_hal::digital::OutputPin;
+use rgb::RGB8;
+
+/// RGB LED with one on/off [`OutputPin`] per channel that implements StatusLed.
+///
+/// A channel turns on when its value is strictly greater than its threshold.
+/// Common-anode LEDs are wired active-low; configure them with
+/// [`with_polarity`](RgbGpioLed::with_polarity).
+///
+/// # Example
+///
+/// ```
+/// use core::convert::Infallible;
+/// use embedded_hal::digital::{ErrorType, OutputPin};
+/// use led_effects::{Polarity, RgbGpioLed, StatusLed};
+/// use rgb::RGB8;
+///
+/// # struct Pin;
+/// # impl ErrorType for Pin { type Error = Infallible; }
+/// # impl OutputPin for Pin {
+/// #     fn set_low(&mut self) -> Result<(), Infallible> { Ok(()) }
+/// #     fn set_high(&mut self) -> Result<(), Infallible> { Ok(()) }
+/// # }
+/// let mut led = RgbGpioLed::new(Pin, Pin, Pin).with_polarity(Polarity::ActiveLow);
+///
+/// // Red and green on, blue off: yellow
+/// led.set_color(RGB8::new(255, 200, 0)).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct RgbGpioLed<R, G, B> {
+    red: R,
+    green: G,
+    blue: B,
+    thresholds: RGB8,
+    polarity: Polarity,
+}
+
+impl<R, G, B> RgbGpioLed<R, G, B>
+where
+    R: OutputPin,
+    G: OutputPin<Error = R::Error>,
+    B: OutputPin<Error = R::Error>,
+{
+    /// Creates an active-high RGB LED with the default threshold (10) on every channel.
+    pub fn new(red: R, green: G, blue: B) -> Self {
+        Self {
+            red,
+            green,
+            blue,
+            thresholds: RGB8::new(
+                DEFAULT_BRIGHTNESS_THRESHOLD,
+                DEFAULT_BRIGHTNESS_THRESHOLD,
+                DEFAULT_BRIGHTNESS_THRESHOLD,
+            ),
+            polarity: Polarity::ActiveHigh,
+        }
+    }
+
+    /// Sets the threshold of each channel.
+    pub fn with_thresholds(mut self, red: u8, green: u8, blue: u8) -> Self {
+        self.thresholds = RGB8::new(red, green, blue);
+        self
+    }
+
+    /// Sets which pin level turns a channel on.
+    pub fn with_polarity(mut self, polarity: Polarity) -> Self {
+        self.polarity = polarity;
+        self
+    }
+
+    /// Returns the threshold of each channel.
+    pub fn thresholds(&self) -> RGB8 {
+        self.thresholds
+    }
+
+    /// Returns which pin level turns a channel on.
+    pub fn polarity(&self) -> Polarity {
+        self.polarity
+    }
+
+    /// Consumes the LED and returns the red, green and blue pins.
+    pub fn into_pins(self) -> (R, G, B) {
+        (self.red, self.green, self.blue)
+    }
+}
+
+impl<R, G, B> StatusLed for RgbGpioLed<R, G, B>
+where
+    R: OutputPin,
+    G: OutputPin<Error = R::Error>,
+    B: OutputPin<Error = R::Error>,
+{
+    type Error = R::Error;
+
+    fn set_color(&mut self, color: RGB8) -> Result<(), Self::Error> {
+        drive_pin(&mut self.red, color.r > self.thresholds.r, self.polarity)?;
+        drive_pin(&mut self.green, color.g > self.thresholds.g, self.polarity)?;
+        drive_pin(&mut self.blue, color.b > self.thresholds.b, self.polarity)
+    }
+}
+
+/// Switched channels cannot be dimmed; the brightness is ignored.
+impl<R, G, B> DimmableLed for RgbGpioLed<R, G, B>
+where
+    R: OutputPin,
+    G: OutputPin<Error = R::Error>,
+    B: OutputPin<Error = R::Error>,
+{
+    fn set_brightness(&mut self, _brightness: u8) {}
+
+    fn brightness(&self) -> u8 {
+        255
+    }
+
+    fn is_dimmable(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::MockPin;
+
+    fn levels(led: RgbGpioLed<MockPin, MockPin, MockPin>) -> (bool, bool, bool) {
+        let (r, g, b) = led.into_pins();
+        (r.is_high, g.is_high, b.is_high)
+    }
+
+    fn led() -> RgbGpioLed<MockPin, MockPin, MockPin> {
+        RgbGpioLed::new(MockPin::default(), MockPin::default(), MockPin::default())
+    }
+
+    #[test]
+    fn test_channels_follow_color() {
+        let mut rgb = led();
+        rgb.set_color(RGB8::new(255, 0, 128)).unwrap();
+        assert_eq!(levels(rgb), (true, false, true));
+    }
+
+    #[test]
+    fn test_per_channel_thresholds() {
+        let mut rgb = led().with_thresholds(100, 0, 200);
+        rgb.set_color(RGB8::new(100, 1, 201)).unwrap();
+        assert_eq!(levels(rgb), (false, true, true));
+    }
+
+    #[test]
+    fn test_active_low_inverts_levels() {
+        let mut rgb = led().with_polarity(Polarity::ActiveLow);
+        rgb.set_color(RGB8::new(0, 255, 0)).unwrap();
+        assert_eq!(levels(rgb), (true, false, true));
+    }
+
+    #[test]
+    fn test_black_turns_everything_off() {
+        let mut rgb = led();
+        rgb.set_color(RGB8::new(255, 255, 255)).unwrap();
+        rgb.set_color(RGB8::new(0, 0, 0)).unwrap();
+        assert_eq!(levels(rgb), (false, false, false));
+    }
+}
@@ -3,8 +3,8 @@
 //! Maps RGB colors to on/off based on a brightness threshold.
 //! Use this for boards with a simple on/off LED instead of an RGB LED.
 //!
-//! The actual threshold logic is in the platform-independent [`exceeds_threshold`](crate::exceeds_threshold)
-//! function, keeping this module as a thin hardware wrapper.
+//! The brightness calculation is in the platform-independent [`BrightnessMode`],
+//! keeping this module as a thin hardware wrapper.
 //!
 //! Generic over [`embedded_hal::digital::OutputPin`], so it works with any HAL
 //! (ESP-IDF, nrf-hal, stm32-hal, or test mocks).
@@ -22,7 +22,7 @@
 //! led.set_color(rgb::RGB8::new(0, 0, 255))?;
 //! ```
 
-use crate::{exceeds_threshold, StatusLed, DEFAULT_BRIGHTNESS_THRESHOLD};
+use crate::{BrightnessMode, DimmableLed, Polarity, StatusLed, DEFAULT_BRIGHTNESS_THRESHOLD};
 use embedded_hal::digital::OutputPin;
 use rgb::RGB8;
 
@@ -31,25 +31,79 @@ use rgb::RGB8;
 /// Converts RGB colors to on/off by checking if any color channel
 /// exceeds the brightness threshold (strict greater-than comparison).
 /// Equality does not turn the LED on, avoiding false triggers from low-level noise.
+///
+/// With [`with_luminance_threshold`](SimpleLed::with_luminance_threshold) the
+/// threshold applies to the perceived brightness instead, so a dim blue that
+/// is hard to see stays off while an equally visible dark yellow turns on.
+///
+/// LEDs wired between the supply and the pin light up when the pin is low;
+/// create those with [`new_active_low`](SimpleLed::new_active_low).
 pub struct SimpleLed<P: OutputPin> {
     pub(crate) pin: P,
     threshold: u8,
+    mode: BrightnessMode,
+    polarity: Polarity,
 }
 
 impl<P: OutputPin> SimpleLed<P> {
     /// Creates a new SimpleLed with the default brightness threshold (10).
     pub fn new(pin: P) -> Self {
-        Self {
-            pin,
-            threshold: DEFAULT_BRIGHTNESS_THRESHOLD,
-        }
+        Self::with_threshold(pin, DEFAULT_BRIGHTNESS_THRESHOLD)
+    }
+
+    /// Creates a new SimpleLed that is on while the pin is low.
+    pub fn new_active_low(pin: P) -> Self {
+        let mut led = Self::new(pin);
+        led.polarity = Polarity::ActiveLow;
+        led
     }
 
     /// Creates a new SimpleLed with a custom brightness threshold.
     ///
     /// The LED turns on when any RGB channel exceeds this threshold.
     pub fn with_threshold(pin: P, threshold: u8) -> Self {
-        Self { pin, threshold }
+        Self {
+            pin,
+            threshold,
+            mode: BrightnessMode::MaxChannel,
+            polarity: Polarity::ActiveHigh,
+        }
+    }
+
+    /// Creates a new SimpleLed that compares the [`luminance`](crate::luminance)
+    /// of each color against `threshold`.
+    pub fn with_luminance_threshold(pin: P, threshold: u8) -> Self {
+        let mut led = Self::with_threshold(pin, threshold);
+        led.mode = BrightnessMode::Luminance;
+        led
+    }
+
+    /// Returns how colors are reduced to a brightness before thresholding.
+    pub fn mode(&self) -> BrightnessMode {
+        self.mode
+    }
+
+    /// Changes which pin level turns the LED on; takes effect on the next color.
+    pub fn set_polarity(&mut self, polarity: Polarity) {
+        self.polarity = polarity;
+    }
+
+    /// Returns which pin level turns the LED on.
+    pub fn polarity(&self) -> Polarity {
+        self.polarity
+    }
+}
+
+/// Switches a GPIO-driven LED on or off, honoring its polarity.
+pub(crate) fn drive_pin<P: OutputPin>(
+    pin: &mut P,
+    on: bool,
+    polarity: Polarity,
+) -> Result<(), P::Error> {
+    if on == (polarity == Polarity::ActiveHigh) {
+        pin.set_high()
+    } else {
+        pin.set_low()
     }
 }
 
@@ -59,10 +113,21 @@ impl<P: OutputPin> StatusLed for SimpleLed<P> {
     type Error = P::Error;
 
     fn set_color(&mut self, color: RGB8) -> Result<(), Self::Error> {
-        if exceeds_threshold(color, self.threshold) {
-            self.pin.set_high()
-        } else {
-            self.pin.set_low()
-        }
+        let on = self.mode.level(color) > self.threshold;
+        drive_pin(&mut self.pin, on, self.polarity)
+    }
+}
+
+/// An on/off LED cannot be dimmed; the brightness is ignored so `SimpleLed`
+/// still works with code written for dimmable LEDs.
+impl<P: OutputPin> DimmableLed for SimpleLed<P> {
+    fn set_brightness(&mut self, _brightness: u8) {}
+
+    fn brightness(&self) -> u8 {
+        255
+    }
+
+    fn is_dimmable(&self) -> bool {
+        false
     }
 }
@@ -0,0 +1,421 @@
+//! Connection status shown on a single status LED.
+//!
+//! [`StatusIndicator`] turns a [`Status`] into a color or blink pattern, so
+//! firmware only reports what it is doing and no longer picks colors and
+//! blink timings itself.
+//!
+//! Short-lived events can be shown as an overlay on top of the base status:
+//! the overlay runs for a number of ticks and the indicator then returns to
+//! the base status on its own. A [`Priority`] decides whether a new overlay
+//! may replace one that is still running.
+
+use crate::blink_code::ErrorBlinkEncoder;
+use crate::colors::{STATUS_CONNECTING, STATUS_ERROR, STATUS_OK, STATUS_WARN};
+use crate::fader::TransitionFader;
+use crate::pattern::{BlinkPattern, BlinkStep, PatternPlayer};
+use crate::{DimmableLed, StatusLed};
+use rgb::RGB8;
+
+static OFF: [BlinkStep; 1] = [BlinkStep::off(1)];
+static SOLID_GREEN: [BlinkStep; 1] = [BlinkStep::new(STATUS_OK, 1)];
+static SLOW_BLUE_BLINK: [BlinkStep; 2] =
+    [BlinkStep::new(STATUS_CONNECTING, 10), BlinkStep::off(10)];
+static FAST_AMBER_BLINK: [BlinkStep; 2] = [BlinkStep::new(STATUS_WARN, 3), BlinkStep::off(3)];
+
+/// What the device is currently doing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Status {
+    /// Nothing to report; the LED is off.
+    #[default]
+    Idle,
+    /// Connecting to a network; slow blue blinking.
+    Connecting,
+    /// Connected; solid green.
+    Connected,
+    /// Failed with an error code; the code is blinked in red, followed by a
+    /// pause (see [`ErrorBlinkEncoder`]). Code 0 shows solid red.
+    Error(u8),
+    /// Updating firmware or configuration; fast amber blinking.
+    Updating,
+}
+
+/// Importance of a temporary status overlay.
+///
+/// A running overlay is only replaced by overlays of the same or a higher priority.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Priority {
+    /// Informational blips that anything else may interrupt.
+    Low,
+    /// Regular notifications.
+    #[default]
+    Normal,
+    /// Errors and warnings that must stay visible for their full duration.
+    High,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Overlay {
+    priority: Priority,
+    remaining: u32,
+}
+
+/// Shows a [`Status`] on a [`StatusLed`].
+///
+/// Call [`tick`](StatusIndicator::tick) every 50 ms; the built-in blink
+/// timings are tuned for that rate. The LED is only written when its color
+/// changes.
+///
+/// # Example
+///
+/// ```
+/// use led_effects::{Priority, Status, StatusIndicator, StatusLed};
+/// use rgb::RGB8;
+///
+/// # struct Led;
+/// # impl StatusLed for Led {
+/// #     type Error = ();
+/// #     fn set_color(&mut self, _color: RGB8) -> Result<(), ()> { Ok(()) }
+/// # }
+/// let mut indicator = StatusIndicator::new(Led);
+///
+/// indicator.set_status(Status::Connecting);
+/// indicator.tick().unwrap();
+///
+/// // Wrong password: three red blinks, repeated
+/// indicator.set_status(Status::Error(3));
+/// indicator.tick().unwrap();
+///
+/// // Connected, but a request failed: blink error 2 for five seconds
+/// indicator.set_status(Status::Connected);
+/// indicator.show_for(Status::Error(2), Priority::High, 100);
+/// assert_eq!(indicator.status(), Status::Error(2));
+/// assert_eq!(indicator.base_status(), Status::Connected);
+/// ```
+#[derive(Debug)]
+pub struct StatusIndicator<L> {
+    led: L,
+    base: Status,
+    status: Status,
+    overlay: Option<Overlay>,
+    player: PatternPlayer<'static>,
+    error_code: ErrorBlinkEncoder,
+    fader: TransitionFader,
+    written: Option<RGB8>,
+}
+
+impl<L: StatusLed> StatusIndicator<L> {
+    /// Creates an indicator showing [`Status::Idle`].
+    pub fn new(led: L) -> Self {
+        let mut indicator = Self {
+            led,
+            base: Status::Idle,
+            status: Status::Idle,
+            overlay: None,
+            player: PatternPlayer::new(),
+            error_code: ErrorBlinkEncoder::new(0).with_color(STATUS_ERROR),
+            fader: TransitionFader::new(0),
+            written: None,
+        };
+        indicator.start(Status::Idle);
+        indicator
+    }
+
+    /// Fades between colors over `ticks` ticks instead of switching instantly.
+    ///
+    /// Applies to status changes and to the steps of blink animations alike;
+    /// 0 disables fading.
+    pub fn with_fade(mut self, ticks: u16) -> Self {
+        self.fader.set_duration(ticks);
+        self
+    }
+
+    /// Sets the base status and shows it from the next tick on.
+    ///
+    /// While an overlay is running, the new base status is shown once the
+    /// overlay ends. Setting the status that is already shown does not restart
+    /// its animation.
+    pub fn set_status(&mut self, status: Status) {
+        self.base = status;
+        if self.overlay.is_none() && status != self.status {
+            self.start(status);
+        }
+    }
+
+    /// Shows `status` for `ticks` ticks, then returns to the base status.
+    ///
+    /// Returns `false` and leaves the display unchanged if an overlay with a
+    /// higher priority is still running.
+    pub fn show_for(&mut self, status: Status, priority: Priority, ticks: u32) -> bool {
+        if let Some(overlay) = self.overlay {
+            if overlay.priority > priority {
+                return false;
+            }
+        }
+        self.overlay = Some(Overlay {
+            priority,
+            remaining: ticks,
+        });
+        self.start(status);
+        true
+    }
+
+    /// Ends the running overlay and returns to the base status.
+    pub fn clear_overlay(&mut self) {
+        if self.overlay.take().is_some() {
+            self.start(self.base);
+        }
+    }
+
+    /// Returns the status being shown, which is the overlay while one is running.
+    pub fn status(&self) -> Status {
+        self.status
+    }
+
+    /// Returns the status shown when no overlay is running.
+    pub fn base_status(&self) -> Status {
+        self.base
+    }
+
+    /// Returns the priority of the running overlay, if any.
+    pub fn overlay_priority(&self) -> Option<Priority> {
+        self.overlay.map(|overlay| overlay.priority)
+    }
+
+    /// Writes the color for this tick to the LED and moves one tick ahead.
+    ///
+    /// # Errors
+    ///
+    /// Returns the LED's error if writing the color fails.
+    pub fn tick(&mut self) -> Result<(), L::Error> {
+        if let Some(overlay) = &mut self.overlay {
+            if overlay.remaining == 0 {
+                self.clear_overlay();
+            } else {
+                overlay.remaining -= 1;
+            }
+        }
+        let color = match self.status {
+            Status::Error(_) => self.error_code.next_color(),
+            _ => self.player.next_color().unwrap_or_default(),
+        };
+        self.fader.set_target(color);
+        let color = self.fader.next_color();
+        if self.written != Some(color) {
+            self.led.set_color(color)?;
+            self.written = Some(color);
+        }
+        Ok(())
+    }
+
+    /// Returns a reference to the LED.
+    pub fn led(&self) -> &L {
+        &self.led
+    }
+
+    /// Returns a mutable reference to the LED.
+    ///
+    /// The indicator rewrites the LED on the next tick after this is called.
+    pub fn led_mut(&mut self) -> &mut L {
+        self.written = None;
+        &mut self.led
+    }
+
+    /// Consumes the indicator and returns the LED.
+    pub fn into_inner(self) -> L {
+        self.led
+    }
+
+    fn start(&mut self, status: Status) {
+        self.status = status;
+        let steps: &'static [BlinkStep] = match status {
+            Status::Idle => &OFF,
+            Status::Connecting => &SLOW_BLUE_BLINK,
+            Status::Connected => &SOLID_GREEN,
+            Status::Error(code) => {
+                self.error_code.set_code(code);
+                return;
+            }
+            Status::Updating => &FAST_AMBER_BLINK,
+        };
+        self.player.play(BlinkPattern::new(steps));
+    }
+}
+
+impl<L: DimmableLed> StatusIndicator<L> {
+    /// Sets the brightness of the LED (0-255, 255 = full).
+    ///
+    /// The current color is rewritten at the new brightness on the next tick.
+    /// LEDs that cannot be dimmed ignore the brightness.
+    pub fn set_brightness(&mut self, brightness: u8) {
+        self.led.set_brightness(brightness);
+        self.written = None;
+    }
+
+    /// Returns the brightness of the LED.
+    pub fn brightness(&self) -> u8 {
+        self.led.brightness()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::colors::{AMBER, BLUE, LIME, RED};
+    use crate::testing::RecordingLed;
+    use crate::Dimmed;
+
+    const OFF_COLOR: RGB8 = RGB8::new(0, 0, 0);
+
+    fn run(indicator: &mut StatusIndicator<RecordingLed>, ticks: usize) -> Vec<RGB8> {
+        let before = indicator.led().colors.len();
+        for _ in 0..ticks {
+            indicator.tick().unwrap();
+        }
+        indicator.led().colors[before..].to_vec()
+    }
+
+    #[test]
+    fn test_idle_turns_led_off() {
+        let mut indicator = StatusIndicator::new(RecordingLed::new());
+        assert_eq!(indicator.status(), Status::Idle);
+        assert_eq!(run(&mut indicator, 5), [OFF_COLOR]);
+    }
+
+    #[test]
+    fn test_connected_is_solid_green() {
+        let mut indicator = StatusIndicator::new(RecordingLed::new());
+        indicator.set_status(Status::Connected);
+        assert_eq!(run(&mut indicator, 50), [LIME]);
+    }
+
+    #[test]
+    fn test_connecting_blinks_blue() {
+        let mut indicator = StatusIndicator::new(RecordingLed::new());
+        indicator.set_status(Status::Connecting);
+        assert_eq!(run(&mut indicator, 40), [BLUE, OFF_COLOR, BLUE, OFF_COLOR]);
+    }
+
+    #[test]
+    fn test_error_code_blinks_then_pauses() {
+        let mut indicator = StatusIndicator::new(RecordingLed::new());
+        indicator.set_status(Status::Error(2));
+
+        // Two blinks of 12 ticks, a pause of 24 ticks, then the blinks again
+        let colors = run(&mut indicator, 24 + 24 + 1);
+        assert_eq!(colors, [RED, OFF_COLOR, RED, OFF_COLOR, RED]);
+    }
+
+    #[test]
+    fn test_error_code_zero_is_solid_red() {
+        let mut indicator = StatusIndicator::new(RecordingLed::new());
+        indicator.set_status(Status::Error(0));
+        assert_eq!(run(&mut indicator, 100), [RED]);
+    }
+
+    #[test]
+    fn test_same_status_does_not_restart() {
+        let mut indicator = StatusIndicator::new(RecordingLed::new());
+        indicator.set_status(Status::Connecting);
+        run(&mut indicator, 10);
+        indicator.set_status(Status::Connecting);
+        assert_eq!(run(&mut indicator, 1), [OFF_COLOR]);
+    }
+
+    #[test]
+    fn test_switching_status_restarts_animation() {
+        let mut indicator = StatusIndicator::new(RecordingLed::new());
+        indicator.set_status(Status::Updating);
+        run(&mut indicator, 4);
+        indicator.set_status(Status::Error(1));
+        indicator.set_status(Status::Updating);
+        assert_eq!(run(&mut indicator, 1), [AMBER]);
+    }
+
+    #[test]
+    fn test_led_mut_forces_rewrite() {
+        let mut indicator = StatusIndicator::new(RecordingLed::new());
+        indicator.set_status(Status::Connected);
+        run(&mut indicator, 2);
+        indicator.led_mut().colors.clear();
+        assert_eq!(run(&mut indicator, 2), [LIME]);
+    }
+
+    #[test]
+    fn test_fade_between_statuses() {
+        let mut indicator = StatusIndicator::new(RecordingLed::new()).with_fade(2);
+        indicator.set_status(Status::Connected);
+        assert_eq!(run(&mut indicator, 3), [RGB8::new(0, 127, 0), LIME]);
+
+        indicator.set_status(Status::Idle);
+        assert_eq!(run(&mut indicator, 3), [RGB8::new(0, 128, 0), OFF_COLOR]);
+    }
+
+    #[test]
+    fn test_overlay_reverts_to_base_status() {
+        let mut indicator = StatusIndicator::new(RecordingLed::new());
+        indicator.set_status(Status::Connected);
+        run(&mut indicator, 1);
+
+        assert!(indicator.show_for(Status::Error(0), Priority::High, 3));
+        assert_eq!(indicator.status(), Status::Error(0));
+        assert_eq!(run(&mut indicator, 3), [RED]);
+        assert_eq!(run(&mut indicator, 1), [LIME]);
+        assert_eq!(indicator.status(), Status::Connected);
+        assert_eq!(indicator.overlay_priority(), None);
+    }
+
+    #[test]
+    fn test_base_status_changes_apply_after_overlay() {
+        let mut indicator = StatusIndicator::new(RecordingLed::new());
+        indicator.show_for(Status::Updating, Priority::Normal, 2);
+        indicator.set_status(Status::Connected);
+        assert_eq!(indicator.status(), Status::Updating);
+        assert_eq!(indicator.base_status(), Status::Connected);
+        assert_eq!(run(&mut indicator, 3), [AMBER, LIME]);
+    }
+
+    #[test]
+    fn test_lower_priority_overlay_is_rejected() {
+        let mut indicator = StatusIndicator::new(RecordingLed::new());
+        assert!(indicator.show_for(Status::Error(0), Priority::High, 10));
+        assert!(!indicator.show_for(Status::Connecting, Priority::Low, 10));
+        assert_eq!(indicator.status(), Status::Error(0));
+
+        assert!(indicator.show_for(Status::Error(4), Priority::High, 10));
+        assert_eq!(indicator.status(), Status::Error(4));
+        assert_eq!(indicator.overlay_priority(), Some(Priority::High));
+    }
+
+    #[test]
+    fn test_expired_overlay_accepts_any_priority() {
+        let mut indicator = StatusIndicator::new(RecordingLed::new());
+        indicator.show_for(Status::Error(0), Priority::High, 1);
+        run(&mut indicator, 2);
+        assert!(indicator.show_for(Status::Connecting, Priority::Low, 1));
+    }
+
+    #[test]
+    fn test_brightness_rewrites_current_color() {
+        let mut indicator = StatusIndicator::new(Dimmed::new(RecordingLed::new(), 255));
+        indicator.set_status(Status::Connected);
+        indicator.tick().unwrap();
+        indicator.tick().unwrap();
+        indicator.set_brightness(51);
+        assert_eq!(indicator.brightness(), 51);
+        indicator.tick().unwrap();
+        indicator.tick().unwrap();
+        assert_eq!(indicator.led().inner().colors, [LIME, RGB8::new(0, 51, 0)]);
+    }
+
+    #[test]
+    fn test_clear_overlay() {
+        let mut indicator = StatusIndicator::new(RecordingLed::new());
+        indicator.set_status(Status::Connected);
+        indicator.show_for(Status::Error(0), Priority::High, 100);
+        run(&mut indicator, 1);
+        indicator.clear_overlay();
+        assert_eq!(run(&mut indicator, 1), [LIME]);
+    }
+}
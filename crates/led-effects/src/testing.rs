@@ -0,0 +1,83 @@
+//! Test doubles shared by the unit tests.
+
+use crate::StatusLed;
+use core::convert::Infallible;
+use rgb::RGB8;
+
+/// Records every color written to it.
+#[derive(Debug, Default)]
+pub(crate) struct RecordingLed {
+    pub(crate) colors: Vec<RGB8>,
+}
+
+impl RecordingLed {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl StatusLed for RecordingLed {
+    type Error = Infallible;
+
+    fn set_color(&mut self, color: RGB8) -> Result<(), Self::Error> {
+        self.colors.push(color);
+        Ok(())
+    }
+}
+
+/// Output pin that tracks its level.
+#[cfg(feature = "hal")]
+#[derive(Debug, Default)]
+pub(crate) struct MockPin {
+    pub(crate) is_high: bool,
+}
+
+#[cfg(feature = "hal")]
+impl embedded_hal::digital::ErrorType for MockPin {
+    type Error = Infallible;
+}
+
+#[cfg(feature = "hal")]
+impl embedded_hal::digital::OutputPin for MockPin {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.is_high = false;
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.is_high = true;
+        Ok(())
+    }
+}
+
+/// PWM channel that tracks its duty cycle.
+#[cfg(feature = "hal")]
+#[derive(Debug)]
+pub(crate) struct MockPwm {
+    pub(crate) max: u16,
+    pub(crate) duty: u16,
+}
+
+#[cfg(feature = "hal")]
+impl MockPwm {
+    pub(crate) fn new(max: u16) -> Self {
+        Self { max, duty: 0 }
+    }
+}
+
+#[cfg(feature = "hal")]
+impl embedded_hal::pwm::ErrorType for MockPwm {
+    type Error = Infallible;
+}
+
+#[cfg(feature = "hal")]
+impl embedded_hal::pwm::SetDutyCycle for MockPwm {
+    fn max_duty_cycle(&self) -> u16 {
+        self.max
+    }
+
+    fn set_duty_cycle(&mut self, duty: u16) -> Result<(), Self::Error> {
+        self.duty = duty;
+        Ok(())
+    }
+}
@@ -0,0 +1,236 @@
+//! Measured values as status colors.
+//!
+//! Battery voltage, signal strength or temperature are often shown as
+//! green, yellow or red. Comparing the value against fixed thresholds makes
+//! the LED flicker whenever a noisy value sits right at a threshold;
+//! [`ColorThresholds`] adds hysteresis so the color only changes once the
+//! value has clearly crossed over.
+
+use crate::StatusLed;
+use rgb::RGB8;
+
+/// Error type for [`ColorThresholds`] configuration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ThresholdError {
+    /// Thresholds must be strictly ascending; `index` is the first one that is not.
+    NotAscending { index: usize },
+}
+
+impl core::fmt::Display for ThresholdError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ThresholdError::NotAscending { index } => {
+                write!(f, "threshold {} is not greater than the one before", index)
+            }
+        }
+    }
+}
+
+/// Maps a measured value to a color through ascending thresholds, with
+/// hysteresis.
+///
+/// Values below the first threshold get the `lowest` color; values at or
+/// above threshold `i` get that threshold's color. Values are plain
+/// integers in any unit, e.g. millivolts or tenths of a degree.
+///
+/// With a hysteresis of `h`, the color only moves up a level once the value
+/// reaches `threshold + h`, and only moves down once it drops below
+/// `threshold - h`. The first value picks its level without hysteresis.
+///
+/// # Example
+///
+/// ```
+/// use led_effects::colors::{AMBER, LIME, RED};
+/// use led_effects::ColorThresholds;
+///
+/// // Battery in millivolts: red below 3.3 V, amber below 3.7 V, green above
+/// let mut battery = ColorThresholds::new(RED, [(3_300, AMBER), (3_700, LIME)])
+///     .unwrap()
+///     .with_hysteresis(50);
+///
+/// assert_eq!(battery.update(3_800), LIME);
+/// // Noise around 3.7 V does not flicker between green and amber
+/// assert_eq!(battery.update(3_690), LIME);
+/// assert_eq!(battery.update(3_640), AMBER);
+/// assert_eq!(battery.update(3_720), AMBER);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ColorThresholds<const N: usize> {
+    lowest: RGB8,
+    levels: [(i32, RGB8); N],
+    hysteresis: i32,
+    level: Option<usize>,
+    written: Option<RGB8>,
+}
+
+impl<const N: usize> ColorThresholds<N> {
+    /// Creates a mapping from `lowest` and `(threshold, color)` levels.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ThresholdError::NotAscending` if the thresholds are not
+    /// strictly ascending.
+    pub fn new(lowest: RGB8, levels: [(i32, RGB8); N]) -> Result<Self, ThresholdError> {
+        for index in 1..N {
+            if levels[index].0 <= levels[index - 1].0 {
+                return Err(ThresholdError::NotAscending { index });
+            }
+        }
+
+        Ok(Self {
+            lowest,
+            levels,
+            hysteresis: 0,
+            level: None,
+            written: None,
+        })
+    }
+
+    /// Sets how far the value must move past a threshold before the level
+    /// changes. Negative values count as 0.
+    pub fn with_hysteresis(mut self, hysteresis: i32) -> Self {
+        self.hysteresis = hysteresis.max(0);
+        self
+    }
+
+    /// Returns the hysteresis.
+    pub fn hysteresis(&self) -> i32 {
+        self.hysteresis
+    }
+
+    /// Returns the current level: 0 below the first threshold, `i + 1` at or
+    /// above threshold `i`, or `None` before the first update.
+    pub fn level(&self) -> Option<usize> {
+        self.level
+    }
+
+    /// Returns the color of the current level, or `None` before the first
+    /// update.
+    pub fn color(&self) -> Option<RGB8> {
+        self.level.map(|level| self.color_of(level))
+    }
+
+    /// Forgets the current level; the next value picks its level without
+    /// hysteresis.
+    pub fn reset(&mut self) {
+        self.level = None;
+    }
+
+    /// Feeds a new value and returns the color of the resulting level.
+    pub fn update(&mut self, value: i32) -> RGB8 {
+        let count = |offset: i32| {
+            self.levels
+                .iter()
+                .filter(|(threshold, _)| value >= threshold.saturating_add(offset))
+                .count()
+        };
+        let level = match self.level {
+            None => count(0),
+            // Climb only to levels passed by the margin, and fall only below
+            // levels left by the margin
+            Some(current) => current.clamp(count(self.hysteresis), count(-self.hysteresis)),
+        };
+        self.level = Some(level);
+        self.color_of(level)
+    }
+
+    /// Feeds a new value and writes the resulting color to `led`.
+    ///
+    /// The LED is only written when its color changes.
+    ///
+    /// # Errors
+    ///
+    /// Returns the LED's error if writing the color fails.
+    pub fn show<L: StatusLed>(&mut self, led: &mut L, value: i32) -> Result<(), L::Error> {
+        let color = self.update(value);
+        if self.written != Some(color) {
+            led.set_color(color)?;
+            self.written = Some(color);
+        }
+        Ok(())
+    }
+
+    /// Forgets the last written color, so the next call to
+    /// [`show`](Self::show) writes the LED even if the color did not change.
+    pub fn invalidate(&mut self) {
+        self.written = None;
+    }
+
+    fn color_of(&self, level: usize) -> RGB8 {
+        match level {
+            0 => self.lowest,
+            _ => self.levels[level - 1].1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::colors::{AMBER, LIME, RED};
+    use crate::testing::RecordingLed;
+
+    fn battery() -> ColorThresholds<2> {
+        ColorThresholds::new(RED, [(3_300, AMBER), (3_700, LIME)])
+            .unwrap()
+            .with_hysteresis(50)
+    }
+
+    #[test]
+    fn test_levels_without_hysteresis() {
+        let mut gauge = ColorThresholds::new(RED, [(10, AMBER), (20, LIME)]).unwrap();
+        assert_eq!(gauge.update(9), RED);
+        assert_eq!(gauge.update(10), AMBER);
+        assert_eq!(gauge.update(19), AMBER);
+        assert_eq!(gauge.update(20), LIME);
+        assert_eq!(gauge.level(), Some(2));
+    }
+
+    #[test]
+    fn test_first_value_ignores_hysteresis() {
+        let mut gauge = battery();
+        assert_eq!(gauge.color(), None);
+        assert_eq!(gauge.update(3_310), AMBER);
+    }
+
+    #[test]
+    fn test_hysteresis_going_up() {
+        let mut gauge = battery();
+        gauge.update(3_000);
+        assert_eq!(gauge.update(3_340), RED);
+        assert_eq!(gauge.update(3_350), AMBER);
+        // Jumps straight past a level that is reached with a margin
+        assert_eq!(gauge.update(3_800), LIME);
+    }
+
+    #[test]
+    fn test_hysteresis_going_down() {
+        let mut gauge = battery();
+        gauge.update(4_000);
+        assert_eq!(gauge.update(3_651), LIME);
+        assert_eq!(gauge.update(3_649), AMBER);
+        assert_eq!(gauge.update(3_200), RED);
+        assert_eq!(gauge.update(3_300), RED);
+    }
+
+    #[test]
+    fn test_rejects_unsorted_thresholds() {
+        let err = ColorThresholds::new(RED, [(5, AMBER), (5, LIME)]).unwrap_err();
+        assert_eq!(err, ThresholdError::NotAscending { index: 1 });
+        assert_eq!(
+            format!("{}", err),
+            "threshold 1 is not greater than the one before"
+        );
+    }
+
+    #[test]
+    fn test_show_writes_only_changes() {
+        let mut led = RecordingLed::new();
+        let mut gauge = battery();
+        for value in [3_800, 3_690, 3_700, 3_600, 3_610] {
+            gauge.show(&mut led, value).unwrap();
+        }
+        assert_eq!(led.colors, [LIME, AMBER]);
+    }
+}
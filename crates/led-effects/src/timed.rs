@@ -0,0 +1,191 @@
+//! Temporary colors that revert on their own.
+//!
+//! Notifications on a status LED ("message received", "button pressed") show
+//! a color for a moment and then return to whatever the LED showed before.
+//! [`TimedStatus`] keeps that timer, so firmware only says what to show and
+//! for how long.
+
+use crate::StatusLed;
+use rgb::RGB8;
+
+/// A status LED with an idle color and temporary colors on top of it.
+///
+/// [`show_for`](TimedStatus::show_for) displays a color for a number of
+/// ticks; afterwards [`tick`](TimedStatus::tick) reverts the LED to the idle
+/// color. The LED is only written when its color changes.
+///
+/// For connection states with blink patterns and priorities, use
+/// [`StatusIndicator`](crate::StatusIndicator) instead.
+///
+/// # Example
+///
+/// ```
+/// use led_effects::colors::{BLACK, CYAN};
+/// use led_effects::{StatusLed, TimedStatus};
+/// use rgb::RGB8;
+///
+/// # struct Led;
+/// # impl StatusLed for Led {
+/// #     type Error = ();
+/// #     fn set_color(&mut self, _color: RGB8) -> Result<(), ()> { Ok(()) }
+/// # }
+/// let mut status = TimedStatus::new(Led, BLACK);
+///
+/// // Show cyan for 2 seconds at 50 ms per tick
+/// status.show_for(CYAN, 40);
+/// for _ in 0..40 {
+///     status.tick().unwrap();
+/// }
+/// status.tick().unwrap();
+/// assert_eq!(status.color(), BLACK);
+/// ```
+#[derive(Debug)]
+pub struct TimedStatus<L> {
+    led: L,
+    idle: RGB8,
+    shown: Option<(RGB8, u32)>,
+    written: Option<RGB8>,
+}
+
+impl<L: StatusLed> TimedStatus<L> {
+    /// Creates a status LED that shows `idle` whenever nothing else is shown.
+    pub fn new(led: L, idle: RGB8) -> Self {
+        Self {
+            led,
+            idle,
+            shown: None,
+            written: None,
+        }
+    }
+
+    /// Changes the idle color; a temporary color keeps running.
+    pub fn set_idle(&mut self, idle: RGB8) {
+        self.idle = idle;
+    }
+
+    /// Returns the idle color.
+    pub fn idle(&self) -> RGB8 {
+        self.idle
+    }
+
+    /// Shows `color` for the next `ticks` ticks, then reverts to the idle
+    /// color. Values below 1 count as 1.
+    ///
+    /// Replaces a temporary color that is still running.
+    pub fn show_for(&mut self, color: RGB8, ticks: u32) {
+        self.shown = Some((color, ticks.max(1)));
+    }
+
+    /// Ends the temporary color; the next tick shows the idle color.
+    pub fn cancel(&mut self) {
+        self.shown = None;
+    }
+
+    /// Returns `true` while a temporary color is shown.
+    pub fn is_showing(&self) -> bool {
+        self.shown.is_some()
+    }
+
+    /// Returns the color the next tick shows.
+    pub fn color(&self) -> RGB8 {
+        self.shown.map_or(self.idle, |(color, _)| color)
+    }
+
+    /// Writes the color for this tick to the LED and moves one tick ahead.
+    ///
+    /// # Errors
+    ///
+    /// Returns the LED's error if writing the color fails.
+    pub fn tick(&mut self) -> Result<(), L::Error> {
+        let color = self.color();
+        if let Some((_, remaining)) = &mut self.shown {
+            *remaining -= 1;
+            if *remaining == 0 {
+                self.shown = None;
+            }
+        }
+        if self.written != Some(color) {
+            self.led.set_color(color)?;
+            self.written = Some(color);
+        }
+        Ok(())
+    }
+
+    /// Returns a reference to the LED.
+    pub fn led(&self) -> &L {
+        &self.led
+    }
+
+    /// Returns a mutable reference to the LED.
+    ///
+    /// The LED is rewritten on the next tick after this is called.
+    pub fn led_mut(&mut self) -> &mut L {
+        self.written = None;
+        &mut self.led
+    }
+
+    /// Consumes the helper and returns the LED.
+    pub fn into_inner(self) -> L {
+        self.led
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::colors::{BLACK, BLUE, LIME, RED};
+    use crate::testing::RecordingLed;
+
+    fn run(status: &mut TimedStatus<RecordingLed>, ticks: usize) -> Vec<RGB8> {
+        let before = status.led().colors.len();
+        for _ in 0..ticks {
+            status.tick().unwrap();
+        }
+        status.led().colors[before..].to_vec()
+    }
+
+    #[test]
+    fn test_shows_idle_color() {
+        let mut status = TimedStatus::new(RecordingLed::new(), LIME);
+        assert_eq!(run(&mut status, 5), [LIME]);
+        assert!(!status.is_showing());
+    }
+
+    #[test]
+    fn test_reverts_after_ticks() {
+        let mut status = TimedStatus::new(RecordingLed::new(), BLACK);
+        status.show_for(RED, 3);
+        run(&mut status, 2);
+        assert!(status.is_showing());
+        run(&mut status, 1);
+        assert!(!status.is_showing());
+        assert_eq!(status.led().colors, [RED]);
+        assert_eq!(run(&mut status, 1), [BLACK]);
+    }
+
+    #[test]
+    fn test_zero_ticks_counts_as_one() {
+        let mut status = TimedStatus::new(RecordingLed::new(), BLACK);
+        status.show_for(RED, 0);
+        assert_eq!(run(&mut status, 2), [RED, BLACK]);
+    }
+
+    #[test]
+    fn test_new_color_replaces_running_one() {
+        let mut status = TimedStatus::new(RecordingLed::new(), BLACK);
+        status.show_for(RED, 10);
+        run(&mut status, 1);
+        status.show_for(BLUE, 2);
+        assert_eq!(run(&mut status, 3), [BLUE, BLACK]);
+    }
+
+    #[test]
+    fn test_cancel_and_idle_change() {
+        let mut status = TimedStatus::new(RecordingLed::new(), BLACK);
+        status.show_for(RED, 10);
+        run(&mut status, 1);
+        status.cancel();
+        status.set_idle(LIME);
+        assert_eq!(run(&mut status, 2), [LIME]);
+    }
+}
@@ -10,7 +10,8 @@
 //! # Status
 //!
 //! **Skeleton only** — all methods currently call `todo!()`.
-//! The real `esp-hal` dependency will be added when implementing (see ADR 005).
+//! The real `esp-hal` dependency will be added when implementing (see ADR 005);
+//! pulse widths will come from `ws2812_pure::Timing`, like in the ESP-IDF driver.
 //!
 //! # Example
 //!
@@ -30,6 +31,7 @@ use rgb::RGB8;
 
 /// Errors that can occur during WS2812 RMT operations.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum Error {
     /// RMT peripheral configuration failed.
     RmtConfig,
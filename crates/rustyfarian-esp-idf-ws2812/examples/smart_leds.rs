@@ -0,0 +1,36 @@
+//! Drives a strip through `smart_leds::SmartLedsWrite`.
+//!
+//! Cycles red, green and blue along an 8-LED strip on GPIO8, gamma-corrected
+//! and dimmed with the `smart-leds` adapters.
+//!
+//! Run with `cargo run --example smart_leds --features smart-leds-trait`.
+
+use esp_idf_hal::delay::FreeRtos;
+use esp_idf_hal::peripherals::Peripherals;
+use rustyfarian_esp_idf_ws2812::WS2812RMT;
+use smart_leds::{brightness, gamma, SmartLedsWrite, RGB8};
+
+const NUM_LEDS: usize = 8;
+
+fn main() -> anyhow::Result<()> {
+    esp_idf_hal::sys::link_patches();
+
+    let peripherals = Peripherals::take()?;
+    let mut strip = WS2812RMT::new(peripherals.pins.gpio8, peripherals.rmt.channel0)?;
+
+    let palette = [
+        RGB8::new(255, 0, 0),
+        RGB8::new(0, 255, 0),
+        RGB8::new(0, 0, 255),
+    ];
+    let mut offset = 0;
+
+    loop {
+        let colors = (0..NUM_LEDS).map(|i| palette[(i + offset) % palette.len()]);
+        // Gamma-correct, then dim to an eighth
+        strip.write(brightness(gamma(colors), 32))?;
+
+        offset = (offset + 1) % palette.len();
+        FreeRtos::delay_ms(250);
+    }
+}
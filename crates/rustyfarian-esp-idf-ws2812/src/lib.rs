@@ -21,6 +21,25 @@
 //! led.set_pixels_slice(&colors)?;
 //! ```
 //!
+//! For sparse updates, give the driver an internal frame with `with_len` and
+//! send it with `show`:
+//!
+//! ```ignore
+//! let mut strip = WS2812RMT::new(peripherals.pins.gpio8, peripherals.rmt.channel0)?
+//!     .with_len(8);
+//!
+//! strip.set_pixel_at(0, RGB8::new(0, 0, 255));
+//! strip.show()?;
+//! ```
+//!
+//! Pulse widths default to the WS2812's. SK6812, WS2811 and clone strips
+//! need their own; pass any `ws2812_pure::Timing` to `with_timing`.
+//!
+//! With the `smart-leds-trait` feature, the driver implements
+//! `SmartLedsWrite`, so code written for the `smart-leds` ecosystem can
+//! drive it directly, including that crate's `brightness` and `gamma`
+//! adapters; see `examples/smart_leds.rs`.
+//!
 //! # Supported Boards
 //!
 //! Works with any ESP32 variant that has RMT support via ESP-IDF:
@@ -39,7 +58,7 @@ use esp_idf_hal::{
     },
 };
 use rgb::RGB8;
-use ws2812_pure::rgb_to_grb;
+use ws2812_pure::{encode_pulses, rgb_to_grb, Timing};
 
 /// WS2812 LED driver using RMT peripheral.
 ///
@@ -47,11 +66,23 @@ use ws2812_pure::rgb_to_grb;
 /// WS2812 protocol without CPU intervention.
 pub struct WS2812RMT<'a> {
     tx_rtm_driver: TxRmtDriver<'a>,
+    /// Pulse widths of the connected chip.
+    timing: Timing,
+    /// High and low pulses of a 0 bit, computed once from the counter clock.
+    bit0: (Pulse, Pulse),
+    /// High and low pulses of a 1 bit.
+    bit1: (Pulse, Pulse),
+    /// Pixels sent by [`show`](Self::show); empty unless created with
+    /// [`with_len`](Self::with_len).
+    frame: Vec<RGB8>,
 }
 
 impl<'d> WS2812RMT<'d> {
     /// Creates a new WS2812 driver.
     ///
+    /// The driver uses [`Timing::WS2812`]; for other chips, follow up with
+    /// [`with_timing`](Self::with_timing).
+    ///
     /// # Arguments
     ///
     /// * `led` - GPIO pin connected to the LED data line
@@ -68,17 +99,133 @@ impl<'d> WS2812RMT<'d> {
     ) -> Result<Self> {
         let config = TransmitConfig::new().clock_divider(2);
         let tx = TxRmtDriver::new(channel, led, &config)?;
-        Ok(Self { tx_rtm_driver: tx })
+        let timing = Timing::WS2812;
+        let (bit0, bit1) = Self::create_pulses(&tx, &timing)?;
+        Ok(Self {
+            tx_rtm_driver: tx,
+            timing,
+            bit0,
+            bit1,
+            frame: Vec::new(),
+        })
+    }
+
+    /// Switches to the pulse widths of `timing`.
+    ///
+    /// Use one of the presets, such as [`Timing::SK6812`] or
+    /// [`Timing::WS2811`], or [`Timing::new`] for clone strips whose
+    /// datasheet disagrees with all of them.
+    ///
+    /// # Errors
+    ///
+    /// Fails if a pulse is too long for the RMT counter.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use ws2812_pure::Timing;
+    ///
+    /// let mut ring = WS2812RMT::new(peripherals.pins.gpio8, peripherals.rmt.channel0)?
+    ///     .with_timing(Timing::SK6812)?;
+    /// ```
+    pub fn with_timing(mut self, timing: Timing) -> Result<Self> {
+        let (bit0, bit1) = Self::create_pulses(&self.tx_rtm_driver, &timing)?;
+        self.timing = timing;
+        self.bit0 = bit0;
+        self.bit1 = bit1;
+        Ok(self)
+    }
+
+    /// Returns the pulse widths in use.
+    pub fn timing(&self) -> &Timing {
+        &self.timing
+    }
+
+    /// Adds an internal frame of `len` pixels, all off.
+    ///
+    /// Update it with [`set_pixel_at`](Self::set_pixel_at),
+    /// [`fill`](Self::fill) or [`clear`](Self::clear), then send it with
+    /// [`show`](Self::show). This suits sparse status displays, where
+    /// changing one pixel should not mean rebuilding the whole slice.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mut strip = WS2812RMT::new(peripherals.pins.gpio8, peripherals.rmt.channel0)?
+    ///     .with_len(12);
+    ///
+    /// strip.set_pixel_at(3, RGB8::new(0, 255, 0));
+    /// strip.show()?;
+    /// ```
+    pub fn with_len(mut self, len: usize) -> Self {
+        self.frame = vec![RGB8::default(); len];
+        self
+    }
+
+    /// Returns the number of pixels in the internal frame.
+    pub fn len(&self) -> usize {
+        self.frame.len()
+    }
+
+    /// Returns `true` if there is no internal frame.
+    pub fn is_empty(&self) -> bool {
+        self.frame.is_empty()
+    }
+
+    /// Returns the internal frame.
+    pub fn pixels(&self) -> &[RGB8] {
+        &self.frame
     }
 
-    /// Creates the WS2812 timing pulses for 0 and 1 bits.
-    fn create_pulses(&mut self) -> Result<(Pulse, Pulse, Pulse, Pulse)> {
-        let ticks_hz = self.tx_rtm_driver.counter_clock()?;
-        let t0h = Pulse::new_with_duration(ticks_hz, PinState::High, &ns(350))?;
-        let t0l = Pulse::new_with_duration(ticks_hz, PinState::Low, &ns(800))?;
-        let t1h = Pulse::new_with_duration(ticks_hz, PinState::High, &ns(700))?;
-        let t1l = Pulse::new_with_duration(ticks_hz, PinState::Low, &ns(600))?;
-        Ok((t0h, t0l, t1h, t1l))
+    /// Returns the internal frame for direct modification.
+    pub fn pixels_mut(&mut self) -> &mut [RGB8] {
+        &mut self.frame
+    }
+
+    /// Sets pixel `index` of the internal frame; takes effect on the next
+    /// [`show`](Self::show).
+    ///
+    /// Returns `false`, and changes nothing, if `index` is out of range.
+    pub fn set_pixel_at(&mut self, index: usize, rgb: RGB8) -> bool {
+        match self.frame.get_mut(index) {
+            Some(pixel) => {
+                *pixel = rgb;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Sets every pixel of the internal frame to `rgb`.
+    pub fn fill(&mut self, rgb: RGB8) {
+        self.frame.fill(rgb);
+    }
+
+    /// Turns every pixel of the internal frame off.
+    ///
+    /// Call [`show`](Self::show) to turn the LEDs off.
+    pub fn clear(&mut self) {
+        self.fill(RGB8::default());
+    }
+
+    /// Sends the internal frame to the LEDs.
+    pub fn show(&mut self) -> Result<()> {
+        let signal = self.encode_pixels(self.frame.iter().copied())?;
+        self.tx_rtm_driver.start_blocking(&signal)?;
+        Ok(())
+    }
+
+    /// Creates the pulses for 0 and 1 bits from `timing`.
+    fn create_pulses(
+        tx: &TxRmtDriver<'_>,
+        timing: &Timing,
+    ) -> Result<((Pulse, Pulse), (Pulse, Pulse))> {
+        let ticks_hz = tx.counter_clock()?;
+        let t0h = Pulse::new_with_duration(ticks_hz, PinState::High, &ns(timing.t0h_ns()))?;
+        let t0l = Pulse::new_with_duration(ticks_hz, PinState::Low, &ns(timing.t0l_ns()))?;
+        let t1h = Pulse::new_with_duration(ticks_hz, PinState::High, &ns(timing.t1h_ns()))?;
+        let t1l = Pulse::new_with_duration(ticks_hz, PinState::Low, &ns(timing.t1l_ns()))?;
+        Ok(((t0h, t0l), (t1h, t1l)))
     }
 
     /// Sets a single pixel color.
@@ -86,27 +233,23 @@ impl<'d> WS2812RMT<'d> {
     /// Use this for single-LED indicators or when updating one pixel at a time.
     pub fn set_pixel(&mut self, rgb: RGB8) -> Result<()> {
         let color = rgb_to_grb(rgb);
-        let (t0h, t0l, t1h, t1l) = self.create_pulses()?;
         let mut signal = FixedLengthSignal::<24>::new();
-        Self::encode_color_bits(color, &mut signal, 0, t0h, t0l, t1h, t1l)?;
+        self.encode_color_bits(color, &mut signal, 0)?;
         self.tx_rtm_driver.start_blocking(&signal)?;
         Ok(())
     }
 
     /// Encodes a 24-bit color value into RMT pulses (MSB first).
     fn encode_color_bits(
+        &self,
         color: u32,
         signal: &mut FixedLengthSignal<24>,
         offset: usize,
-        t0h: Pulse,
-        t0l: Pulse,
-        t1h: Pulse,
-        t1l: Pulse,
     ) -> Result<()> {
         for i in (0..24).rev() {
             let bit = (color >> i) & 1 != 0;
-            let (high_pulse, low_pulse) = if bit { (t1h, t1l) } else { (t0h, t0l) };
-            signal.set(offset + (23 - i as usize), &(high_pulse, low_pulse))?;
+            let pulses = if bit { &self.bit1 } else { &self.bit0 };
+            signal.set(offset + (23 - i as usize), pulses)?;
         }
         Ok(())
     }
@@ -119,33 +262,28 @@ impl<'d> WS2812RMT<'d> {
     ///
     /// * `rgbs` - Slice of colors, one per pixel in order
     pub fn set_pixels_slice(&mut self, rgbs: &[RGB8]) -> Result<()> {
-        let (t0h, t0l, t1h, t1l) = self.create_pulses()?;
-        let mut signal = VariableLengthSignal::new();
-        for rgb in rgbs {
-            let pulses = Self::color_to_pulses(*rgb, t0h, t0l, t1h, t1l);
-            signal.push(&pulses)?;
-        }
+        let signal = self.encode_pixels(rgbs.iter().copied())?;
         self.tx_rtm_driver.start_blocking(&signal)?;
         Ok(())
     }
 
-    /// Converts a color to individual pulses (no allocation, returns an array).
-    fn color_to_pulses(rgb: RGB8, t0h: Pulse, t0l: Pulse, t1h: Pulse, t1l: Pulse) -> [Pulse; 48] {
-        let color = rgb_to_grb(rgb);
-        let mut pulses = [t0h; 48]; // Initialize with dummy values
-        for i in (0..24).rev() {
-            let bit = (color >> i) & 1 != 0;
-            let (high, low) = if bit { (t1h, t1l) } else { (t0h, t0l) };
-            let idx = (23 - i) * 2;
-            pulses[idx] = high;
-            pulses[idx + 1] = low;
-        }
-        pulses
+    /// Encodes pixels into one signal, in order.
+    ///
+    /// The per-pixel encoding is `ws2812_pure::encode_pulses`, which is tested
+    /// on the host.
+    fn encode_pixels<I>(&self, pixels: I) -> Result<VariableLengthSignal>
+    where
+        I: IntoIterator,
+        I::Item: Into<RGB8>,
+    {
+        let mut signal = VariableLengthSignal::new();
+        encode_pulses(pixels, self.bit0, self.bit1, |pulses| signal.push(pulses))?;
+        Ok(signal)
     }
 }
 
-fn ns(nanos: u64) -> Duration {
-    Duration::from_nanos(nanos)
+fn ns(nanos: u32) -> Duration {
+    Duration::from_nanos(nanos as u64)
 }
 
 #[cfg(feature = "led-effects")]
@@ -156,3 +294,35 @@ impl led_effects::StatusLed for WS2812RMT<'_> {
         self.set_pixel(color)
     }
 }
+
+/// Sends the pixels of `iterator` as one frame, like
+/// [`set_pixels_slice`](WS2812RMT::set_pixels_slice).
+///
+/// The internal frame of [`with_len`](WS2812RMT::with_len) is left alone.
+///
+/// # Example
+///
+/// ```ignore
+/// use smart_leds::{brightness, gamma, SmartLedsWrite, RGB8};
+///
+/// let mut strip = WS2812RMT::new(peripherals.pins.gpio8, peripherals.rmt.channel0)?;
+/// let colors = [RGB8::new(255, 0, 0), RGB8::new(0, 255, 0), RGB8::new(0, 0, 255)];
+///
+/// // Gamma-correct, then dim to an eighth
+/// strip.write(brightness(gamma(colors.iter().cloned()), 32))?;
+/// ```
+#[cfg(feature = "smart-leds-trait")]
+impl smart_leds_trait::SmartLedsWrite for WS2812RMT<'_> {
+    type Error = anyhow::Error;
+    type Color = RGB8;
+
+    fn write<T, I>(&mut self, iterator: T) -> Result<(), Self::Error>
+    where
+        T: IntoIterator<Item = I>,
+        I: Into<Self::Color>,
+    {
+        let signal = self.encode_pixels(iterator)?;
+        self.tx_rtm_driver.start_blocking(&signal)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,301 @@
+//! Streaming bit iterators.
+//!
+//! [`color_to_bits`](crate::color_to_bits) builds an array per pixel. Drivers
+//! that encode while transmitting only need one bit at a time, which these
+//! iterators yield straight from the packed color.
+//!
+//! Pulse-based drivers such as an RMT peripheral map every bit to a high and a
+//! low pulse; [`pixel_pulses`] and [`encode_pulses`] do that for any pulse type.
+
+use crate::{pack_color, rgb_to_grb, ColorOrder};
+use core::iter::FusedIterator;
+use rgb::RGB8;
+
+/// Yields the bits of a packed color, MSB first.
+///
+/// # Example
+///
+/// ```
+/// use ws2812_pure::{color_to_bits, rgb_to_grb, ColorBits};
+/// use rgb::RGB8;
+///
+/// let color = rgb_to_grb(RGB8::new(255, 0, 128));
+/// assert!(ColorBits::new(color).eq(color_to_bits(color)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ColorBits {
+    color: u32,
+    remaining: u8,
+}
+
+impl ColorBits {
+    /// Yields the 24 low bits of `color`, as sent to RGB LEDs.
+    pub const fn new(color: u32) -> Self {
+        Self {
+            color,
+            remaining: 24,
+        }
+    }
+
+    /// Yields all 32 bits of `color`, as sent to RGBW LEDs.
+    pub const fn new_32(color: u32) -> Self {
+        Self {
+            color,
+            remaining: 32,
+        }
+    }
+}
+
+impl Iterator for ColorBits {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<bool> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        Some((self.color >> self.remaining) & 1 != 0)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.remaining as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for ColorBits {}
+
+impl FusedIterator for ColorBits {}
+
+/// Yields the bits of every pixel in a slice, first pixel first.
+///
+/// Pixels are packed in GRB order unless [`with_order`](Self::with_order)
+/// picks another one; RGBW orders yield 32 bits per pixel with the white LED
+/// off.
+///
+/// # Example
+///
+/// ```
+/// use ws2812_pure::{ColorOrder, FrameBits};
+/// use rgb::RGB8;
+///
+/// let pixels = [RGB8::new(255, 0, 0), RGB8::new(0, 0, 255)];
+/// assert_eq!(FrameBits::new(&pixels).len(), 48);
+/// assert_eq!(FrameBits::new(&pixels).with_order(ColorOrder::Grbw).len(), 64);
+///
+/// // Red is the second byte in GRB order
+/// let bits: Vec<bool> = FrameBits::new(&pixels).take(16).collect();
+/// assert!(bits[..8].iter().all(|&bit| !bit));
+/// assert!(bits[8..].iter().all(|&bit| bit));
+/// ```
+#[derive(Debug, Clone)]
+pub struct FrameBits<'a> {
+    pixels: core::slice::Iter<'a, RGB8>,
+    order: ColorOrder,
+    current: ColorBits,
+}
+
+impl<'a> FrameBits<'a> {
+    /// Yields the bits of `pixels` in GRB order.
+    pub fn new(pixels: &'a [RGB8]) -> Self {
+        Self {
+            pixels: pixels.iter(),
+            order: ColorOrder::Grb,
+            current: ColorBits {
+                color: 0,
+                remaining: 0,
+            },
+        }
+    }
+
+    /// Packs the pixels in `order` instead.
+    ///
+    /// Call this before taking any bits; a pixel that has already started
+    /// keeps its order.
+    pub fn with_order(mut self, order: ColorOrder) -> Self {
+        self.order = order;
+        self
+    }
+}
+
+impl Iterator for FrameBits<'_> {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<bool> {
+        loop {
+            if let Some(bit) = self.current.next() {
+                return Some(bit);
+            }
+            let pixel = self.pixels.next()?;
+            let color = pack_color(*pixel, self.order);
+            self.current = if self.order.has_white() {
+                ColorBits::new_32(color)
+            } else {
+                ColorBits::new(color)
+            };
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.current.len() + self.pixels.len() * self.order.bits_per_pixel() as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for FrameBits<'_> {}
+
+impl FusedIterator for FrameBits<'_> {}
+
+/// Returns the high and low pulses of every bit of `rgb` in GRB order, MSB first.
+///
+/// `bit0` and `bit1` are the pulse pairs of a 0 and a 1 bit.
+///
+/// # Example
+///
+/// ```
+/// use ws2812_pure::pixel_pulses;
+/// use rgb::RGB8;
+///
+/// let pulses = pixel_pulses(RGB8::new(255, 0, 0), ('s', 'L'), ('S', 'l'));
+/// // Green is sent first and is off, then red is fully on
+/// assert_eq!(pulses[..2], ['s', 'L']);
+/// assert_eq!(pulses[16..18], ['S', 'l']);
+/// ```
+pub fn pixel_pulses<P: Copy>(rgb: RGB8, bit0: (P, P), bit1: (P, P)) -> [P; 48] {
+    let mut pulses = [bit0.0; 48];
+    for (i, bit) in ColorBits::new(rgb_to_grb(rgb)).enumerate() {
+        let (high, low) = if bit { bit1 } else { bit0 };
+        pulses[i * 2] = high;
+        pulses[i * 2 + 1] = low;
+    }
+    pulses
+}
+
+/// Encodes `pixels` in order and hands the [`pixel_pulses`] of each one to `push`.
+///
+/// Stops at the first error returned by `push`.
+///
+/// # Errors
+///
+/// Returns the error of `push`.
+pub fn encode_pulses<P, E, I>(
+    pixels: I,
+    bit0: (P, P),
+    bit1: (P, P),
+    mut push: impl FnMut(&[P; 48]) -> Result<(), E>,
+) -> Result<(), E>
+where
+    P: Copy,
+    I: IntoIterator,
+    I::Item: Into<RGB8>,
+{
+    for pixel in pixels {
+        push(&pixel_pulses(pixel.into(), bit0, bit1))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{color_to_bits, color_to_bits_32};
+
+    #[test]
+    fn test_color_bits_match_arrays() {
+        for color in [0, 0xFF_FF_FF, 0xA5_0F_3C, 0x80_00_01] {
+            let bits: Vec<bool> = ColorBits::new(color).collect();
+            assert_eq!(bits, color_to_bits(color));
+        }
+        let bits: Vec<bool> = ColorBits::new_32(0x8000_0001).collect();
+        assert_eq!(bits, color_to_bits_32(0x8000_0001));
+    }
+
+    const BIT0: (u8, u8) = (1, 2);
+    const BIT1: (u8, u8) = (3, 4);
+
+    #[test]
+    fn test_pixel_pulses_follow_grb_bits() {
+        let rgb = RGB8::new(0x12, 0xA5, 0x3C);
+        let pulses = pixel_pulses(rgb, BIT0, BIT1);
+        for (i, bit) in ColorBits::new(rgb_to_grb(rgb)).enumerate() {
+            let expected = if bit { BIT1 } else { BIT0 };
+            assert_eq!((pulses[i * 2], pulses[i * 2 + 1]), expected, "bit {}", i);
+        }
+    }
+
+    #[test]
+    fn test_encode_pulses_converts_and_keeps_order() {
+        let mut frames = Vec::new();
+        let result: Result<(), ()> =
+            encode_pulses([(255, 0, 0), (0, 0, 1)], BIT0, BIT1, |pulses| {
+                frames.push(*pulses);
+                Ok(())
+            });
+        assert_eq!(result, Ok(()));
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0], pixel_pulses(RGB8::new(255, 0, 0), BIT0, BIT1));
+        assert_eq!(frames[1][46..], [3, 4]);
+        assert!(frames[1][..46].chunks(2).all(|pair| pair == [1, 2]));
+    }
+
+    #[test]
+    fn test_encode_pulses_stops_at_first_error() {
+        let mut pushed = 0;
+        let result = encode_pulses([RGB8::default(); 3], BIT0, BIT1, |_| {
+            pushed += 1;
+            if pushed == 2 {
+                Err("full")
+            } else {
+                Ok(())
+            }
+        });
+        assert_eq!(result, Err("full"));
+        assert_eq!(pushed, 2);
+    }
+
+    #[test]
+    fn test_color_bits_ignore_high_byte() {
+        assert!(ColorBits::new(0xFF00_0000).all(|bit| !bit));
+    }
+
+    #[test]
+    fn test_color_bits_len() {
+        let mut bits = ColorBits::new(0);
+        assert_eq!(bits.len(), 24);
+        bits.nth(9);
+        assert_eq!(bits.len(), 14);
+        assert_eq!(ColorBits::new_32(0).count(), 32);
+    }
+
+    #[test]
+    fn test_frame_bits_concatenate_pixels() {
+        let pixels = [RGB8::new(1, 2, 3), RGB8::new(255, 128, 0)];
+        let expected: Vec<bool> = pixels
+            .iter()
+            .flat_map(|&pixel| color_to_bits(crate::rgb_to_grb(pixel)))
+            .collect();
+        let bits: Vec<bool> = FrameBits::new(&pixels).collect();
+        assert_eq!(bits, expected);
+    }
+
+    #[test]
+    fn test_frame_bits_order() {
+        let pixels = [RGB8::new(255, 0, 0)];
+        let bits: Vec<bool> = FrameBits::new(&pixels)
+            .with_order(ColorOrder::Rgbw)
+            .collect();
+        assert_eq!(bits.len(), 32);
+        assert!(bits[..8].iter().all(|&bit| bit));
+        assert!(bits[8..].iter().all(|&bit| !bit));
+    }
+
+    #[test]
+    fn test_frame_bits_len_and_empty() {
+        let pixels = [RGB8::default(); 3];
+        let mut bits = FrameBits::new(&pixels);
+        assert_eq!(bits.len(), 72);
+        bits.nth(29);
+        assert_eq!(bits.len(), 42);
+        assert_eq!(FrameBits::new(&[]).next(), None);
+    }
+}
@@ -0,0 +1,120 @@
+//! Per-channel calibration.
+//!
+//! LEDs from different packages, and even different batches of the same
+//! strip, mix full red, green and blue into visibly different whites.
+//! Scaling each channel down to the weakest one's level makes them match.
+
+use rgb::RGB8;
+
+/// Per-channel scale factors, 255 meaning unchanged.
+///
+/// The presets use the values commonly used for the listed packages, the
+/// same as ferriswheel's `ColorCorrection`. For a custom profile, light a
+/// strip white at full power and lower the channels that overpower the
+/// others until the white looks neutral.
+///
+/// # Example
+///
+/// ```
+/// use ws2812_pure::{apply_calibration, Calibration};
+/// use rgb::RGB8;
+///
+/// let mut pixels = [RGB8::new(255, 255, 255), RGB8::new(0, 100, 0)];
+/// apply_calibration(&mut pixels, Calibration::SMD5050);
+/// assert_eq!(pixels, [RGB8::new(255, 176, 240), RGB8::new(0, 69, 0)]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Calibration {
+    /// Red scale factor.
+    pub r: u8,
+    /// Green scale factor.
+    pub g: u8,
+    /// Blue scale factor.
+    pub b: u8,
+}
+
+impl Calibration {
+    /// No calibration.
+    pub const NONE: Calibration = Calibration::new(255, 255, 255);
+
+    /// 5050 SMD packages such as the WS2812B and SK6812, whose green and
+    /// blue outshine red.
+    pub const SMD5050: Calibration = Calibration::new(255, 176, 240);
+
+    /// 8 mm through-hole "pixel" LEDs, with a strong blue.
+    pub const PIXEL_8MM: Calibration = Calibration::new(255, 224, 140);
+
+    /// Creates a calibration from per-channel scale factors.
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    /// Applies the calibration to a single color, rounding down.
+    pub fn apply(&self, color: RGB8) -> RGB8 {
+        let scale = |channel: u8, factor: u8| ((channel as u16 * factor as u16) / 255) as u8;
+        RGB8::new(
+            scale(color.r, self.r),
+            scale(color.g, self.g),
+            scale(color.b, self.b),
+        )
+    }
+}
+
+impl Default for Calibration {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
+/// Applies `calibration` to every color in `pixels` in place.
+///
+/// Apply it to the finished frame, after any gamma correction.
+pub fn apply_calibration(pixels: &mut [RGB8], calibration: Calibration) {
+    if calibration == Calibration::NONE {
+        return;
+    }
+    for pixel in pixels.iter_mut() {
+        *pixel = calibration.apply(*pixel);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_none_is_identity() {
+        let mut pixels = [RGB8::new(1, 128, 255), RGB8::new(77, 0, 3)];
+        let original = pixels;
+        apply_calibration(&mut pixels, Calibration::default());
+        assert_eq!(pixels, original);
+    }
+
+    #[test]
+    fn test_presets_scale_white() {
+        let white = RGB8::new(255, 255, 255);
+        assert_eq!(Calibration::SMD5050.apply(white), RGB8::new(255, 176, 240));
+        assert_eq!(
+            Calibration::PIXEL_8MM.apply(white),
+            RGB8::new(255, 224, 140)
+        );
+    }
+
+    #[test]
+    fn test_presets_scale_mixed_colors() {
+        let a = RGB8::new(128, 200, 64);
+        let b = RGB8::new(10, 100, 254);
+        assert_eq!(Calibration::SMD5050.apply(a), RGB8::new(128, 138, 60));
+        assert_eq!(Calibration::SMD5050.apply(b), RGB8::new(10, 69, 239));
+        assert_eq!(Calibration::PIXEL_8MM.apply(a), RGB8::new(128, 175, 35));
+        assert_eq!(Calibration::PIXEL_8MM.apply(b), RGB8::new(10, 87, 139));
+    }
+
+    #[test]
+    fn test_custom_profile_per_channel() {
+        let calibration = Calibration::new(0, 128, 255);
+        let mut pixels = [RGB8::new(200, 200, 200)];
+        apply_calibration(&mut pixels, calibration);
+        assert_eq!(pixels, [RGB8::new(0, 100, 200)]);
+    }
+}
@@ -0,0 +1,131 @@
+//! Channel-wise color arithmetic.
+//!
+//! The building blocks of compositing: adding light, taking it away, and
+//! mixing two layers. Every function works on each channel on its own and
+//! never wraps around. ferriswheel's `add_color` is this one, re-exported.
+
+use rgb::RGB8;
+
+/// Applies `op` to each pair of channels.
+fn per_channel(a: RGB8, b: RGB8, op: impl Fn(u8, u8) -> u8) -> RGB8 {
+    RGB8::new(op(a.r, b.r), op(a.g, b.g), op(a.b, b.b))
+}
+
+/// Adds two colors channel by channel, saturating at 255.
+///
+/// # Example
+///
+/// ```
+/// use ws2812_pure::add_color;
+/// use rgb::RGB8;
+///
+/// let sum = add_color(RGB8::new(200, 100, 0), RGB8::new(100, 100, 10));
+/// assert_eq!(sum, RGB8::new(255, 200, 10));
+/// ```
+pub fn add_color(a: RGB8, b: RGB8) -> RGB8 {
+    per_channel(a, b, u8::saturating_add)
+}
+
+/// Subtracts `b` from `a` channel by channel, saturating at 0.
+///
+/// # Example
+///
+/// ```
+/// use ws2812_pure::sub_color;
+/// use rgb::RGB8;
+///
+/// let rest = sub_color(RGB8::new(200, 100, 0), RGB8::new(100, 150, 10));
+/// assert_eq!(rest, RGB8::new(100, 0, 0));
+/// ```
+pub fn sub_color(a: RGB8, b: RGB8) -> RGB8 {
+    per_channel(a, b, u8::saturating_sub)
+}
+
+/// Averages two colors channel by channel, rounding down.
+///
+/// # Example
+///
+/// ```
+/// use ws2812_pure::average_color;
+/// use rgb::RGB8;
+///
+/// let mix = average_color(RGB8::new(255, 0, 9), RGB8::new(0, 255, 0));
+/// assert_eq!(mix, RGB8::new(127, 127, 4));
+/// ```
+pub fn average_color(a: RGB8, b: RGB8) -> RGB8 {
+    per_channel(a, b, |x, y| ((x as u16 + y as u16) / 2) as u8)
+}
+
+/// Takes the brighter value of each channel, the "lighten" blend mode.
+///
+/// # Example
+///
+/// ```
+/// use ws2812_pure::max_color;
+/// use rgb::RGB8;
+///
+/// let lighter = max_color(RGB8::new(200, 10, 0), RGB8::new(100, 50, 0));
+/// assert_eq!(lighter, RGB8::new(200, 50, 0));
+/// ```
+pub fn max_color(a: RGB8, b: RGB8) -> RGB8 {
+    per_channel(a, b, u8::max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Pairs of channel values covering both ends and the middle.
+    fn samples() -> impl Iterator<Item = (u8, u8)> {
+        let values = [0u8, 1, 2, 127, 128, 129, 200, 254, 255];
+        values
+            .into_iter()
+            .flat_map(move |x| values.into_iter().map(move |y| (x, y)))
+    }
+
+    #[test]
+    fn test_add_saturates_at_255() {
+        for (x, y) in samples() {
+            let sum = add_color(RGB8::new(x, y, x), RGB8::new(y, x, 0));
+            assert_eq!(sum.r as u16, (x as u16 + y as u16).min(255));
+            assert_eq!(sum.g, sum.r);
+            assert_eq!(sum.b, x);
+        }
+    }
+
+    #[test]
+    fn test_sub_saturates_at_zero() {
+        for (x, y) in samples() {
+            let diff = sub_color(RGB8::new(x, x, x), RGB8::new(y, 0, 255));
+            assert_eq!(diff.r as i16, (x as i16 - y as i16).max(0));
+            assert_eq!(diff.g, x);
+            assert_eq!(diff.b, 0);
+        }
+    }
+
+    #[test]
+    fn test_average_rounds_down_without_overflow() {
+        for (x, y) in samples() {
+            let avg = average_color(RGB8::new(x, x, x), RGB8::new(y, y, y));
+            assert_eq!(avg.r as u16, (x as u16 + y as u16) / 2);
+            assert_eq!(avg, average_color(RGB8::new(y, y, y), RGB8::new(x, x, x)));
+        }
+        let white = RGB8::new(255, 255, 255);
+        assert_eq!(average_color(white, white), white);
+    }
+
+    #[test]
+    fn test_max_is_per_channel() {
+        for (x, y) in samples() {
+            let lighter = max_color(RGB8::new(x, y, 0), RGB8::new(y, x, 0));
+            assert_eq!(lighter, RGB8::new(x.max(y), x.max(y), 0));
+        }
+    }
+
+    #[test]
+    fn test_sub_undoes_add_below_saturation() {
+        let a = RGB8::new(10, 100, 150);
+        let b = RGB8::new(20, 50, 100);
+        assert_eq!(sub_color(add_color(a, b), b), a);
+    }
+}
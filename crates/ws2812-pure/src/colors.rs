@@ -0,0 +1,69 @@
+//! Named colors for status LEDs.
+//!
+//! The basic web colors with their CSS values, plus semantic `STATUS_*`
+//! aliases. led-effects re-exports this module, so firmware, drivers and
+//! its status indicator use the same aliases and "OK" looks the same on
+//! every board.
+//!
+//! # Example
+//!
+//! ```
+//! use ws2812_pure::colors::{self, STATUS_ERROR};
+//! use ws2812_pure::rgb_to_grb;
+//!
+//! assert_eq!(rgb_to_grb(STATUS_ERROR), 0x00FF00);
+//! assert_eq!(colors::STATUS_OK, colors::LIME);
+//! ```
+
+use rgb::RGB8;
+
+/// Black, `#000000`; the LED is off.
+pub const BLACK: RGB8 = RGB8::new(0, 0, 0);
+/// White, `#FFFFFF`.
+pub const WHITE: RGB8 = RGB8::new(255, 255, 255);
+/// Silver, `#C0C0C0`.
+pub const SILVER: RGB8 = RGB8::new(192, 192, 192);
+/// Gray, `#808080`.
+pub const GRAY: RGB8 = RGB8::new(128, 128, 128);
+/// Red, `#FF0000`.
+pub const RED: RGB8 = RGB8::new(255, 0, 0);
+/// Maroon, `#800000`.
+pub const MAROON: RGB8 = RGB8::new(128, 0, 0);
+/// Lime, `#00FF00`; full-brightness green.
+pub const LIME: RGB8 = RGB8::new(0, 255, 0);
+/// Green, `#008000`; CSS green is half brightness, use [`LIME`] for full green.
+pub const GREEN: RGB8 = RGB8::new(0, 128, 0);
+/// Blue, `#0000FF`.
+pub const BLUE: RGB8 = RGB8::new(0, 0, 255);
+/// Navy, `#000080`.
+pub const NAVY: RGB8 = RGB8::new(0, 0, 128);
+/// Yellow, `#FFFF00`.
+pub const YELLOW: RGB8 = RGB8::new(255, 255, 0);
+/// Olive, `#808000`.
+pub const OLIVE: RGB8 = RGB8::new(128, 128, 0);
+/// Cyan (aqua), `#00FFFF`.
+pub const CYAN: RGB8 = RGB8::new(0, 255, 255);
+/// Teal, `#008080`.
+pub const TEAL: RGB8 = RGB8::new(0, 128, 128);
+/// Magenta (fuchsia), `#FF00FF`.
+pub const MAGENTA: RGB8 = RGB8::new(255, 0, 255);
+/// Purple, `#800080`.
+pub const PURPLE: RGB8 = RGB8::new(128, 0, 128);
+/// Orange, `#FFA500`.
+pub const ORANGE: RGB8 = RGB8::new(255, 165, 0);
+/// Amber, `#FF7800`; an orange that stays distinct from yellow and red on
+/// WS2812 LEDs, where the green die is much brighter than the red one.
+pub const AMBER: RGB8 = RGB8::new(255, 120, 0);
+
+/// The LED is off.
+pub const OFF: RGB8 = BLACK;
+/// Everything works, e.g. connected.
+pub const STATUS_OK: RGB8 = LIME;
+/// Degraded but running, e.g. a firmware update or a weak signal.
+pub const STATUS_WARN: RGB8 = AMBER;
+/// Failed; also the default color of error blink codes.
+pub const STATUS_ERROR: RGB8 = RED;
+/// Busy connecting to a network.
+pub const STATUS_CONNECTING: RGB8 = BLUE;
+/// Waiting to be set up, e.g. an access point for Wi-Fi provisioning.
+pub const STATUS_PROVISIONING: RGB8 = MAGENTA;
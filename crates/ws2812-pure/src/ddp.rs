@@ -0,0 +1,548 @@
+//! DDP (Distributed Display Protocol) packets.
+//!
+//! xLights, LedFx and WLED stream pixel data as DDP over UDP. A packet is a
+//! 10-byte header, or 14 bytes with a timecode, followed by raw channel
+//! data written at a byte offset into the receiver's frame. These functions
+//! only encode and decode the bytes; the sockets are up to the firmware.
+
+use rgb::RGB8;
+
+/// UDP port DDP receivers listen on.
+pub const DDP_PORT: u16 = 4048;
+
+/// Length of a DDP header without timecode.
+pub const DDP_HEADER_LEN: usize = 10;
+
+/// Most data bytes in one packet: 480 RGB pixels.
+pub const DDP_MAX_DATA_LEN: usize = 1440;
+
+/// Destination id of the default output device.
+pub const DDP_ID_DISPLAY: u8 = 1;
+
+/// Data type of 8-bit RGB pixels.
+pub const DDP_TYPE_RGB8: u8 = 0x0B;
+
+/// Data type of 8-bit RGB pixels sent by older senders.
+const DDP_TYPE_RGB8_LEGACY: u8 = 0x01;
+
+// Protocol version in the top two bits of the flags, then the flag bits
+const VERSION_1: u8 = 0x40;
+const VERSION_MASK: u8 = 0xC0;
+const FLAG_TIMECODE: u8 = 0x10;
+const FLAG_STORAGE: u8 = 0x08;
+const FLAG_REPLY: u8 = 0x04;
+const FLAG_QUERY: u8 = 0x02;
+const FLAG_PUSH: u8 = 0x01;
+
+/// Error type for encoding and decoding DDP packets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DdpError {
+    /// The buffer is too small for the packet.
+    BufferTooSmall {
+        /// Number of bytes needed.
+        required: usize,
+        /// Actual buffer size provided.
+        actual: usize,
+    },
+    /// More data than one packet carries.
+    TooMuchData {
+        /// Data bytes provided.
+        actual: usize,
+    },
+    /// The packet is shorter than its header and data length claim.
+    Truncated,
+    /// The packet is not DDP version 1; holds the version bits.
+    UnsupportedVersion(u8),
+    /// The packet does not carry 8-bit RGB pixels; holds its data type.
+    UnsupportedDataType(u8),
+}
+
+impl core::fmt::Display for DdpError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            DdpError::BufferTooSmall { required, actual } => write!(
+                f,
+                "DDP buffer too small: {} bytes required, {} provided",
+                required, actual
+            ),
+            DdpError::TooMuchData { actual } => write!(
+                f,
+                "too much data for one DDP packet: {} bytes (max {})",
+                actual, DDP_MAX_DATA_LEN
+            ),
+            DdpError::Truncated => write!(f, "DDP packet is truncated"),
+            DdpError::UnsupportedVersion(version) => {
+                write!(f, "unsupported DDP version {}", version)
+            }
+            DdpError::UnsupportedDataType(data_type) => {
+                write!(f, "unsupported DDP data type {:#04x}", data_type)
+            }
+        }
+    }
+}
+
+/// The header of a DDP packet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DdpHeader {
+    /// Display the frame once this packet has arrived.
+    pub push: bool,
+    /// The sender asks for data instead of sending it.
+    pub query: bool,
+    /// The packet answers a query.
+    pub reply: bool,
+    /// Read from or write to storage instead of the display.
+    pub storage: bool,
+    /// Sequence number 1-15, or 0 if unused. Only the low 4 bits are sent.
+    pub sequence: u8,
+    /// Format of the data, e.g. [`DDP_TYPE_RGB8`].
+    pub data_type: u8,
+    /// Destination id, e.g. [`DDP_ID_DISPLAY`].
+    pub id: u8,
+    /// Byte offset of the data in the receiver's frame.
+    pub offset: u32,
+    /// Number of data bytes after the header.
+    pub length: u16,
+    /// Optional timecode for synchronized display.
+    pub timecode: Option<u32>,
+}
+
+impl DdpHeader {
+    /// Creates the header of an RGB data packet for the display.
+    pub const fn rgb(offset: u32, length: u16) -> Self {
+        Self {
+            push: false,
+            query: false,
+            reply: false,
+            storage: false,
+            sequence: 0,
+            data_type: DDP_TYPE_RGB8,
+            id: DDP_ID_DISPLAY,
+            offset,
+            length,
+            timecode: None,
+        }
+    }
+
+    /// Returns the encoded size: 10 bytes, or 14 with a timecode.
+    pub const fn encoded_len(&self) -> usize {
+        match self.timecode {
+            Some(_) => DDP_HEADER_LEN + 4,
+            None => DDP_HEADER_LEN,
+        }
+    }
+
+    /// Writes the header to the start of `buffer` and returns its length.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DdpError::BufferTooSmall` if `buffer` cannot hold the header.
+    pub fn encode(&self, buffer: &mut [u8]) -> Result<usize, DdpError> {
+        let len = self.encoded_len();
+        if buffer.len() < len {
+            return Err(DdpError::BufferTooSmall {
+                required: len,
+                actual: buffer.len(),
+            });
+        }
+
+        let flags = [
+            (self.timecode.is_some(), FLAG_TIMECODE),
+            (self.storage, FLAG_STORAGE),
+            (self.reply, FLAG_REPLY),
+            (self.query, FLAG_QUERY),
+            (self.push, FLAG_PUSH),
+        ];
+        buffer[0] = flags
+            .iter()
+            .filter(|(set, _)| *set)
+            .fold(VERSION_1, |acc, (_, flag)| acc | flag);
+        buffer[1] = self.sequence & 0x0F;
+        buffer[2] = self.data_type;
+        buffer[3] = self.id;
+        buffer[4..8].copy_from_slice(&self.offset.to_be_bytes());
+        buffer[8..10].copy_from_slice(&self.length.to_be_bytes());
+        if let Some(timecode) = self.timecode {
+            buffer[10..14].copy_from_slice(&timecode.to_be_bytes());
+        }
+        Ok(len)
+    }
+
+    /// Reads a header from the start of `bytes`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DdpError::Truncated` if `bytes` is shorter than the header, or
+    /// `DdpError::UnsupportedVersion` if it is not DDP version 1.
+    pub fn decode(bytes: &[u8]) -> Result<Self, DdpError> {
+        if bytes.len() < DDP_HEADER_LEN {
+            return Err(DdpError::Truncated);
+        }
+        let flags = bytes[0];
+        if flags & VERSION_MASK != VERSION_1 {
+            return Err(DdpError::UnsupportedVersion(flags >> 6));
+        }
+
+        let timecode = if flags & FLAG_TIMECODE != 0 {
+            let bytes = bytes
+                .get(DDP_HEADER_LEN..DDP_HEADER_LEN + 4)
+                .ok_or(DdpError::Truncated)?;
+            Some(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+        } else {
+            None
+        };
+        Ok(Self {
+            push: flags & FLAG_PUSH != 0,
+            query: flags & FLAG_QUERY != 0,
+            reply: flags & FLAG_REPLY != 0,
+            storage: flags & FLAG_STORAGE != 0,
+            sequence: bytes[1] & 0x0F,
+            data_type: bytes[2],
+            id: bytes[3],
+            offset: u32::from_be_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]),
+            length: u16::from_be_bytes([bytes[8], bytes[9]]),
+            timecode,
+        })
+    }
+}
+
+/// Encodes an RGB data packet for the pixels from `start` on.
+///
+/// Set `push` on the last packet of a frame, so the receiver displays it.
+/// Returns the packet length.
+///
+/// # Errors
+///
+/// Returns `DdpError::TooMuchData` for more than 480 pixels, or
+/// `DdpError::BufferTooSmall` if `buffer` cannot hold the packet.
+///
+/// # Example
+///
+/// ```
+/// use ws2812_pure::{decode_ddp, encode_ddp_rgb};
+/// use rgb::RGB8;
+///
+/// let pixels = [RGB8::new(255, 0, 0), RGB8::new(0, 0, 255)];
+/// let mut packet = [0u8; 16];
+/// let len = encode_ddp_rgb(&pixels, 0, 1, true, &mut packet).unwrap();
+/// assert_eq!(len, 16);
+///
+/// let mut frame = [RGB8::default(); 2];
+/// let decoded = decode_ddp(&packet[..len]).unwrap();
+/// assert!(decoded.header().push);
+/// decoded.apply(&mut frame).unwrap();
+/// assert_eq!(frame, pixels);
+/// ```
+pub fn encode_ddp_rgb(
+    pixels: &[RGB8],
+    start: u32,
+    sequence: u8,
+    push: bool,
+    buffer: &mut [u8],
+) -> Result<usize, DdpError> {
+    let length = pixels.len() * 3;
+    if length > DDP_MAX_DATA_LEN {
+        return Err(DdpError::TooMuchData { actual: length });
+    }
+    let required = DDP_HEADER_LEN + length;
+    if buffer.len() < required {
+        return Err(DdpError::BufferTooSmall {
+            required,
+            actual: buffer.len(),
+        });
+    }
+
+    let header = DdpHeader {
+        push,
+        sequence,
+        ..DdpHeader::rgb(start.saturating_mul(3), length as u16)
+    };
+    header.encode(buffer)?;
+    let data = &mut buffer[DDP_HEADER_LEN..required];
+    for (color, bytes) in pixels.iter().zip(data.chunks_exact_mut(3)) {
+        bytes.copy_from_slice(&[color.r, color.g, color.b]);
+    }
+    Ok(required)
+}
+
+/// Decodes a DDP packet.
+///
+/// Bytes after the data length in the header are ignored.
+///
+/// # Errors
+///
+/// Returns `DdpError::Truncated` if the packet is shorter than its header
+/// and data length, or `DdpError::UnsupportedVersion`.
+pub fn decode_ddp(packet: &[u8]) -> Result<DdpPacket<'_>, DdpError> {
+    let header = DdpHeader::decode(packet)?;
+    let start = header.encoded_len();
+    let data = packet
+        .get(start..start + header.length as usize)
+        .ok_or(DdpError::Truncated)?;
+    Ok(DdpPacket { header, data })
+}
+
+/// A decoded DDP packet, borrowing its data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DdpPacket<'a> {
+    header: DdpHeader,
+    data: &'a [u8],
+}
+
+impl<'a> DdpPacket<'a> {
+    /// Returns the header.
+    pub fn header(&self) -> &DdpHeader {
+        &self.header
+    }
+
+    /// Returns the data bytes.
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Returns `true` if the data holds 8-bit RGB pixels.
+    pub fn is_rgb(&self) -> bool {
+        matches!(self.header.data_type, DDP_TYPE_RGB8 | DDP_TYPE_RGB8_LEGACY)
+    }
+
+    /// Writes the data into `frame` at the header's byte offset, skipping
+    /// bytes past its end.
+    ///
+    /// Offsets need not fall on a pixel boundary. Returns the number of
+    /// channel bytes written.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DdpError::UnsupportedDataType` unless the data holds 8-bit
+    /// RGB pixels.
+    pub fn apply(&self, frame: &mut [RGB8]) -> Result<usize, DdpError> {
+        if !self.is_rgb() {
+            return Err(DdpError::UnsupportedDataType(self.header.data_type));
+        }
+
+        let mut written = 0;
+        for (i, &value) in self.data.iter().enumerate() {
+            // The offset comes from the network and may overflow a 32-bit usize
+            let Some(position) = (self.header.offset as usize).checked_add(i) else {
+                break;
+            };
+            let Some(pixel) = frame.get_mut(position / 3) else {
+                break;
+            };
+            match position % 3 {
+                0 => pixel.r = value,
+                1 => pixel.g = value,
+                _ => pixel.b = value,
+            }
+            written += 1;
+        }
+        Ok(written)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn full_header() -> DdpHeader {
+        DdpHeader {
+            push: true,
+            query: true,
+            reply: true,
+            storage: true,
+            sequence: 9,
+            data_type: 0x1B,
+            id: 254,
+            offset: 0x0102_0304,
+            length: 0x0506,
+            timecode: Some(0xA0B0_C0D0),
+        }
+    }
+
+    #[test]
+    fn test_header_bytes() {
+        let mut buffer = [0u8; 10];
+        let header = DdpHeader {
+            push: true,
+            sequence: 3,
+            ..DdpHeader::rgb(0x0000_05A0, 1440)
+        };
+        assert_eq!(header.encode(&mut buffer), Ok(10));
+        assert_eq!(buffer, [0x41, 3, 0x0B, 1, 0, 0, 0x05, 0xA0, 0x05, 0xA0]);
+    }
+
+    #[test]
+    fn test_header_with_timecode_bytes() {
+        let mut buffer = [0u8; 14];
+        assert_eq!(full_header().encode(&mut buffer), Ok(14));
+        assert_eq!(
+            buffer,
+            [
+                0x5F, 9, 0x1B, 254, 1, 2, 3, 4, 5, 6, //
+                0xA0, 0xB0, 0xC0, 0xD0,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_each_flag_round_trips() {
+        let base = DdpHeader::rgb(0, 0);
+        let variants = [
+            DdpHeader { push: true, ..base },
+            DdpHeader {
+                query: true,
+                ..base
+            },
+            DdpHeader {
+                reply: true,
+                ..base
+            },
+            DdpHeader {
+                storage: true,
+                ..base
+            },
+            DdpHeader {
+                timecode: Some(7),
+                ..base
+            },
+        ];
+        let mut seen = Vec::new();
+        for header in variants {
+            let mut buffer = [0u8; 14];
+            let len = header.encode(&mut buffer).unwrap();
+            assert_eq!(DdpHeader::decode(&buffer[..len]), Ok(header));
+            seen.push(buffer[0]);
+        }
+        assert_eq!(seen, [0x41, 0x42, 0x44, 0x48, 0x50]);
+    }
+
+    #[test]
+    fn test_full_header_round_trips() {
+        let mut buffer = [0u8; 14];
+        full_header().encode(&mut buffer).unwrap();
+        assert_eq!(DdpHeader::decode(&buffer), Ok(full_header()));
+    }
+
+    #[test]
+    fn test_sequence_keeps_low_bits() {
+        let mut buffer = [0u8; 10];
+        let header = DdpHeader {
+            sequence: 0x1F,
+            ..DdpHeader::rgb(0, 0)
+        };
+        header.encode(&mut buffer).unwrap();
+        assert_eq!(buffer[1], 0x0F);
+        buffer[1] = 0xF3;
+        assert_eq!(DdpHeader::decode(&buffer).unwrap().sequence, 3);
+    }
+
+    #[test]
+    fn test_decode_rejects_bad_headers() {
+        assert_eq!(DdpHeader::decode(&[0x41; 9]), Err(DdpError::Truncated));
+        // Timecode flag without the timecode
+        assert_eq!(DdpHeader::decode(&[0x51; 12]), Err(DdpError::Truncated));
+        assert_eq!(
+            DdpHeader::decode(&[0x81; 10]),
+            Err(DdpError::UnsupportedVersion(2))
+        );
+        assert_eq!(
+            DdpHeader::decode(&[0x01; 10]),
+            Err(DdpError::UnsupportedVersion(0))
+        );
+    }
+
+    #[test]
+    fn test_encode_header_buffer_too_small() {
+        assert_eq!(
+            full_header().encode(&mut [0u8; 13]),
+            Err(DdpError::BufferTooSmall {
+                required: 14,
+                actual: 13
+            })
+        );
+    }
+
+    #[test]
+    fn test_packet_round_trip_with_offset() {
+        let pixels = [RGB8::new(1, 2, 3), RGB8::new(4, 5, 6)];
+        let mut buffer = [0u8; 32];
+        let len = encode_ddp_rgb(&pixels, 2, 5, false, &mut buffer).unwrap();
+
+        let packet = decode_ddp(&buffer[..len]).unwrap();
+        assert_eq!(packet.header().offset, 6);
+        assert_eq!(packet.header().sequence, 5);
+        assert_eq!(packet.data(), [1, 2, 3, 4, 5, 6]);
+
+        let mut frame = [RGB8::default(); 3];
+        assert_eq!(packet.apply(&mut frame), Ok(3));
+        assert_eq!(frame[2], RGB8::new(1, 2, 3));
+    }
+
+    #[test]
+    fn test_apply_unaligned_offset() {
+        let mut packet = [0u8; 14];
+        DdpHeader::rgb(1, 4).encode(&mut packet).unwrap();
+        packet[10..].copy_from_slice(&[10, 20, 30, 40]);
+
+        let mut frame = [RGB8::default(); 2];
+        decode_ddp(&packet).unwrap().apply(&mut frame).unwrap();
+        assert_eq!(frame, [RGB8::new(0, 10, 20), RGB8::new(30, 40, 0)]);
+    }
+
+    #[test]
+    fn test_apply_offset_near_u32_max_writes_nothing() {
+        let mut packet = [0u8; 13];
+        DdpHeader::rgb(u32::MAX - 1, 3).encode(&mut packet).unwrap();
+        packet[10..].copy_from_slice(&[10, 20, 30]);
+
+        let mut frame = [RGB8::default(); 2];
+        let written = decode_ddp(&packet).unwrap().apply(&mut frame).unwrap();
+        assert_eq!(written, 0);
+        assert_eq!(frame, [RGB8::default(); 2]);
+    }
+
+    #[test]
+    fn test_apply_rejects_other_data_types() {
+        let mut packet = [0u8; 10];
+        DdpHeader {
+            data_type: 0x1B,
+            ..DdpHeader::rgb(0, 0)
+        }
+        .encode(&mut packet)
+        .unwrap();
+        let err = decode_ddp(&packet).unwrap().apply(&mut []).unwrap_err();
+        assert_eq!(err, DdpError::UnsupportedDataType(0x1B));
+        assert_eq!(format!("{}", err), "unsupported DDP data type 0x1b");
+    }
+
+    #[test]
+    fn test_legacy_rgb_type() {
+        let mut packet = [0x41, 0, 0x01, 1, 0, 0, 0, 0, 0, 3, 9, 8, 7];
+        let mut frame = [RGB8::default(); 1];
+        decode_ddp(&packet).unwrap().apply(&mut frame).unwrap();
+        assert_eq!(frame, [RGB8::new(9, 8, 7)]);
+
+        // Data length larger than the packet
+        packet[9] = 4;
+        assert_eq!(decode_ddp(&packet), Err(DdpError::Truncated));
+    }
+
+    #[test]
+    fn test_encode_limits() {
+        let mut buffer = [0u8; 1500];
+        assert_eq!(
+            encode_ddp_rgb(&[RGB8::default(); 480], 0, 0, true, &mut buffer),
+            Ok(1450)
+        );
+        assert_eq!(
+            encode_ddp_rgb(&[RGB8::default(); 481], 0, 0, true, &mut buffer),
+            Err(DdpError::TooMuchData { actual: 1443 })
+        );
+        assert_eq!(
+            encode_ddp_rgb(&[RGB8::default(); 2], 0, 0, true, &mut buffer[..15]),
+            Err(DdpError::BufferTooSmall {
+                required: 16,
+                actual: 15
+            })
+        );
+    }
+}
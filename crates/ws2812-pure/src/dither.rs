@@ -0,0 +1,192 @@
+//! Temporal dithering.
+//!
+//! With 8 bits per channel, a dim fade has only a handful of steps, and
+//! scaling a frame down rounds small channels to 0. Dithering alternates
+//! between the two nearest 8-bit values from frame to frame, so at a high
+//! enough frame rate the eye sees the value in between.
+
+use rgb::{RGB16, RGB8};
+
+/// Renders 16-bit colors as 8-bit frames whose average over time matches.
+///
+/// Each pixel channel keeps the fraction that was rounded off in the last
+/// frame and adds it to the next one, so no light is lost over time. A
+/// target of `v` (0-65535) averages to `v / 256` in 8-bit units.
+///
+/// # Example
+///
+/// ```
+/// use ws2812_pure::Ditherer;
+/// use rgb::{RGB16, RGB8};
+///
+/// let mut ditherer = Ditherer::<1>::new();
+/// let mut frame = [RGB8::default(); 1];
+///
+/// // 2.5 in 8-bit units alternates between 2 and 3
+/// let target = [RGB16::new(640, 0, 0)];
+/// ditherer.dither(&target, &mut frame);
+/// assert_eq!(frame[0].r, 2);
+/// ditherer.dither(&target, &mut frame);
+/// assert_eq!(frame[0].r, 3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Ditherer<const N: usize> {
+    residuals: [[u8; 3]; N],
+}
+
+impl<const N: usize> Ditherer<N> {
+    /// Creates a ditherer for `N` pixels with nothing carried over.
+    pub const fn new() -> Self {
+        Self {
+            residuals: [[0; 3]; N],
+        }
+    }
+
+    /// Drops the fractions carried over, e.g. after a scene change.
+    pub fn reset(&mut self) {
+        self.residuals = [[0; 3]; N];
+    }
+
+    /// Renders one frame of `targets` into `out`.
+    ///
+    /// Pixels past `N`, or past the end of either slice, are skipped.
+    pub fn dither(&mut self, targets: &[RGB16], out: &mut [RGB8]) {
+        for ((target, pixel), residual) in targets.iter().zip(out).zip(&mut self.residuals) {
+            *pixel = RGB8::new(
+                step(target.r, &mut residual[0]),
+                step(target.g, &mut residual[1]),
+                step(target.b, &mut residual[2]),
+            );
+        }
+    }
+
+    /// Scales `pixels` in place by `brightness` (0-255), dithering the result.
+    ///
+    /// The dithered counterpart of [`scale_frame`](crate::scale_frame): a
+    /// channel of 3 at brightness 64 averages to 0.75 instead of always 0.
+    pub fn scale(&mut self, pixels: &mut [RGB8], brightness: u8) {
+        // channel * brightness / 255 in 8.8 fixed point
+        let scale = |channel: u8| {
+            ((channel as u32 * brightness as u32 * 256 + 127) / 255).min(u16::MAX as u32) as u16
+        };
+        for (pixel, residual) in pixels.iter_mut().zip(&mut self.residuals) {
+            *pixel = RGB8::new(
+                step(scale(pixel.r), &mut residual[0]),
+                step(scale(pixel.g), &mut residual[1]),
+                step(scale(pixel.b), &mut residual[2]),
+            );
+        }
+    }
+}
+
+impl<const N: usize> Default for Ditherer<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Adds the carried fraction to `target` and returns the 8-bit value,
+/// keeping the new fraction in `residual`.
+fn step(target: u16, residual: &mut u8) -> u8 {
+    let total = target as u32 + *residual as u32;
+    let value = (total >> 8).min(255);
+    *residual = (total - (value << 8)).min(255) as u8;
+    value as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the red channel of `frames` dithered frames of `target`.
+    fn run(target: u16, frames: usize) -> Vec<u8> {
+        let mut ditherer = Ditherer::<1>::new();
+        let mut out = [RGB8::default()];
+        (0..frames)
+            .map(|_| {
+                ditherer.dither(&[RGB16::new(target, 0, 0)], &mut out);
+                out[0].r
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_average_matches_target() {
+        for target in [0u16, 1, 64, 255, 256, 640, 1000, 12_345, 65_280] {
+            let sum: u32 = run(target, 256).iter().map(|&v| v as u32).sum();
+            // Over 256 frames the sum is the target, minus the fraction
+            // still carried
+            assert!(target as u32 - sum < 256, "target {}", target);
+        }
+    }
+
+    #[test]
+    fn test_whole_values_do_not_flicker() {
+        assert!(run(5 * 256, 10).iter().all(|&v| v == 5));
+    }
+
+    #[test]
+    fn test_quarter_step_pattern() {
+        assert_eq!(run(64, 8), [0, 0, 0, 1, 0, 0, 0, 1]);
+    }
+
+    #[test]
+    fn test_full_scale_saturates() {
+        assert!(run(u16::MAX, 10).iter().all(|&v| v == 255));
+    }
+
+    #[test]
+    fn test_channels_and_pixels_are_independent() {
+        let mut ditherer = Ditherer::<2>::new();
+        let targets = [RGB16::new(128, 256, 0), RGB16::new(0, 0, 384)];
+        let mut out = [RGB8::default(); 2];
+        ditherer.dither(&targets, &mut out);
+        assert_eq!(out, [RGB8::new(0, 1, 0), RGB8::new(0, 0, 1)]);
+        ditherer.dither(&targets, &mut out);
+        assert_eq!(out, [RGB8::new(1, 1, 0), RGB8::new(0, 0, 2)]);
+    }
+
+    #[test]
+    fn test_reset_drops_residuals() {
+        let mut ditherer = Ditherer::<1>::new();
+        let mut out = [RGB8::default()];
+        ditherer.dither(&[RGB16::new(200, 0, 0)], &mut out);
+        ditherer.reset();
+        ditherer.dither(&[RGB16::new(200, 0, 0)], &mut out);
+        assert_eq!(out[0].r, 0);
+    }
+
+    #[test]
+    fn test_extra_pixels_are_skipped() {
+        let mut ditherer = Ditherer::<1>::new();
+        let mut out = [RGB8::new(9, 9, 9); 2];
+        ditherer.dither(&[RGB16::new(512, 512, 512); 2], &mut out);
+        assert_eq!(out, [RGB8::new(2, 2, 2), RGB8::new(9, 9, 9)]);
+    }
+
+    #[test]
+    fn test_scale_keeps_dim_channels_alive() {
+        let mut ditherer = Ditherer::<1>::new();
+        let mut lit = 0;
+        for _ in 0..100 {
+            let mut pixels = [RGB8::new(3, 0, 255)];
+            ditherer.scale(&mut pixels, 64);
+            lit += pixels[0].r as u32;
+            // Full channels match scale_frame, give or take the fraction
+            assert!((64..=65).contains(&pixels[0].b));
+        }
+        // 3 * 64 / 255 = 0.75 per frame
+        assert_eq!(lit, 75);
+    }
+
+    #[test]
+    fn test_scale_full_brightness_is_identity() {
+        let mut ditherer = Ditherer::<2>::new();
+        let original = [RGB8::new(0, 1, 2), RGB8::new(128, 254, 255)];
+        for _ in 0..5 {
+            let mut pixels = original;
+            ditherer.scale(&mut pixels, 255);
+            assert_eq!(pixels, original);
+        }
+    }
+}
@@ -0,0 +1,449 @@
+//! E1.31 (sACN) DMX data packets.
+//!
+//! Lighting desks and tools like xLights send DMX universes over UDP as
+//! E1.31. A data packet nests three layers, root, framing and DMP, each with
+//! its own length and vector, around up to 512 DMX slots. [`parse_e131`]
+//! checks all three and exposes the slots as RGB pixels, three slots each.
+
+use rgb::RGB8;
+
+/// UDP port E1.31 receivers listen on.
+pub const E131_PORT: u16 = 5568;
+
+/// Most DMX slots in one universe.
+pub const E131_MAX_SLOTS: usize = 512;
+
+/// ACN packet identifier at offset 4.
+const ACN_IDENTIFIER: [u8; 12] = *b"ASC-E1.17\0\0\0";
+const VECTOR_ROOT_DATA: u32 = 0x0000_0004;
+const VECTOR_FRAMING_DATA: u32 = 0x0000_0002;
+const VECTOR_DMP_SET_PROPERTY: u8 = 0x02;
+const DMP_ADDRESS_AND_DATA_TYPE: u8 = 0xA1;
+
+// Offsets of the fields used here
+const ROOT_LENGTH: usize = 16;
+const ROOT_VECTOR: usize = 18;
+const FRAMING_LENGTH: usize = 38;
+const FRAMING_VECTOR: usize = 40;
+const SOURCE_NAME: usize = 44;
+const PRIORITY: usize = 108;
+const SEQUENCE: usize = 111;
+const OPTIONS: usize = 112;
+const UNIVERSE: usize = 113;
+const DMP_LENGTH: usize = 115;
+const DMP_VECTOR: usize = 117;
+const DMP_TYPE: usize = 118;
+const FIRST_ADDRESS: usize = 119;
+const ADDRESS_INCREMENT: usize = 121;
+const VALUE_COUNT: usize = 123;
+const START_CODE: usize = 125;
+
+const OPTION_PREVIEW: u8 = 0x80;
+const OPTION_TERMINATED: u8 = 0x40;
+
+/// Error type for [`parse_e131`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum E131Error {
+    /// The packet is shorter than its layers claim.
+    Truncated,
+    /// The preamble or ACN packet identifier is wrong.
+    InvalidIdentifier,
+    /// A layer length does not match the packet size.
+    LengthMismatch,
+    /// The packet is not a data packet, e.g. a sync or discovery packet;
+    /// holds the unexpected vector.
+    UnsupportedVector(u32),
+    /// The DMP layer does not use the address layout E1.31 requires.
+    InvalidDmpLayer,
+    /// The universe is outside 1-63999.
+    InvalidUniverse(u16),
+}
+
+impl core::fmt::Display for E131Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            E131Error::Truncated => write!(f, "E1.31 packet is truncated"),
+            E131Error::InvalidIdentifier => write!(f, "not an E1.31 packet"),
+            E131Error::LengthMismatch => {
+                write!(f, "E1.31 layer length does not match the packet")
+            }
+            E131Error::UnsupportedVector(vector) => {
+                write!(f, "unsupported E1.31 vector {:#x}", vector)
+            }
+            E131Error::InvalidDmpLayer => write!(f, "invalid E1.31 DMP layer"),
+            E131Error::InvalidUniverse(universe) => {
+                write!(f, "invalid E1.31 universe {}", universe)
+            }
+        }
+    }
+}
+
+fn read_u16(packet: &[u8], offset: usize) -> u16 {
+    u16::from_be_bytes([packet[offset], packet[offset + 1]])
+}
+
+fn read_u32(packet: &[u8], offset: usize) -> u32 {
+    u32::from_be_bytes([
+        packet[offset],
+        packet[offset + 1],
+        packet[offset + 2],
+        packet[offset + 3],
+    ])
+}
+
+/// Checks the flags and length field of the layer starting at `offset`.
+fn check_layer_length(packet: &[u8], offset: usize) -> Result<(), E131Error> {
+    let field = read_u16(packet, offset);
+    let length = (field & 0x0FFF) as usize;
+    if field & 0xF000 != 0x7000 || length != packet.len() - offset {
+        return Err(E131Error::LengthMismatch);
+    }
+    Ok(())
+}
+
+/// Parses an E1.31 data packet.
+///
+/// # Errors
+///
+/// Returns an [`E131Error`] if any of the three layers is malformed, or if
+/// the packet is not a data packet.
+///
+/// # Example
+///
+/// ```
+/// use ws2812_pure::parse_e131;
+/// use rgb::RGB8;
+///
+/// # let mut packet = [0u8; 132];
+/// # packet[..16].copy_from_slice(&[0, 0x10, 0, 0, 0x41, 0x53, 0x43, 0x2d, 0x45, 0x31, 0x2e, 0x31, 0x37, 0, 0, 0]);
+/// # packet[16..22].copy_from_slice(&[0x70, 116, 0, 0, 0, 4]);
+/// # packet[38..44].copy_from_slice(&[0x70, 94, 0, 0, 0, 2]);
+/// # packet[108] = 100;
+/// # packet[113..115].copy_from_slice(&[0, 1]);
+/// # packet[115..126].copy_from_slice(&[0x70, 17, 2, 0xA1, 0, 0, 0, 1, 0, 7, 0]);
+/// # packet[126..].copy_from_slice(&[255, 0, 0, 0, 0, 255]);
+/// let universe = parse_e131(&packet).unwrap();
+/// assert_eq!(universe.universe(), 1);
+///
+/// let mut frame = [RGB8::default(); 2];
+/// universe.apply(&mut frame);
+/// assert_eq!(frame, [RGB8::new(255, 0, 0), RGB8::new(0, 0, 255)]);
+/// ```
+pub fn parse_e131(packet: &[u8]) -> Result<E131Packet<'_>, E131Error> {
+    if packet.len() < START_CODE + 1 {
+        return Err(E131Error::Truncated);
+    }
+    if packet[..4] != [0x00, 0x10, 0x00, 0x00] || packet[4..16] != ACN_IDENTIFIER {
+        return Err(E131Error::InvalidIdentifier);
+    }
+
+    check_layer_length(packet, ROOT_LENGTH)?;
+    let vector = read_u32(packet, ROOT_VECTOR);
+    if vector != VECTOR_ROOT_DATA {
+        return Err(E131Error::UnsupportedVector(vector));
+    }
+
+    check_layer_length(packet, FRAMING_LENGTH)?;
+    let vector = read_u32(packet, FRAMING_VECTOR);
+    if vector != VECTOR_FRAMING_DATA {
+        return Err(E131Error::UnsupportedVector(vector));
+    }
+    let universe = read_u16(packet, UNIVERSE);
+    if !(1..=63_999).contains(&universe) {
+        return Err(E131Error::InvalidUniverse(universe));
+    }
+
+    check_layer_length(packet, DMP_LENGTH)?;
+    if packet[DMP_VECTOR] != VECTOR_DMP_SET_PROPERTY {
+        return Err(E131Error::UnsupportedVector(packet[DMP_VECTOR] as u32));
+    }
+    let count = read_u16(packet, VALUE_COUNT) as usize;
+    if packet[DMP_TYPE] != DMP_ADDRESS_AND_DATA_TYPE
+        || read_u16(packet, FIRST_ADDRESS) != 0
+        || read_u16(packet, ADDRESS_INCREMENT) != 1
+        || !(1..=E131_MAX_SLOTS + 1).contains(&count)
+    {
+        return Err(E131Error::InvalidDmpLayer);
+    }
+    if packet.len() != START_CODE + count {
+        return Err(E131Error::LengthMismatch);
+    }
+
+    Ok(E131Packet {
+        universe,
+        sequence: packet[SEQUENCE],
+        priority: packet[PRIORITY],
+        options: packet[OPTIONS],
+        source_name: &packet[SOURCE_NAME..PRIORITY],
+        start_code: packet[START_CODE],
+        slots: &packet[START_CODE + 1..],
+    })
+}
+
+/// A parsed E1.31 data packet, borrowing its DMX slots.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct E131Packet<'a> {
+    universe: u16,
+    sequence: u8,
+    priority: u8,
+    options: u8,
+    source_name: &'a [u8],
+    start_code: u8,
+    slots: &'a [u8],
+}
+
+impl<'a> E131Packet<'a> {
+    /// Returns the universe, 1-63999.
+    pub fn universe(&self) -> u16 {
+        self.universe
+    }
+
+    /// Returns the sequence number.
+    pub fn sequence(&self) -> u8 {
+        self.sequence
+    }
+
+    /// Returns the priority, 0-200; receivers follow the highest source.
+    pub fn priority(&self) -> u8 {
+        self.priority
+    }
+
+    /// Returns the sender's name, or `""` if it is not valid UTF-8.
+    pub fn source_name(&self) -> &'a str {
+        let end = self
+            .source_name
+            .iter()
+            .position(|&b| b == 0)
+            .unwrap_or(self.source_name.len());
+        core::str::from_utf8(&self.source_name[..end]).unwrap_or("")
+    }
+
+    /// Returns `true` if the data is meant for previews, not live output.
+    pub fn is_preview(&self) -> bool {
+        self.options & OPTION_PREVIEW != 0
+    }
+
+    /// Returns `true` if the source stops sending to this universe.
+    pub fn is_terminated(&self) -> bool {
+        self.options & OPTION_TERMINATED != 0
+    }
+
+    /// Returns the DMX start code; 0 for dimmer data.
+    pub fn start_code(&self) -> u8 {
+        self.start_code
+    }
+
+    /// Returns the DMX slots after the start code.
+    pub fn slots(&self) -> &'a [u8] {
+        self.slots
+    }
+
+    /// Returns the slots as RGB pixels, three slots each. A trailing partial
+    /// pixel is dropped.
+    pub fn pixels(&self) -> impl Iterator<Item = RGB8> + 'a {
+        self.slots
+            .chunks_exact(3)
+            .map(|slot| RGB8::new(slot[0], slot[1], slot[2]))
+    }
+
+    /// Writes the pixels into `frame` from its start and returns how many
+    /// were written.
+    ///
+    /// Only dimmer data (start code 0) is written.
+    pub fn apply(&self, frame: &mut [RGB8]) -> usize {
+        if self.start_code != 0 {
+            return 0;
+        }
+        let mut written = 0;
+        for (pixel, color) in frame.iter_mut().zip(self.pixels()) {
+            *pixel = color;
+            written += 1;
+        }
+        written
+    }
+
+    /// Returns `true` unless the packet arrived out of order after the one
+    /// with sequence number `last`.
+    ///
+    /// Following E1.31, a packet is stale if its sequence number is at most
+    /// 19 behind `last`, or equal to it.
+    pub fn is_newer_than(&self, last: u8) -> bool {
+        let diff = self.sequence.wrapping_sub(last) as i8;
+        !(-20 < diff && diff <= 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a valid data packet for universe 1 with `slots`.
+    fn packet(slots: &[u8]) -> Vec<u8> {
+        let len = START_CODE + 1 + slots.len();
+        let mut packet = vec![0u8; len];
+        packet[..4].copy_from_slice(&[0x00, 0x10, 0x00, 0x00]);
+        packet[4..16].copy_from_slice(&ACN_IDENTIFIER);
+        let layer = |offset: usize| (0x7000 | (len - offset) as u16).to_be_bytes();
+        packet[ROOT_LENGTH..ROOT_LENGTH + 2].copy_from_slice(&layer(ROOT_LENGTH));
+        packet[ROOT_VECTOR..ROOT_VECTOR + 4].copy_from_slice(&VECTOR_ROOT_DATA.to_be_bytes());
+        packet[FRAMING_LENGTH..FRAMING_LENGTH + 2].copy_from_slice(&layer(FRAMING_LENGTH));
+        packet[FRAMING_VECTOR..FRAMING_VECTOR + 4]
+            .copy_from_slice(&VECTOR_FRAMING_DATA.to_be_bytes());
+        packet[SOURCE_NAME..SOURCE_NAME + 6].copy_from_slice(b"desk 1");
+        packet[PRIORITY] = 100;
+        packet[SEQUENCE] = 42;
+        packet[UNIVERSE..UNIVERSE + 2].copy_from_slice(&1u16.to_be_bytes());
+        packet[DMP_LENGTH..DMP_LENGTH + 2].copy_from_slice(&layer(DMP_LENGTH));
+        packet[DMP_VECTOR] = VECTOR_DMP_SET_PROPERTY;
+        packet[DMP_TYPE] = DMP_ADDRESS_AND_DATA_TYPE;
+        packet[ADDRESS_INCREMENT + 1] = 1;
+        packet[VALUE_COUNT..VALUE_COUNT + 2]
+            .copy_from_slice(&(slots.len() as u16 + 1).to_be_bytes());
+        packet[START_CODE + 1..].copy_from_slice(slots);
+        packet
+    }
+
+    #[test]
+    fn test_parse_metadata() {
+        let bytes = packet(&[1, 2, 3]);
+        let packet = parse_e131(&bytes).unwrap();
+        assert_eq!(packet.universe(), 1);
+        assert_eq!(packet.sequence(), 42);
+        assert_eq!(packet.priority(), 100);
+        assert_eq!(packet.source_name(), "desk 1");
+        assert_eq!(packet.start_code(), 0);
+        assert_eq!(packet.slots(), [1, 2, 3]);
+        assert!(!packet.is_preview());
+        assert!(!packet.is_terminated());
+    }
+
+    #[test]
+    fn test_options() {
+        let mut bytes = packet(&[]);
+        bytes[OPTIONS] = OPTION_PREVIEW | OPTION_TERMINATED;
+        let packet = parse_e131(&bytes).unwrap();
+        assert!(packet.is_preview());
+        assert!(packet.is_terminated());
+    }
+
+    #[test]
+    fn test_pixels_drop_partial_pixel() {
+        let bytes = packet(&[1, 2, 3, 4, 5, 6, 7]);
+        let pixels: Vec<RGB8> = parse_e131(&bytes).unwrap().pixels().collect();
+        assert_eq!(pixels, [RGB8::new(1, 2, 3), RGB8::new(4, 5, 6)]);
+    }
+
+    #[test]
+    fn test_full_universe() {
+        let slots = [7u8; E131_MAX_SLOTS];
+        let bytes = packet(&slots);
+        assert_eq!(bytes.len(), 638);
+        let packet = parse_e131(&bytes).unwrap();
+        assert_eq!(packet.pixels().count(), 170);
+
+        let mut frame = [RGB8::default(); 200];
+        assert_eq!(packet.apply(&mut frame), 170);
+        assert_eq!(frame[169], RGB8::new(7, 7, 7));
+        assert_eq!(frame[170], RGB8::default());
+    }
+
+    #[test]
+    fn test_apply_ignores_other_start_codes() {
+        let mut bytes = packet(&[1, 2, 3]);
+        bytes[START_CODE] = 0xDD;
+        let mut frame = [RGB8::default(); 1];
+        assert_eq!(parse_e131(&bytes).unwrap().apply(&mut frame), 0);
+        assert_eq!(frame, [RGB8::default()]);
+    }
+
+    #[test]
+    fn test_rejects_bad_identifier() {
+        let mut bytes = packet(&[]);
+        bytes[4] = b'X';
+        assert_eq!(parse_e131(&bytes), Err(E131Error::InvalidIdentifier));
+        assert_eq!(parse_e131(&bytes[..100]), Err(E131Error::Truncated));
+    }
+
+    #[test]
+    fn test_rejects_length_mismatches() {
+        let bytes = packet(&[1, 2, 3]);
+        // Cut off the last slot
+        assert_eq!(
+            parse_e131(&bytes[..bytes.len() - 1]),
+            Err(E131Error::LengthMismatch)
+        );
+
+        for offset in [ROOT_LENGTH, FRAMING_LENGTH, DMP_LENGTH] {
+            let mut bad = bytes.clone();
+            bad[offset] = 0x60;
+            assert_eq!(parse_e131(&bad), Err(E131Error::LengthMismatch));
+        }
+
+        let mut bad = bytes.clone();
+        bad[VALUE_COUNT + 1] = 3;
+        assert_eq!(parse_e131(&bad), Err(E131Error::LengthMismatch));
+    }
+
+    #[test]
+    fn test_rejects_other_vectors() {
+        let mut sync = packet(&[]);
+        sync[ROOT_VECTOR + 3] = 0x08;
+        assert_eq!(parse_e131(&sync), Err(E131Error::UnsupportedVector(8)));
+
+        let mut discovery = packet(&[]);
+        discovery[FRAMING_VECTOR + 3] = 0x01;
+        assert_eq!(parse_e131(&discovery), Err(E131Error::UnsupportedVector(1)));
+
+        let mut dmp = packet(&[]);
+        dmp[DMP_VECTOR] = 0x01;
+        assert_eq!(parse_e131(&dmp), Err(E131Error::UnsupportedVector(1)));
+    }
+
+    #[test]
+    fn test_rejects_bad_dmp_layout() {
+        for (offset, value) in [
+            (DMP_TYPE, 0xA2),
+            (FIRST_ADDRESS + 1, 1),
+            (ADDRESS_INCREMENT + 1, 2),
+        ] {
+            let mut bad = packet(&[]);
+            bad[offset] = value;
+            assert_eq!(parse_e131(&bad), Err(E131Error::InvalidDmpLayer));
+        }
+    }
+
+    #[test]
+    fn test_rejects_invalid_universe() {
+        for universe in [0u16, 64_000] {
+            let mut bad = packet(&[]);
+            bad[UNIVERSE..UNIVERSE + 2].copy_from_slice(&universe.to_be_bytes());
+            let err = parse_e131(&bad).unwrap_err();
+            assert_eq!(err, E131Error::InvalidUniverse(universe));
+        }
+        assert_eq!(
+            format!("{}", E131Error::InvalidUniverse(0)),
+            "invalid E1.31 universe 0"
+        );
+    }
+
+    #[test]
+    fn test_sequence_order() {
+        let bytes = packet(&[]);
+        let packet = parse_e131(&bytes).unwrap();
+        assert!(packet.is_newer_than(41));
+        assert!(!packet.is_newer_than(42));
+        assert!(!packet.is_newer_than(61));
+        assert!(packet.is_newer_than(62));
+        // Wraps around
+        let mut bytes = bytes.clone();
+        bytes[SEQUENCE] = 2;
+        assert!(parse_e131(&bytes).unwrap().is_newer_than(250));
+    }
+
+    #[test]
+    fn test_invalid_source_name() {
+        let mut bytes = packet(&[]);
+        bytes[SOURCE_NAME] = 0xFF;
+        assert_eq!(parse_e131(&bytes).unwrap().source_name(), "");
+    }
+}
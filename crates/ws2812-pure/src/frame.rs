@@ -0,0 +1,219 @@
+//! Fixed-capacity pixel storage.
+
+use core::slice;
+use rgb::RGB8;
+
+/// The colors of a strip of `N` pixels.
+///
+/// A thin wrapper around `[RGB8; N]` with bounds-checked access: writes past
+/// the end are ignored instead of panicking, so effects can draw shapes that
+/// run off the strip. [`as_slice`](Self::as_slice) hands the frame to any
+/// function that takes `&[RGB8]`, such as [`pack_frame`](crate::pack_frame).
+///
+/// # Example
+///
+/// ```
+/// use ws2812_pure::FrameBuffer;
+/// use rgb::RGB8;
+///
+/// let mut frame = FrameBuffer::<8>::new();
+/// frame.fill(RGB8::new(0, 0, 32));
+/// frame.set(0, RGB8::new(255, 0, 0));
+/// assert!(!frame.set(8, RGB8::new(255, 0, 0)));
+///
+/// assert_eq!(frame.get(0), Some(RGB8::new(255, 0, 0)));
+/// assert_eq!(frame.get(8), None);
+/// assert_eq!(frame.iter().filter(|c| c.b == 32).count(), 7);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FrameBuffer<const N: usize> {
+    pixels: [RGB8; N],
+}
+
+impl<const N: usize> FrameBuffer<N> {
+    /// Creates a frame with every pixel off.
+    pub const fn new() -> Self {
+        Self {
+            pixels: [RGB8 { r: 0, g: 0, b: 0 }; N],
+        }
+    }
+
+    /// Returns the number of pixels, `N`.
+    pub const fn len(&self) -> usize {
+        N
+    }
+
+    /// Returns `true` if the frame has no pixels.
+    pub const fn is_empty(&self) -> bool {
+        N == 0
+    }
+
+    /// Sets pixel `index` to `color`.
+    ///
+    /// Returns `false`, and changes nothing, if `index` is out of range.
+    pub fn set(&mut self, index: usize, color: RGB8) -> bool {
+        match self.pixels.get_mut(index) {
+            Some(pixel) => {
+                *pixel = color;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the color of pixel `index`, or `None` if it is out of range.
+    pub fn get(&self, index: usize) -> Option<RGB8> {
+        self.pixels.get(index).copied()
+    }
+
+    /// Sets every pixel to `color`.
+    pub fn fill(&mut self, color: RGB8) {
+        self.pixels.fill(color);
+    }
+
+    /// Turns every pixel off.
+    pub fn clear(&mut self) {
+        self.fill(RGB8::default());
+    }
+
+    /// Returns the pixels as a slice.
+    pub fn as_slice(&self) -> &[RGB8] {
+        &self.pixels
+    }
+
+    /// Returns the pixels as a mutable slice.
+    pub fn as_mut_slice(&mut self) -> &mut [RGB8] {
+        &mut self.pixels
+    }
+
+    /// Returns an iterator over the pixels.
+    pub fn iter(&self) -> slice::Iter<'_, RGB8> {
+        self.pixels.iter()
+    }
+
+    /// Returns an iterator that allows modifying each pixel.
+    pub fn iter_mut(&mut self) -> slice::IterMut<'_, RGB8> {
+        self.pixels.iter_mut()
+    }
+
+    /// Consumes the frame and returns the pixel array.
+    pub fn into_array(self) -> [RGB8; N] {
+        self.pixels
+    }
+}
+
+impl<const N: usize> Default for FrameBuffer<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> From<[RGB8; N]> for FrameBuffer<N> {
+    fn from(pixels: [RGB8; N]) -> Self {
+        Self { pixels }
+    }
+}
+
+impl<const N: usize> AsRef<[RGB8]> for FrameBuffer<N> {
+    fn as_ref(&self) -> &[RGB8] {
+        &self.pixels
+    }
+}
+
+impl<const N: usize> AsMut<[RGB8]> for FrameBuffer<N> {
+    fn as_mut(&mut self) -> &mut [RGB8] {
+        &mut self.pixels
+    }
+}
+
+impl<'a, const N: usize> IntoIterator for &'a FrameBuffer<N> {
+    type Item = &'a RGB8;
+    type IntoIter = slice::Iter<'a, RGB8>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, const N: usize> IntoIterator for &'a mut FrameBuffer<N> {
+    type Item = &'a mut RGB8;
+    type IntoIter = slice::IterMut<'a, RGB8>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<const N: usize> IntoIterator for FrameBuffer<N> {
+    type Item = RGB8;
+    type IntoIter = core::array::IntoIter<RGB8, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.pixels.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{pack_frame, ColorOrder};
+
+    const RED: RGB8 = RGB8::new(255, 0, 0);
+
+    #[test]
+    fn test_new_is_off() {
+        let frame = FrameBuffer::<4>::new();
+        assert_eq!(frame.len(), 4);
+        assert!(frame.iter().all(|&pixel| pixel == RGB8::default()));
+        assert_eq!(frame, FrameBuffer::default());
+    }
+
+    #[test]
+    fn test_set_and_get_bounds() {
+        let mut frame = FrameBuffer::<3>::new();
+        assert!(frame.set(2, RED));
+        assert!(!frame.set(3, RED));
+        assert_eq!(frame.get(2), Some(RED));
+        assert_eq!(frame.get(3), None);
+        assert_eq!(frame.into_array(), [RGB8::default(), RGB8::default(), RED]);
+    }
+
+    #[test]
+    fn test_fill_and_clear() {
+        let mut frame = FrameBuffer::<5>::new();
+        frame.fill(RED);
+        assert!(frame.iter().all(|&pixel| pixel == RED));
+        frame.clear();
+        assert_eq!(frame, FrameBuffer::new());
+    }
+
+    #[test]
+    fn test_iteration() {
+        let mut frame = FrameBuffer::from([RGB8::new(1, 0, 0), RGB8::new(2, 0, 0)]);
+        for pixel in &mut frame {
+            pixel.g = pixel.r * 10;
+        }
+        let greens: Vec<u8> = frame.iter().map(|pixel| pixel.g).collect();
+        assert_eq!(greens, [10, 20]);
+        assert_eq!((&frame).into_iter().count(), 2);
+        assert_eq!(frame.into_iter().last(), Some(RGB8::new(2, 20, 0)));
+    }
+
+    #[test]
+    fn test_slices() {
+        let mut frame = FrameBuffer::<2>::new();
+        frame.as_mut_slice()[1] = RED;
+        let mut bytes = [0u8; 6];
+        pack_frame(frame.as_slice(), ColorOrder::Rgb, &mut bytes).unwrap();
+        assert_eq!(bytes, [0, 0, 0, 255, 0, 0]);
+        assert_eq!(frame.as_ref(), frame.as_slice());
+    }
+
+    #[test]
+    fn test_empty_frame() {
+        let mut frame = FrameBuffer::<0>::new();
+        assert!(frame.is_empty());
+        assert!(!frame.set(0, RED));
+        frame.fill(RED);
+    }
+}
@@ -0,0 +1,161 @@
+//! Gamma correction.
+//!
+//! WS2812 brightness is linear in the PWM value, but the eye is not: without
+//! correction, dim colors look too bright and fades rush through the dark
+//! end. The tables map a linear value to the PWM value that looks that
+//! bright, rounded from `255 * (value / 255) ^ gamma`.
+
+use rgb::RGB8;
+
+/// Gamma 1.8 table, a gentler curve that keeps more detail at the dark end.
+#[rustfmt::skip]
+pub const GAMMA_1_8: [u8; 256] = [
+      0,   0,   0,   0,   0,   0,   0,   0,   1,   1,   1,   1,   1,   1,   1,   2,
+      2,   2,   2,   2,   3,   3,   3,   3,   4,   4,   4,   4,   5,   5,   5,   6,
+      6,   6,   7,   7,   8,   8,   8,   9,   9,  10,  10,  10,  11,  11,  12,  12,
+     13,  13,  14,  14,  15,  15,  16,  16,  17,  17,  18,  18,  19,  19,  20,  21,
+     21,  22,  22,  23,  24,  24,  25,  26,  26,  27,  28,  28,  29,  30,  30,  31,
+     32,  32,  33,  34,  35,  35,  36,  37,  38,  38,  39,  40,  41,  41,  42,  43,
+     44,  45,  46,  46,  47,  48,  49,  50,  51,  52,  53,  53,  54,  55,  56,  57,
+     58,  59,  60,  61,  62,  63,  64,  65,  66,  67,  68,  69,  70,  71,  72,  73,
+     74,  75,  76,  77,  78,  79,  80,  81,  82,  83,  84,  86,  87,  88,  89,  90,
+     91,  92,  93,  95,  96,  97,  98,  99, 100, 102, 103, 104, 105, 107, 108, 109,
+    110, 111, 113, 114, 115, 116, 118, 119, 120, 122, 123, 124, 126, 127, 128, 129,
+    131, 132, 134, 135, 136, 138, 139, 140, 142, 143, 145, 146, 147, 149, 150, 152,
+    153, 154, 156, 157, 159, 160, 162, 163, 165, 166, 168, 169, 171, 172, 174, 175,
+    177, 178, 180, 181, 183, 184, 186, 188, 189, 191, 192, 194, 195, 197, 199, 200,
+    202, 204, 205, 207, 208, 210, 212, 213, 215, 217, 218, 220, 222, 224, 225, 227,
+    229, 230, 232, 234, 236, 237, 239, 241, 243, 244, 246, 248, 250, 251, 253, 255,
+];
+
+/// Gamma 2.2 table, the sRGB-like default used by [`gamma8`].
+#[rustfmt::skip]
+pub const GAMMA_2_2: [u8; 256] = [
+      0,   0,   0,   0,   0,   0,   0,   0,   0,   0,   0,   0,   0,   0,   0,   1,
+      1,   1,   1,   1,   1,   1,   1,   1,   1,   2,   2,   2,   2,   2,   2,   2,
+      3,   3,   3,   3,   3,   4,   4,   4,   4,   5,   5,   5,   5,   6,   6,   6,
+      6,   7,   7,   7,   8,   8,   8,   9,   9,   9,  10,  10,  11,  11,  11,  12,
+     12,  13,  13,  13,  14,  14,  15,  15,  16,  16,  17,  17,  18,  18,  19,  19,
+     20,  20,  21,  22,  22,  23,  23,  24,  25,  25,  26,  26,  27,  28,  28,  29,
+     30,  30,  31,  32,  33,  33,  34,  35,  35,  36,  37,  38,  39,  39,  40,  41,
+     42,  43,  43,  44,  45,  46,  47,  48,  49,  49,  50,  51,  52,  53,  54,  55,
+     56,  57,  58,  59,  60,  61,  62,  63,  64,  65,  66,  67,  68,  69,  70,  71,
+     73,  74,  75,  76,  77,  78,  79,  81,  82,  83,  84,  85,  87,  88,  89,  90,
+     91,  93,  94,  95,  97,  98,  99, 100, 102, 103, 105, 106, 107, 109, 110, 111,
+    113, 114, 116, 117, 119, 120, 121, 123, 124, 126, 127, 129, 130, 132, 133, 135,
+    137, 138, 140, 141, 143, 145, 146, 148, 149, 151, 153, 154, 156, 158, 159, 161,
+    163, 165, 166, 168, 170, 172, 173, 175, 177, 179, 181, 182, 184, 186, 188, 190,
+    192, 194, 196, 197, 199, 201, 203, 205, 207, 209, 211, 213, 215, 217, 219, 221,
+    223, 225, 227, 229, 231, 234, 236, 238, 240, 242, 244, 246, 248, 251, 253, 255,
+];
+
+/// Gamma 2.5 table, a steeper curve for colors that look washed out at 2.2.
+#[rustfmt::skip]
+pub const GAMMA_2_5: [u8; 256] = [
+      0,   0,   0,   0,   0,   0,   0,   0,   0,   0,   0,   0,   0,   0,   0,   0,
+      0,   0,   0,   0,   0,   0,   1,   1,   1,   1,   1,   1,   1,   1,   1,   1,
+      1,   2,   2,   2,   2,   2,   2,   2,   2,   3,   3,   3,   3,   3,   4,   4,
+      4,   4,   4,   5,   5,   5,   5,   6,   6,   6,   6,   7,   7,   7,   7,   8,
+      8,   8,   9,   9,   9,  10,  10,  10,  11,  11,  12,  12,  12,  13,  13,  14,
+     14,  15,  15,  15,  16,  16,  17,  17,  18,  18,  19,  19,  20,  20,  21,  22,
+     22,  23,  23,  24,  25,  25,  26,  26,  27,  28,  28,  29,  30,  30,  31,  32,
+     33,  33,  34,  35,  36,  36,  37,  38,  39,  40,  40,  41,  42,  43,  44,  45,
+     46,  46,  47,  48,  49,  50,  51,  52,  53,  54,  55,  56,  57,  58,  59,  60,
+     61,  62,  63,  64,  65,  67,  68,  69,  70,  71,  72,  73,  75,  76,  77,  78,
+     80,  81,  82,  83,  85,  86,  87,  89,  90,  91,  93,  94,  95,  97,  98,  99,
+    101, 102, 104, 105, 107, 108, 110, 111, 113, 114, 116, 117, 119, 121, 122, 124,
+    125, 127, 129, 130, 132, 134, 135, 137, 139, 141, 142, 144, 146, 148, 150, 151,
+    153, 155, 157, 159, 161, 163, 165, 166, 168, 170, 172, 174, 176, 178, 180, 182,
+    184, 186, 189, 191, 193, 195, 197, 199, 201, 204, 206, 208, 210, 212, 215, 217,
+    219, 221, 224, 226, 228, 231, 233, 235, 238, 240, 243, 245, 248, 250, 253, 255,
+];
+
+/// Gamma-corrects a single channel value with [`GAMMA_2_2`].
+///
+/// 0 and 255 are unchanged.
+///
+/// # Example
+///
+/// ```
+/// use ws2812_pure::gamma8;
+///
+/// assert_eq!(gamma8(0), 0);
+/// assert_eq!(gamma8(128), 56);
+/// assert_eq!(gamma8(255), 255);
+/// ```
+pub const fn gamma8(value: u8) -> u8 {
+    GAMMA_2_2[value as usize]
+}
+
+/// Gamma-corrects every color in `pixels` in place with [`GAMMA_2_2`].
+///
+/// Apply it once to the finished frame, right before packing it.
+pub fn gamma_correct_slice(pixels: &mut [RGB8]) {
+    gamma_correct_slice_with(pixels, &GAMMA_2_2);
+}
+
+/// Gamma-corrects every color in `pixels` in place with `table`, e.g.
+/// [`GAMMA_1_8`] or [`GAMMA_2_5`].
+pub fn gamma_correct_slice_with(pixels: &mut [RGB8], table: &[u8; 256]) {
+    for pixel in pixels.iter_mut() {
+        *pixel = RGB8::new(
+            table[pixel.r as usize],
+            table[pixel.g as usize],
+            table[pixel.b as usize],
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Rounds `255 * (value / 255) ^ gamma`, the formula behind the tables.
+    fn reference(value: u8, gamma: f64) -> u8 {
+        (255.0 * (value as f64 / 255.0).powf(gamma)).round() as u8
+    }
+
+    #[test]
+    fn test_tables_match_formula() {
+        for value in 0..=255u8 {
+            assert_eq!(GAMMA_1_8[value as usize], reference(value, 1.8));
+            assert_eq!(GAMMA_2_2[value as usize], reference(value, 2.2));
+            assert_eq!(GAMMA_2_5[value as usize], reference(value, 2.5));
+        }
+    }
+
+    #[test]
+    fn test_tables_monotonic_and_below_linear() {
+        for table in [&GAMMA_1_8, &GAMMA_2_2, &GAMMA_2_5] {
+            assert_eq!(table[0], 0);
+            assert_eq!(table[255], 255);
+            for value in 0..255 {
+                assert!(table[value] <= table[value + 1]);
+                assert!(table[value] as usize <= value);
+            }
+        }
+    }
+
+    #[test]
+    fn test_steeper_gamma_is_darker() {
+        for value in 1..255 {
+            assert!(GAMMA_2_5[value] <= GAMMA_2_2[value]);
+            assert!(GAMMA_2_2[value] <= GAMMA_1_8[value]);
+        }
+    }
+
+    #[test]
+    fn test_gamma_correct_slice() {
+        let mut pixels = [RGB8::new(0, 128, 255), RGB8::new(64, 64, 64)];
+        gamma_correct_slice(&mut pixels);
+        assert_eq!(pixels[0], RGB8::new(0, gamma8(128), 255));
+        assert_eq!(pixels[1], RGB8::new(gamma8(64), gamma8(64), gamma8(64)));
+
+        let mut pixels = [RGB8::new(128, 128, 128)];
+        gamma_correct_slice_with(&mut pixels, &GAMMA_1_8);
+        assert_eq!(
+            pixels[0],
+            RGB8::new(GAMMA_1_8[128], GAMMA_1_8[128], GAMMA_1_8[128])
+        );
+    }
+}
@@ -5,9 +5,330 @@
 //! This crate provides hardware-independent color conversion and bit manipulation
 //! utilities for WS2812 (NeoPixel) LEDs. It has no ESP or embedded dependencies,
 //! making it fully testable on any platform.
+//!
+//! Clones and WS2811 driver ICs often expect a different channel order;
+//! [`pack_color`] packs a color for any [`ColorOrder`], while [`rgb_to_grb`]
+//! stays the fast path for genuine WS2812 LEDs.
+//!
+//! [`Timing`] holds the datasheet pulse widths of common LED variants, so
+//! drivers share one tested source instead of hard-coding them.
+//! [`reference_waveform`] turns a frame and a timing into the exact pulses a
+//! driver must send, as a golden reference for encoder tests.
+//!
+//! Without an RMT peripheral, [`encode_spi`] turns pixels into an SPI byte
+//! stream that reproduces the WS2812 timing on the MOSI line.
+//!
+//! [`gamma8`] and [`gamma_correct_slice`] apply the gamma 2.2 table that
+//! ferriswheel's effects also use, so a driver that corrects frames itself
+//! gets the same curve; [`GAMMA_1_8`] and [`GAMMA_2_5`] are the alternates.
+//!
+//! The [`colors`] module names the basic web colors and the `STATUS_*` colors
+//! shared by firmware, drivers and led-effects.
+//!
+//! [`QUARTER_SINE`] is the integer sine table behind ferriswheel's easing
+//! curves and led-effects' pulse shapes.
+//!
+//! [`FrameBuffer`] holds the colors of a fixed-size strip with bounds-checked
+//! access, for drivers and effects alike.
+//!
+//! [`add_color`], [`sub_color`], [`average_color`] and [`max_color`] are the
+//! saturating channel arithmetic that compositing builds on.
+//!
+//! [`scale_frame`] dims a whole frame for a global brightness setting, and
+//! [`apply_calibration`] matches the white balance of different LED batches.
+//! [`Ditherer`] renders colors with 16-bit precision as 8-bit frames whose
+//! average over time matches, for smooth fades at low brightness.
+//!
+//! [`pack_frame`] packs a whole strip into the bytes sent on the wire, the
+//! common ground of SPI, I2S and bit-banged drivers.
+//!
+//! Drivers that encode while transmitting can stream bits with [`ColorBits`]
+//! and [`FrameBits`] instead of building an array per pixel, and pulse-based
+//! drivers turn any pixel iterator into pulses with [`encode_pulses`].
+//!
+//! [`build_drgb`], [`build_dnrgb`], [`build_warls`] and [`parse_wled`] handle
+//! WLED realtime UDP payloads, and [`encode_ddp_rgb`] and [`decode_ddp`] handle
+//! DDP packets from xLights or LedFx, and [`parse_e131`] reads E1.31 (sACN)
+//! universes from lighting desks, all without any networking code.
+//!
+//! SK6812 RGBW strips add a white LED to every pixel and take 32 bits per
+//! pixel in GRBW order; see [`RGBW8`], [`rgbw_to_grbw`] and [`color_to_bits_32`].
+//!
+//! [`grb_to_rgb`] and [`grbw_to_rgbw`] undo the packing, e.g. to decode
+//! captured LED data.
 
 use rgb::RGB8;
 
+mod bits;
+mod calibration;
+mod color_math;
+pub mod colors;
+mod ddp;
+mod dither;
+mod e131;
+mod frame;
+mod gamma;
+mod sine;
+mod spi;
+mod timing;
+mod waveform;
+mod wled;
+
+pub use bits::{encode_pulses, pixel_pulses, ColorBits, FrameBits};
+pub use calibration::{apply_calibration, Calibration};
+pub use color_math::{add_color, average_color, max_color, sub_color};
+pub use ddp::{
+    decode_ddp, encode_ddp_rgb, DdpError, DdpHeader, DdpPacket, DDP_HEADER_LEN, DDP_ID_DISPLAY,
+    DDP_MAX_DATA_LEN, DDP_PORT, DDP_TYPE_RGB8,
+};
+pub use dither::Ditherer;
+pub use e131::{parse_e131, E131Error, E131Packet, E131_MAX_SLOTS, E131_PORT};
+pub use frame::FrameBuffer;
+pub use gamma::{
+    gamma8, gamma_correct_slice, gamma_correct_slice_with, GAMMA_1_8, GAMMA_2_2, GAMMA_2_5,
+};
+pub use sine::QUARTER_SINE;
+pub use spi::{encode_spi, SpiEncodeError, SpiEncoding};
+pub use timing::{Timing, TimingError};
+pub use waveform::{reference_waveform, ReferenceWaveform};
+pub use wled::{
+    build_dnrgb, build_drgb, build_warls, parse_wled, WledError, WledPacket, WledProtocol,
+    WLED_NO_TIMEOUT, WLED_UDP_PORT,
+};
+
+/// A color with red, green, blue and white channels.
+///
+/// The `rgb` crate has no white channel, so this mirrors its `RGB<T>` type.
+/// ferriswheel re-exports it, so RGBW frames rendered by effects go straight
+/// into [`pack_rgbw`] and [`rgbw_to_grbw`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RGBW<T> {
+    /// Red channel.
+    pub r: T,
+    /// Green channel.
+    pub g: T,
+    /// Blue channel.
+    pub b: T,
+    /// White channel.
+    pub w: T,
+}
+
+/// An 8-bit RGBW color, one byte per LED of an SK6812 RGBW pixel.
+pub type RGBW8 = RGBW<u8>;
+
+impl<T> RGBW<T> {
+    /// Creates a color from its four channels.
+    pub const fn new(r: T, g: T, b: T, w: T) -> Self {
+        Self { r, g, b, w }
+    }
+}
+
+impl<T: Copy> RGBW<T> {
+    /// Returns the red, green and blue channels, ignoring white.
+    pub fn rgb(&self) -> rgb::RGB<T> {
+        rgb::RGB::new(self.r, self.g, self.b)
+    }
+}
+
+impl From<RGB8> for RGBW8 {
+    /// Copies the color channels and leaves the white LED off.
+    fn from(color: RGB8) -> Self {
+        Self::new(color.r, color.g, color.b, 0)
+    }
+}
+
+/// Order in which an LED expects its color channels on the wire.
+///
+/// The three-channel orders send 24 bits per pixel, the RGBW orders 32.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ColorOrder {
+    /// Red, green, blue; common on WS2811 driver ICs.
+    Rgb,
+    /// Red, blue, green.
+    Rbg,
+    /// Green, red, blue; WS2812 and SK6812 RGB.
+    #[default]
+    Grb,
+    /// Green, blue, red.
+    Gbr,
+    /// Blue, red, green.
+    Brg,
+    /// Blue, green, red.
+    Bgr,
+    /// Green, red, blue, white; SK6812 RGBW.
+    Grbw,
+    /// Red, green, blue, white.
+    Rgbw,
+}
+
+impl ColorOrder {
+    /// Returns `true` for orders with a white channel.
+    pub const fn has_white(self) -> bool {
+        matches!(self, ColorOrder::Grbw | ColorOrder::Rgbw)
+    }
+
+    /// Returns the number of bits sent per pixel: 24, or 32 with white.
+    pub const fn bits_per_pixel(self) -> u32 {
+        if self.has_white() {
+            32
+        } else {
+            24
+        }
+    }
+
+    /// Returns the number of bytes sent per pixel: 3, or 4 with white.
+    pub const fn bytes_per_pixel(self) -> usize {
+        self.bits_per_pixel() as usize / 8
+    }
+}
+
+/// Error returned by [`pack_frame`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PackError {
+    /// The buffer is too small for the pixels.
+    BufferTooSmall {
+        /// Number of bytes needed.
+        required: usize,
+        /// Actual buffer size provided.
+        actual: usize,
+    },
+}
+
+impl core::fmt::Display for PackError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            PackError::BufferTooSmall { required, actual } => write!(
+                f,
+                "frame buffer too small: {} bytes required, {} provided",
+                required, actual
+            ),
+        }
+    }
+}
+
+/// Packs a color in the given channel order, first channel in the highest byte.
+///
+/// Three-channel orders fill bits 23-0; RGBW orders fill bits 31-0 and leave
+/// the white LED off. Use [`pack_rgbw`] to drive the white LED.
+///
+/// # Example
+///
+/// ```
+/// use ws2812_pure::{pack_color, rgb_to_grb, ColorOrder};
+/// use rgb::RGB8;
+///
+/// let color = RGB8::new(0x12, 0x34, 0x56);
+/// assert_eq!(pack_color(color, ColorOrder::Rgb), 0x123456);
+/// assert_eq!(pack_color(color, ColorOrder::Grb), rgb_to_grb(color));
+/// assert_eq!(pack_color(color, ColorOrder::Grbw), 0x34125600);
+/// ```
+pub fn pack_color(rgb: RGB8, order: ColorOrder) -> u32 {
+    pack_rgbw(RGBW8::from(rgb), order)
+}
+
+/// Packs an RGBW color in the given channel order, first channel in the
+/// highest byte.
+///
+/// Three-channel orders ignore the white channel.
+///
+/// # Example
+///
+/// ```
+/// use ws2812_pure::{pack_rgbw, ColorOrder, RGBW8};
+///
+/// let color = RGBW8::new(0x12, 0x34, 0x56, 0x78);
+/// assert_eq!(pack_rgbw(color, ColorOrder::Rgbw), 0x12345678);
+/// assert_eq!(pack_rgbw(color, ColorOrder::Bgr), 0x563412);
+/// ```
+pub fn pack_rgbw(color: RGBW8, order: ColorOrder) -> u32 {
+    let RGBW { r, g, b, w } = color;
+    let bytes = match order {
+        ColorOrder::Rgb => [r, g, b],
+        ColorOrder::Rbg => [r, b, g],
+        ColorOrder::Grb => [g, r, b],
+        ColorOrder::Gbr => [g, b, r],
+        ColorOrder::Brg => [b, r, g],
+        ColorOrder::Bgr => [b, g, r],
+        ColorOrder::Grbw => return u32::from_be_bytes([g, r, b, w]),
+        ColorOrder::Rgbw => return u32::from_be_bytes([r, g, b, w]),
+    };
+    u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]])
+}
+
+/// Packs a whole strip into the byte stream sent on the wire.
+///
+/// Each pixel takes [`ColorOrder::bytes_per_pixel`] bytes, first channel
+/// first. The bytes go to the start of `buffer`; the function returns their
+/// count and leaves the rest of the buffer untouched.
+///
+/// # Errors
+///
+/// Returns `PackError::BufferTooSmall` if `buffer` cannot hold every pixel.
+///
+/// # Example
+///
+/// ```
+/// use ws2812_pure::{pack_frame, ColorOrder};
+/// use rgb::RGB8;
+///
+/// let pixels = [RGB8::new(1, 2, 3), RGB8::new(4, 5, 6)];
+/// let mut buffer = [0u8; 8];
+///
+/// assert_eq!(pack_frame(&pixels, ColorOrder::Grb, &mut buffer), Ok(6));
+/// assert_eq!(buffer[..6], [2, 1, 3, 5, 4, 6]);
+/// assert_eq!(pack_frame(&pixels, ColorOrder::Grbw, &mut buffer), Ok(8));
+/// assert_eq!(buffer, [2, 1, 3, 0, 5, 4, 6, 0]);
+/// ```
+pub fn pack_frame(
+    pixels: &[RGB8],
+    order: ColorOrder,
+    buffer: &mut [u8],
+) -> Result<usize, PackError> {
+    let size = order.bytes_per_pixel();
+    let required = pixels.len() * size;
+    if buffer.len() < required {
+        return Err(PackError::BufferTooSmall {
+            required,
+            actual: buffer.len(),
+        });
+    }
+
+    for (pixel, out) in pixels.iter().zip(buffer.chunks_exact_mut(size)) {
+        let bytes = pack_color(*pixel, order).to_be_bytes();
+        out.copy_from_slice(&bytes[4 - size..]);
+    }
+    Ok(required)
+}
+
+/// Scales every color in `pixels` in place by `brightness` (0-255).
+///
+/// Each channel becomes `channel * brightness / 255`, rounded down, so 255
+/// leaves the frame unchanged and 0 turns it off. This matches
+/// `ferriswheel::scale_brightness`, for drivers that offer a global
+/// brightness without depending on ferriswheel.
+///
+/// # Example
+///
+/// ```
+/// use ws2812_pure::scale_frame;
+/// use rgb::RGB8;
+///
+/// let mut pixels = [RGB8::new(255, 128, 1), RGB8::new(200, 100, 50)];
+/// scale_frame(&mut pixels, 128);
+/// assert_eq!(pixels, [RGB8::new(128, 64, 0), RGB8::new(100, 50, 25)]);
+/// ```
+pub fn scale_frame(pixels: &mut [RGB8], brightness: u8) {
+    if brightness == u8::MAX {
+        return;
+    }
+    let b = brightness as u16;
+    let scale = |channel: u8| ((channel as u16 * b) / 255) as u8;
+    for pixel in pixels.iter_mut() {
+        *pixel = RGB8::new(scale(pixel.r), scale(pixel.g), scale(pixel.b));
+    }
+}
+
 /// Converts RGB to GRB u32 format (WS2812 color order).
 ///
 /// WS2812 LEDs expect color data in GRB order, not RGB.
@@ -29,6 +350,86 @@ pub fn rgb_to_grb(rgb: RGB8) -> u32 {
     ((rgb.g as u32) << 16) | ((rgb.r as u32) << 8) | rgb.b as u32
 }
 
+/// Converts a GRB u32 value back to RGB, the inverse of [`rgb_to_grb`].
+///
+/// Bits 31-24 are ignored, so `grb_to_rgb(rgb_to_grb(c)) == c` for every
+/// color. Useful for simulators and for decoding captured LED data.
+///
+/// # Example
+///
+/// ```
+/// use ws2812_pure::{grb_to_rgb, rgb_to_grb};
+/// use rgb::RGB8;
+///
+/// assert_eq!(grb_to_rgb(0x00FF00), RGB8::new(255, 0, 0));
+///
+/// let color = RGB8::new(12, 34, 56);
+/// assert_eq!(grb_to_rgb(rgb_to_grb(color)), color);
+/// ```
+pub fn grb_to_rgb(grb: u32) -> RGB8 {
+    let [_, g, r, b] = grb.to_be_bytes();
+    RGB8::new(r, g, b)
+}
+
+/// Converts RGBW to GRBW u32 format (SK6812 RGBW color order).
+///
+/// This function packs the color into a 32-bit value with:
+/// - Bits 31-24: Green
+/// - Bits 23-16: Red
+/// - Bits 15-8: Blue
+/// - Bits 7-0: White
+///
+/// # Example
+///
+/// ```
+/// use ws2812_pure::{rgbw_to_grbw, RGBW8};
+///
+/// let warm_white = RGBW8::new(0, 0, 0, 255);
+/// assert_eq!(rgbw_to_grbw(warm_white), 0x000000FF);
+/// ```
+pub fn rgbw_to_grbw(rgbw: RGBW8) -> u32 {
+    ((rgbw.g as u32) << 24) | ((rgbw.r as u32) << 16) | ((rgbw.b as u32) << 8) | rgbw.w as u32
+}
+
+/// Converts a GRBW u32 value back to RGBW, the inverse of [`rgbw_to_grbw`].
+///
+/// All 32 bits are used, so the two functions round-trip in both directions.
+///
+/// # Example
+///
+/// ```
+/// use ws2812_pure::{grbw_to_rgbw, rgbw_to_grbw, RGBW8};
+///
+/// assert_eq!(grbw_to_rgbw(0x000000FF), RGBW8::new(0, 0, 0, 255));
+///
+/// let color = RGBW8::new(12, 34, 56, 78);
+/// assert_eq!(grbw_to_rgbw(rgbw_to_grbw(color)), color);
+/// ```
+pub fn grbw_to_rgbw(grbw: u32) -> RGBW8 {
+    let [g, r, b, w] = grbw.to_be_bytes();
+    RGBW8::new(r, g, b, w)
+}
+
+/// Moves the white part of an RGB color onto the white channel.
+///
+/// The white part is the smallest of the three channels. It is subtracted
+/// from each of them, so saturated colors keep the white LED off and pure
+/// white lights only the white LED.
+///
+/// # Example
+///
+/// ```
+/// use ws2812_pure::{rgb_to_rgbw, RGBW8};
+/// use rgb::RGB8;
+///
+/// assert_eq!(rgb_to_rgbw(RGB8::new(255, 200, 100)), RGBW8::new(155, 100, 0, 100));
+/// assert_eq!(rgb_to_rgbw(RGB8::new(255, 0, 0)), RGBW8::new(255, 0, 0, 0));
+/// ```
+pub fn rgb_to_rgbw(rgb: RGB8) -> RGBW8 {
+    let w = rgb.r.min(rgb.g).min(rgb.b);
+    RGBW8::new(rgb.r - w, rgb.g - w, rgb.b - w, w)
+}
+
 /// Extracts bit values from a 24-bit color for WS2812 transmission.
 ///
 /// Returns an array of 24 booleans representing each bit, MSB first.
@@ -52,6 +453,28 @@ pub fn color_to_bits(color: u32) -> [bool; 24] {
     bits
 }
 
+/// Extracts bit values from a 32-bit GRBW color for SK6812 RGBW transmission.
+///
+/// Returns an array of 32 booleans representing each bit, MSB first.
+///
+/// # Example
+///
+/// ```
+/// use ws2812_pure::color_to_bits_32;
+///
+/// let bits = color_to_bits_32(0x8000_0001);
+/// assert_eq!(bits[0], true);  // MSB
+/// assert_eq!(bits[1], false);
+/// assert_eq!(bits[31], true); // LSB
+/// ```
+pub fn color_to_bits_32(color: u32) -> [bool; 32] {
+    let mut bits = [false; 32];
+    for i in (0..32).rev() {
+        bits[31 - i] = (color >> i) & 1 != 0;
+    }
+    bits
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -110,6 +533,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::needless_range_loop)]
     fn test_color_to_bits_alternating() {
         // 0xAAAAAA = 10101010 10101010 10101010
         let bits = color_to_bits(0xAAAAAA);
@@ -133,4 +557,200 @@ mod tests {
         assert!(bits[23], "LSB should be set");
         assert!(bits[..23].iter().all(|&b| !b), "all other bits should be 0");
     }
+
+    #[test]
+    fn test_rgbw_to_grbw_channel_positions() {
+        assert_eq!(rgbw_to_grbw(RGBW8::new(0xFF, 0, 0, 0)), 0x00FF0000);
+        assert_eq!(rgbw_to_grbw(RGBW8::new(0, 0xFF, 0, 0)), 0xFF000000);
+        assert_eq!(rgbw_to_grbw(RGBW8::new(0, 0, 0xFF, 0)), 0x0000FF00);
+        assert_eq!(rgbw_to_grbw(RGBW8::new(0, 0, 0, 0xFF)), 0x000000FF);
+    }
+
+    #[test]
+    fn test_rgbw_to_grbw_mixed() {
+        let color = RGBW8::new(0x12, 0x34, 0x56, 0x78);
+        // GRBW format: G=0x34, R=0x12, B=0x56, W=0x78
+        assert_eq!(rgbw_to_grbw(color), 0x34125678);
+    }
+
+    #[test]
+    fn test_rgbw_from_rgb_keeps_white_off() {
+        let color = RGBW8::from(RGB8::new(1, 2, 3));
+        assert_eq!(color, RGBW8::new(1, 2, 3, 0));
+        assert_eq!(color.rgb(), RGB8::new(1, 2, 3));
+    }
+
+    #[test]
+    fn test_rgb_to_rgbw_extracts_white() {
+        assert_eq!(
+            rgb_to_rgbw(RGB8::new(255, 255, 255)),
+            RGBW8::new(0, 0, 0, 255)
+        );
+        assert_eq!(
+            rgb_to_rgbw(RGB8::new(10, 20, 30)),
+            RGBW8::new(0, 10, 20, 10)
+        );
+        assert_eq!(rgb_to_rgbw(RGB8::new(0, 0, 0)), RGBW8::default());
+    }
+
+    #[test]
+    fn test_pack_color_every_order() {
+        let color = RGB8::new(0x11, 0x22, 0x33);
+        let cases = [
+            (ColorOrder::Rgb, 0x112233),
+            (ColorOrder::Rbg, 0x113322),
+            (ColorOrder::Grb, 0x221133),
+            (ColorOrder::Gbr, 0x223311),
+            (ColorOrder::Brg, 0x331122),
+            (ColorOrder::Bgr, 0x332211),
+            (ColorOrder::Grbw, 0x22113300),
+            (ColorOrder::Rgbw, 0x11223300),
+        ];
+        for (order, expected) in cases {
+            assert_eq!(pack_color(color, order), expected, "{:?}", order);
+        }
+    }
+
+    #[test]
+    fn test_pack_color_grb_matches_fast_path() {
+        for color in [RGB8::new(255, 0, 0), RGB8::new(0x12, 0x34, 0x56)] {
+            assert_eq!(pack_color(color, ColorOrder::default()), rgb_to_grb(color));
+        }
+    }
+
+    #[test]
+    fn test_pack_rgbw_matches_grbw_fast_path() {
+        let color = RGBW8::new(1, 2, 3, 4);
+        assert_eq!(pack_rgbw(color, ColorOrder::Grbw), rgbw_to_grbw(color));
+        assert_eq!(pack_rgbw(color, ColorOrder::Grb), 0x020103);
+    }
+
+    #[test]
+    fn test_bits_per_pixel() {
+        assert_eq!(ColorOrder::Rgb.bits_per_pixel(), 24);
+        assert_eq!(ColorOrder::Grbw.bits_per_pixel(), 32);
+        assert_eq!(ColorOrder::Rgb.bytes_per_pixel(), 3);
+        assert_eq!(ColorOrder::Rgbw.bytes_per_pixel(), 4);
+        assert!(!ColorOrder::Bgr.has_white());
+    }
+
+    #[test]
+    fn test_pack_frame_matches_pack_color() {
+        let pixels = [RGB8::new(0x12, 0x34, 0x56), RGB8::new(0xAB, 0xCD, 0xEF)];
+        for order in [ColorOrder::Rgb, ColorOrder::Grb, ColorOrder::Bgr] {
+            let mut buffer = [0u8; 6];
+            assert_eq!(pack_frame(&pixels, order, &mut buffer), Ok(6));
+            for (pixel, bytes) in pixels.iter().zip(buffer.chunks(3)) {
+                let packed = pack_color(*pixel, order);
+                assert_eq!(bytes, &packed.to_be_bytes()[1..]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_pack_frame_grb_bytes() {
+        let mut buffer = [0xAAu8; 4];
+        assert_eq!(
+            pack_frame(&[RGB8::new(255, 0, 0)], ColorOrder::Grb, &mut buffer),
+            Ok(3)
+        );
+        assert_eq!(buffer, [0x00, 0xFF, 0x00, 0xAA]);
+    }
+
+    #[test]
+    fn test_pack_frame_rgbw_leaves_white_off() {
+        let mut buffer = [0xAAu8; 4];
+        pack_frame(&[RGB8::new(1, 2, 3)], ColorOrder::Rgbw, &mut buffer).unwrap();
+        assert_eq!(buffer, [1, 2, 3, 0]);
+    }
+
+    #[test]
+    fn test_pack_frame_buffer_too_small() {
+        let mut buffer = [0u8; 5];
+        let err = pack_frame(&[RGB8::default(); 2], ColorOrder::Grb, &mut buffer).unwrap_err();
+        assert_eq!(
+            err,
+            PackError::BufferTooSmall {
+                required: 6,
+                actual: 5
+            }
+        );
+        assert_eq!(
+            format!("{}", err),
+            "frame buffer too small: 6 bytes required, 5 provided"
+        );
+        assert_eq!(pack_frame(&[], ColorOrder::Grb, &mut []), Ok(0));
+    }
+
+    #[test]
+    fn test_scale_frame_rounds_down() {
+        let cases = [
+            (128, RGB8::new(255, 254, 127), RGB8::new(128, 127, 63)),
+            (64, RGB8::new(200, 100, 50), RGB8::new(50, 25, 12)),
+            (1, RGB8::new(255, 254, 3), RGB8::new(1, 0, 0)),
+        ];
+        for (brightness, pixel, expected) in cases {
+            let mut pixels = [pixel];
+            scale_frame(&mut pixels, brightness);
+            assert_eq!(pixels, [expected], "brightness {}", brightness);
+        }
+    }
+
+    #[test]
+    fn test_scale_frame_bounds() {
+        let mut pixels = [RGB8::new(255, 1, 0)];
+        scale_frame(&mut pixels, 255);
+        assert_eq!(pixels, [RGB8::new(255, 1, 0)]);
+        scale_frame(&mut pixels, 0);
+        assert_eq!(pixels, [RGB8::new(0, 0, 0)]);
+        scale_frame(&mut [], 128);
+    }
+
+    #[test]
+    fn test_grb_to_rgb_round_trip() {
+        for v in 0..=255u8 {
+            for color in [
+                RGB8::new(v, 0, 0),
+                RGB8::new(0, v, 0),
+                RGB8::new(0, 0, v),
+                RGB8::new(v, v.wrapping_mul(7), 255 - v),
+            ] {
+                assert_eq!(grb_to_rgb(rgb_to_grb(color)), color);
+            }
+        }
+        for grb in [0, 0x00FF_FFFF, 0x0012_3456, 0x00A5_5A0F] {
+            assert_eq!(rgb_to_grb(grb_to_rgb(grb)), grb);
+        }
+    }
+
+    #[test]
+    fn test_grb_to_rgb_ignores_high_byte() {
+        assert_eq!(grb_to_rgb(0xFF12_3456), grb_to_rgb(0x0012_3456));
+        assert_eq!(grb_to_rgb(0x0012_3456), RGB8::new(0x34, 0x12, 0x56));
+    }
+
+    #[test]
+    fn test_grbw_to_rgbw_round_trip() {
+        for v in 0..=255u8 {
+            let color = RGBW8::new(v, 255 - v, v / 3, v.wrapping_mul(5));
+            assert_eq!(grbw_to_rgbw(rgbw_to_grbw(color)), color);
+        }
+        for grbw in [0, u32::MAX, 0x1234_5678, 0x8000_0001] {
+            assert_eq!(rgbw_to_grbw(grbw_to_rgbw(grbw)), grbw);
+        }
+    }
+
+    #[test]
+    fn test_color_to_bits_32_msb_first() {
+        let bits = color_to_bits_32(0x8000_0000);
+        assert!(bits[0], "MSB should be set");
+        assert!(bits[1..].iter().all(|&b| !b), "all other bits should be 0");
+    }
+
+    #[test]
+    fn test_color_to_bits_32_matches_24_bit_for_low_bytes() {
+        let bits = color_to_bits_32(0x00AB_CDEF);
+        assert!(bits[..8].iter().all(|&b| !b));
+        assert_eq!(bits[8..], color_to_bits(0xAB_CDEF));
+    }
 }
@@ -0,0 +1,36 @@
+//! Integer sine lookup.
+//!
+//! Effects ease brightness along sine curves, and a table keeps that free of
+//! floating point on targets without an FPU.
+
+/// Quarter sine wave: `sin(i / 64 · π/2) · 255` for `i` in 0..=64.
+///
+/// Shared by ferriswheel's easing curves and led-effects' pulse shapes, so
+/// both crates breathe along the same curve.
+#[rustfmt::skip]
+pub const QUARTER_SINE: [u8; 65] = [
+      0,   6,  13,  19,  25,  31,  37,  44,  50,  56,  62,  68,  74,  80,  86,  92,
+     98, 103, 109, 115, 120, 126, 131, 136, 142, 147, 152, 157, 162, 167, 171, 176,
+    180, 185, 189, 193, 197, 201, 205, 208, 212, 215, 219, 222, 225, 228, 231, 233,
+    236, 238, 240, 242, 244, 246, 247, 249, 250, 251, 252, 253, 254, 254, 255, 255,
+    255,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quarter_sine_endpoints() {
+        assert_eq!(QUARTER_SINE[0], 0);
+        assert_eq!(QUARTER_SINE[32], 180);
+        assert_eq!(QUARTER_SINE[64], 255);
+    }
+
+    #[test]
+    fn test_quarter_sine_is_monotonic() {
+        for pair in QUARTER_SINE.windows(2) {
+            assert!(pair[0] <= pair[1]);
+        }
+    }
+}
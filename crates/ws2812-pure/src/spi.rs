@@ -0,0 +1,230 @@
+//! SPI bit patterns for WS2812 LEDs.
+//!
+//! Chips without an RMT peripheral can still drive WS2812 LEDs from the MOSI
+//! line of an SPI bus: every data bit becomes a short group of SPI bits whose
+//! high time matches the WS2812 timing for a 0 or a 1.
+
+use crate::rgb_to_grb;
+use rgb::RGB8;
+
+/// How each WS2812 data bit is expanded into SPI bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SpiEncoding {
+    /// Three SPI bits per data bit at 2.4 MHz: `0b100` for 0, `0b110` for 1.
+    ///
+    /// 9 bytes per pixel.
+    #[default]
+    ThreeBit,
+    /// Four SPI bits per data bit at 3.2 MHz: `0b1000` for 0, `0b1110` for 1.
+    ///
+    /// 12 bytes per pixel, with more margin on the high times.
+    FourBit,
+}
+
+impl SpiEncoding {
+    /// Returns the SPI clock this encoding is timed for, in Hz.
+    pub const fn frequency_hz(self) -> u32 {
+        match self {
+            SpiEncoding::ThreeBit => 2_400_000,
+            SpiEncoding::FourBit => 3_200_000,
+        }
+    }
+
+    /// Returns the number of bytes one pixel takes.
+    pub const fn bytes_per_pixel(self) -> usize {
+        match self {
+            SpiEncoding::ThreeBit => 9,
+            SpiEncoding::FourBit => 12,
+        }
+    }
+
+    /// Returns the number of bytes `pixels` pixels take.
+    pub const fn buffer_len(self, pixels: usize) -> usize {
+        pixels * self.bytes_per_pixel()
+    }
+
+    /// Returns the SPI bits for one data bit, right-aligned, and their count.
+    const fn symbol(self, bit: bool) -> (u128, u32) {
+        match (self, bit) {
+            (SpiEncoding::ThreeBit, false) => (0b100, 3),
+            (SpiEncoding::ThreeBit, true) => (0b110, 3),
+            (SpiEncoding::FourBit, false) => (0b1000, 4),
+            (SpiEncoding::FourBit, true) => (0b1110, 4),
+        }
+    }
+}
+
+/// Error returned when an SPI buffer cannot hold the encoded pixels.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpiEncodeError {
+    /// The buffer is too small for the pixels.
+    BufferTooSmall {
+        /// Number of bytes needed.
+        required: usize,
+        /// Actual buffer size provided.
+        actual: usize,
+    },
+}
+
+impl core::fmt::Display for SpiEncodeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            SpiEncodeError::BufferTooSmall { required, actual } => write!(
+                f,
+                "SPI buffer too small: {} bytes required, {} provided",
+                required, actual
+            ),
+        }
+    }
+}
+
+/// Encodes one pixel in GRB order into `out`, which holds exactly one pixel.
+fn encode_pixel(rgb: RGB8, encoding: SpiEncoding, out: &mut [u8]) {
+    let color = rgb_to_grb(rgb);
+    let mut bits: u128 = 0;
+    for i in (0..24).rev() {
+        let (symbol, width) = encoding.symbol((color >> i) & 1 != 0);
+        bits = (bits << width) | symbol;
+    }
+    let bytes = bits.to_be_bytes();
+    out.copy_from_slice(&bytes[bytes.len() - out.len()..]);
+}
+
+/// Encodes pixels into an SPI byte stream, MSB first, in WS2812 GRB order.
+///
+/// Writes [`SpiEncoding::buffer_len`] bytes to the start of `buffer` and
+/// returns their count. Send them with the SPI clock set to
+/// [`SpiEncoding::frequency_hz`]. The LEDs latch the colors once the line
+/// stays low for the reset time, so follow the data with zero bytes or a
+/// pause.
+///
+/// # Errors
+///
+/// Returns `SpiEncodeError::BufferTooSmall` if `buffer` cannot hold every pixel.
+///
+/// # Example
+///
+/// ```
+/// use ws2812_pure::{encode_spi, SpiEncoding};
+/// use rgb::RGB8;
+///
+/// let pixels = [RGB8::new(255, 0, 0), RGB8::new(0, 0, 16)];
+/// let mut buffer = [0u8; 18];
+///
+/// let len = encode_spi(&pixels, SpiEncoding::ThreeBit, &mut buffer).unwrap();
+/// assert_eq!(len, 18);
+/// // Green is 0: eight 0b100 symbols
+/// assert_eq!(buffer[..3], [0b1001_0010, 0b0100_1001, 0b0010_0100]);
+/// ```
+pub fn encode_spi(
+    pixels: &[RGB8],
+    encoding: SpiEncoding,
+    buffer: &mut [u8],
+) -> Result<usize, SpiEncodeError> {
+    let required = encoding.buffer_len(pixels.len());
+    if buffer.len() < required {
+        return Err(SpiEncodeError::BufferTooSmall {
+            required,
+            actual: buffer.len(),
+        });
+    }
+
+    let chunks = buffer[..required].chunks_exact_mut(encoding.bytes_per_pixel());
+    for (pixel, out) in pixels.iter().zip(chunks) {
+        encode_pixel(*pixel, encoding, out);
+    }
+    Ok(required)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_three_bit_black_and_white() {
+        let mut buffer = [0u8; 9];
+        encode_spi(&[RGB8::new(0, 0, 0)], SpiEncoding::ThreeBit, &mut buffer).unwrap();
+        // 0b100 repeated: 100100100100100100100100 = 0x92 0x49 0x24
+        assert_eq!(
+            buffer,
+            [0x92, 0x49, 0x24, 0x92, 0x49, 0x24, 0x92, 0x49, 0x24]
+        );
+
+        encode_spi(
+            &[RGB8::new(255, 255, 255)],
+            SpiEncoding::ThreeBit,
+            &mut buffer,
+        )
+        .unwrap();
+        // 0b110 repeated: 110110110110110110110110 = 0xDB 0x6D 0xB6
+        assert_eq!(
+            buffer,
+            [0xDB, 0x6D, 0xB6, 0xDB, 0x6D, 0xB6, 0xDB, 0x6D, 0xB6]
+        );
+    }
+
+    #[test]
+    fn test_four_bit_symbols() {
+        let mut buffer = [0u8; 12];
+        encode_spi(
+            &[RGB8::new(0xF0, 0, 0x01)],
+            SpiEncoding::FourBit,
+            &mut buffer,
+        )
+        .unwrap();
+        // Green 0x00, red 0xF0, blue 0x01; two data bits per byte
+        assert_eq!(
+            buffer,
+            [
+                0x88, 0x88, 0x88, 0x88, // green
+                0xEE, 0xEE, 0x88, 0x88, // red
+                0x88, 0x88, 0x88, 0x8E, // blue
+            ]
+        );
+    }
+
+    #[test]
+    fn test_grb_order() {
+        let mut red = [0u8; 12];
+        let mut green = [0u8; 12];
+        encode_spi(&[RGB8::new(255, 0, 0)], SpiEncoding::FourBit, &mut red).unwrap();
+        encode_spi(&[RGB8::new(0, 255, 0)], SpiEncoding::FourBit, &mut green).unwrap();
+        assert_eq!(red[4..8], [0xEE; 4]);
+        assert_eq!(green[..4], [0xEE; 4]);
+    }
+
+    #[test]
+    fn test_multiple_pixels_and_longer_buffer() {
+        let pixels = [RGB8::new(0, 0, 0), RGB8::new(255, 255, 255)];
+        let mut buffer = [0xAAu8; 30];
+        let len = encode_spi(&pixels, SpiEncoding::ThreeBit, &mut buffer).unwrap();
+        assert_eq!(len, 18);
+        assert_eq!(buffer[..3], [0x92, 0x49, 0x24]);
+        assert_eq!(buffer[9..12], [0xDB, 0x6D, 0xB6]);
+        assert!(buffer[18..].iter().all(|&b| b == 0xAA));
+    }
+
+    #[test]
+    fn test_buffer_too_small() {
+        let mut buffer = [0u8; 20];
+        let err = encode_spi(&[RGB8::default(); 2], SpiEncoding::FourBit, &mut buffer).unwrap_err();
+        assert_eq!(
+            err,
+            SpiEncodeError::BufferTooSmall {
+                required: 24,
+                actual: 20
+            }
+        );
+        assert_eq!(
+            format!("{}", err),
+            "SPI buffer too small: 24 bytes required, 20 provided"
+        );
+    }
+
+    #[test]
+    fn test_sizes() {
+        assert_eq!(SpiEncoding::ThreeBit.buffer_len(10), 90);
+        assert_eq!(SpiEncoding::FourBit.buffer_len(10), 120);
+        assert_eq!(SpiEncoding::default().frequency_hz(), 2_400_000);
+    }
+}
@@ -0,0 +1,307 @@
+//! Bit timing of WS2812-style LEDs.
+//!
+//! Every data bit is a high pulse followed by a low pulse; a long high pulse
+//! means 1, a short one means 0. After the last pixel the line stays low for
+//! the reset time, which latches the colors.
+
+/// Error type for [`Timing`] construction and checks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TimingError {
+    /// A pulse or the reset time is zero.
+    ZeroDuration,
+    /// The high time of a 1 bit is not longer than the high time of a 0 bit.
+    BitsIndistinguishable,
+    /// A pulse is further from the datasheet value than its tolerance allows.
+    OutOfTolerance {
+        /// Name of the pulse: `"t0h"`, `"t0l"`, `"t1h"` or `"t1l"`.
+        pulse: &'static str,
+        /// Datasheet value in nanoseconds.
+        expected: u32,
+        /// Checked value in nanoseconds.
+        actual: u32,
+    },
+    /// The reset time is shorter than the datasheet minimum.
+    ResetTooShort {
+        /// Datasheet minimum in nanoseconds.
+        minimum: u32,
+        /// Checked value in nanoseconds.
+        actual: u32,
+    },
+}
+
+impl core::fmt::Display for TimingError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            TimingError::ZeroDuration => write!(f, "timing contains a zero duration"),
+            TimingError::BitsIndistinguishable => {
+                write!(f, "t1h must be longer than t0h")
+            }
+            TimingError::OutOfTolerance {
+                pulse,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "{} is {} ns, datasheet value is {} ns",
+                pulse, actual, expected
+            ),
+            TimingError::ResetTooShort { minimum, actual } => write!(
+                f,
+                "reset is {} ns, datasheet minimum is {} ns",
+                actual, minimum
+            ),
+        }
+    }
+}
+
+/// Pulse widths of one LED variant, in nanoseconds.
+///
+/// The constants hold datasheet values. Drivers rarely hit them exactly,
+/// since peripherals count in clock ticks; [`check`](Self::check) tells
+/// whether the pulses a driver actually produces are close enough.
+///
+/// # Example
+///
+/// ```
+/// use ws2812_pure::Timing;
+///
+/// // Pulses rounded to a 25 ns RMT tick
+/// let actual = Timing::new(350, 800, 700, 600, 50_000).unwrap();
+/// assert!(Timing::WS2812.check(&actual).is_ok());
+///
+/// let too_slow = Timing::new(350, 800, 900, 600, 50_000).unwrap();
+/// assert!(Timing::WS2812.check(&too_slow).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Timing {
+    t0h: u32,
+    t0l: u32,
+    t1h: u32,
+    t1l: u32,
+    reset: u32,
+    tolerance: u32,
+}
+
+impl Timing {
+    /// WS2812 (original), ±150 ns.
+    pub const WS2812: Timing = Timing::datasheet(350, 800, 700, 600, 50_000, 150);
+
+    /// WS2812B, ±150 ns.
+    ///
+    /// The reset time follows newer revisions, which need 280 µs instead of
+    /// the 50 µs of the first datasheet.
+    pub const WS2812B: Timing = Timing::datasheet(400, 850, 800, 450, 280_000, 150);
+
+    /// SK6812, RGB and RGBW, ±150 ns.
+    pub const SK6812: Timing = Timing::datasheet(300, 900, 600, 600, 80_000, 150);
+
+    /// WS2811 in 800 kHz mode, ±75 ns.
+    pub const WS2811: Timing = Timing::datasheet(250, 1_000, 600, 650, 50_000, 75);
+
+    /// Tolerance of timings created with [`new`](Self::new).
+    pub const DEFAULT_TOLERANCE_NS: u32 = 150;
+
+    const fn datasheet(t0h: u32, t0l: u32, t1h: u32, t1l: u32, reset: u32, tolerance: u32) -> Self {
+        Self {
+            t0h,
+            t0l,
+            t1h,
+            t1l,
+            reset,
+            tolerance,
+        }
+    }
+
+    /// Creates a custom timing from pulse widths and reset time in
+    /// nanoseconds, with a tolerance of [`DEFAULT_TOLERANCE_NS`](Self::DEFAULT_TOLERANCE_NS).
+    ///
+    /// # Errors
+    ///
+    /// Returns `TimingError::ZeroDuration` if any value is zero, or
+    /// `TimingError::BitsIndistinguishable` if `t1h` is not longer than `t0h`.
+    pub const fn new(
+        t0h: u32,
+        t0l: u32,
+        t1h: u32,
+        t1l: u32,
+        reset: u32,
+    ) -> Result<Self, TimingError> {
+        if t0h == 0 || t0l == 0 || t1h == 0 || t1l == 0 || reset == 0 {
+            return Err(TimingError::ZeroDuration);
+        }
+        if t1h <= t0h {
+            return Err(TimingError::BitsIndistinguishable);
+        }
+        Ok(Self::datasheet(
+            t0h,
+            t0l,
+            t1h,
+            t1l,
+            reset,
+            Self::DEFAULT_TOLERANCE_NS,
+        ))
+    }
+
+    /// Sets how far each pulse may deviate, in nanoseconds.
+    pub const fn with_tolerance(mut self, tolerance_ns: u32) -> Self {
+        self.tolerance = tolerance_ns;
+        self
+    }
+
+    /// Returns the high time of a 0 bit.
+    pub const fn t0h_ns(&self) -> u32 {
+        self.t0h
+    }
+
+    /// Returns the low time of a 0 bit.
+    pub const fn t0l_ns(&self) -> u32 {
+        self.t0l
+    }
+
+    /// Returns the high time of a 1 bit.
+    pub const fn t1h_ns(&self) -> u32 {
+        self.t1h
+    }
+
+    /// Returns the low time of a 1 bit.
+    pub const fn t1l_ns(&self) -> u32 {
+        self.t1l
+    }
+
+    /// Returns the minimum low time that latches the colors.
+    pub const fn reset_ns(&self) -> u32 {
+        self.reset
+    }
+
+    /// Returns how far each pulse may deviate.
+    pub const fn tolerance_ns(&self) -> u32 {
+        self.tolerance
+    }
+
+    /// Checks `actual` pulse widths against this datasheet timing.
+    ///
+    /// Each pulse must be within the tolerance, and the reset time must be
+    /// at least as long as the datasheet minimum.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TimingError::OutOfTolerance` for the first pulse that is off,
+    /// or `TimingError::ResetTooShort`.
+    pub fn check(&self, actual: &Timing) -> Result<(), TimingError> {
+        let pulses = [
+            ("t0h", self.t0h, actual.t0h),
+            ("t0l", self.t0l, actual.t0l),
+            ("t1h", self.t1h, actual.t1h),
+            ("t1l", self.t1l, actual.t1l),
+        ];
+        for (pulse, expected, actual) in pulses {
+            if expected.abs_diff(actual) > self.tolerance {
+                return Err(TimingError::OutOfTolerance {
+                    pulse,
+                    expected,
+                    actual,
+                });
+            }
+        }
+
+        if actual.reset < self.reset {
+            return Err(TimingError::ResetTooShort {
+                minimum: self.reset,
+                actual: actual.reset,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl Default for Timing {
+    fn default() -> Self {
+        Self::WS2812
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_constants_are_valid() {
+        for timing in [
+            Timing::WS2812,
+            Timing::WS2812B,
+            Timing::SK6812,
+            Timing::WS2811,
+        ] {
+            let custom = Timing::new(
+                timing.t0h_ns(),
+                timing.t0l_ns(),
+                timing.t1h_ns(),
+                timing.t1l_ns(),
+                timing.reset_ns(),
+            )
+            .unwrap();
+            assert_eq!(timing.check(&custom), Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_new_rejects_invalid_values() {
+        assert_eq!(
+            Timing::new(0, 800, 700, 600, 50_000),
+            Err(TimingError::ZeroDuration)
+        );
+        assert_eq!(
+            Timing::new(350, 800, 700, 600, 0),
+            Err(TimingError::ZeroDuration)
+        );
+        assert_eq!(
+            Timing::new(700, 800, 700, 600, 50_000),
+            Err(TimingError::BitsIndistinguishable)
+        );
+    }
+
+    #[test]
+    fn test_check_tolerance_bounds() {
+        let at_edge = Timing::new(500, 650, 700, 600, 50_000).unwrap();
+        assert_eq!(Timing::WS2812.check(&at_edge), Ok(()));
+
+        let past_edge = Timing::new(350, 800, 700, 449, 50_000).unwrap();
+        assert_eq!(
+            Timing::WS2812.check(&past_edge),
+            Err(TimingError::OutOfTolerance {
+                pulse: "t1l",
+                expected: 600,
+                actual: 449
+            })
+        );
+    }
+
+    #[test]
+    fn test_check_reset() {
+        let short = Timing::new(350, 800, 700, 600, 49_999).unwrap();
+        let err = Timing::WS2812.check(&short).unwrap_err();
+        assert_eq!(
+            err,
+            TimingError::ResetTooShort {
+                minimum: 50_000,
+                actual: 49_999
+            }
+        );
+        assert_eq!(
+            format!("{}", err),
+            "reset is 49999 ns, datasheet minimum is 50000 ns"
+        );
+
+        let long = Timing::new(350, 800, 700, 600, 300_000).unwrap();
+        assert_eq!(Timing::WS2812.check(&long), Ok(()));
+    }
+
+    #[test]
+    fn test_tolerance() {
+        let actual = Timing::new(300, 900, 600, 600, 80_000).unwrap();
+        assert!(Timing::WS2811.check(&actual).is_err());
+        assert_eq!(Timing::WS2811.with_tolerance(150).check(&actual), Ok(()));
+        assert_eq!(actual.tolerance_ns(), Timing::DEFAULT_TOLERANCE_NS);
+        assert_eq!(Timing::default(), Timing::WS2812);
+    }
+}
@@ -0,0 +1,188 @@
+//! Golden reference of the line signal.
+//!
+//! Drivers turn pixels into pulses in many ways: RMT items, SPI bytes,
+//! bit-banged GPIO. [`reference_waveform`] spells out what all of them must
+//! produce, so a driver's encoder can be checked against it on the host.
+
+use crate::{ColorOrder, FrameBits, Timing};
+use core::iter::FusedIterator;
+use rgb::RGB8;
+
+/// Returns the line levels and durations a frame is sent as.
+///
+/// See [`ReferenceWaveform`] for the exact sequence.
+///
+/// # Example
+///
+/// ```
+/// use ws2812_pure::{reference_waveform, Timing};
+/// use rgb::RGB8;
+///
+/// let pixels = [RGB8::new(0, 128, 0)];
+/// let mut wave = reference_waveform(&pixels, Timing::WS2812);
+///
+/// // Green comes first: its top bit is a 1
+/// assert_eq!(wave.next(), Some((true, 700)));
+/// assert_eq!(wave.next(), Some((false, 600)));
+/// // ... then a 0
+/// assert_eq!(wave.next(), Some((true, 350)));
+/// assert_eq!(wave.next(), Some((false, 800)));
+/// // ... and the reset after the last bit
+/// assert_eq!(wave.last(), Some((false, 50_000)));
+/// ```
+pub fn reference_waveform(pixels: &[RGB8], timing: Timing) -> ReferenceWaveform<'_> {
+    ReferenceWaveform {
+        bits: FrameBits::new(pixels),
+        timing,
+        low: None,
+        reset_sent: false,
+    }
+}
+
+/// Yields `(level, duration_ns)` pairs for a frame, `true` meaning high.
+///
+/// Every bit, MSB first in the pixel's color order, is a high pulse followed
+/// by a low pulse: `t1h`/`t1l` for a 1, `t0h`/`t0l` for a 0. The last pair is
+/// the reset, low for [`Timing::reset_ns`]. The low pulse of the last bit and
+/// the reset are yielded separately, as drivers usually emit them.
+#[derive(Debug, Clone)]
+pub struct ReferenceWaveform<'a> {
+    bits: FrameBits<'a>,
+    timing: Timing,
+    /// Low pulse still owed for the bit whose high pulse was just yielded.
+    low: Option<u32>,
+    reset_sent: bool,
+}
+
+impl ReferenceWaveform<'_> {
+    /// Sends the pixels in `order` instead of GRB.
+    ///
+    /// Call this before taking any pulses.
+    pub fn with_order(mut self, order: ColorOrder) -> Self {
+        self.bits = self.bits.with_order(order);
+        self
+    }
+}
+
+impl Iterator for ReferenceWaveform<'_> {
+    type Item = (bool, u32);
+
+    fn next(&mut self) -> Option<(bool, u32)> {
+        if let Some(low) = self.low.take() {
+            return Some((false, low));
+        }
+        match self.bits.next() {
+            Some(true) => {
+                self.low = Some(self.timing.t1l_ns());
+                Some((true, self.timing.t1h_ns()))
+            }
+            Some(false) => {
+                self.low = Some(self.timing.t0l_ns());
+                Some((true, self.timing.t0h_ns()))
+            }
+            None if !self.reset_sent => {
+                self.reset_sent = true;
+                Some((false, self.timing.reset_ns()))
+            }
+            None => None,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = 2 * self.bits.len() + self.low.is_some() as usize + !self.reset_sent as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for ReferenceWaveform<'_> {}
+
+impl FusedIterator for ReferenceWaveform<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Decodes a waveform back into bits by comparing each high pulse with
+    /// the midpoint of `t0h` and `t1h`.
+    fn decode(wave: &[(bool, u32)], timing: Timing) -> Vec<bool> {
+        let threshold = (timing.t0h_ns() + timing.t1h_ns()) / 2;
+        wave.iter()
+            .filter(|(high, _)| *high)
+            .map(|&(_, duration)| duration > threshold)
+            .collect()
+    }
+
+    #[test]
+    fn test_pulse_pairs_and_reset() {
+        let wave: Vec<_> = reference_waveform(&[RGB8::new(0, 0, 1)], Timing::SK6812).collect();
+        assert_eq!(wave.len(), 24 * 2 + 1);
+        assert!(wave.chunks(2).take(24).all(|pair| pair[0].0 && !pair[1].0));
+        // Blue is last, so the very last bit is the 1
+        assert_eq!(
+            wave[44..],
+            [
+                (true, 300),
+                (false, 900),
+                (true, 600),
+                (false, 600),
+                (false, 80_000)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bit_order_is_grb_msb_first() {
+        let pixels = [RGB8::new(0x12, 0x34, 0x56), RGB8::new(0xFF, 0x00, 0x80)];
+        let wave: Vec<_> = reference_waveform(&pixels, Timing::WS2812).collect();
+        let expected: Vec<bool> = FrameBits::new(&pixels).collect();
+        assert_eq!(decode(&wave, Timing::WS2812), expected);
+        // First byte is green, 0x34 = 0b0011_0100
+        assert_eq!(
+            decode(&wave, Timing::WS2812)[..8],
+            [false, false, true, true, false, true, false, false]
+        );
+    }
+
+    #[test]
+    fn test_durations_follow_timing() {
+        let timing = Timing::new(100, 200, 300, 400, 5_000).unwrap();
+        let wave: Vec<_> = reference_waveform(&[RGB8::new(0, 0x80, 0)], timing).collect();
+        assert_eq!(
+            wave[..4],
+            [(true, 300), (false, 400), (true, 100), (false, 200)]
+        );
+        let total: u32 = wave.iter().map(|&(_, duration)| duration).sum();
+        assert_eq!(total, 300 + 400 + 23 * 300 + 5_000);
+    }
+
+    #[test]
+    fn test_color_order() {
+        let wave: Vec<_> = reference_waveform(&[RGB8::new(255, 0, 0)], Timing::WS2812)
+            .with_order(ColorOrder::Rgbw)
+            .collect();
+        assert_eq!(wave.len(), 32 * 2 + 1);
+        let bits = decode(&wave, Timing::WS2812);
+        assert!(bits[..8].iter().all(|&bit| bit));
+        assert!(bits[8..].iter().all(|&bit| !bit));
+    }
+
+    #[test]
+    fn test_empty_frame_is_only_reset() {
+        let wave: Vec<_> = reference_waveform(&[], Timing::WS2812B).collect();
+        assert_eq!(wave, [(false, 280_000)]);
+    }
+
+    #[test]
+    fn test_len_tracks_progress() {
+        let pixels = [RGB8::default(); 2];
+        let mut wave = reference_waveform(&pixels, Timing::WS2812);
+        assert_eq!(wave.len(), 97);
+        wave.next();
+        assert_eq!(wave.len(), 96);
+        wave.nth(94);
+        assert_eq!(wave.len(), 1);
+        assert_eq!(wave.next(), Some((false, 50_000)));
+        assert_eq!(wave.len(), 0);
+        assert_eq!(wave.next(), None);
+    }
+}
@@ -0,0 +1,420 @@
+//! WLED realtime UDP payloads.
+//!
+//! WLED and tools that speak to it (Hyperion, LedFx, xLights) stream frames
+//! as small UDP packets. These functions only build and parse the payload
+//! bytes; sending and receiving them is up to the firmware.
+//!
+//! Every payload starts with the protocol id and a timeout in seconds, after
+//! which a receiver returns to its own effects. [`WLED_NO_TIMEOUT`] keeps it
+//! in realtime mode until told otherwise.
+
+use rgb::RGB8;
+
+/// UDP port WLED listens on for realtime payloads.
+pub const WLED_UDP_PORT: u16 = 21324;
+
+/// Timeout that keeps a receiver in realtime mode indefinitely.
+pub const WLED_NO_TIMEOUT: u8 = 255;
+
+/// Layout of a WLED realtime payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WledProtocol {
+    /// `[index, r, g, b]` per pixel, for sparse updates of up to 255 pixels.
+    Warls,
+    /// `[r, g, b]` per pixel, starting at the first pixel.
+    Drgb,
+    /// A big-endian `u16` start index, then `[r, g, b]` per pixel; for
+    /// strips longer than one packet.
+    Dnrgb,
+}
+
+impl WledProtocol {
+    /// Returns the protocol id sent in the first byte.
+    pub const fn id(self) -> u8 {
+        match self {
+            WledProtocol::Warls => 1,
+            WledProtocol::Drgb => 2,
+            WledProtocol::Dnrgb => 4,
+        }
+    }
+
+    /// Returns the protocol for an id, or `None` for ids this crate does not
+    /// handle (e.g. 3 for DRGBW).
+    pub const fn from_id(id: u8) -> Option<Self> {
+        match id {
+            1 => Some(WledProtocol::Warls),
+            2 => Some(WledProtocol::Drgb),
+            4 => Some(WledProtocol::Dnrgb),
+            _ => None,
+        }
+    }
+
+    /// Returns the most pixels one packet carries.
+    pub const fn max_pixels(self) -> usize {
+        match self {
+            WledProtocol::Warls => 255,
+            WledProtocol::Drgb => 490,
+            WledProtocol::Dnrgb => 489,
+        }
+    }
+
+    /// Returns the number of bytes before the pixel data.
+    const fn header_len(self) -> usize {
+        match self {
+            WledProtocol::Dnrgb => 4,
+            _ => 2,
+        }
+    }
+
+    /// Returns the number of bytes per pixel.
+    const fn pixel_len(self) -> usize {
+        match self {
+            WledProtocol::Warls => 4,
+            _ => 3,
+        }
+    }
+}
+
+/// Error type for building and parsing WLED payloads.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WledError {
+    /// The buffer is too small for the payload.
+    BufferTooSmall {
+        /// Number of bytes needed.
+        required: usize,
+        /// Actual buffer size provided.
+        actual: usize,
+    },
+    /// More pixels than one packet of the protocol carries.
+    TooManyPixels {
+        /// Most pixels per packet.
+        max: usize,
+        /// Pixels provided.
+        actual: usize,
+    },
+    /// The payload ends in the middle of the header or a pixel.
+    Truncated,
+    /// The payload uses a protocol id this crate does not handle.
+    UnsupportedProtocol(u8),
+}
+
+impl core::fmt::Display for WledError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            WledError::BufferTooSmall { required, actual } => write!(
+                f,
+                "WLED buffer too small: {} bytes required, {} provided",
+                required, actual
+            ),
+            WledError::TooManyPixels { max, actual } => write!(
+                f,
+                "too many pixels for one WLED packet: {} (max {})",
+                actual, max
+            ),
+            WledError::Truncated => write!(f, "WLED payload is truncated"),
+            WledError::UnsupportedProtocol(id) => {
+                write!(f, "unsupported WLED protocol id {}", id)
+            }
+        }
+    }
+}
+
+/// Checks the pixel count and buffer size, writes the header and returns the
+/// payload length.
+fn write_header(
+    protocol: WledProtocol,
+    pixels: usize,
+    timeout_s: u8,
+    buffer: &mut [u8],
+) -> Result<usize, WledError> {
+    if pixels > protocol.max_pixels() {
+        return Err(WledError::TooManyPixels {
+            max: protocol.max_pixels(),
+            actual: pixels,
+        });
+    }
+    let required = protocol.header_len() + pixels * protocol.pixel_len();
+    if buffer.len() < required {
+        return Err(WledError::BufferTooSmall {
+            required,
+            actual: buffer.len(),
+        });
+    }
+    buffer[0] = protocol.id();
+    buffer[1] = timeout_s;
+    Ok(required)
+}
+
+/// Builds a WARLS payload that sets individual pixels by index.
+///
+/// Returns the payload length.
+///
+/// # Errors
+///
+/// Returns `WledError::TooManyPixels` for more than 255 pixels, or
+/// `WledError::BufferTooSmall` if `buffer` cannot hold the payload.
+pub fn build_warls(
+    pixels: &[(u8, RGB8)],
+    timeout_s: u8,
+    buffer: &mut [u8],
+) -> Result<usize, WledError> {
+    let len = write_header(WledProtocol::Warls, pixels.len(), timeout_s, buffer)?;
+    for (&(index, color), out) in pixels.iter().zip(buffer[2..len].chunks_exact_mut(4)) {
+        out.copy_from_slice(&[index, color.r, color.g, color.b]);
+    }
+    Ok(len)
+}
+
+/// Builds a DRGB payload with the colors of the first `pixels.len()` pixels.
+///
+/// Returns the payload length.
+///
+/// # Errors
+///
+/// Returns `WledError::TooManyPixels` for more than 490 pixels, or
+/// `WledError::BufferTooSmall` if `buffer` cannot hold the payload.
+///
+/// # Example
+///
+/// ```
+/// use ws2812_pure::{build_drgb, parse_wled, WLED_NO_TIMEOUT};
+/// use rgb::RGB8;
+///
+/// let pixels = [RGB8::new(255, 0, 0), RGB8::new(0, 0, 255)];
+/// let mut payload = [0u8; 8];
+///
+/// let len = build_drgb(&pixels, WLED_NO_TIMEOUT, &mut payload).unwrap();
+/// assert_eq!(payload[..len], [2, 255, 255, 0, 0, 0, 0, 255]);
+///
+/// let mut frame = [RGB8::default(); 2];
+/// parse_wled(&payload[..len]).unwrap().apply(&mut frame);
+/// assert_eq!(frame, pixels);
+/// ```
+pub fn build_drgb(pixels: &[RGB8], timeout_s: u8, buffer: &mut [u8]) -> Result<usize, WledError> {
+    let len = write_header(WledProtocol::Drgb, pixels.len(), timeout_s, buffer)?;
+    write_rgb(pixels, &mut buffer[2..len]);
+    Ok(len)
+}
+
+/// Builds a DNRGB payload with the colors of the pixels from `start` on.
+///
+/// Returns the payload length.
+///
+/// # Errors
+///
+/// Returns `WledError::TooManyPixels` for more than 489 pixels, or
+/// `WledError::BufferTooSmall` if `buffer` cannot hold the payload.
+pub fn build_dnrgb(
+    start: u16,
+    pixels: &[RGB8],
+    timeout_s: u8,
+    buffer: &mut [u8],
+) -> Result<usize, WledError> {
+    let len = write_header(WledProtocol::Dnrgb, pixels.len(), timeout_s, buffer)?;
+    buffer[2..4].copy_from_slice(&start.to_be_bytes());
+    write_rgb(pixels, &mut buffer[4..len]);
+    Ok(len)
+}
+
+fn write_rgb(pixels: &[RGB8], out: &mut [u8]) {
+    for (color, bytes) in pixels.iter().zip(out.chunks_exact_mut(3)) {
+        bytes.copy_from_slice(&[color.r, color.g, color.b]);
+    }
+}
+
+/// Parses a WLED realtime payload.
+///
+/// # Errors
+///
+/// Returns `WledError::UnsupportedProtocol` for protocol ids other than
+/// WARLS, DRGB and DNRGB, or `WledError::Truncated` if the payload ends in
+/// the middle of the header or a pixel.
+pub fn parse_wled(payload: &[u8]) -> Result<WledPacket<'_>, WledError> {
+    let &id = payload.first().ok_or(WledError::Truncated)?;
+    let protocol = WledProtocol::from_id(id).ok_or(WledError::UnsupportedProtocol(id))?;
+    if payload.len() < protocol.header_len() {
+        return Err(WledError::Truncated);
+    }
+    let data = &payload[protocol.header_len()..];
+    if !data.len().is_multiple_of(protocol.pixel_len()) {
+        return Err(WledError::Truncated);
+    }
+
+    let start = match protocol {
+        WledProtocol::Dnrgb => u16::from_be_bytes([payload[2], payload[3]]),
+        _ => 0,
+    };
+    Ok(WledPacket {
+        protocol,
+        timeout_s: payload[1],
+        start,
+        data,
+    })
+}
+
+/// A parsed WLED realtime payload, borrowing its pixel data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WledPacket<'a> {
+    protocol: WledProtocol,
+    timeout_s: u8,
+    start: u16,
+    data: &'a [u8],
+}
+
+impl<'a> WledPacket<'a> {
+    /// Returns the protocol of the payload.
+    pub fn protocol(&self) -> WledProtocol {
+        self.protocol
+    }
+
+    /// Returns the timeout in seconds; [`WLED_NO_TIMEOUT`] means none.
+    pub fn timeout_s(&self) -> u8 {
+        self.timeout_s
+    }
+
+    /// Returns the index of the first pixel; 0 except for DNRGB.
+    pub fn start(&self) -> u16 {
+        self.start
+    }
+
+    /// Returns the number of pixels in the payload.
+    pub fn len(&self) -> usize {
+        self.data.len() / self.protocol.pixel_len()
+    }
+
+    /// Returns `true` if the payload carries no pixels.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Returns the pixels as `(index, color)` pairs.
+    pub fn pixels(&self) -> impl Iterator<Item = (usize, RGB8)> + 'a {
+        let protocol = self.protocol;
+        let start = self.start as usize;
+        self.data
+            .chunks_exact(protocol.pixel_len())
+            .enumerate()
+            .map(move |(i, bytes)| match protocol {
+                WledProtocol::Warls => (bytes[0] as usize, RGB8::new(bytes[1], bytes[2], bytes[3])),
+                _ => (start + i, RGB8::new(bytes[0], bytes[1], bytes[2])),
+            })
+    }
+
+    /// Writes the pixels into `frame`, skipping indices past its end.
+    ///
+    /// Returns the number of pixels written.
+    pub fn apply(&self, frame: &mut [RGB8]) -> usize {
+        let mut written = 0;
+        for (index, color) in self.pixels() {
+            if let Some(pixel) = frame.get_mut(index) {
+                *pixel = color;
+                written += 1;
+            }
+        }
+        written
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: RGB8 = RGB8::new(255, 0, 0);
+    const BLUE: RGB8 = RGB8::new(0, 0, 255);
+
+    #[test]
+    fn test_warls_round_trip() {
+        let mut payload = [0u8; 10];
+        let len = build_warls(&[(3, RED), (0, BLUE)], 2, &mut payload).unwrap();
+        assert_eq!(payload, [1, 2, 3, 255, 0, 0, 0, 0, 0, 255]);
+        assert_eq!(len, 10);
+
+        let packet = parse_wled(&payload).unwrap();
+        assert_eq!(packet.protocol(), WledProtocol::Warls);
+        assert_eq!(packet.timeout_s(), 2);
+        let pixels: Vec<_> = packet.pixels().collect();
+        assert_eq!(pixels, [(3, RED), (0, BLUE)]);
+    }
+
+    #[test]
+    fn test_dnrgb_round_trip() {
+        let mut payload = [0u8; 10];
+        let len = build_dnrgb(0x0102, &[RED, BLUE], 5, &mut payload).unwrap();
+        assert_eq!(payload, [4, 5, 1, 2, 255, 0, 0, 0, 0, 255]);
+
+        let packet = parse_wled(&payload[..len]).unwrap();
+        assert_eq!(packet.start(), 258);
+        assert_eq!(packet.len(), 2);
+        let pixels: Vec<_> = packet.pixels().collect();
+        assert_eq!(pixels, [(258, RED), (259, BLUE)]);
+    }
+
+    #[test]
+    fn test_apply_skips_out_of_range() {
+        let mut payload = [0u8; 10];
+        build_dnrgb(1, &[RED, BLUE], WLED_NO_TIMEOUT, &mut payload).unwrap();
+        let mut frame = [RGB8::default(); 2];
+        assert_eq!(parse_wled(&payload).unwrap().apply(&mut frame), 1);
+        assert_eq!(frame, [RGB8::default(), RED]);
+    }
+
+    #[test]
+    fn test_max_pixels() {
+        let pixels = [RED; 491];
+        let mut buffer = [0u8; 1500];
+        assert_eq!(
+            build_drgb(&pixels, 1, &mut buffer),
+            Err(WledError::TooManyPixels {
+                max: 490,
+                actual: 491
+            })
+        );
+        assert_eq!(build_drgb(&pixels[..490], 1, &mut buffer), Ok(1472));
+        assert_eq!(build_dnrgb(0, &pixels[..489], 1, &mut buffer), Ok(1471));
+        assert!(build_warls(&[(0, RED); 256], 1, &mut buffer).is_err());
+    }
+
+    #[test]
+    fn test_buffer_too_small() {
+        let err = build_drgb(&[RED, BLUE], 1, &mut [0u8; 7]).unwrap_err();
+        assert_eq!(
+            err,
+            WledError::BufferTooSmall {
+                required: 8,
+                actual: 7
+            }
+        );
+        assert_eq!(
+            format!("{}", err),
+            "WLED buffer too small: 8 bytes required, 7 provided"
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_payloads() {
+        assert_eq!(parse_wled(&[]), Err(WledError::Truncated));
+        assert_eq!(parse_wled(&[2]), Err(WledError::Truncated));
+        assert_eq!(parse_wled(&[4, 1, 0]), Err(WledError::Truncated));
+        assert_eq!(parse_wled(&[2, 1, 255, 0]), Err(WledError::Truncated));
+        assert_eq!(parse_wled(&[1, 1, 0, 255, 0]), Err(WledError::Truncated));
+        assert_eq!(
+            parse_wled(&[3, 1, 0, 0, 0, 0]),
+            Err(WledError::UnsupportedProtocol(3))
+        );
+        assert_eq!(parse_wled(&[0, 1]), Err(WledError::UnsupportedProtocol(0)));
+    }
+
+    #[test]
+    fn test_empty_payload() {
+        let packet = parse_wled(&[2, 1]).unwrap();
+        assert!(packet.is_empty());
+        assert_eq!(packet.pixels().count(), 0);
+    }
+
+    #[test]
+    fn test_protocol_ids() {
+        for protocol in [WledProtocol::Warls, WledProtocol::Drgb, WledProtocol::Dnrgb] {
+            assert_eq!(WledProtocol::from_id(protocol.id()), Some(protocol));
+        }
+    }
+}